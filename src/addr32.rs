@@ -0,0 +1,238 @@
+//! Loading into a 32-bit address space.
+//!
+//! The [`crate::ElfLoader`] callbacks use 64-bit addresses ([`crate::VAddr`]),
+//! which a loader on a 32-bit target (e.g., riscv32 or ARMv7-M) has to
+//! narrow and range check itself. Implement [`ElfLoader32`] instead and
+//! load the binary with [`crate::ElfBinary::load32`]: addresses and
+//! relocation entries are handed over as `u32`, and a binary that doesn't
+//! fit into the 32-bit address space fails with `AddressOverflow` before
+//! the callback that would receive the address.
+
+use core::convert::TryFrom;
+
+use crate::{
+    openbsd, AllocateRequests, CoalescedAllocation, Disposition, ElfLoader, ElfLoaderErr,
+    LoadProgress, ProgramHeader, RelocationEntry, RelocationSegment, RelocationType,
+    SegmentPermissions,
+};
+
+/// A virtual address in a 32-bit address space.
+pub type VAddr32 = u32;
+
+/// A [`RelocationEntry`] with the offset and addend narrowed to 32 bits.
+#[derive(Debug)]
+pub struct RelocationEntry32 {
+    pub rtype: RelocationType,
+    /// The raw relocation type number (also for types decoded as `Unknown`).
+    pub type_num: u32,
+    pub offset: VAddr32,
+    pub index: u32,
+    /// The addend of the relocation, truncated to 32 bits (relocation
+    /// arithmetic on a 32-bit target is modulo 2^32, so negative addends of
+    /// ELF64 files keep their meaning).
+    pub addend: Option<u32>,
+    /// See [`RelocationEntry::segment`].
+    pub segment: Option<RelocationSegment>,
+}
+
+impl TryFrom<RelocationEntry> for RelocationEntry32 {
+    type Error = ElfLoaderErr;
+
+    fn try_from(entry: RelocationEntry) -> Result<Self, Self::Error> {
+        Ok(RelocationEntry32 {
+            rtype: entry.rtype,
+            type_num: entry.type_num,
+            offset: narrow(entry.offset, "relocation offset")?,
+            index: entry.index,
+            addend: entry.addend.map(|addend| addend as u32),
+            segment: entry.segment,
+        })
+    }
+}
+
+/// Converts an address or size to 32 bits.
+fn narrow(value: u64, source: &'static str) -> Result<u32, ElfLoaderErr> {
+    u32::try_from(value).map_err(|_| ElfLoaderErr::AddressOverflow { source })
+}
+
+/// Makes sure the `size` bytes at `vaddr` lie in the 32-bit address space.
+fn check_range(vaddr: u64, size: u64, source: &'static str) -> Result<(), ElfLoaderErr> {
+    let last = vaddr
+        .checked_add(size.saturating_sub(1))
+        .ok_or(ElfLoaderErr::AddressOverflow { source })?;
+    narrow(last, source).map(|_| ())
+}
+
+/// Like [`ElfLoader`], for loaders of a 32-bit address space.
+///
+/// The methods correspond to the ones of [`ElfLoader`] with the same name,
+/// see there for when they are called.
+pub trait ElfLoader32 {
+    /// See [`ElfLoader::allocate`].
+    fn allocate(&mut self, requests: AllocateRequests) -> Result<(), ElfLoaderErr>;
+
+    /// See [`ElfLoader::allocate_coalesced`].
+    ///
+    /// Note: The default implementation calls `allocate` with the LOAD
+    /// segments.
+    fn allocate_coalesced(&mut self, allocation: CoalescedAllocation) -> Result<(), ElfLoaderErr> {
+        self.allocate(allocation.segments)
+    }
+
+    /// See [`ElfLoader::load`].
+    fn load(
+        &mut self,
+        flags: SegmentPermissions,
+        base: VAddr32,
+        region: &[u8],
+    ) -> Result<(), ElfLoaderErr>;
+
+    /// See [`ElfLoader::relocate`].
+    fn relocate(&mut self, entry: RelocationEntry32) -> Result<(), ElfLoaderErr>;
+
+    /// See [`ElfLoader::write_u32`].
+    fn write_u32(&mut self, _vaddr: VAddr32, _value: u32) -> Result<(), ElfLoaderErr> {
+        Err(ElfLoaderErr::UnsupportedRelocationEntry)
+    }
+
+    /// See [`ElfLoader::make_writable`].
+    fn make_writable(&mut self, _base: VAddr32, _size: usize) -> Result<(), ElfLoaderErr> {
+        Ok(())
+    }
+
+    /// See [`ElfLoader::restore_permissions`].
+    fn restore_permissions(
+        &mut self,
+        _base: VAddr32,
+        _size: usize,
+        _flags: SegmentPermissions,
+    ) -> Result<(), ElfLoaderErr> {
+        Ok(())
+    }
+
+    /// See [`ElfLoader::tls`].
+    fn tls(
+        &mut self,
+        _tdata_start: VAddr32,
+        _tdata: &[u8],
+        _tbss_length: u32,
+        _align: u32,
+    ) -> Result<(), ElfLoaderErr> {
+        Ok(())
+    }
+
+    /// See [`ElfLoader::openbsd_segment`].
+    fn openbsd_segment(
+        &mut self,
+        _kind: openbsd::SegmentKind,
+        _base: VAddr32,
+        _size: usize,
+    ) -> Result<(), ElfLoaderErr> {
+        Ok(())
+    }
+
+    /// See [`ElfLoader::make_readonly`], called for the pages of every
+    /// GNU_RELRO segment.
+    fn make_readonly(&mut self, _base: VAddr32, _size: usize) -> Result<(), ElfLoaderErr> {
+        Ok(())
+    }
+
+    /// See [`ElfLoader::disposition`].
+    fn disposition(&mut self, _header: ProgramHeader) -> Result<Disposition, ElfLoaderErr> {
+        Ok(Disposition::Load)
+    }
+
+    /// See [`ElfLoader::progress`].
+    fn progress(&mut self, _progress: LoadProgress) -> Result<(), ElfLoaderErr> {
+        Ok(())
+    }
+}
+
+/// Adapts an [`ElfLoader32`] to the [`ElfLoader`] interface, narrowing all
+/// addresses to 32 bits.
+pub struct Loader32<'a, L: ElfLoader32 + ?Sized>(pub &'a mut L);
+
+impl<'a, L: ElfLoader32 + ?Sized> ElfLoader for Loader32<'a, L> {
+    fn allocate(&mut self, requests: AllocateRequests) -> Result<(), ElfLoaderErr> {
+        for request in requests.clone() {
+            check_range(request.vaddr, request.mem_size, "LOAD segment")?;
+        }
+        self.0.allocate(requests)
+    }
+
+    fn allocate_coalesced(&mut self, allocation: CoalescedAllocation) -> Result<(), ElfLoaderErr> {
+        check_range(allocation.vaddr, allocation.mem_size, "LOAD segment span")?;
+        self.0.allocate_coalesced(allocation)
+    }
+
+    fn load(
+        &mut self,
+        flags: SegmentPermissions,
+        base: u64,
+        region: &[u8],
+    ) -> Result<(), ElfLoaderErr> {
+        self.0
+            .load(flags, narrow(base, "LOAD segment address")?, region)
+    }
+
+    fn relocate(&mut self, entry: RelocationEntry) -> Result<(), ElfLoaderErr> {
+        self.0.relocate(RelocationEntry32::try_from(entry)?)
+    }
+
+    fn write_u32(&mut self, vaddr: u64, value: u32) -> Result<(), ElfLoaderErr> {
+        self.0.write_u32(narrow(vaddr, "relocation offset")?, value)
+    }
+
+    fn make_writable(&mut self, base: u64, size: usize) -> Result<(), ElfLoaderErr> {
+        self.0
+            .make_writable(narrow(base, "LOAD segment address")?, size)
+    }
+
+    fn restore_permissions(
+        &mut self,
+        base: u64,
+        size: usize,
+        flags: SegmentPermissions,
+    ) -> Result<(), ElfLoaderErr> {
+        self.0
+            .restore_permissions(narrow(base, "LOAD segment address")?, size, flags)
+    }
+
+    fn tls(
+        &mut self,
+        tdata_start: u64,
+        tdata: &[u8],
+        tbss_length: u64,
+        align: u64,
+    ) -> Result<(), ElfLoaderErr> {
+        self.0.tls(
+            narrow(tdata_start, "TLS segment address")?,
+            tdata,
+            narrow(tbss_length, "TLS segment size")?,
+            narrow(align, "TLS segment alignment")?,
+        )
+    }
+
+    fn openbsd_segment(
+        &mut self,
+        kind: openbsd::SegmentKind,
+        base: u64,
+        size: usize,
+    ) -> Result<(), ElfLoaderErr> {
+        self.0
+            .openbsd_segment(kind, narrow(base, "OpenBSD segment address")?, size)
+    }
+
+    fn make_readonly(&mut self, base: u64, size: usize) -> Result<(), ElfLoaderErr> {
+        self.0
+            .make_readonly(narrow(base, "RELRO segment address")?, size)
+    }
+
+    fn disposition(&mut self, header: ProgramHeader) -> Result<Disposition, ElfLoaderErr> {
+        self.0.disposition(header)
+    }
+
+    fn progress(&mut self, progress: LoadProgress) -> Result<(), ElfLoaderErr> {
+        self.0.progress(progress)
+    }
+}
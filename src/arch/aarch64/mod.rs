@@ -1,7 +1,21 @@
 //! AArch64 relocation types
 //!
-//! As defined in the "ELF for the ARM® 64-bit Architecture (AArch64)" doc.
-//! Dcoument number: ARM IHI 0056B, current through AArch64 ABI release 1.0
+//! As defined in the "ELF for the Arm® 64-bit Architecture (AArch64)" doc
+//! (AAELF64, release 2023Q3). Only the LP64 relocations are included, the
+//! ILP32 (`R_AARCH64_P32_*`) codes map to `Unknown`.
+//!
+//! The `R_AARCH64_AUTH_*` relocations of signed pointers are defined in the
+//! "PAuth ABI Extension to ELF for the Arm® 64-bit Architecture" doc
+//! (PAUTHABIELF64).
+
+use super::{align_up, TlsSegment};
+use crate::elf;
+use crate::note::GnuProperty;
+use crate::reloc::{tp_relative, RelocationResolver};
+use crate::{ElfLoaderErr, RelocationEntry};
+#[cfg(feature = "apply")]
+use crate::{RelocationType, VAddr};
+use bitflags::bitflags;
 
 #[cfg(test)]
 mod test;
@@ -64,6 +78,8 @@ pub enum RelocationTypes {
     R_AARCH64_ADR_GOT_PAGE,
     R_AARCH64_LD64_GOT_LO12_NC,
     R_AARCH64_LD64_GOTPAGE_LO15,
+    R_AARCH64_PLT32,
+    R_AARCH64_GOTPCREL32,
     R_AARCH64_TLSGD_ADR_PREL21,
     R_AARCH64_TLSGD_ADR_PAGE21,
     R_AARCH64_TLSGD_ADD_LO12_NC,
@@ -130,11 +146,33 @@ pub enum RelocationTypes {
     R_AARCH64_GLOB_DAT,
     R_AARCH64_JUMP_SLOT,
     R_AARCH64_RELATIVE,
-    R_AARCH64_TLS_DTPREL64,
     R_AARCH64_TLS_DTPMOD64,
+    R_AARCH64_TLS_DTPREL64,
     R_AARCH64_TLS_TPREL64,
     R_AARCH64_TLSDESC,
     R_AARCH64_IRELATIVE,
+    R_AARCH64_AUTH_ABS64,
+    R_AARCH64_AUTH_MOVW_GOTOFF_G0,
+    R_AARCH64_AUTH_MOVW_GOTOFF_G0_NC,
+    R_AARCH64_AUTH_MOVW_GOTOFF_G1,
+    R_AARCH64_AUTH_MOVW_GOTOFF_G1_NC,
+    R_AARCH64_AUTH_MOVW_GOTOFF_G2,
+    R_AARCH64_AUTH_MOVW_GOTOFF_G2_NC,
+    R_AARCH64_AUTH_MOVW_GOTOFF_G3,
+    R_AARCH64_AUTH_GOT_LD_PREL19,
+    R_AARCH64_AUTH_LD64_GOTOFF_LO15,
+    R_AARCH64_AUTH_ADR_GOT_PAGE,
+    R_AARCH64_AUTH_LD64_GOT_LO12_NC,
+    R_AARCH64_AUTH_LD64_GOTPAGE_LO15,
+    R_AARCH64_AUTH_GOT_ADD_LO12_NC,
+    R_AARCH64_AUTH_GOT_ADR_PREL_LO21,
+    R_AARCH64_AUTH_TLSDESC_ADR_PAGE21,
+    R_AARCH64_AUTH_TLSDESC_LD64_LO12,
+    R_AARCH64_AUTH_TLSDESC_ADD_LO12,
+    R_AARCH64_AUTH_RELATIVE,
+    R_AARCH64_AUTH_GLOB_DAT,
+    R_AARCH64_AUTH_TLSDESC,
+    R_AARCH64_AUTH_IRELATIVE,
     /// Unknown
     Unknown(u32),
 }
@@ -199,6 +237,8 @@ impl RelocationTypes {
             311 => R_AARCH64_ADR_GOT_PAGE,
             312 => R_AARCH64_LD64_GOT_LO12_NC,
             313 => R_AARCH64_LD64_GOTPAGE_LO15,
+            314 => R_AARCH64_PLT32,
+            315 => R_AARCH64_GOTPCREL32,
             512 => R_AARCH64_TLSGD_ADR_PREL21,
             513 => R_AARCH64_TLSGD_ADR_PAGE21,
             514 => R_AARCH64_TLSGD_ADD_LO12_NC,
@@ -265,12 +305,181 @@ impl RelocationTypes {
             1025 => R_AARCH64_GLOB_DAT,
             1026 => R_AARCH64_JUMP_SLOT,
             1027 => R_AARCH64_RELATIVE,
-            1028 => R_AARCH64_TLS_DTPREL64,
-            1029 => R_AARCH64_TLS_DTPMOD64,
+            1028 => R_AARCH64_TLS_DTPMOD64,
+            1029 => R_AARCH64_TLS_DTPREL64,
             1030 => R_AARCH64_TLS_TPREL64,
             1031 => R_AARCH64_TLSDESC,
             1032 => R_AARCH64_IRELATIVE,
+            0xe100 => R_AARCH64_AUTH_ABS64,
+            0x8101 => R_AARCH64_AUTH_MOVW_GOTOFF_G0,
+            0x8102 => R_AARCH64_AUTH_MOVW_GOTOFF_G0_NC,
+            0x8103 => R_AARCH64_AUTH_MOVW_GOTOFF_G1,
+            0x8104 => R_AARCH64_AUTH_MOVW_GOTOFF_G1_NC,
+            0x8105 => R_AARCH64_AUTH_MOVW_GOTOFF_G2,
+            0x8106 => R_AARCH64_AUTH_MOVW_GOTOFF_G2_NC,
+            0x8107 => R_AARCH64_AUTH_MOVW_GOTOFF_G3,
+            0x8108 => R_AARCH64_AUTH_GOT_LD_PREL19,
+            0x8109 => R_AARCH64_AUTH_LD64_GOTOFF_LO15,
+            0x810a => R_AARCH64_AUTH_ADR_GOT_PAGE,
+            0x810b => R_AARCH64_AUTH_LD64_GOT_LO12_NC,
+            0x810c => R_AARCH64_AUTH_LD64_GOTPAGE_LO15,
+            0x810d => R_AARCH64_AUTH_GOT_ADD_LO12_NC,
+            0x810e => R_AARCH64_AUTH_GOT_ADR_PREL_LO21,
+            0x810f => R_AARCH64_AUTH_TLSDESC_ADR_PAGE21,
+            0x8110 => R_AARCH64_AUTH_TLSDESC_LD64_LO12,
+            0x8111 => R_AARCH64_AUTH_TLSDESC_ADD_LO12,
+            0x411 => R_AARCH64_AUTH_RELATIVE,
+            0x412 => R_AARCH64_AUTH_GLOB_DAT,
+            0x413 => R_AARCH64_AUTH_TLSDESC,
+            0x414 => R_AARCH64_AUTH_IRELATIVE,
             x => Unknown(x),
         }
     }
 }
+
+/// GNU property with the AArch64 features the binary is compatible with
+/// (the bitwise AND over all its object files), see [`Feature1`].
+pub const GNU_PROPERTY_AARCH64_FEATURE_1_AND: u32 = 0xc000_0000;
+/// GNU property with the pointer authentication ABI of the binary, see
+/// [`PauthAbi`].
+pub const GNU_PROPERTY_AARCH64_FEATURE_PAUTH: u32 = 0xc000_0001;
+/// Segment with MTE allocation tags (PT_AARCH64_MEMTAG_MTE).
+pub const PT_AARCH64_MEMTAG_MTE: u32 = 0x7000_0002;
+
+bitflags! {
+    /// The bits of the `GNU_PROPERTY_AARCH64_FEATURE_1_AND` property.
+    #[derive(Default)]
+    pub struct Feature1: u32 {
+        /// Branch target identification: the code can run with BTI enabled
+        /// (PROT_BTI mappings).
+        const BTI = 1 << 0;
+        /// Return addresses are signed with pointer authentication.
+        const PAC = 1 << 1;
+        /// The code is compatible with the guarded control stack.
+        const GCS = 1 << 2;
+    }
+}
+
+/// The pointer authentication ABI (`GNU_PROPERTY_AARCH64_FEATURE_PAUTH`),
+/// which signing schema the binary uses for code and data pointers.
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub struct PauthAbi {
+    pub platform: u64,
+    pub version: u64,
+}
+
+/// The AArch64 properties of a binary, see
+/// [`crate::ElfBinary::aarch64_properties`].
+#[derive(Eq, PartialEq, Debug, Clone, Copy, Default)]
+pub struct Properties {
+    pub feature_1: Feature1,
+    pub pauth: Option<PauthAbi>,
+}
+
+impl Properties {
+    /// Collect the AArch64 properties from the properties of a note of a
+    /// file with `encoding`.
+    pub fn parse<'s>(
+        properties: impl Iterator<Item = GnuProperty<'s>>,
+        encoding: elf::Encoding,
+    ) -> Properties {
+        let mut result = Properties::default();
+        for property in properties {
+            match property.typ {
+                GNU_PROPERTY_AARCH64_FEATURE_1_AND => {
+                    if let Ok(bits) = encoding.u32(property.data, 0) {
+                        result.feature_1 = Feature1::from_bits_truncate(bits);
+                    }
+                }
+                GNU_PROPERTY_AARCH64_FEATURE_PAUTH => {
+                    if let (Ok(platform), Ok(version)) = (
+                        encoding.u64(property.data, 0),
+                        encoding.u64(property.data, 8),
+                    ) {
+                        result.pauth = Some(PauthAbi { platform, version });
+                    }
+                }
+                _ => {}
+            }
+        }
+        result
+    }
+}
+
+/// A PT_AARCH64_MEMTAG_MTE segment, the (unrelocated) range whose mapping
+/// needs MTE tagged memory (PROT_MTE).
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub struct MemtagSegment {
+    pub vaddr: u64,
+    pub mem_size: u64,
+}
+
+/// Size of the thread control block the thread pointer points to.
+pub const TCB_SIZE: u64 = 16;
+
+/// Thread pointer relative offset of `value` in the TLS block of the main
+/// executable (TLS variant I: the block follows the TCB).
+pub fn tp_offset(tls: &TlsSegment, value: u64) -> i64 {
+    (align_up(TCB_SIZE, tls.align) + value) as i64
+}
+
+/// Computes the value and width (in bytes) of an AArch64 relocation, see
+/// [`RelocationEntry::evaluate`].
+pub(crate) fn evaluate<R: RelocationResolver>(
+    typ: RelocationTypes,
+    entry: &RelocationEntry,
+    resolver: &mut R,
+) -> Result<Option<(u64, usize)>, ElfLoaderErr> {
+    use RelocationTypes::*;
+
+    let i = entry.index;
+    let a = || entry.required_addend();
+    let b = resolver.load_bias();
+    let p = b.wrapping_add(entry.offset);
+    let value = match typ {
+        R_AARCH64_NONE | R_ARM_NONE => return Ok(None),
+        R_AARCH64_ABS64 | R_AARCH64_GLOB_DAT | R_AARCH64_JUMP_SLOT => {
+            (resolver.symbol_value(i)?.wrapping_add(a()?), 8)
+        }
+        R_AARCH64_ABS32 => (resolver.symbol_value(i)?.wrapping_add(a()?), 4),
+        R_AARCH64_PREL64 => (
+            resolver.symbol_value(i)?.wrapping_add(a()?).wrapping_sub(p),
+            8,
+        ),
+        R_AARCH64_PREL32 => (
+            resolver.symbol_value(i)?.wrapping_add(a()?).wrapping_sub(p),
+            4,
+        ),
+        R_AARCH64_RELATIVE => (b.wrapping_add(a()?), 8),
+        R_AARCH64_TLS_DTPMOD64 => (resolver.tls_module(i)? as u64, 8),
+        R_AARCH64_TLS_DTPREL64 => (resolver.symbol_value(i)?.wrapping_add(a()?), 8),
+        R_AARCH64_TLS_TPREL64 => (tp_relative(resolver, i, a()?)?, 8),
+        _ => return Err(ElfLoaderErr::UnsupportedRelocationEntry),
+    };
+    Ok(Some(value))
+}
+
+/// Applies an AArch64 dynamic relocation of a binary loaded at `base`.
+///
+/// `resolve_symbol` returns the (already relocated) address S of the symbol
+/// with the given index, `write` stores the 64-bit little-endian value at
+/// the given (relocated) address.
+///
+/// Supports the types [`RelocationEntry::evaluate`] handles, except for TLS
+/// relocations. Other types return `UnsupportedRelocationEntry`.
+#[cfg(feature = "apply")]
+pub fn apply<S, W>(
+    entry: RelocationEntry,
+    base: VAddr,
+    resolve_symbol: S,
+    write: W,
+) -> Result<(), ElfLoaderErr>
+where
+    S: FnMut(u32) -> Result<u64, ElfLoaderErr>,
+    W: FnMut(VAddr, &[u8]) -> Result<(), ElfLoaderErr>,
+{
+    match entry.rtype {
+        RelocationType::AArch64(_) => super::apply(entry, base, resolve_symbol, write),
+        _ => Err(ElfLoaderErr::UnsupportedRelocationEntry),
+    }
+}
@@ -1,4 +1,5 @@
 use std::fs;
+use std::vec::Vec;
 
 use crate::arch::test::*;
 use crate::*;
@@ -33,11 +34,19 @@ fn load_pie_elf() {
     // GNU_RELRO      0x000d90 0x0000000000010d90 0x0000000000010d90 0x000270 0x000270 R   0x1
     assert_eq!(
         loader.actions[0],
-        LoaderAction::Allocate(VAddr::from(0x0u64), 0x8cc, Flags(1 | 4))
+        LoaderAction::Allocate(
+            VAddr::from(0x0u64),
+            0x8cc,
+            SegmentPermissions::READ | SegmentPermissions::EXECUTE
+        )
     );
     assert_eq!(
         loader.actions[1],
-        LoaderAction::Allocate(VAddr::from(0x10d90u64), 0x288, Flags(0b110))
+        LoaderAction::Allocate(
+            VAddr::from(0x10d90u64),
+            0x288,
+            SegmentPermissions::READ | SegmentPermissions::WRITE
+        )
     );
     assert_eq!(
         loader.actions[2],
@@ -104,7 +113,10 @@ fn load_pie_elf() {
         LoaderAction::Relocate(0x1000_0000 + 0x10ff8, 0x1000_0000)
     );*/
 
-    assert_eq!(loader.actions.len(), 8);
+    // GNU_RELRO is made read-only last
+    assert_eq!(loader.actions[8], LoaderAction::Relro(0x10d90, 0x270));
+
+    assert_eq!(loader.actions.len(), 9);
 }
 
 #[test]
@@ -147,3 +159,116 @@ fn check_tls() {
         .find(|&&x| x == LoaderAction::Tls(VAddr::from(0x10d8cu64), 0x4, 0x8, 0x4))
         .is_some());
 }
+
+#[test]
+fn check_tp_offset() {
+    // PT_TLS of tls.aarch64: memory size 0x8, align 0x4
+    let tls = TlsSegment {
+        mem_size: 0x8,
+        align: 0x4,
+    };
+    // The TLS block follows the 16 byte TCB
+    assert_eq!(tls.tp_offset(Machine::AArch64, 0), Ok(16));
+    assert_eq!(tls.tp_offset(Machine::AArch64, 4), Ok(20));
+
+    let tls = TlsSegment {
+        mem_size: 0x8,
+        align: 0x40,
+    };
+    assert_eq!(tls.tp_offset(Machine::AArch64, 4), Ok(0x44));
+    assert_eq!(
+        tls.tp_offset(Machine::Other(0x1234), 4),
+        Err(ElfLoaderErr::UnsupportedArchitecture)
+    );
+}
+
+#[test]
+fn check_relocation_types() {
+    use crate::arch::aarch64::RelocationTypes::{self, *};
+
+    assert_eq!(RelocationTypes::from(314), R_AARCH64_PLT32);
+    assert_eq!(RelocationTypes::from(315), R_AARCH64_GOTPCREL32);
+    assert_eq!(RelocationTypes::from(1027), R_AARCH64_RELATIVE);
+    assert_eq!(RelocationTypes::from(1028), R_AARCH64_TLS_DTPMOD64);
+    assert_eq!(RelocationTypes::from(1029), R_AARCH64_TLS_DTPREL64);
+    assert_eq!(RelocationTypes::from(1030), R_AARCH64_TLS_TPREL64);
+    assert_eq!(RelocationTypes::from(1031), R_AARCH64_TLSDESC);
+    assert_eq!(RelocationTypes::from(1032), R_AARCH64_IRELATIVE);
+    assert_eq!(RelocationTypes::from(1033), Unknown(1033));
+    // PAuth ABI
+    assert_eq!(RelocationTypes::from(0xe100), R_AARCH64_AUTH_ABS64);
+    assert_eq!(RelocationTypes::from(0x8101), R_AARCH64_AUTH_MOVW_GOTOFF_G0);
+    assert_eq!(RelocationTypes::from(0x810a), R_AARCH64_AUTH_ADR_GOT_PAGE);
+    assert_eq!(
+        RelocationTypes::from(0x8111),
+        R_AARCH64_AUTH_TLSDESC_ADD_LO12
+    );
+    assert_eq!(RelocationTypes::from(0x411), R_AARCH64_AUTH_RELATIVE);
+    assert_eq!(RelocationTypes::from(0x412), R_AARCH64_AUTH_GLOB_DAT);
+    assert_eq!(RelocationTypes::from(0x413), R_AARCH64_AUTH_TLSDESC);
+    assert_eq!(RelocationTypes::from(0x414), R_AARCH64_AUTH_IRELATIVE);
+    assert_eq!(RelocationTypes::from(0x415), Unknown(0x415));
+}
+
+#[test]
+fn check_bind_lazy() {
+    init();
+    let binary_blob = fs::read("test/test.aarch64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+
+    assert_eq!(
+        binary.lazy_binding_abi().map(|abi| abi.selector),
+        Ok(TrampolineArg::Stack { offset: 0 })
+    );
+    // The selector is the address of the GOT slot: __cxa_finalize
+    let fixup = binary.bind_lazy(0x1000_0000, 0x1001_0fb0, |entry| {
+        assert_eq!(entry.index, 5);
+        Ok(0x1234)
+    });
+    assert_eq!(fixup.map(|fixup| fixup.address), Ok(0x1001_0fb0));
+    // The reserved GOT[2] slot
+    assert_eq!(
+        binary.bind_lazy(0x1000_0000, 0x1001_0fa0, |_| Ok(0)),
+        Err(ElfLoaderErr::InvalidPltSelector {
+            selector: 0x1001_0fa0
+        })
+    );
+}
+
+#[test]
+fn check_properties() {
+    init();
+    let mut binary_blob = fs::read("test/test.aarch64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+    assert_eq!(binary.aarch64_properties(), None);
+    assert_eq!(binary.aarch64_memtag_segments().count(), 0);
+
+    // Replace .note.ABI-tag (at 0x278) by a property note with BTI and PAC,
+    // and turn GNU_EH_FRAME (program header 6) into PT_AARCH64_MEMTAG_MTE
+    let mut note = Vec::new();
+    for word in [4u32, 16, note::NT_GNU_PROPERTY_TYPE_0] {
+        note.extend_from_slice(&word.to_le_bytes());
+    }
+    note.extend_from_slice(b"GNU\0");
+    for word in [arch::aarch64::GNU_PROPERTY_AARCH64_FEATURE_1_AND, 4, 3, 0] {
+        note.extend_from_slice(&word.to_le_bytes());
+    }
+    binary_blob[0x278..0x298].copy_from_slice(&note);
+    binary_blob[0x190..0x194].copy_from_slice(&arch::aarch64::PT_AARCH64_MEMTAG_MTE.to_le_bytes());
+
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+    let properties = binary.aarch64_properties().expect("Has property note");
+    assert_eq!(
+        properties.feature_1,
+        arch::aarch64::Feature1::BTI | arch::aarch64::Feature1::PAC
+    );
+    assert_eq!(properties.pauth, None);
+    let segments: Vec<arch::aarch64::MemtagSegment> = binary.aarch64_memtag_segments().collect();
+    assert_eq!(
+        segments,
+        [arch::aarch64::MemtagSegment {
+            vaddr: 0x7e4,
+            mem_size: 0x3c
+        }]
+    );
+}
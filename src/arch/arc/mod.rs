@@ -0,0 +1,216 @@
+//! ARC relocation types
+//!
+//! As defined in the "DesignWare ARCv2 ELF Application Binary Interface"
+//! and the ARC port of binutils (`include/elf/arc-reloc.def`).
+//!
+//! "Middle endian" (`_ME`) relocations store a 32 bit long immediate with
+//! the two 16 bit halves swapped, as they appear in the instruction stream.
+
+/// Machine number of ARCompact (ARCv1) processors (not known to xmas-elf).
+pub const EM_ARC_COMPACT: u16 = 93;
+/// Machine number of ARCv2 (ARC EM/HS) processors (not known to xmas-elf).
+pub const EM_ARC_COMPACT2: u16 = 195;
+
+#[cfg(test)]
+mod test;
+
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+#[allow(non_camel_case_types)]
+#[repr(u32)]
+pub enum RelocationTypes {
+    /// No relocation.
+    R_ARC_NONE,
+    /// Direct 8 bit.
+    R_ARC_8,
+    /// Direct 16 bit.
+    R_ARC_16,
+    /// Direct 24 bit.
+    R_ARC_24,
+    /// Direct 32 bit.
+    R_ARC_32,
+    /// 26 bit branch target (ARCompact A4).
+    R_ARC_B26,
+    /// 22 bit PC relative branch.
+    R_ARC_B22_PCREL,
+    /// Direct 30 bit, shifted by 2.
+    R_ARC_H30,
+    /// Negated 8 bit.
+    R_ARC_N8,
+    /// Negated 16 bit.
+    R_ARC_N16,
+    /// Negated 24 bit.
+    R_ARC_N24,
+    /// Negated 32 bit.
+    R_ARC_N32,
+    /// Small data area relative.
+    R_ARC_SDA,
+    /// Offset from the start of the section.
+    R_ARC_SECTOFF,
+    /// 21 bit PC relative branch (halfword aligned).
+    R_ARC_S21H_PCREL,
+    /// 21 bit PC relative branch (word aligned).
+    R_ARC_S21W_PCREL,
+    /// 25 bit PC relative branch (halfword aligned).
+    R_ARC_S25H_PCREL,
+    /// 25 bit PC relative branch (word aligned).
+    R_ARC_S25W_PCREL,
+    /// 32 bit small data area relative.
+    R_ARC_SDA32,
+    /// Small data area relative load/store (byte).
+    R_ARC_SDA_LDST,
+    /// Small data area relative load/store (halfword).
+    R_ARC_SDA_LDST1,
+    /// Small data area relative load/store (word).
+    R_ARC_SDA_LDST2,
+    /// 16 bit small data area relative load (byte).
+    R_ARC_SDA16_LD,
+    /// 16 bit small data area relative load (halfword).
+    R_ARC_SDA16_LD1,
+    /// 16 bit small data area relative load (word).
+    R_ARC_SDA16_LD2,
+    /// 13 bit PC relative branch.
+    R_ARC_S13_PCREL,
+    /// Direct 32 bit, word aligned.
+    R_ARC_W,
+    /// Direct 32 bit in middle endian (long immediate) order.
+    R_ARC_32_ME,
+    /// Negated 32 bit in middle endian order.
+    R_ARC_N32_ME,
+    /// Section offset in middle endian order.
+    R_ARC_SECTOFF_ME,
+    /// 32 bit small data area relative in middle endian order.
+    R_ARC_SDA32_ME,
+    /// Direct 32 bit word aligned in middle endian order.
+    R_ARC_W_ME,
+    /// Direct 30 bit shifted by 2 in middle endian order.
+    R_ARC_H30_ME,
+    /// Unsigned 8 bit section offset.
+    R_ARC_SECTOFF_U8,
+    /// Signed 9 bit section offset.
+    R_ARC_SECTOFF_S9,
+    /// Section offset in middle endian order, shifted by 1.
+    R_ARC_SECTOFF_ME_1,
+    /// Section offset in middle endian order, shifted by 2.
+    R_ARC_SECTOFF_ME_2,
+    /// Section offset, shifted by 1.
+    R_ARC_SECTOFF_1,
+    /// Section offset, shifted by 2.
+    R_ARC_SECTOFF_2,
+    /// 32 bit PC relative.
+    R_ARC_PC32,
+    /// 32 bit PC relative offset of the GOT entry.
+    R_ARC_GOTPC32,
+    /// 32 bit PC relative PLT address.
+    R_ARC_PLT32,
+    /// Copy data from shared object.
+    R_ARC_COPY,
+    /// Set GOT entry to data address.
+    R_ARC_GLOB_DAT,
+    /// Set GOT entry to code address.
+    R_ARC_JUMP_SLOT,
+    /// Add load address of shared object.
+    R_ARC_RELATIVE,
+    /// 32 bit offset to GOT.
+    R_ARC_GOTOFF,
+    /// 32 bit PC relative offset to GOT.
+    R_ARC_GOTPC,
+    /// 32 bit GOT entry offset.
+    R_ARC_GOT32,
+    /// ID of module containing symbol.
+    R_ARC_TLS_DTPMOD,
+    /// Offset in TLS block.
+    R_ARC_TLS_DTPOFF,
+    /// Offset in static TLS block.
+    R_ARC_TLS_TPOFF,
+    /// PC relative offset of the GD GOT entry.
+    R_ARC_TLS_GD_GOT,
+    /// Marks the load of a GD sequence.
+    R_ARC_TLS_GD_LD,
+    /// Marks the call to __tls_get_addr of a GD sequence.
+    R_ARC_TLS_GD_CALL,
+    /// PC relative offset of the IE GOT entry.
+    R_ARC_TLS_IE_GOT,
+    /// Signed 9 bit offset in TLS block.
+    R_ARC_TLS_DTPOFF_S9,
+    /// Signed 9 bit offset in static TLS block.
+    R_ARC_TLS_LE_S9,
+    /// 32 bit offset in static TLS block.
+    R_ARC_TLS_LE_32,
+    /// 25 bit PC relative branch to the PLT entry (word aligned).
+    R_ARC_S25W_PCREL_PLT,
+    /// 21 bit PC relative branch to the PLT entry (halfword aligned).
+    R_ARC_S21H_PCREL_PLT,
+    /// Unknown
+    Unknown(u32),
+}
+
+impl RelocationTypes {
+    /// Construct new arc::RelocationTypes
+    pub fn from(typ: u32) -> RelocationTypes {
+        use RelocationTypes::*;
+        match typ {
+            0 => R_ARC_NONE,
+            1 => R_ARC_8,
+            2 => R_ARC_16,
+            3 => R_ARC_24,
+            4 => R_ARC_32,
+            5 => R_ARC_B26,
+            6 => R_ARC_B22_PCREL,
+            7 => R_ARC_H30,
+            8 => R_ARC_N8,
+            9 => R_ARC_N16,
+            10 => R_ARC_N24,
+            11 => R_ARC_N32,
+            12 => R_ARC_SDA,
+            13 => R_ARC_SECTOFF,
+            14 => R_ARC_S21H_PCREL,
+            15 => R_ARC_S21W_PCREL,
+            16 => R_ARC_S25H_PCREL,
+            17 => R_ARC_S25W_PCREL,
+            18 => R_ARC_SDA32,
+            19 => R_ARC_SDA_LDST,
+            20 => R_ARC_SDA_LDST1,
+            21 => R_ARC_SDA_LDST2,
+            22 => R_ARC_SDA16_LD,
+            23 => R_ARC_SDA16_LD1,
+            24 => R_ARC_SDA16_LD2,
+            25 => R_ARC_S13_PCREL,
+            26 => R_ARC_W,
+            27 => R_ARC_32_ME,
+            28 => R_ARC_N32_ME,
+            29 => R_ARC_SECTOFF_ME,
+            30 => R_ARC_SDA32_ME,
+            31 => R_ARC_W_ME,
+            32 => R_ARC_H30_ME,
+            33 => R_ARC_SECTOFF_U8,
+            34 => R_ARC_SECTOFF_S9,
+            41 => R_ARC_SECTOFF_ME_1,
+            42 => R_ARC_SECTOFF_ME_2,
+            43 => R_ARC_SECTOFF_1,
+            44 => R_ARC_SECTOFF_2,
+            50 => R_ARC_PC32,
+            51 => R_ARC_GOTPC32,
+            52 => R_ARC_PLT32,
+            53 => R_ARC_COPY,
+            54 => R_ARC_GLOB_DAT,
+            55 => R_ARC_JUMP_SLOT,
+            56 => R_ARC_RELATIVE,
+            57 => R_ARC_GOTOFF,
+            58 => R_ARC_GOTPC,
+            59 => R_ARC_GOT32,
+            66 => R_ARC_TLS_DTPMOD,
+            67 => R_ARC_TLS_DTPOFF,
+            68 => R_ARC_TLS_TPOFF,
+            69 => R_ARC_TLS_GD_GOT,
+            70 => R_ARC_TLS_GD_LD,
+            71 => R_ARC_TLS_GD_CALL,
+            72 => R_ARC_TLS_IE_GOT,
+            73 => R_ARC_TLS_DTPOFF_S9,
+            74 => R_ARC_TLS_LE_S9,
+            75 => R_ARC_TLS_LE_32,
+            76 => R_ARC_S25W_PCREL_PLT,
+            77 => R_ARC_S21H_PCREL_PLT,
+            x => Unknown(x),
+        }
+    }
+}
@@ -0,0 +1,28 @@
+use crate::arch::arc::RelocationTypes::{self, *};
+use crate::arch::arc::{EM_ARC_COMPACT, EM_ARC_COMPACT2};
+use crate::arch::RelocationType;
+use crate::Machine;
+
+#[test]
+fn check_relocation_types() {
+    assert_eq!(RelocationTypes::from(4), R_ARC_32);
+    assert_eq!(RelocationTypes::from(53), R_ARC_COPY);
+    assert_eq!(RelocationTypes::from(55), R_ARC_JUMP_SLOT);
+    assert_eq!(RelocationTypes::from(56), R_ARC_RELATIVE);
+    // TLS codes of arc-reloc.def
+    assert_eq!(RelocationTypes::from(66), R_ARC_TLS_DTPMOD);
+    assert_eq!(RelocationTypes::from(67), R_ARC_TLS_DTPOFF);
+    assert_eq!(RelocationTypes::from(68), R_ARC_TLS_TPOFF);
+    assert_eq!(RelocationTypes::from(72), R_ARC_TLS_IE_GOT);
+    assert_eq!(RelocationTypes::from(73), R_ARC_TLS_DTPOFF_S9);
+    assert_eq!(RelocationTypes::from(74), R_ARC_TLS_LE_S9);
+    assert_eq!(RelocationTypes::from(75), R_ARC_TLS_LE_32);
+    assert_eq!(RelocationTypes::from(78), Unknown(78));
+
+    for machine in [EM_ARC_COMPACT, EM_ARC_COMPACT2] {
+        assert!(matches!(
+            RelocationType::from(Machine::Other(machine), 56),
+            Ok(RelocationType::Arc(R_ARC_RELATIVE))
+        ));
+    }
+}
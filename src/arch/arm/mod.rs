@@ -1,3 +1,9 @@
+use super::{align_up, TlsSegment};
+use crate::reloc::{tp_relative, RelocationResolver};
+use crate::{ElfLoaderErr, RelocationEntry};
+#[cfg(feature = "apply")]
+use crate::{RelocationType, VAddr};
+
 // Should be in xmas-elf see: https://github.com/nrc/xmas-elf/issues/54
 /// Relocation types for ARM 32-bit.
 ///
@@ -294,6 +300,8 @@ pub enum RelocationTypes {
     R_ARM_THM_ALU_ABS_G2_NC,
     /// Static, Thumb16, S + A.
     R_ARM_THM_ALU_ABS_G3,
+    /// Dynamic, call the resolver at B + A.
+    R_ARM_IRELATIVE,
     /// Unknown
     Unknown(u32),
 }
@@ -439,7 +447,72 @@ impl RelocationTypes {
             133 => R_ARM_THM_ALU_ABS_G1_NC,
             134 => R_ARM_THM_ALU_ABS_G2_NC,
             135 => R_ARM_THM_ALU_ABS_G3,
+            160 => R_ARM_IRELATIVE,
             x => Unknown(x),
         }
     }
 }
+
+/// Size of the thread control block the thread pointer points to.
+pub const TCB_SIZE: u64 = 8;
+
+/// Thread pointer relative offset of `value` in the TLS block of the main
+/// executable (TLS variant I: the block follows the TCB).
+pub fn tp_offset(tls: &TlsSegment, value: u64) -> i64 {
+    (align_up(TCB_SIZE, tls.align) + value) as i64
+}
+
+/// Computes the value and width (in bytes) of an ARM relocation, see
+/// [`RelocationEntry::evaluate`].
+pub(crate) fn evaluate<R: RelocationResolver>(
+    typ: RelocationTypes,
+    entry: &RelocationEntry,
+    resolver: &mut R,
+) -> Result<Option<(u64, usize)>, ElfLoaderErr> {
+    use RelocationTypes::*;
+
+    let i = entry.index;
+    let a = || entry.required_addend();
+    let b = resolver.load_bias();
+    let p = b.wrapping_add(entry.offset);
+    let value = match typ {
+        R_ARM_NONE => return Ok(None),
+        R_ARM_ABS32 => (resolver.symbol_value(i)?.wrapping_add(a()?), 4),
+        R_ARM_REL32 => (
+            resolver.symbol_value(i)?.wrapping_add(a()?).wrapping_sub(p),
+            4,
+        ),
+        R_ARM_GLOB_DAT | R_ARM_JUMP_SLOT => (resolver.symbol_value(i)?, 4),
+        R_ARM_RELATIVE => (b.wrapping_add(a()?), 4),
+        R_ARM_TLS_DTPMOD32 => (resolver.tls_module(i)? as u64, 4),
+        R_ARM_TLS_DTPOFF32 => (resolver.symbol_value(i)?.wrapping_add(a()?), 4),
+        R_ARM_TLS_TPOFF32 => (tp_relative(resolver, i, a()?)?, 4),
+        _ => return Err(ElfLoaderErr::UnsupportedRelocationEntry),
+    };
+    Ok(Some(value))
+}
+
+/// Applies an ARM dynamic relocation of a binary loaded at `base`.
+///
+/// `resolve_symbol` returns the (already relocated) address S of the symbol
+/// with the given index, `write` stores the 32-bit little-endian value at
+/// the given (relocated) address.
+///
+/// Supports the types [`RelocationEntry::evaluate`] handles, except for TLS
+/// relocations. Other types return `UnsupportedRelocationEntry`.
+#[cfg(feature = "apply")]
+pub fn apply<S, W>(
+    entry: RelocationEntry,
+    base: VAddr,
+    resolve_symbol: S,
+    write: W,
+) -> Result<(), ElfLoaderErr>
+where
+    S: FnMut(u32) -> Result<u64, ElfLoaderErr>,
+    W: FnMut(VAddr, &[u8]) -> Result<(), ElfLoaderErr>,
+{
+    match entry.rtype {
+        RelocationType::Arm(_) => super::apply(entry, base, resolve_symbol, write),
+        _ => Err(ElfLoaderErr::UnsupportedRelocationEntry),
+    }
+}
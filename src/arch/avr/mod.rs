@@ -0,0 +1,142 @@
+//! AVR relocation types
+//!
+//! As defined by the AVR port of binutils (`include/elf/avr.h`).
+//!
+//! AVR has separate program (flash, word addressed) and data address
+//! spaces; the `_PM` and `_GS` relocations refer to word addresses in
+//! program memory.
+
+#[cfg(test)]
+mod test;
+
+/// Machine number of Atmel AVR (not known to xmas-elf).
+pub const EM_AVR: u16 = 83;
+
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+#[allow(non_camel_case_types)]
+#[repr(u32)]
+pub enum RelocationTypes {
+    /// No relocation.
+    R_AVR_NONE,
+    /// Direct 32 bit.
+    R_AVR_32,
+    /// 7 bit PC relative branch offset (in words).
+    R_AVR_7_PCREL,
+    /// 13 bit PC relative `rjmp`/`rcall` offset (in words).
+    R_AVR_13_PCREL,
+    /// Direct 16 bit.
+    R_AVR_16,
+    /// 16 bit program memory (word) address.
+    R_AVR_16_PM,
+    /// Bits 0..7 of S + A in an `ldi` instruction.
+    R_AVR_LO8_LDI,
+    /// Bits 8..15 of S + A in an `ldi` instruction.
+    R_AVR_HI8_LDI,
+    /// Bits 16..23 of S + A in an `ldi` instruction.
+    R_AVR_HH8_LDI,
+    /// Bits 0..7 of -(S + A) in an `ldi` instruction.
+    R_AVR_LO8_LDI_NEG,
+    /// Bits 8..15 of -(S + A) in an `ldi` instruction.
+    R_AVR_HI8_LDI_NEG,
+    /// Bits 16..23 of -(S + A) in an `ldi` instruction.
+    R_AVR_HH8_LDI_NEG,
+    /// Bits 0..7 of the word address in an `ldi` instruction.
+    R_AVR_LO8_LDI_PM,
+    /// Bits 8..15 of the word address in an `ldi` instruction.
+    R_AVR_HI8_LDI_PM,
+    /// Bits 16..23 of the word address in an `ldi` instruction.
+    R_AVR_HH8_LDI_PM,
+    /// Bits 0..7 of the negated word address in an `ldi` instruction.
+    R_AVR_LO8_LDI_PM_NEG,
+    /// Bits 8..15 of the negated word address in an `ldi` instruction.
+    R_AVR_HI8_LDI_PM_NEG,
+    /// Bits 16..23 of the negated word address in an `ldi` instruction.
+    R_AVR_HH8_LDI_PM_NEG,
+    /// 22 bit word address in a `call`/`jmp` instruction.
+    R_AVR_CALL,
+    /// 8 bit immediate in an `ldi` instruction.
+    R_AVR_LDI,
+    /// 6 bit displacement in an `ldd`/`std` instruction.
+    R_AVR_6,
+    /// 6 bit immediate in an `adiw`/`sbiw` instruction.
+    R_AVR_6_ADIW,
+    /// Bits 24..31 of S + A in an `ldi` instruction.
+    R_AVR_MS8_LDI,
+    /// Bits 24..31 of -(S + A) in an `ldi` instruction.
+    R_AVR_MS8_LDI_NEG,
+    /// Bits 0..7 of the word address (or its stub) in an `ldi` instruction.
+    R_AVR_LO8_LDI_GS,
+    /// Bits 8..15 of the word address (or its stub) in an `ldi` instruction.
+    R_AVR_HI8_LDI_GS,
+    /// Direct 8 bit.
+    R_AVR_8,
+    /// Bits 0..7 of S + A.
+    R_AVR_8_LO8,
+    /// Bits 8..15 of S + A.
+    R_AVR_8_HI8,
+    /// Bits 16..23 of S + A.
+    R_AVR_8_HLO8,
+    /// 8 bit difference of two symbols (for linker relaxation).
+    R_AVR_DIFF8,
+    /// 16 bit difference of two symbols (for linker relaxation).
+    R_AVR_DIFF16,
+    /// 32 bit difference of two symbols (for linker relaxation).
+    R_AVR_DIFF32,
+    /// 7 bit address in a 16 bit `lds`/`sts` instruction.
+    R_AVR_LDS_STS_16,
+    /// 6 bit I/O port address in an `in`/`out` instruction.
+    R_AVR_PORT6,
+    /// 5 bit I/O port address in a `cbi`/`sbi` instruction.
+    R_AVR_PORT5,
+    /// 32 bit PC relative.
+    R_AVR_32_PCREL,
+    /// Unknown
+    Unknown(u32),
+}
+
+impl RelocationTypes {
+    /// Construct new avr::RelocationTypes
+    pub fn from(typ: u32) -> RelocationTypes {
+        use RelocationTypes::*;
+        match typ {
+            0 => R_AVR_NONE,
+            1 => R_AVR_32,
+            2 => R_AVR_7_PCREL,
+            3 => R_AVR_13_PCREL,
+            4 => R_AVR_16,
+            5 => R_AVR_16_PM,
+            6 => R_AVR_LO8_LDI,
+            7 => R_AVR_HI8_LDI,
+            8 => R_AVR_HH8_LDI,
+            9 => R_AVR_LO8_LDI_NEG,
+            10 => R_AVR_HI8_LDI_NEG,
+            11 => R_AVR_HH8_LDI_NEG,
+            12 => R_AVR_LO8_LDI_PM,
+            13 => R_AVR_HI8_LDI_PM,
+            14 => R_AVR_HH8_LDI_PM,
+            15 => R_AVR_LO8_LDI_PM_NEG,
+            16 => R_AVR_HI8_LDI_PM_NEG,
+            17 => R_AVR_HH8_LDI_PM_NEG,
+            18 => R_AVR_CALL,
+            19 => R_AVR_LDI,
+            20 => R_AVR_6,
+            21 => R_AVR_6_ADIW,
+            22 => R_AVR_MS8_LDI,
+            23 => R_AVR_MS8_LDI_NEG,
+            24 => R_AVR_LO8_LDI_GS,
+            25 => R_AVR_HI8_LDI_GS,
+            26 => R_AVR_8,
+            27 => R_AVR_8_LO8,
+            28 => R_AVR_8_HI8,
+            29 => R_AVR_8_HLO8,
+            30 => R_AVR_DIFF8,
+            31 => R_AVR_DIFF16,
+            32 => R_AVR_DIFF32,
+            33 => R_AVR_LDS_STS_16,
+            34 => R_AVR_PORT6,
+            35 => R_AVR_PORT5,
+            36 => R_AVR_32_PCREL,
+            x => Unknown(x),
+        }
+    }
+}
@@ -0,0 +1,22 @@
+use crate::arch::avr::RelocationTypes::{self, *};
+use crate::arch::avr::EM_AVR;
+use crate::arch::RelocationType;
+use crate::Machine;
+
+#[test]
+fn check_relocation_types() {
+    assert_eq!(RelocationTypes::from(0), R_AVR_NONE);
+    assert_eq!(RelocationTypes::from(1), R_AVR_32);
+    assert_eq!(RelocationTypes::from(4), R_AVR_16);
+    assert_eq!(RelocationTypes::from(5), R_AVR_16_PM);
+    assert_eq!(RelocationTypes::from(18), R_AVR_CALL);
+    assert_eq!(RelocationTypes::from(26), R_AVR_8);
+    assert_eq!(RelocationTypes::from(32), R_AVR_DIFF32);
+    assert_eq!(RelocationTypes::from(36), R_AVR_32_PCREL);
+    assert_eq!(RelocationTypes::from(37), Unknown(37));
+
+    assert!(matches!(
+        RelocationType::from(Machine::Other(EM_AVR), 18),
+        Ok(RelocationType::Avr(R_AVR_CALL))
+    ));
+}
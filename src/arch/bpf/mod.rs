@@ -0,0 +1,49 @@
+//! eBPF relocation types
+//!
+//! As defined in the Linux kernel documentation ("BPF LLVM Relocations")
+//! and LLVM's `BPF.def`.
+//!
+//! BPF programs are usually relocatable objects (ET_REL), the relocations
+//! are applied by the BPF loader rather than a dynamic linker.
+
+#[cfg(test)]
+mod test;
+
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+#[allow(non_camel_case_types)]
+#[repr(u32)]
+pub enum RelocationTypes {
+    /// No relocation.
+    R_BPF_NONE,
+    /// 64 bit immediate of an `ld_imm64` instruction: S + A.
+    R_BPF_64_64,
+    /// Direct 64 bit (data).
+    R_BPF_64_ABS64,
+    /// Direct 32 bit (data).
+    R_BPF_64_ABS32,
+    /// Direct 32 bit in sections not loaded at runtime (e.g. .BTF.ext).
+    R_BPF_64_NODYLD32,
+    /// Add load address of the program (used by Solana style runtimes).
+    R_BPF_64_RELATIVE,
+    /// 32 bit call offset in instructions: (S + A) / 8 - 1.
+    R_BPF_64_32,
+    /// Unknown
+    Unknown(u32),
+}
+
+impl RelocationTypes {
+    /// Construct new bpf::RelocationTypes
+    pub fn from(typ: u32) -> RelocationTypes {
+        use RelocationTypes::*;
+        match typ {
+            0 => R_BPF_NONE,
+            1 => R_BPF_64_64,
+            2 => R_BPF_64_ABS64,
+            3 => R_BPF_64_ABS32,
+            4 => R_BPF_64_NODYLD32,
+            8 => R_BPF_64_RELATIVE,
+            10 => R_BPF_64_32,
+            x => Unknown(x),
+        }
+    }
+}
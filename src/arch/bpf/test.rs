@@ -0,0 +1,20 @@
+use crate::arch::bpf::RelocationTypes::{self, *};
+use crate::arch::RelocationType;
+use crate::Machine;
+
+#[test]
+fn check_relocation_types() {
+    assert_eq!(RelocationTypes::from(0), R_BPF_NONE);
+    assert_eq!(RelocationTypes::from(1), R_BPF_64_64);
+    assert_eq!(RelocationTypes::from(2), R_BPF_64_ABS64);
+    assert_eq!(RelocationTypes::from(3), R_BPF_64_ABS32);
+    assert_eq!(RelocationTypes::from(4), R_BPF_64_NODYLD32);
+    assert_eq!(RelocationTypes::from(8), R_BPF_64_RELATIVE);
+    assert_eq!(RelocationTypes::from(10), R_BPF_64_32);
+    assert_eq!(RelocationTypes::from(5), Unknown(5));
+
+    assert!(matches!(
+        RelocationType::from(Machine::BPF, 1),
+        Ok(RelocationType::Bpf(R_BPF_64_64))
+    ));
+}
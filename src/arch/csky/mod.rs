@@ -0,0 +1,193 @@
+//! C-SKY relocation types
+//!
+//! As defined in the "C-SKY V2 CPU Applications Binary Interface Standards
+//! Manual" (abiv2).
+
+#[cfg(test)]
+mod test;
+
+/// Machine number of C-SKY (not known to xmas-elf).
+pub const EM_CSKY: u16 = 252;
+
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+#[allow(non_camel_case_types)]
+#[repr(u32)]
+pub enum RelocationTypes {
+    /// No relocation.
+    R_CKCORE_NONE,
+    /// Direct 32 bit: S + A.
+    R_CKCORE_ADDR32,
+    /// 8 bit PC relative offset, shifted by 2.
+    R_CKCORE_PCRELIMM8BY4,
+    /// 11 bit PC relative offset, shifted by 1.
+    R_CKCORE_PCRELIMM11BY2,
+    /// 32 bit PC relative: S + A - P.
+    R_CKCORE_PCREL32,
+    /// 11 bit PC relative `jsr` offset, shifted by 1.
+    R_CKCORE_PCRELJSR_IMM11BY2,
+    /// Add load address of shared object: B + A.
+    R_CKCORE_RELATIVE,
+    /// Copy data from shared object.
+    R_CKCORE_COPY,
+    /// Set GOT entry to data address.
+    R_CKCORE_GLOB_DAT,
+    /// Set GOT entry to code address.
+    R_CKCORE_JUMP_SLOT,
+    /// 32 bit offset to GOT: S + A - GOT.
+    R_CKCORE_GOTOFF,
+    /// 32 bit PC relative offset to GOT: GOT + A - P.
+    R_CKCORE_GOTPC,
+    /// 32 bit GOT entry offset.
+    R_CKCORE_GOT32,
+    /// 32 bit PLT entry offset.
+    R_CKCORE_PLT32,
+    /// 32 bit GOT entry address.
+    R_CKCORE_ADDRGOT,
+    /// 32 bit PLT entry address.
+    R_CKCORE_ADDRPLT,
+    /// 26 bit PC relative offset, shifted by 1.
+    R_CKCORE_PCREL_IMM26BY2,
+    /// 16 bit PC relative offset, shifted by 1.
+    R_CKCORE_PCREL_IMM16BY2,
+    /// 16 bit PC relative offset, shifted by 2.
+    R_CKCORE_PCREL_IMM16BY4,
+    /// 10 bit PC relative offset, shifted by 1.
+    R_CKCORE_PCREL_IMM10BY2,
+    /// 10 bit PC relative offset, shifted by 2.
+    R_CKCORE_PCREL_IMM10BY4,
+    /// High 16 bits of S + A.
+    R_CKCORE_ADDR_HI16,
+    /// Low 16 bits of S + A.
+    R_CKCORE_ADDR_LO16,
+    /// High 16 bits of the PC relative offset to GOT.
+    R_CKCORE_GOTPC_HI16,
+    /// Low 16 bits of the PC relative offset to GOT.
+    R_CKCORE_GOTPC_LO16,
+    /// High 16 bits of the offset to GOT.
+    R_CKCORE_GOTOFF_HI16,
+    /// Low 16 bits of the offset to GOT.
+    R_CKCORE_GOTOFF_LO16,
+    /// 12 bit GOT entry offset.
+    R_CKCORE_GOT12,
+    /// High 16 bits of the GOT entry offset.
+    R_CKCORE_GOT_HI16,
+    /// Low 16 bits of the GOT entry offset.
+    R_CKCORE_GOT_LO16,
+    /// 12 bit PLT entry offset.
+    R_CKCORE_PLT12,
+    /// High 16 bits of the PLT entry offset.
+    R_CKCORE_PLT_HI16,
+    /// Low 16 bits of the PLT entry offset.
+    R_CKCORE_PLT_LO16,
+    /// High 16 bits of the GOT entry address.
+    R_CKCORE_ADDRGOT_HI16,
+    /// Low 16 bits of the GOT entry address.
+    R_CKCORE_ADDRGOT_LO16,
+    /// High 16 bits of the PLT entry address.
+    R_CKCORE_ADDRPLT_HI16,
+    /// Low 16 bits of the PLT entry address.
+    R_CKCORE_ADDRPLT_LO16,
+    /// 26 bit PC relative `jsr` offset, shifted by 1.
+    R_CKCORE_PCREL_JSR_IMM26BY2,
+    /// Low 16 bits of the offset from the text base.
+    R_CKCORE_TOFFSET_LO16,
+    /// Low 16 bits of the offset from the data base.
+    R_CKCORE_DOFFSET_LO16,
+    /// 18 bit PC relative offset, shifted by 1.
+    R_CKCORE_PCREL_IMM18BY2,
+    /// 18 bit offset from the data base.
+    R_CKCORE_DOFFSET_IMM18,
+    /// 18 bit offset from the data base, shifted by 1.
+    R_CKCORE_DOFFSET_IMM18BY2,
+    /// 18 bit offset from the data base, shifted by 2.
+    R_CKCORE_DOFFSET_IMM18BY4,
+    /// 18 bit GOT entry offset, shifted by 2.
+    R_CKCORE_GOT_IMM18BY4,
+    /// 18 bit PLT entry offset, shifted by 2.
+    R_CKCORE_PLT_IMM18BY4,
+    /// 7 bit PC relative offset, shifted by 2.
+    R_CKCORE_PCREL_IMM7BY4,
+    /// 32 bit offset in static TLS block (local exec).
+    R_CKCORE_TLS_LE32,
+    /// 32 bit offset of the IE GOT entry.
+    R_CKCORE_TLS_IE32,
+    /// 32 bit offset of the GD GOT entry.
+    R_CKCORE_TLS_GD32,
+    /// 32 bit offset of the LDM GOT entry.
+    R_CKCORE_TLS_LDM32,
+    /// 32 bit offset in TLS block (local dynamic).
+    R_CKCORE_TLS_LDO32,
+    /// ID of module containing symbol.
+    R_CKCORE_TLS_DTPMOD32,
+    /// Offset in TLS block.
+    R_CKCORE_TLS_DTPOFF32,
+    /// Offset in static TLS block.
+    R_CKCORE_TLS_TPOFF32,
+    /// Unknown
+    Unknown(u32),
+}
+
+impl RelocationTypes {
+    /// Construct new csky::RelocationTypes
+    pub fn from(typ: u32) -> RelocationTypes {
+        use RelocationTypes::*;
+        match typ {
+            0 => R_CKCORE_NONE,
+            1 => R_CKCORE_ADDR32,
+            2 => R_CKCORE_PCRELIMM8BY4,
+            3 => R_CKCORE_PCRELIMM11BY2,
+            5 => R_CKCORE_PCREL32,
+            6 => R_CKCORE_PCRELJSR_IMM11BY2,
+            9 => R_CKCORE_RELATIVE,
+            10 => R_CKCORE_COPY,
+            11 => R_CKCORE_GLOB_DAT,
+            12 => R_CKCORE_JUMP_SLOT,
+            13 => R_CKCORE_GOTOFF,
+            14 => R_CKCORE_GOTPC,
+            15 => R_CKCORE_GOT32,
+            16 => R_CKCORE_PLT32,
+            17 => R_CKCORE_ADDRGOT,
+            18 => R_CKCORE_ADDRPLT,
+            19 => R_CKCORE_PCREL_IMM26BY2,
+            20 => R_CKCORE_PCREL_IMM16BY2,
+            21 => R_CKCORE_PCREL_IMM16BY4,
+            22 => R_CKCORE_PCREL_IMM10BY2,
+            23 => R_CKCORE_PCREL_IMM10BY4,
+            24 => R_CKCORE_ADDR_HI16,
+            25 => R_CKCORE_ADDR_LO16,
+            26 => R_CKCORE_GOTPC_HI16,
+            27 => R_CKCORE_GOTPC_LO16,
+            28 => R_CKCORE_GOTOFF_HI16,
+            29 => R_CKCORE_GOTOFF_LO16,
+            30 => R_CKCORE_GOT12,
+            31 => R_CKCORE_GOT_HI16,
+            32 => R_CKCORE_GOT_LO16,
+            33 => R_CKCORE_PLT12,
+            34 => R_CKCORE_PLT_HI16,
+            35 => R_CKCORE_PLT_LO16,
+            36 => R_CKCORE_ADDRGOT_HI16,
+            37 => R_CKCORE_ADDRGOT_LO16,
+            38 => R_CKCORE_ADDRPLT_HI16,
+            39 => R_CKCORE_ADDRPLT_LO16,
+            40 => R_CKCORE_PCREL_JSR_IMM26BY2,
+            41 => R_CKCORE_TOFFSET_LO16,
+            42 => R_CKCORE_DOFFSET_LO16,
+            43 => R_CKCORE_PCREL_IMM18BY2,
+            44 => R_CKCORE_DOFFSET_IMM18,
+            45 => R_CKCORE_DOFFSET_IMM18BY2,
+            46 => R_CKCORE_DOFFSET_IMM18BY4,
+            48 => R_CKCORE_GOT_IMM18BY4,
+            49 => R_CKCORE_PLT_IMM18BY4,
+            50 => R_CKCORE_PCREL_IMM7BY4,
+            51 => R_CKCORE_TLS_LE32,
+            52 => R_CKCORE_TLS_IE32,
+            53 => R_CKCORE_TLS_GD32,
+            54 => R_CKCORE_TLS_LDM32,
+            55 => R_CKCORE_TLS_LDO32,
+            56 => R_CKCORE_TLS_DTPMOD32,
+            57 => R_CKCORE_TLS_DTPOFF32,
+            58 => R_CKCORE_TLS_TPOFF32,
+            x => Unknown(x),
+        }
+    }
+}
@@ -0,0 +1,24 @@
+use crate::arch::csky::RelocationTypes::{self, *};
+use crate::arch::csky::EM_CSKY;
+use crate::arch::RelocationType;
+use crate::Machine;
+
+#[test]
+fn check_relocation_types() {
+    assert_eq!(RelocationTypes::from(0), R_CKCORE_NONE);
+    assert_eq!(RelocationTypes::from(1), R_CKCORE_ADDR32);
+    assert_eq!(RelocationTypes::from(9), R_CKCORE_RELATIVE);
+    assert_eq!(RelocationTypes::from(10), R_CKCORE_COPY);
+    assert_eq!(RelocationTypes::from(11), R_CKCORE_GLOB_DAT);
+    assert_eq!(RelocationTypes::from(12), R_CKCORE_JUMP_SLOT);
+    assert_eq!(RelocationTypes::from(56), R_CKCORE_TLS_DTPMOD32);
+    assert_eq!(RelocationTypes::from(57), R_CKCORE_TLS_DTPOFF32);
+    assert_eq!(RelocationTypes::from(58), R_CKCORE_TLS_TPOFF32);
+    assert_eq!(RelocationTypes::from(4), Unknown(4));
+    assert_eq!(RelocationTypes::from(59), Unknown(59));
+
+    assert!(matches!(
+        RelocationType::from(Machine::Other(EM_CSKY), 9),
+        Ok(RelocationType::Csky(R_CKCORE_RELATIVE))
+    ));
+}
@@ -0,0 +1,331 @@
+//! Hexagon relocation types
+//!
+//! As defined in the "Qualcomm Hexagon Application Binary Interface User
+//! Guide" (80-N2040-23).
+//!
+//! The `_X` variants relocate instructions that are combined with a
+//! constant extender, which supplies the upper 26 bits of the value.
+
+#[cfg(test)]
+mod test;
+
+/// Machine number of Qualcomm Hexagon (not known to xmas-elf).
+pub const EM_HEXAGON: u16 = 164;
+
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+#[allow(non_camel_case_types)]
+#[repr(u32)]
+pub enum RelocationTypes {
+    /// No relocation.
+    R_HEX_NONE,
+    /// 22 bit PC relative branch, shifted by 2.
+    R_HEX_B22_PCREL,
+    /// 15 bit PC relative branch, shifted by 2.
+    R_HEX_B15_PCREL,
+    /// 7 bit PC relative branch, shifted by 2.
+    R_HEX_B7_PCREL,
+    /// Low 16 bits of S + A.
+    R_HEX_LO16,
+    /// High 16 bits of S + A.
+    R_HEX_HI16,
+    /// Direct 32 bit.
+    R_HEX_32,
+    /// Direct 16 bit.
+    R_HEX_16,
+    /// Direct 8 bit.
+    R_HEX_8,
+    /// 16 bit GP relative offset (byte access).
+    R_HEX_GPREL16_0,
+    /// 16 bit GP relative offset (halfword access).
+    R_HEX_GPREL16_1,
+    /// 16 bit GP relative offset (word access).
+    R_HEX_GPREL16_2,
+    /// 16 bit GP relative offset (doubleword access).
+    R_HEX_GPREL16_3,
+    /// Low and high 16 bits of S + A in an instruction pair.
+    R_HEX_HL16,
+    /// 13 bit PC relative branch, shifted by 2.
+    R_HEX_B13_PCREL,
+    /// 9 bit PC relative branch, shifted by 2.
+    R_HEX_B9_PCREL,
+    /// Upper 26 bits of a 32 bit PC relative branch (constant extender).
+    R_HEX_B32_PCREL_X,
+    /// Upper 26 bits of a 32 bit value (constant extender).
+    R_HEX_32_6_X,
+    /// Lower 6 bits of an extended 22 bit PC relative branch.
+    R_HEX_B22_PCREL_X,
+    /// Lower 6 bits of an extended 15 bit PC relative branch.
+    R_HEX_B15_PCREL_X,
+    /// Lower 6 bits of an extended 13 bit PC relative branch.
+    R_HEX_B13_PCREL_X,
+    /// Lower 6 bits of an extended 9 bit PC relative branch.
+    R_HEX_B9_PCREL_X,
+    /// Lower 6 bits of an extended 7 bit PC relative branch.
+    R_HEX_B7_PCREL_X,
+    /// Lower 6 bits of an extended 16 bit immediate.
+    R_HEX_16_X,
+    /// Lower 6 bits of an extended 12 bit immediate.
+    R_HEX_12_X,
+    /// Lower 6 bits of an extended 11 bit immediate.
+    R_HEX_11_X,
+    /// Lower 6 bits of an extended 10 bit immediate.
+    R_HEX_10_X,
+    /// Lower 6 bits of an extended 9 bit immediate.
+    R_HEX_9_X,
+    /// Lower 6 bits of an extended 8 bit immediate.
+    R_HEX_8_X,
+    /// Lower 6 bits of an extended 7 bit immediate.
+    R_HEX_7_X,
+    /// Lower 6 bits of an extended 6 bit immediate.
+    R_HEX_6_X,
+    /// 32 bit PC relative.
+    R_HEX_32_PCREL,
+    /// Copy data from shared object.
+    R_HEX_COPY,
+    /// Set GOT entry to data address.
+    R_HEX_GLOB_DAT,
+    /// Set GOT entry to code address.
+    R_HEX_JMP_SLOT,
+    /// Add load address of shared object.
+    R_HEX_RELATIVE,
+    /// 22 bit PC relative branch to the PLT entry.
+    R_HEX_PLT_B22_PCREL,
+    /// Low 16 bits of the GOT relative offset of the symbol.
+    R_HEX_GOTREL_LO16,
+    /// High 16 bits of the GOT relative offset of the symbol.
+    R_HEX_GOTREL_HI16,
+    /// 32 bit GOT relative offset of the symbol.
+    R_HEX_GOTREL_32,
+    /// Low 16 bits of the GOT entry offset.
+    R_HEX_GOT_LO16,
+    /// High 16 bits of the GOT entry offset.
+    R_HEX_GOT_HI16,
+    /// 32 bit GOT entry offset.
+    R_HEX_GOT_32,
+    /// 16 bit GOT entry offset.
+    R_HEX_GOT_16,
+    /// 32 bit ID of module containing symbol.
+    R_HEX_DTPMOD_32,
+    /// Low 16 bits of the offset in the TLS block.
+    R_HEX_DTPREL_LO16,
+    /// High 16 bits of the offset in the TLS block.
+    R_HEX_DTPREL_HI16,
+    /// 32 bit offset in the TLS block.
+    R_HEX_DTPREL_32,
+    /// 16 bit offset in the TLS block.
+    R_HEX_DTPREL_16,
+    /// 22 bit PC relative call to __tls_get_addr for GD.
+    R_HEX_GD_PLT_B22_PCREL,
+    /// Low 16 bits of the GD GOT entry offset.
+    R_HEX_GD_GOT_LO16,
+    /// High 16 bits of the GD GOT entry offset.
+    R_HEX_GD_GOT_HI16,
+    /// 32 bit GD GOT entry offset.
+    R_HEX_GD_GOT_32,
+    /// 16 bit GD GOT entry offset.
+    R_HEX_GD_GOT_16,
+    /// Low 16 bits of the IE GOT entry address.
+    R_HEX_IE_LO16,
+    /// High 16 bits of the IE GOT entry address.
+    R_HEX_IE_HI16,
+    /// 32 bit IE GOT entry address.
+    R_HEX_IE_32,
+    /// Low 16 bits of the IE GOT entry offset.
+    R_HEX_IE_GOT_LO16,
+    /// High 16 bits of the IE GOT entry offset.
+    R_HEX_IE_GOT_HI16,
+    /// 32 bit IE GOT entry offset.
+    R_HEX_IE_GOT_32,
+    /// 16 bit IE GOT entry offset.
+    R_HEX_IE_GOT_16,
+    /// Low 16 bits of the offset in the static TLS block.
+    R_HEX_TPREL_LO16,
+    /// High 16 bits of the offset in the static TLS block.
+    R_HEX_TPREL_HI16,
+    /// 32 bit offset in the static TLS block.
+    R_HEX_TPREL_32,
+    /// 16 bit offset in the static TLS block.
+    R_HEX_TPREL_16,
+    /// Lower 6 bits of an extended PC relative value.
+    R_HEX_6_PCREL_X,
+    /// Upper 26 bits of the GOT relative offset (constant extender).
+    R_HEX_GOTREL_32_6_X,
+    /// Lower 6 bits of an extended 16 bit GOT relative offset.
+    R_HEX_GOTREL_16_X,
+    /// Lower 6 bits of an extended 11 bit GOT relative offset.
+    R_HEX_GOTREL_11_X,
+    /// Upper 26 bits of the GOT entry offset (constant extender).
+    R_HEX_GOT_32_6_X,
+    /// Lower 6 bits of an extended 16 bit GOT entry offset.
+    R_HEX_GOT_16_X,
+    /// Lower 6 bits of an extended 11 bit GOT entry offset.
+    R_HEX_GOT_11_X,
+    /// Upper 26 bits of the offset in the TLS block (constant extender).
+    R_HEX_DTPREL_32_6_X,
+    /// Lower 6 bits of an extended 16 bit offset in the TLS block.
+    R_HEX_DTPREL_16_X,
+    /// Lower 6 bits of an extended 11 bit offset in the TLS block.
+    R_HEX_DTPREL_11_X,
+    /// Upper 26 bits of the GD GOT entry offset (constant extender).
+    R_HEX_GD_GOT_32_6_X,
+    /// Lower 6 bits of an extended 16 bit GD GOT entry offset.
+    R_HEX_GD_GOT_16_X,
+    /// Lower 6 bits of an extended 11 bit GD GOT entry offset.
+    R_HEX_GD_GOT_11_X,
+    /// Upper 26 bits of the IE GOT entry address (constant extender).
+    R_HEX_IE_32_6_X,
+    /// Lower 6 bits of an extended 16 bit IE GOT entry address.
+    R_HEX_IE_16_X,
+    /// Upper 26 bits of the IE GOT entry offset (constant extender).
+    R_HEX_IE_GOT_32_6_X,
+    /// Lower 6 bits of an extended 16 bit IE GOT entry offset.
+    R_HEX_IE_GOT_16_X,
+    /// Lower 6 bits of an extended 11 bit IE GOT entry offset.
+    R_HEX_IE_GOT_11_X,
+    /// Upper 26 bits of the offset in the static TLS block (constant extender).
+    R_HEX_TPREL_32_6_X,
+    /// Lower 6 bits of an extended 16 bit offset in the static TLS block.
+    R_HEX_TPREL_16_X,
+    /// Lower 6 bits of an extended 11 bit offset in the static TLS block.
+    R_HEX_TPREL_11_X,
+    /// 22 bit PC relative call to __tls_get_addr for LD.
+    R_HEX_LD_PLT_B22_PCREL,
+    /// Low 16 bits of the LD GOT entry offset.
+    R_HEX_LD_GOT_LO16,
+    /// High 16 bits of the LD GOT entry offset.
+    R_HEX_LD_GOT_HI16,
+    /// 32 bit LD GOT entry offset.
+    R_HEX_LD_GOT_32,
+    /// 16 bit LD GOT entry offset.
+    R_HEX_LD_GOT_16,
+    /// Upper 26 bits of the LD GOT entry offset (constant extender).
+    R_HEX_LD_GOT_32_6_X,
+    /// Lower 6 bits of an extended 16 bit LD GOT entry offset.
+    R_HEX_LD_GOT_16_X,
+    /// Lower 6 bits of an extended 11 bit LD GOT entry offset.
+    R_HEX_LD_GOT_11_X,
+    /// 23 bit register relative value.
+    R_HEX_23_REG,
+    /// Lower 6 bits of an extended call to __tls_get_addr for GD.
+    R_HEX_GD_PLT_B22_PCREL_X,
+    /// Upper 26 bits of an extended call to __tls_get_addr for GD.
+    R_HEX_GD_PLT_B32_PCREL_X,
+    /// Lower 6 bits of an extended call to __tls_get_addr for LD.
+    R_HEX_LD_PLT_B22_PCREL_X,
+    /// Upper 26 bits of an extended call to __tls_get_addr for LD.
+    R_HEX_LD_PLT_B32_PCREL_X,
+    /// 27 bit register relative value.
+    R_HEX_27_REG,
+    /// Unknown
+    Unknown(u32),
+}
+
+impl RelocationTypes {
+    /// Construct new hexagon::RelocationTypes
+    pub fn from(typ: u32) -> RelocationTypes {
+        use RelocationTypes::*;
+        match typ {
+            0 => R_HEX_NONE,
+            1 => R_HEX_B22_PCREL,
+            2 => R_HEX_B15_PCREL,
+            3 => R_HEX_B7_PCREL,
+            4 => R_HEX_LO16,
+            5 => R_HEX_HI16,
+            6 => R_HEX_32,
+            7 => R_HEX_16,
+            8 => R_HEX_8,
+            9 => R_HEX_GPREL16_0,
+            10 => R_HEX_GPREL16_1,
+            11 => R_HEX_GPREL16_2,
+            12 => R_HEX_GPREL16_3,
+            13 => R_HEX_HL16,
+            14 => R_HEX_B13_PCREL,
+            15 => R_HEX_B9_PCREL,
+            16 => R_HEX_B32_PCREL_X,
+            17 => R_HEX_32_6_X,
+            18 => R_HEX_B22_PCREL_X,
+            19 => R_HEX_B15_PCREL_X,
+            20 => R_HEX_B13_PCREL_X,
+            21 => R_HEX_B9_PCREL_X,
+            22 => R_HEX_B7_PCREL_X,
+            23 => R_HEX_16_X,
+            24 => R_HEX_12_X,
+            25 => R_HEX_11_X,
+            26 => R_HEX_10_X,
+            27 => R_HEX_9_X,
+            28 => R_HEX_8_X,
+            29 => R_HEX_7_X,
+            30 => R_HEX_6_X,
+            31 => R_HEX_32_PCREL,
+            32 => R_HEX_COPY,
+            33 => R_HEX_GLOB_DAT,
+            34 => R_HEX_JMP_SLOT,
+            35 => R_HEX_RELATIVE,
+            36 => R_HEX_PLT_B22_PCREL,
+            37 => R_HEX_GOTREL_LO16,
+            38 => R_HEX_GOTREL_HI16,
+            39 => R_HEX_GOTREL_32,
+            40 => R_HEX_GOT_LO16,
+            41 => R_HEX_GOT_HI16,
+            42 => R_HEX_GOT_32,
+            43 => R_HEX_GOT_16,
+            44 => R_HEX_DTPMOD_32,
+            45 => R_HEX_DTPREL_LO16,
+            46 => R_HEX_DTPREL_HI16,
+            47 => R_HEX_DTPREL_32,
+            48 => R_HEX_DTPREL_16,
+            49 => R_HEX_GD_PLT_B22_PCREL,
+            50 => R_HEX_GD_GOT_LO16,
+            51 => R_HEX_GD_GOT_HI16,
+            52 => R_HEX_GD_GOT_32,
+            53 => R_HEX_GD_GOT_16,
+            54 => R_HEX_IE_LO16,
+            55 => R_HEX_IE_HI16,
+            56 => R_HEX_IE_32,
+            57 => R_HEX_IE_GOT_LO16,
+            58 => R_HEX_IE_GOT_HI16,
+            59 => R_HEX_IE_GOT_32,
+            60 => R_HEX_IE_GOT_16,
+            61 => R_HEX_TPREL_LO16,
+            62 => R_HEX_TPREL_HI16,
+            63 => R_HEX_TPREL_32,
+            64 => R_HEX_TPREL_16,
+            65 => R_HEX_6_PCREL_X,
+            66 => R_HEX_GOTREL_32_6_X,
+            67 => R_HEX_GOTREL_16_X,
+            68 => R_HEX_GOTREL_11_X,
+            69 => R_HEX_GOT_32_6_X,
+            70 => R_HEX_GOT_16_X,
+            71 => R_HEX_GOT_11_X,
+            72 => R_HEX_DTPREL_32_6_X,
+            73 => R_HEX_DTPREL_16_X,
+            74 => R_HEX_DTPREL_11_X,
+            75 => R_HEX_GD_GOT_32_6_X,
+            76 => R_HEX_GD_GOT_16_X,
+            77 => R_HEX_GD_GOT_11_X,
+            78 => R_HEX_IE_32_6_X,
+            79 => R_HEX_IE_16_X,
+            80 => R_HEX_IE_GOT_32_6_X,
+            81 => R_HEX_IE_GOT_16_X,
+            82 => R_HEX_IE_GOT_11_X,
+            83 => R_HEX_TPREL_32_6_X,
+            84 => R_HEX_TPREL_16_X,
+            85 => R_HEX_TPREL_11_X,
+            86 => R_HEX_LD_PLT_B22_PCREL,
+            87 => R_HEX_LD_GOT_LO16,
+            88 => R_HEX_LD_GOT_HI16,
+            89 => R_HEX_LD_GOT_32,
+            90 => R_HEX_LD_GOT_16,
+            91 => R_HEX_LD_GOT_32_6_X,
+            92 => R_HEX_LD_GOT_16_X,
+            93 => R_HEX_LD_GOT_11_X,
+            94 => R_HEX_23_REG,
+            95 => R_HEX_GD_PLT_B22_PCREL_X,
+            96 => R_HEX_GD_PLT_B32_PCREL_X,
+            97 => R_HEX_LD_PLT_B22_PCREL_X,
+            98 => R_HEX_LD_PLT_B32_PCREL_X,
+            99 => R_HEX_27_REG,
+            x => Unknown(x),
+        }
+    }
+}
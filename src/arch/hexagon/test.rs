@@ -0,0 +1,24 @@
+use crate::arch::hexagon::RelocationTypes::{self, *};
+use crate::arch::hexagon::EM_HEXAGON;
+use crate::arch::RelocationType;
+use crate::Machine;
+
+#[test]
+fn check_relocation_types() {
+    assert_eq!(RelocationTypes::from(0), R_HEX_NONE);
+    assert_eq!(RelocationTypes::from(6), R_HEX_32);
+    assert_eq!(RelocationTypes::from(32), R_HEX_COPY);
+    assert_eq!(RelocationTypes::from(33), R_HEX_GLOB_DAT);
+    assert_eq!(RelocationTypes::from(34), R_HEX_JMP_SLOT);
+    assert_eq!(RelocationTypes::from(35), R_HEX_RELATIVE);
+    assert_eq!(RelocationTypes::from(44), R_HEX_DTPMOD_32);
+    assert_eq!(RelocationTypes::from(47), R_HEX_DTPREL_32);
+    assert_eq!(RelocationTypes::from(63), R_HEX_TPREL_32);
+    assert_eq!(RelocationTypes::from(99), R_HEX_27_REG);
+    assert_eq!(RelocationTypes::from(100), Unknown(100));
+
+    assert!(matches!(
+        RelocationType::from(Machine::Other(EM_HEXAGON), 35),
+        Ok(RelocationType::Hexagon(R_HEX_RELATIVE))
+    ));
+}
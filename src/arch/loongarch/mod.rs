@@ -0,0 +1,382 @@
+//! LoongArch relocation types
+//!
+//! As defined in the "ELF for the LoongArch Architecture" specification
+//! (LoongArch ELF psABI v2.30).
+//!
+//! The SOP_* stack machine relocations are only emitted by old toolchains
+//! (before binutils 2.40) and are kept so such objects can be recognized.
+
+#[cfg(test)]
+mod test;
+
+/// Machine number of LoongArch (not known to xmas-elf).
+pub const EM_LOONGARCH: u16 = 258;
+
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+#[allow(non_camel_case_types)]
+#[repr(u32)]
+pub enum RelocationTypes {
+    /// No relocation.
+    R_LARCH_NONE,
+    /// Runtime address resolving, 32 bit: S + A.
+    R_LARCH_32,
+    /// Runtime address resolving, 64 bit: S + A.
+    R_LARCH_64,
+    /// Runtime fixup for load address: B + A.
+    R_LARCH_RELATIVE,
+    /// Copy data from shared object.
+    R_LARCH_COPY,
+    /// Set PLT GOT entry to code address.
+    R_LARCH_JUMP_SLOT,
+    /// 32 bit ID of module containing symbol.
+    R_LARCH_TLS_DTPMOD32,
+    /// 64 bit ID of module containing symbol.
+    R_LARCH_TLS_DTPMOD64,
+    /// 32 bit offset in TLS block.
+    R_LARCH_TLS_DTPREL32,
+    /// 64 bit offset in TLS block.
+    R_LARCH_TLS_DTPREL64,
+    /// 32 bit offset in static TLS block.
+    R_LARCH_TLS_TPREL32,
+    /// 64 bit offset in static TLS block.
+    R_LARCH_TLS_TPREL64,
+    /// Set GOT entry to the result of calling the resolver at B + A.
+    R_LARCH_IRELATIVE,
+    /// 32 bit TLS descriptor.
+    R_LARCH_TLS_DESC32,
+    /// 64 bit TLS descriptor.
+    R_LARCH_TLS_DESC64,
+    /// Marks a `la.abs` pseudo instruction.
+    R_LARCH_MARK_LA,
+    /// Marks an external jump.
+    R_LARCH_MARK_PCREL,
+    /// Push S - P (stack machine relocation, deprecated).
+    R_LARCH_SOP_PUSH_PCREL,
+    /// Push S + A (stack machine relocation, deprecated).
+    R_LARCH_SOP_PUSH_ABSOLUTE,
+    /// Duplicate the top of the stack (deprecated).
+    R_LARCH_SOP_PUSH_DUP,
+    /// Push the GOT offset of the symbol (deprecated).
+    R_LARCH_SOP_PUSH_GPREL,
+    /// Push the TP offset of the symbol (deprecated).
+    R_LARCH_SOP_PUSH_TLS_TPREL,
+    /// Push the GOT offset of the IE entry (deprecated).
+    R_LARCH_SOP_PUSH_TLS_GOT,
+    /// Push the GOT offset of the GD entry (deprecated).
+    R_LARCH_SOP_PUSH_TLS_GD,
+    /// Push the PC relative PLT address (deprecated).
+    R_LARCH_SOP_PUSH_PLT_PCREL,
+    /// Pop and assert the value is not zero (deprecated).
+    R_LARCH_SOP_ASSERT,
+    /// Logical not of the top of the stack (deprecated).
+    R_LARCH_SOP_NOT,
+    /// Pop two values and push their difference (deprecated).
+    R_LARCH_SOP_SUB,
+    /// Pop two values and push the left shift (deprecated).
+    R_LARCH_SOP_SL,
+    /// Pop two values and push the right shift (deprecated).
+    R_LARCH_SOP_SR,
+    /// Pop two values and push their sum (deprecated).
+    R_LARCH_SOP_ADD,
+    /// Pop two values and push the bitwise and (deprecated).
+    R_LARCH_SOP_AND,
+    /// Pop three values and push the selected one (deprecated).
+    R_LARCH_SOP_IF_ELSE,
+    /// Pop into a signed 5 bit field at bit 10 (deprecated).
+    R_LARCH_SOP_POP_32_S_10_5,
+    /// Pop into an unsigned 12 bit field at bit 10 (deprecated).
+    R_LARCH_SOP_POP_32_U_10_12,
+    /// Pop into a signed 12 bit field at bit 10 (deprecated).
+    R_LARCH_SOP_POP_32_S_10_12,
+    /// Pop into a signed 16 bit field at bit 10 (deprecated).
+    R_LARCH_SOP_POP_32_S_10_16,
+    /// Pop into a signed 16 bit field at bit 10, shifted by 2 (deprecated).
+    R_LARCH_SOP_POP_32_S_10_16_S2,
+    /// Pop into a signed 20 bit field at bit 5 (deprecated).
+    R_LARCH_SOP_POP_32_S_5_20,
+    /// Pop into a signed 21 bit branch offset, shifted by 2 (deprecated).
+    R_LARCH_SOP_POP_32_S_0_5_10_16_S2,
+    /// Pop into a signed 26 bit branch offset, shifted by 2 (deprecated).
+    R_LARCH_SOP_POP_32_S_0_10_10_16_S2,
+    /// Pop into an unsigned 32 bit word (deprecated).
+    R_LARCH_SOP_POP_32_U,
+    /// 8 bit in-place addition.
+    R_LARCH_ADD8,
+    /// 16 bit in-place addition.
+    R_LARCH_ADD16,
+    /// 24 bit in-place addition.
+    R_LARCH_ADD24,
+    /// 32 bit in-place addition.
+    R_LARCH_ADD32,
+    /// 64 bit in-place addition.
+    R_LARCH_ADD64,
+    /// 8 bit in-place subtraction.
+    R_LARCH_SUB8,
+    /// 16 bit in-place subtraction.
+    R_LARCH_SUB16,
+    /// 24 bit in-place subtraction.
+    R_LARCH_SUB24,
+    /// 32 bit in-place subtraction.
+    R_LARCH_SUB32,
+    /// 64 bit in-place subtraction.
+    R_LARCH_SUB64,
+    /// GNU C++ vtable hierarchy.
+    R_LARCH_GNU_VTINHERIT,
+    /// GNU C++ vtable member usage.
+    R_LARCH_GNU_VTENTRY,
+    /// 18 bit PC relative branch, shifted by 2.
+    R_LARCH_B16,
+    /// 23 bit PC relative branch, shifted by 2.
+    R_LARCH_B21,
+    /// 28 bit PC relative branch, shifted by 2.
+    R_LARCH_B26,
+    /// Bits 12..31 of an absolute address.
+    R_LARCH_ABS_HI20,
+    /// Bits 0..11 of an absolute address.
+    R_LARCH_ABS_LO12,
+    /// Bits 32..51 of an absolute address.
+    R_LARCH_ABS64_LO20,
+    /// Bits 52..63 of an absolute address.
+    R_LARCH_ABS64_HI12,
+    /// Bits 12..31 of the page offset of the symbol (`pcalau12i`).
+    R_LARCH_PCALA_HI20,
+    /// Bits 0..11 of the symbol address.
+    R_LARCH_PCALA_LO12,
+    /// Bits 32..51 of the page offset of the symbol.
+    R_LARCH_PCALA64_LO20,
+    /// Bits 52..63 of the page offset of the symbol.
+    R_LARCH_PCALA64_HI12,
+    /// Bits 12..31 of the page offset of the GOT entry.
+    R_LARCH_GOT_PC_HI20,
+    /// Bits 0..11 of the GOT entry address.
+    R_LARCH_GOT_PC_LO12,
+    /// Bits 32..51 of the page offset of the GOT entry.
+    R_LARCH_GOT64_PC_LO20,
+    /// Bits 52..63 of the page offset of the GOT entry.
+    R_LARCH_GOT64_PC_HI12,
+    /// Bits 12..31 of the GOT entry address.
+    R_LARCH_GOT_HI20,
+    /// Bits 0..11 of the GOT entry address.
+    R_LARCH_GOT_LO12,
+    /// Bits 32..51 of the GOT entry address.
+    R_LARCH_GOT64_LO20,
+    /// Bits 52..63 of the GOT entry address.
+    R_LARCH_GOT64_HI12,
+    /// Bits 12..31 of the TP offset.
+    R_LARCH_TLS_LE_HI20,
+    /// Bits 0..11 of the TP offset.
+    R_LARCH_TLS_LE_LO12,
+    /// Bits 32..51 of the TP offset.
+    R_LARCH_TLS_LE64_LO20,
+    /// Bits 52..63 of the TP offset.
+    R_LARCH_TLS_LE64_HI12,
+    /// Bits 12..31 of the page offset of the IE GOT entry.
+    R_LARCH_TLS_IE_PC_HI20,
+    /// Bits 0..11 of the IE GOT entry address.
+    R_LARCH_TLS_IE_PC_LO12,
+    /// Bits 32..51 of the page offset of the IE GOT entry.
+    R_LARCH_TLS_IE64_PC_LO20,
+    /// Bits 52..63 of the page offset of the IE GOT entry.
+    R_LARCH_TLS_IE64_PC_HI12,
+    /// Bits 12..31 of the IE GOT entry address.
+    R_LARCH_TLS_IE_HI20,
+    /// Bits 0..11 of the IE GOT entry address.
+    R_LARCH_TLS_IE_LO12,
+    /// Bits 32..51 of the IE GOT entry address.
+    R_LARCH_TLS_IE64_LO20,
+    /// Bits 52..63 of the IE GOT entry address.
+    R_LARCH_TLS_IE64_HI12,
+    /// Bits 12..31 of the page offset of the LD GOT entry.
+    R_LARCH_TLS_LD_PC_HI20,
+    /// Bits 12..31 of the LD GOT entry address.
+    R_LARCH_TLS_LD_HI20,
+    /// Bits 12..31 of the page offset of the GD GOT entry.
+    R_LARCH_TLS_GD_PC_HI20,
+    /// Bits 12..31 of the GD GOT entry address.
+    R_LARCH_TLS_GD_HI20,
+    /// 32 bit PC relative: S + A - P.
+    R_LARCH_32_PCREL,
+    /// Marks an instruction that may be relaxed.
+    R_LARCH_RELAX,
+    /// Reserved.
+    R_LARCH_DELETE,
+    /// Alignment requirement for linker relaxation.
+    R_LARCH_ALIGN,
+    /// 22 bit PC relative offset, shifted by 2 (`pcaddi`).
+    R_LARCH_PCREL20_S2,
+    /// Reserved.
+    R_LARCH_CFA,
+    /// 6 bit in-place addition.
+    R_LARCH_ADD6,
+    /// 6 bit in-place subtraction.
+    R_LARCH_SUB6,
+    /// ULEB128 in-place addition.
+    R_LARCH_ADD_ULEB128,
+    /// ULEB128 in-place subtraction.
+    R_LARCH_SUB_ULEB128,
+    /// 64 bit PC relative: S + A - P.
+    R_LARCH_64_PCREL,
+    /// 38 bit PC relative call (`pcaddu18i` + `jirl`).
+    R_LARCH_CALL36,
+    /// Bits 12..31 of the page offset of the TLS descriptor.
+    R_LARCH_TLS_DESC_PC_HI20,
+    /// Bits 0..11 of the TLS descriptor address.
+    R_LARCH_TLS_DESC_PC_LO12,
+    /// Bits 32..51 of the page offset of the TLS descriptor.
+    R_LARCH_TLS_DESC64_PC_LO20,
+    /// Bits 52..63 of the page offset of the TLS descriptor.
+    R_LARCH_TLS_DESC64_PC_HI12,
+    /// Bits 12..31 of the TLS descriptor address.
+    R_LARCH_TLS_DESC_HI20,
+    /// Bits 0..11 of the TLS descriptor address.
+    R_LARCH_TLS_DESC_LO12,
+    /// Bits 32..51 of the TLS descriptor address.
+    R_LARCH_TLS_DESC64_LO20,
+    /// Bits 52..63 of the TLS descriptor address.
+    R_LARCH_TLS_DESC64_HI12,
+    /// Marks the load of the TLS descriptor function.
+    R_LARCH_TLS_DESC_LD,
+    /// Marks the call of the TLS descriptor function.
+    R_LARCH_TLS_DESC_CALL,
+    /// Bits 12..31 of the TP offset (relaxable).
+    R_LARCH_TLS_LE_HI20_R,
+    /// Marks the add of the thread pointer (relaxable).
+    R_LARCH_TLS_LE_ADD_R,
+    /// Bits 0..11 of the TP offset (relaxable).
+    R_LARCH_TLS_LE_LO12_R,
+    /// 22 bit PC relative offset of the LD GOT entry, shifted by 2.
+    R_LARCH_TLS_LD_PCREL20_S2,
+    /// 22 bit PC relative offset of the GD GOT entry, shifted by 2.
+    R_LARCH_TLS_GD_PCREL20_S2,
+    /// 22 bit PC relative offset of the TLS descriptor, shifted by 2.
+    R_LARCH_TLS_DESC_PCREL20_S2,
+    /// Unknown
+    Unknown(u32),
+}
+
+impl RelocationTypes {
+    /// Construct new loongarch::RelocationTypes
+    pub fn from(typ: u32) -> RelocationTypes {
+        use RelocationTypes::*;
+        match typ {
+            0 => R_LARCH_NONE,
+            1 => R_LARCH_32,
+            2 => R_LARCH_64,
+            3 => R_LARCH_RELATIVE,
+            4 => R_LARCH_COPY,
+            5 => R_LARCH_JUMP_SLOT,
+            6 => R_LARCH_TLS_DTPMOD32,
+            7 => R_LARCH_TLS_DTPMOD64,
+            8 => R_LARCH_TLS_DTPREL32,
+            9 => R_LARCH_TLS_DTPREL64,
+            10 => R_LARCH_TLS_TPREL32,
+            11 => R_LARCH_TLS_TPREL64,
+            12 => R_LARCH_IRELATIVE,
+            13 => R_LARCH_TLS_DESC32,
+            14 => R_LARCH_TLS_DESC64,
+            20 => R_LARCH_MARK_LA,
+            21 => R_LARCH_MARK_PCREL,
+            22 => R_LARCH_SOP_PUSH_PCREL,
+            23 => R_LARCH_SOP_PUSH_ABSOLUTE,
+            24 => R_LARCH_SOP_PUSH_DUP,
+            25 => R_LARCH_SOP_PUSH_GPREL,
+            26 => R_LARCH_SOP_PUSH_TLS_TPREL,
+            27 => R_LARCH_SOP_PUSH_TLS_GOT,
+            28 => R_LARCH_SOP_PUSH_TLS_GD,
+            29 => R_LARCH_SOP_PUSH_PLT_PCREL,
+            30 => R_LARCH_SOP_ASSERT,
+            31 => R_LARCH_SOP_NOT,
+            32 => R_LARCH_SOP_SUB,
+            33 => R_LARCH_SOP_SL,
+            34 => R_LARCH_SOP_SR,
+            35 => R_LARCH_SOP_ADD,
+            36 => R_LARCH_SOP_AND,
+            37 => R_LARCH_SOP_IF_ELSE,
+            38 => R_LARCH_SOP_POP_32_S_10_5,
+            39 => R_LARCH_SOP_POP_32_U_10_12,
+            40 => R_LARCH_SOP_POP_32_S_10_12,
+            41 => R_LARCH_SOP_POP_32_S_10_16,
+            42 => R_LARCH_SOP_POP_32_S_10_16_S2,
+            43 => R_LARCH_SOP_POP_32_S_5_20,
+            44 => R_LARCH_SOP_POP_32_S_0_5_10_16_S2,
+            45 => R_LARCH_SOP_POP_32_S_0_10_10_16_S2,
+            46 => R_LARCH_SOP_POP_32_U,
+            47 => R_LARCH_ADD8,
+            48 => R_LARCH_ADD16,
+            49 => R_LARCH_ADD24,
+            50 => R_LARCH_ADD32,
+            51 => R_LARCH_ADD64,
+            52 => R_LARCH_SUB8,
+            53 => R_LARCH_SUB16,
+            54 => R_LARCH_SUB24,
+            55 => R_LARCH_SUB32,
+            56 => R_LARCH_SUB64,
+            57 => R_LARCH_GNU_VTINHERIT,
+            58 => R_LARCH_GNU_VTENTRY,
+            64 => R_LARCH_B16,
+            65 => R_LARCH_B21,
+            66 => R_LARCH_B26,
+            67 => R_LARCH_ABS_HI20,
+            68 => R_LARCH_ABS_LO12,
+            69 => R_LARCH_ABS64_LO20,
+            70 => R_LARCH_ABS64_HI12,
+            71 => R_LARCH_PCALA_HI20,
+            72 => R_LARCH_PCALA_LO12,
+            73 => R_LARCH_PCALA64_LO20,
+            74 => R_LARCH_PCALA64_HI12,
+            75 => R_LARCH_GOT_PC_HI20,
+            76 => R_LARCH_GOT_PC_LO12,
+            77 => R_LARCH_GOT64_PC_LO20,
+            78 => R_LARCH_GOT64_PC_HI12,
+            79 => R_LARCH_GOT_HI20,
+            80 => R_LARCH_GOT_LO12,
+            81 => R_LARCH_GOT64_LO20,
+            82 => R_LARCH_GOT64_HI12,
+            83 => R_LARCH_TLS_LE_HI20,
+            84 => R_LARCH_TLS_LE_LO12,
+            85 => R_LARCH_TLS_LE64_LO20,
+            86 => R_LARCH_TLS_LE64_HI12,
+            87 => R_LARCH_TLS_IE_PC_HI20,
+            88 => R_LARCH_TLS_IE_PC_LO12,
+            89 => R_LARCH_TLS_IE64_PC_LO20,
+            90 => R_LARCH_TLS_IE64_PC_HI12,
+            91 => R_LARCH_TLS_IE_HI20,
+            92 => R_LARCH_TLS_IE_LO12,
+            93 => R_LARCH_TLS_IE64_LO20,
+            94 => R_LARCH_TLS_IE64_HI12,
+            95 => R_LARCH_TLS_LD_PC_HI20,
+            96 => R_LARCH_TLS_LD_HI20,
+            97 => R_LARCH_TLS_GD_PC_HI20,
+            98 => R_LARCH_TLS_GD_HI20,
+            99 => R_LARCH_32_PCREL,
+            100 => R_LARCH_RELAX,
+            101 => R_LARCH_DELETE,
+            102 => R_LARCH_ALIGN,
+            103 => R_LARCH_PCREL20_S2,
+            104 => R_LARCH_CFA,
+            105 => R_LARCH_ADD6,
+            106 => R_LARCH_SUB6,
+            107 => R_LARCH_ADD_ULEB128,
+            108 => R_LARCH_SUB_ULEB128,
+            109 => R_LARCH_64_PCREL,
+            110 => R_LARCH_CALL36,
+            111 => R_LARCH_TLS_DESC_PC_HI20,
+            112 => R_LARCH_TLS_DESC_PC_LO12,
+            113 => R_LARCH_TLS_DESC64_PC_LO20,
+            114 => R_LARCH_TLS_DESC64_PC_HI12,
+            115 => R_LARCH_TLS_DESC_HI20,
+            116 => R_LARCH_TLS_DESC_LO12,
+            117 => R_LARCH_TLS_DESC64_LO20,
+            118 => R_LARCH_TLS_DESC64_HI12,
+            119 => R_LARCH_TLS_DESC_LD,
+            120 => R_LARCH_TLS_DESC_CALL,
+            121 => R_LARCH_TLS_LE_HI20_R,
+            122 => R_LARCH_TLS_LE_ADD_R,
+            123 => R_LARCH_TLS_LE_LO12_R,
+            124 => R_LARCH_TLS_LD_PCREL20_S2,
+            125 => R_LARCH_TLS_GD_PCREL20_S2,
+            126 => R_LARCH_TLS_DESC_PCREL20_S2,
+            x => Unknown(x),
+        }
+    }
+}
@@ -0,0 +1,26 @@
+use crate::arch::loongarch::RelocationTypes::{self, *};
+use crate::arch::loongarch::EM_LOONGARCH;
+use crate::arch::RelocationType;
+use crate::Machine;
+
+#[test]
+fn check_relocation_types() {
+    assert_eq!(RelocationTypes::from(0), R_LARCH_NONE);
+    assert_eq!(RelocationTypes::from(2), R_LARCH_64);
+    assert_eq!(RelocationTypes::from(3), R_LARCH_RELATIVE);
+    assert_eq!(RelocationTypes::from(4), R_LARCH_COPY);
+    assert_eq!(RelocationTypes::from(5), R_LARCH_JUMP_SLOT);
+    assert_eq!(RelocationTypes::from(7), R_LARCH_TLS_DTPMOD64);
+    assert_eq!(RelocationTypes::from(9), R_LARCH_TLS_DTPREL64);
+    assert_eq!(RelocationTypes::from(11), R_LARCH_TLS_TPREL64);
+    assert_eq!(RelocationTypes::from(12), R_LARCH_IRELATIVE);
+    assert_eq!(RelocationTypes::from(14), R_LARCH_TLS_DESC64);
+    assert_eq!(RelocationTypes::from(110), R_LARCH_CALL36);
+    assert_eq!(RelocationTypes::from(15), Unknown(15));
+    assert_eq!(RelocationTypes::from(127), Unknown(127));
+
+    assert!(matches!(
+        RelocationType::from(Machine::Other(EM_LOONGARCH), 3),
+        Ok(RelocationType::LoongArch(R_LARCH_RELATIVE))
+    ));
+}
@@ -0,0 +1,161 @@
+//! m68k relocation types
+//!
+//! As defined in the "System V Application Binary Interface Motorola 68000
+//! Processor Family Supplement" and the GNU TLS extensions.
+//!
+//! Note: m68k is big-endian, which [`crate::ElfBinary`] does not accept
+//! yet, so these types are currently only reachable through
+//! [`super::RelocationType::from`].
+
+#[cfg(test)]
+mod test;
+
+/// Machine number of the Motorola 68000 family (not known to xmas-elf).
+pub const EM_68K: u16 = 4;
+
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+#[allow(non_camel_case_types)]
+#[repr(u32)]
+pub enum RelocationTypes {
+    /// No relocation.
+    R_68K_NONE,
+    /// Direct 32 bit.
+    R_68K_32,
+    /// Direct 16 bit.
+    R_68K_16,
+    /// Direct 8 bit.
+    R_68K_8,
+    /// PC relative 32 bit.
+    R_68K_PC32,
+    /// PC relative 16 bit.
+    R_68K_PC16,
+    /// PC relative 8 bit.
+    R_68K_PC8,
+    /// 32 bit PC relative GOT entry.
+    R_68K_GOT32,
+    /// 16 bit PC relative GOT entry.
+    R_68K_GOT16,
+    /// 8 bit PC relative GOT entry.
+    R_68K_GOT8,
+    /// 32 bit GOT offset.
+    R_68K_GOT32O,
+    /// 16 bit GOT offset.
+    R_68K_GOT16O,
+    /// 8 bit GOT offset.
+    R_68K_GOT8O,
+    /// 32 bit PC relative PLT address.
+    R_68K_PLT32,
+    /// 16 bit PC relative PLT address.
+    R_68K_PLT16,
+    /// 8 bit PC relative PLT address.
+    R_68K_PLT8,
+    /// 32 bit PLT offset.
+    R_68K_PLT32O,
+    /// 16 bit PLT offset.
+    R_68K_PLT16O,
+    /// 8 bit PLT offset.
+    R_68K_PLT8O,
+    /// Copy symbol at runtime.
+    R_68K_COPY,
+    /// Create GOT entry.
+    R_68K_GLOB_DAT,
+    /// Create PLT entry.
+    R_68K_JMP_SLOT,
+    /// Adjust by program base.
+    R_68K_RELATIVE,
+    /// GNU C++ vtable hierarchy.
+    R_68K_GNU_VTINHERIT,
+    /// GNU C++ vtable member usage.
+    R_68K_GNU_VTENTRY,
+    /// 32 bit GOT offset for GD.
+    R_68K_TLS_GD32,
+    /// 16 bit GOT offset for GD.
+    R_68K_TLS_GD16,
+    /// 8 bit GOT offset for GD.
+    R_68K_TLS_GD8,
+    /// 32 bit GOT offset for LDM.
+    R_68K_TLS_LDM32,
+    /// 16 bit GOT offset for LDM.
+    R_68K_TLS_LDM16,
+    /// 8 bit GOT offset for LDM.
+    R_68K_TLS_LDM8,
+    /// 32 bit module-relative offset.
+    R_68K_TLS_LDO32,
+    /// 16 bit module-relative offset.
+    R_68K_TLS_LDO16,
+    /// 8 bit module-relative offset.
+    R_68K_TLS_LDO8,
+    /// 32 bit GOT offset for IE.
+    R_68K_TLS_IE32,
+    /// 16 bit GOT offset for IE.
+    R_68K_TLS_IE16,
+    /// 8 bit GOT offset for IE.
+    R_68K_TLS_IE8,
+    /// 32 bit offset relative to static TLS block.
+    R_68K_TLS_LE32,
+    /// 16 bit offset relative to static TLS block.
+    R_68K_TLS_LE16,
+    /// 8 bit offset relative to static TLS block.
+    R_68K_TLS_LE8,
+    /// 32 bit module number.
+    R_68K_TLS_DTPMOD32,
+    /// 32 bit module-relative offset.
+    R_68K_TLS_DTPREL32,
+    /// 32 bit TP-relative offset.
+    R_68K_TLS_TPREL32,
+    /// Unknown
+    Unknown(u32),
+}
+
+impl RelocationTypes {
+    /// Construct new m68k::RelocationTypes
+    pub fn from(typ: u32) -> RelocationTypes {
+        use RelocationTypes::*;
+        match typ {
+            0 => R_68K_NONE,
+            1 => R_68K_32,
+            2 => R_68K_16,
+            3 => R_68K_8,
+            4 => R_68K_PC32,
+            5 => R_68K_PC16,
+            6 => R_68K_PC8,
+            7 => R_68K_GOT32,
+            8 => R_68K_GOT16,
+            9 => R_68K_GOT8,
+            10 => R_68K_GOT32O,
+            11 => R_68K_GOT16O,
+            12 => R_68K_GOT8O,
+            13 => R_68K_PLT32,
+            14 => R_68K_PLT16,
+            15 => R_68K_PLT8,
+            16 => R_68K_PLT32O,
+            17 => R_68K_PLT16O,
+            18 => R_68K_PLT8O,
+            19 => R_68K_COPY,
+            20 => R_68K_GLOB_DAT,
+            21 => R_68K_JMP_SLOT,
+            22 => R_68K_RELATIVE,
+            23 => R_68K_GNU_VTINHERIT,
+            24 => R_68K_GNU_VTENTRY,
+            25 => R_68K_TLS_GD32,
+            26 => R_68K_TLS_GD16,
+            27 => R_68K_TLS_GD8,
+            28 => R_68K_TLS_LDM32,
+            29 => R_68K_TLS_LDM16,
+            30 => R_68K_TLS_LDM8,
+            31 => R_68K_TLS_LDO32,
+            32 => R_68K_TLS_LDO16,
+            33 => R_68K_TLS_LDO8,
+            34 => R_68K_TLS_IE32,
+            35 => R_68K_TLS_IE16,
+            36 => R_68K_TLS_IE8,
+            37 => R_68K_TLS_LE32,
+            38 => R_68K_TLS_LE16,
+            39 => R_68K_TLS_LE8,
+            40 => R_68K_TLS_DTPMOD32,
+            41 => R_68K_TLS_DTPREL32,
+            42 => R_68K_TLS_TPREL32,
+            x => Unknown(x),
+        }
+    }
+}
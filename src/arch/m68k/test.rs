@@ -0,0 +1,23 @@
+use crate::arch::m68k::RelocationTypes::{self, *};
+use crate::arch::m68k::EM_68K;
+use crate::arch::RelocationType;
+use crate::Machine;
+
+#[test]
+fn check_relocation_types() {
+    assert_eq!(RelocationTypes::from(0), R_68K_NONE);
+    assert_eq!(RelocationTypes::from(1), R_68K_32);
+    assert_eq!(RelocationTypes::from(19), R_68K_COPY);
+    assert_eq!(RelocationTypes::from(20), R_68K_GLOB_DAT);
+    assert_eq!(RelocationTypes::from(21), R_68K_JMP_SLOT);
+    assert_eq!(RelocationTypes::from(22), R_68K_RELATIVE);
+    assert_eq!(RelocationTypes::from(40), R_68K_TLS_DTPMOD32);
+    assert_eq!(RelocationTypes::from(41), R_68K_TLS_DTPREL32);
+    assert_eq!(RelocationTypes::from(42), R_68K_TLS_TPREL32);
+    assert_eq!(RelocationTypes::from(43), Unknown(43));
+
+    assert!(matches!(
+        RelocationType::from(Machine::Other(EM_68K), 22),
+        Ok(RelocationType::M68k(R_68K_RELATIVE))
+    ));
+}
@@ -0,0 +1,242 @@
+//! MIPS relocation types
+//!
+//! As defined in the "SYSTEM V APPLICATION BINARY INTERFACE MIPS RISC
+//! Processor Supplement" and the 64-bit ELF object file specification.
+//!
+//! Note: MIPS64 uses its own `r_info` layout (a 32 bit symbol index
+//! followed by up to three 8 bit types) which is not decoded yet, so only
+//! 32 bit MIPS relocation entries map to the right types.
+
+#[cfg(test)]
+mod test;
+
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+#[allow(non_camel_case_types)]
+#[repr(u32)]
+pub enum RelocationTypes {
+    /// No relocation.
+    R_MIPS_NONE,
+    /// Direct 16 bit.
+    R_MIPS_16,
+    /// Direct 32 bit.
+    R_MIPS_32,
+    /// PC relative 32 bit.
+    R_MIPS_REL32,
+    /// Direct 26 bit shifted.
+    R_MIPS_26,
+    /// High 16 bit.
+    R_MIPS_HI16,
+    /// Low 16 bit.
+    R_MIPS_LO16,
+    /// GP relative 16 bit.
+    R_MIPS_GPREL16,
+    /// 16 bit literal entry.
+    R_MIPS_LITERAL,
+    /// 16 bit GOT entry.
+    R_MIPS_GOT16,
+    /// PC relative 16 bit.
+    R_MIPS_PC16,
+    /// 16 bit GOT entry for function.
+    R_MIPS_CALL16,
+    /// GP relative 32 bit.
+    R_MIPS_GPREL32,
+    /// 5 bit shift amount.
+    R_MIPS_SHIFT5,
+    /// 6 bit shift amount.
+    R_MIPS_SHIFT6,
+    /// Direct 64 bit.
+    R_MIPS_64,
+    /// Displacement of the GOT entry.
+    R_MIPS_GOT_DISP,
+    /// GOT entry for the page of the symbol.
+    R_MIPS_GOT_PAGE,
+    /// Offset of the symbol within its GOT page.
+    R_MIPS_GOT_OFST,
+    /// High 16 bit of a GOT displacement.
+    R_MIPS_GOT_HI16,
+    /// Low 16 bit of a GOT displacement.
+    R_MIPS_GOT_LO16,
+    /// Subtract symbol value.
+    R_MIPS_SUB,
+    /// Insert instruction (obsolete).
+    R_MIPS_INSERT_A,
+    /// Insert instruction (obsolete).
+    R_MIPS_INSERT_B,
+    /// Delete instruction (obsolete).
+    R_MIPS_DELETE,
+    /// Bits 32..47 of a 64 bit address.
+    R_MIPS_HIGHER,
+    /// Bits 48..63 of a 64 bit address.
+    R_MIPS_HIGHEST,
+    /// High 16 bit of a function GOT displacement.
+    R_MIPS_CALL_HI16,
+    /// Low 16 bit of a function GOT displacement.
+    R_MIPS_CALL_LO16,
+    /// Section displacement.
+    R_MIPS_SCN_DISP,
+    /// PC relative 16 bit (obsolete).
+    R_MIPS_REL16,
+    /// Add immediate (obsolete).
+    R_MIPS_ADD_IMMEDIATE,
+    /// Jump (obsolete).
+    R_MIPS_PJUMP,
+    /// Relative GOT entry (obsolete).
+    R_MIPS_RELGOT,
+    /// Marks a `jalr` that can be turned into a branch.
+    R_MIPS_JALR,
+    /// Module number 32 bit.
+    R_MIPS_TLS_DTPMOD32,
+    /// Module-relative offset 32 bit.
+    R_MIPS_TLS_DTPREL32,
+    /// Module number 64 bit.
+    R_MIPS_TLS_DTPMOD64,
+    /// Module-relative offset 64 bit.
+    R_MIPS_TLS_DTPREL64,
+    /// 16 bit GOT offset for GD.
+    R_MIPS_TLS_GD,
+    /// 16 bit GOT offset for LDM.
+    R_MIPS_TLS_LDM,
+    /// Module-relative offset, high 16 bits.
+    R_MIPS_TLS_DTPREL_HI16,
+    /// Module-relative offset, low 16 bits.
+    R_MIPS_TLS_DTPREL_LO16,
+    /// 16 bit GOT offset for IE.
+    R_MIPS_TLS_GOTTPREL,
+    /// TP-relative offset, 32 bit.
+    R_MIPS_TLS_TPREL32,
+    /// TP-relative offset, 64 bit.
+    R_MIPS_TLS_TPREL64,
+    /// TP-relative offset, high 16 bits.
+    R_MIPS_TLS_TPREL_HI16,
+    /// TP-relative offset, low 16 bits.
+    R_MIPS_TLS_TPREL_LO16,
+    /// Set GOT entry to symbol value.
+    R_MIPS_GLOB_DAT,
+    /// PC relative 21 bit shifted (R6).
+    R_MIPS_PC21_S2,
+    /// PC relative 26 bit shifted (R6).
+    R_MIPS_PC26_S2,
+    /// PC relative 18 bit shifted (R6).
+    R_MIPS_PC18_S3,
+    /// PC relative 19 bit shifted (R6).
+    R_MIPS_PC19_S2,
+    /// PC relative high 16 bit (R6).
+    R_MIPS_PCHI16,
+    /// PC relative low 16 bit (R6).
+    R_MIPS_PCLO16,
+    /// Copy data from shared object.
+    R_MIPS_COPY,
+    /// Set PLT GOT entry to code address.
+    R_MIPS_JUMP_SLOT,
+    /// Unknown
+    Unknown(u32),
+}
+
+impl RelocationTypes {
+    /// Construct new mips::RelocationTypes
+    pub fn from(typ: u32) -> RelocationTypes {
+        use RelocationTypes::*;
+        match typ {
+            0 => R_MIPS_NONE,
+            1 => R_MIPS_16,
+            2 => R_MIPS_32,
+            3 => R_MIPS_REL32,
+            4 => R_MIPS_26,
+            5 => R_MIPS_HI16,
+            6 => R_MIPS_LO16,
+            7 => R_MIPS_GPREL16,
+            8 => R_MIPS_LITERAL,
+            9 => R_MIPS_GOT16,
+            10 => R_MIPS_PC16,
+            11 => R_MIPS_CALL16,
+            12 => R_MIPS_GPREL32,
+            16 => R_MIPS_SHIFT5,
+            17 => R_MIPS_SHIFT6,
+            18 => R_MIPS_64,
+            19 => R_MIPS_GOT_DISP,
+            20 => R_MIPS_GOT_PAGE,
+            21 => R_MIPS_GOT_OFST,
+            22 => R_MIPS_GOT_HI16,
+            23 => R_MIPS_GOT_LO16,
+            24 => R_MIPS_SUB,
+            25 => R_MIPS_INSERT_A,
+            26 => R_MIPS_INSERT_B,
+            27 => R_MIPS_DELETE,
+            28 => R_MIPS_HIGHER,
+            29 => R_MIPS_HIGHEST,
+            30 => R_MIPS_CALL_HI16,
+            31 => R_MIPS_CALL_LO16,
+            32 => R_MIPS_SCN_DISP,
+            33 => R_MIPS_REL16,
+            34 => R_MIPS_ADD_IMMEDIATE,
+            35 => R_MIPS_PJUMP,
+            36 => R_MIPS_RELGOT,
+            37 => R_MIPS_JALR,
+            38 => R_MIPS_TLS_DTPMOD32,
+            39 => R_MIPS_TLS_DTPREL32,
+            40 => R_MIPS_TLS_DTPMOD64,
+            41 => R_MIPS_TLS_DTPREL64,
+            42 => R_MIPS_TLS_GD,
+            43 => R_MIPS_TLS_LDM,
+            44 => R_MIPS_TLS_DTPREL_HI16,
+            45 => R_MIPS_TLS_DTPREL_LO16,
+            46 => R_MIPS_TLS_GOTTPREL,
+            47 => R_MIPS_TLS_TPREL32,
+            48 => R_MIPS_TLS_TPREL64,
+            49 => R_MIPS_TLS_TPREL_HI16,
+            50 => R_MIPS_TLS_TPREL_LO16,
+            51 => R_MIPS_GLOB_DAT,
+            60 => R_MIPS_PC21_S2,
+            61 => R_MIPS_PC26_S2,
+            62 => R_MIPS_PC18_S3,
+            63 => R_MIPS_PC19_S2,
+            64 => R_MIPS_PCHI16,
+            65 => R_MIPS_PCLO16,
+            126 => R_MIPS_COPY,
+            127 => R_MIPS_JUMP_SLOT,
+            x => Unknown(x),
+        }
+    }
+}
+
+/// Number of local GOT entries (DT_MIPS_LOCAL_GOTNO).
+pub const DT_MIPS_LOCAL_GOTNO: u64 = 0x7000000a;
+/// Number of entries in .dynsym (DT_MIPS_SYMTABNO).
+pub const DT_MIPS_SYMTABNO: u64 = 0x70000011;
+/// Index of the first .dynsym entry with a GOT entry (DT_MIPS_GOTSYM).
+pub const DT_MIPS_GOTSYM: u64 = 0x70000013;
+
+/// Layout of the MIPS GOT as described by the dynamic section.
+///
+/// The MIPS ABI does not use GLOB_DAT relocations, instead the GOT starts
+/// with `local_gotno` local entries (which need to be moved by the load
+/// bias) followed by one entry for each of the dynamic symbols starting at
+/// index `gotsym` (which need to be set to the symbol values).
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub struct GotInfo {
+    /// Address of the GOT (DT_PLTGOT).
+    pub address: u64,
+    /// Number of local GOT entries.
+    pub local_gotno: u64,
+    /// Index of the first dynamic symbol with a global GOT entry.
+    pub gotsym: u64,
+    /// Number of dynamic symbols.
+    pub symtabno: u64,
+}
+
+impl GotInfo {
+    /// Number of global GOT entries (following the local entries).
+    pub fn global_gotno(&self) -> u64 {
+        self.symtabno.saturating_sub(self.gotsym)
+    }
+
+    /// Address of the GOT entry for the dynamic symbol `index`.
+    ///
+    /// Returns None for symbols without a global GOT entry.
+    pub fn symbol_entry(&self, index: u64, word_size: u64) -> Option<u64> {
+        if index < self.gotsym || index >= self.symtabno {
+            return None;
+        }
+        Some(self.address + (self.local_gotno + index - self.gotsym) * word_size)
+    }
+}
@@ -0,0 +1,34 @@
+use crate::arch::mips::RelocationTypes::{self, *};
+use crate::arch::RelocationType;
+use crate::Machine;
+
+#[test]
+fn check_relocation_types() {
+    assert_eq!(RelocationTypes::from(0), R_MIPS_NONE);
+    assert_eq!(RelocationTypes::from(2), R_MIPS_32);
+    assert_eq!(RelocationTypes::from(3), R_MIPS_REL32);
+    assert_eq!(RelocationTypes::from(18), R_MIPS_64);
+    assert_eq!(RelocationTypes::from(38), R_MIPS_TLS_DTPMOD32);
+    assert_eq!(RelocationTypes::from(47), R_MIPS_TLS_TPREL32);
+    assert_eq!(RelocationTypes::from(51), R_MIPS_GLOB_DAT);
+    assert_eq!(RelocationTypes::from(126), R_MIPS_COPY);
+    assert_eq!(RelocationTypes::from(127), R_MIPS_JUMP_SLOT);
+    assert_eq!(RelocationTypes::from(13), Unknown(13));
+    assert_eq!(RelocationTypes::from(128), Unknown(128));
+
+    assert!(matches!(
+        RelocationType::from(Machine::Mips, 3),
+        Ok(RelocationType::Mips(R_MIPS_REL32))
+    ));
+}
+
+#[test]
+fn check_implicit_addend_big_endian() {
+    use crate::arch::test::big_endian_elf32;
+    use crate::ElfBinary;
+
+    // A REL target of a MIPS BE binary (EM_MIPS = 8) at 0x100
+    let blob = big_endian_elf32(8, &[], &0x1234_5678u32.to_be_bytes());
+    let binary = ElfBinary::new(blob.as_slice()).expect("Got proper ELF file");
+    assert_eq!(binary.implicit_addend(0x100), Some(0x1234_5678));
+}
@@ -1,13 +1,28 @@
-use crate::{ElfLoaderErr, Machine};
+use crate::{ElfLoaderErr, GotFixupKind, Machine, VAddr};
 
 pub mod aarch64;
+pub mod arc;
 pub mod arm;
+pub mod avr;
+pub mod bpf;
+pub mod csky;
+pub mod hexagon;
+pub mod loongarch;
+pub mod m68k;
+pub mod mips;
+pub mod msp430;
+pub mod or1k;
+pub mod powerpc;
+pub mod powerpc64;
 pub mod riscv;
+pub mod s390x;
+pub mod sparc64;
 pub mod x86;
 pub mod x86_64;
+pub mod xtensa;
 
 #[cfg(test)]
-mod test;
+pub(crate) mod test;
 
 #[derive(Debug)]
 #[allow(non_camel_case_types)]
@@ -17,6 +32,21 @@ pub enum RelocationType {
     Arm(arm::RelocationTypes),
     AArch64(aarch64::RelocationTypes),
     RiscV(riscv::RelocationTypes),
+    Mips(mips::RelocationTypes),
+    PowerPc(powerpc::RelocationTypes),
+    PowerPc64(powerpc64::RelocationTypes),
+    S390x(s390x::RelocationTypes),
+    Sparc64(sparc64::RelocationTypes),
+    LoongArch(loongarch::RelocationTypes),
+    M68k(m68k::RelocationTypes),
+    Xtensa(xtensa::RelocationTypes),
+    Hexagon(hexagon::RelocationTypes),
+    Arc(arc::RelocationTypes),
+    Or1k(or1k::RelocationTypes),
+    Csky(csky::RelocationTypes),
+    Msp430(msp430::RelocationTypes),
+    Avr(avr::RelocationTypes),
+    Bpf(bpf::RelocationTypes),
 }
 
 impl RelocationType {
@@ -28,8 +58,352 @@ impl RelocationType {
             Machine::Arm => RelocationType::Arm(arm::RelocationTypes::from(type_num)),
             Machine::AArch64 => RelocationType::AArch64(aarch64::RelocationTypes::from(type_num)),
             Machine::RISC_V => RelocationType::RiscV(riscv::RelocationTypes::from(type_num)),
+            Machine::Mips => RelocationType::Mips(mips::RelocationTypes::from(type_num)),
+            Machine::PowerPC => RelocationType::PowerPc(powerpc::RelocationTypes::from(type_num)),
+            Machine::Other(powerpc64::EM_PPC64) => {
+                RelocationType::PowerPc64(powerpc64::RelocationTypes::from(type_num))
+            }
+            Machine::Other(s390x::EM_S390) => {
+                RelocationType::S390x(s390x::RelocationTypes::from(type_num))
+            }
+            Machine::Sparc | Machine::Other(sparc64::EM_SPARCV9) => {
+                RelocationType::Sparc64(sparc64::RelocationTypes::from(type_num))
+            }
+            Machine::Other(loongarch::EM_LOONGARCH) => {
+                RelocationType::LoongArch(loongarch::RelocationTypes::from(type_num))
+            }
+            Machine::Other(m68k::EM_68K) => {
+                RelocationType::M68k(m68k::RelocationTypes::from(type_num))
+            }
+            Machine::Other(xtensa::EM_XTENSA) => {
+                RelocationType::Xtensa(xtensa::RelocationTypes::from(type_num))
+            }
+            Machine::Other(hexagon::EM_HEXAGON) => {
+                RelocationType::Hexagon(hexagon::RelocationTypes::from(type_num))
+            }
+            Machine::Other(arc::EM_ARC_COMPACT) | Machine::Other(arc::EM_ARC_COMPACT2) => {
+                RelocationType::Arc(arc::RelocationTypes::from(type_num))
+            }
+            Machine::Other(or1k::EM_OPENRISC) => {
+                RelocationType::Or1k(or1k::RelocationTypes::from(type_num))
+            }
+            Machine::Other(csky::EM_CSKY) => {
+                RelocationType::Csky(csky::RelocationTypes::from(type_num))
+            }
+            Machine::Other(msp430::EM_MSP430) => {
+                RelocationType::Msp430(msp430::RelocationTypes::from(type_num))
+            }
+            Machine::Other(avr::EM_AVR) => {
+                RelocationType::Avr(avr::RelocationTypes::from(type_num))
+            }
+            Machine::BPF => RelocationType::Bpf(bpf::RelocationTypes::from(type_num)),
             _ => return Err(ElfLoaderErr::UnsupportedArchitecture),
         };
         Ok(typ)
     }
+
+    /// Whether this is the RELATIVE relocation of its architecture (base
+    /// address plus addend).
+    pub(crate) fn is_relative(&self) -> bool {
+        self.got_fixup_kind() == Some(GotFixupKind::Relative)
+    }
+
+    /// The kind of GOT slot relocations of this type patch, if any.
+    pub(crate) fn got_fixup_kind(&self) -> Option<GotFixupKind> {
+        use GotFixupKind::*;
+        match self {
+            RelocationType::x86(typ) => match typ {
+                x86::RelocationTypes::R_386_RELATIVE => Some(Relative),
+                x86::RelocationTypes::R_386_GLOB_DAT => Some(GlobDat),
+                x86::RelocationTypes::R_386_JMP_SLOT => Some(JumpSlot),
+                x86::RelocationTypes::R_386_IRELATIVE => Some(Ifunc),
+                _ => None,
+            },
+            RelocationType::x86_64(typ) => match typ {
+                x86_64::RelocationTypes::R_AMD64_RELATIVE => Some(Relative),
+                x86_64::RelocationTypes::R_AMD64_GLOB_DAT => Some(GlobDat),
+                x86_64::RelocationTypes::R_AMD64_JMP_SLOT => Some(JumpSlot),
+                x86_64::RelocationTypes::R_AMD64_IRELATIVE => Some(Ifunc),
+                _ => None,
+            },
+            RelocationType::Arm(typ) => match typ {
+                arm::RelocationTypes::R_ARM_RELATIVE => Some(Relative),
+                arm::RelocationTypes::R_ARM_GLOB_DAT => Some(GlobDat),
+                arm::RelocationTypes::R_ARM_JUMP_SLOT => Some(JumpSlot),
+                arm::RelocationTypes::R_ARM_IRELATIVE => Some(Ifunc),
+                _ => None,
+            },
+            RelocationType::AArch64(typ) => match typ {
+                aarch64::RelocationTypes::R_AARCH64_RELATIVE => Some(Relative),
+                aarch64::RelocationTypes::R_AARCH64_GLOB_DAT => Some(GlobDat),
+                aarch64::RelocationTypes::R_AARCH64_JUMP_SLOT => Some(JumpSlot),
+                aarch64::RelocationTypes::R_AARCH64_IRELATIVE => Some(Ifunc),
+                _ => None,
+            },
+            // RISC-V has no GLOB_DAT, GOT entries of symbols use the word
+            // sized absolute relocation
+            RelocationType::RiscV(typ) => match typ {
+                riscv::RelocationTypes::R_RISCV_RELATIVE => Some(Relative),
+                riscv::RelocationTypes::R_RISCV_32 | riscv::RelocationTypes::R_RISCV_64 => {
+                    Some(GlobDat)
+                }
+                riscv::RelocationTypes::R_RISCV_JUMP_SLOT => Some(JumpSlot),
+                riscv::RelocationTypes::R_RISCV_IRELATIVE => Some(Ifunc),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Is this the relocation type of lazily bound PLT entries?
+    pub(crate) fn is_jump_slot(&self) -> bool {
+        matches!(
+            self,
+            RelocationType::x86(x86::RelocationTypes::R_386_JMP_SLOT)
+                | RelocationType::x86_64(x86_64::RelocationTypes::R_AMD64_JMP_SLOT)
+                | RelocationType::Arm(arm::RelocationTypes::R_ARM_JUMP_SLOT)
+                | RelocationType::AArch64(aarch64::RelocationTypes::R_AARCH64_JUMP_SLOT)
+                | RelocationType::RiscV(riscv::RelocationTypes::R_RISCV_JUMP_SLOT)
+        )
+    }
+}
+
+/// The reserved GOT slots a dynamic linker fills to enable lazy binding.
+///
+/// When DT_PLTGOT is present, the first entries of the PLT GOT are reserved:
+/// one receives a handle the loader uses to identify the object (the
+/// `link_map` pointer in glibc) and one receives the address of the
+/// resolver stub that unresolved PLT entries jump to.
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub struct LazyBindingSlots {
+    /// Address of the slot for the object handle (link map).
+    pub link_map: VAddr,
+    /// Address of the slot for the lazy resolver entry point.
+    pub resolver: VAddr,
+}
+
+impl LazyBindingSlots {
+    /// Compute the reserved slots for `machine` given the (unrelocated)
+    /// DT_PLTGOT address and the size of a GOT entry in bytes.
+    pub fn new(
+        machine: Machine,
+        pltgot: VAddr,
+        word_size: u64,
+    ) -> Result<LazyBindingSlots, ElfLoaderErr> {
+        let slot = |idx: u64| pltgot + idx * word_size;
+        match machine {
+            // GOT[0] is the address of _DYNAMIC, GOT[1] the link map and
+            // GOT[2] the resolver.
+            Machine::X86 | Machine::X86_64 | Machine::Arm | Machine::AArch64 => {
+                Ok(LazyBindingSlots {
+                    link_map: slot(1),
+                    resolver: slot(2),
+                })
+            }
+            // .got.plt[0] is the resolver and .got.plt[1] the link map.
+            Machine::RISC_V => Ok(LazyBindingSlots {
+                link_map: slot(1),
+                resolver: slot(0),
+            }),
+            _ => Err(ElfLoaderErr::UnsupportedArchitecture),
+        }
+    }
+}
+
+/// Where the PLT0 stub leaves a value for the lazy resolver.
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub enum TrampolineArg {
+    /// On the stack, `offset` bytes above the stack pointer on entry to the
+    /// resolver.
+    Stack { offset: u8 },
+    /// In the register `name`.
+    Register { name: &'static str },
+    /// In memory, at the address held in register `name` plus `offset`.
+    Indirect { name: &'static str, offset: i8 },
+}
+
+/// How the value the PLT stubs pass to the resolver identifies the
+/// DT_JMPREL entry to bind.
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub enum PltSelector {
+    /// Index of the entry in the DT_JMPREL table.
+    Index,
+    /// Byte offset of the entry in the DT_JMPREL table.
+    JmprelOffset,
+    /// Address of the PLT GOT slot of the entry, following `reserved`
+    /// slots at DT_PLTGOT.
+    GotSlot { reserved: u64 },
+    /// Byte offset of the PLT GOT slot of the entry from the first
+    /// non-reserved slot.
+    GotOffset,
+}
+
+/// The interface between the PLT stubs of a binary and the lazy resolver
+/// installed in [`LazyBindingSlots::resolver`].
+///
+/// An unbound PLT entry jumps to PLT0, which hands the value of the
+/// [`LazyBindingSlots::link_map`] slot and a selector for the PLT
+/// relocation to the resolver. The resolver reads both from the locations
+/// given here (saving the argument registers first), passes the selector
+/// to [`crate::ElfBinary::bind_lazy`] and stores the returned
+/// [`JumpSlotFixup`] before jumping to the bound function.
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub struct LazyBindingAbi {
+    /// Where the resolver finds the object handle.
+    pub link_map: TrampolineArg,
+    /// Where the resolver finds the selector of the PLT relocation.
+    pub selector: TrampolineArg,
+    /// How to interpret the selector.
+    pub selector_kind: PltSelector,
+}
+
+impl LazyBindingAbi {
+    /// The lazy binding interface of the glibc PLT stubs on `machine`.
+    pub fn new(machine: Machine) -> Result<LazyBindingAbi, ElfLoaderErr> {
+        match machine {
+            // PLT0 pushes GOT[1] on top of the relocation pushed by the entry.
+            Machine::X86 => Ok(LazyBindingAbi {
+                link_map: TrampolineArg::Stack { offset: 0 },
+                selector: TrampolineArg::Stack { offset: 4 },
+                selector_kind: PltSelector::JmprelOffset,
+            }),
+            Machine::X86_64 => Ok(LazyBindingAbi {
+                link_map: TrampolineArg::Stack { offset: 0 },
+                selector: TrampolineArg::Stack { offset: 8 },
+                selector_kind: PltSelector::Index,
+            }),
+            // The entry leaves the address of its GOT slot in ip, PLT0 the
+            // address of GOT[2] in lr (the caller's lr is at [sp]).
+            Machine::Arm => Ok(LazyBindingAbi {
+                link_map: TrampolineArg::Indirect {
+                    name: "lr",
+                    offset: -4,
+                },
+                selector: TrampolineArg::Register { name: "ip" },
+                selector_kind: PltSelector::GotSlot { reserved: 3 },
+            }),
+            // PLT0 pushes the entry's x16 (address of its GOT slot) and
+            // x30, then points x16 to GOT[2].
+            Machine::AArch64 => Ok(LazyBindingAbi {
+                link_map: TrampolineArg::Indirect {
+                    name: "x16",
+                    offset: -8,
+                },
+                selector: TrampolineArg::Stack { offset: 0 },
+                selector_kind: PltSelector::GotSlot { reserved: 3 },
+            }),
+            Machine::RISC_V => Ok(LazyBindingAbi {
+                link_map: TrampolineArg::Register { name: "t0" },
+                selector: TrampolineArg::Register { name: "t1" },
+                selector_kind: PltSelector::GotOffset,
+            }),
+            _ => Err(ElfLoaderErr::UnsupportedArchitecture),
+        }
+    }
+}
+
+/// The final store of a lazily bound PLT entry: `value` is written to the
+/// GOT slot at `address`, after which the PLT entry jumps to `value`
+/// directly.
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub struct JumpSlotFixup {
+    /// (Relocated) address of the GOT slot.
+    pub address: VAddr,
+    /// The resolved address of the function.
+    pub value: u64,
+    /// Size of the GOT slot in bytes (4 or 8).
+    pub size: u64,
+}
+
+impl JumpSlotFixup {
+    /// Writes `value` to the GOT slot.
+    ///
+    /// # Safety
+    /// `address` must be a mapped, writable and aligned GOT slot of `size`
+    /// bytes in the current address space.
+    pub unsafe fn apply(&self) {
+        if self.size == 4 {
+            core::ptr::write_volatile(self.address as usize as *mut u32, self.value as u32);
+        } else {
+            core::ptr::write_volatile(self.address as usize as *mut u64, self.value);
+        }
+    }
+}
+
+/// Size and alignment of the TLS block of the main executable, as given by
+/// the `mem_size` and `align` of its PT_TLS segment.
+///
+/// The executable is always the first module in the static TLS block, so
+/// its position relative to the thread pointer only depends on the TLS
+/// variant and the size of the thread control block of the architecture.
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub struct TlsSegment {
+    pub mem_size: u64,
+    pub align: u64,
+}
+
+impl TlsSegment {
+    /// Returns the thread pointer relative offset of `value` (symbol value
+    /// plus addend of a TLS symbol of the executable) on `machine`.
+    ///
+    /// This is the value R_*_TPOFF/TPREL relocations against the executable
+    /// resolve to.
+    pub fn tp_offset(&self, machine: Machine, value: u64) -> Result<i64, ElfLoaderErr> {
+        match machine {
+            Machine::X86 => Ok(x86::tp_offset(self, value)),
+            Machine::X86_64 => Ok(x86_64::tp_offset(self, value)),
+            Machine::Arm => Ok(arm::tp_offset(self, value)),
+            Machine::AArch64 => Ok(aarch64::tp_offset(self, value)),
+            Machine::RISC_V => Ok(riscv::tp_offset(self, value)),
+            _ => Err(ElfLoaderErr::UnsupportedArchitecture),
+        }
+    }
+}
+
+/// Round `value` up to the next multiple of `align` (0 means no alignment).
+pub(crate) fn align_up(value: u64, align: u64) -> u64 {
+    let align = align.max(1);
+    (value + align - 1) & !(align - 1)
+}
+
+/// Resolves symbols through a closure, for the per-architecture `apply` functions.
+#[cfg(feature = "apply")]
+struct SymbolResolver<S> {
+    base: VAddr,
+    resolve_symbol: S,
+}
+
+#[cfg(feature = "apply")]
+impl<S> crate::reloc::RelocationResolver for SymbolResolver<S>
+where
+    S: FnMut(u32) -> Result<u64, ElfLoaderErr>,
+{
+    fn symbol_value(&mut self, index: u32) -> Result<u64, ElfLoaderErr> {
+        (self.resolve_symbol)(index)
+    }
+
+    fn load_bias(&self) -> u64 {
+        self.base
+    }
+}
+
+#[cfg(feature = "apply")]
+fn apply<S, W>(
+    entry: crate::RelocationEntry,
+    base: VAddr,
+    resolve_symbol: S,
+    mut write: W,
+) -> Result<(), ElfLoaderErr>
+where
+    S: FnMut(u32) -> Result<u64, ElfLoaderErr>,
+    W: FnMut(VAddr, &[u8]) -> Result<(), ElfLoaderErr>,
+{
+    let mut resolver = SymbolResolver {
+        base,
+        resolve_symbol,
+    };
+    match entry.evaluate(&mut resolver)? {
+        Some(v) => write(v.target, &v.value.to_le_bytes()[..v.width]),
+        None => Ok(()),
+    }
 }
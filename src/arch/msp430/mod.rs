@@ -0,0 +1,70 @@
+//! MSP430 relocation types
+//!
+//! As defined by the MSP430 port of binutils (`include/elf/msp430.h`).
+//!
+//! Note: Objects for MSP430X built for the TI EABI (ELFOSABI_STANDALONE)
+//! use a separate, overlapping numbering (`R_MSP430X_*`) which is not
+//! covered here.
+
+#[cfg(test)]
+mod test;
+
+/// Machine number of TI MSP430 (not known to xmas-elf).
+pub const EM_MSP430: u16 = 105;
+
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+#[allow(non_camel_case_types)]
+#[repr(u32)]
+pub enum RelocationTypes {
+    /// No relocation.
+    R_MSP430_NONE,
+    /// Direct 32 bit.
+    R_MSP430_32,
+    /// 10 bit PC relative jump offset, shifted by 1.
+    R_MSP430_10_PCREL,
+    /// Direct 16 bit.
+    R_MSP430_16,
+    /// 16 bit PC relative.
+    R_MSP430_16_PCREL,
+    /// Direct 16 bit, byte aligned.
+    R_MSP430_16_BYTE,
+    /// 16 bit PC relative, byte aligned.
+    R_MSP430_16_PCREL_BYTE,
+    /// Two 10 bit PC relative jumps (relaxed branch).
+    R_MSP430_2X_PCREL,
+    /// 16 bit PC relative with relaxation.
+    R_MSP430_RL_PCREL,
+    /// Direct 8 bit.
+    R_MSP430_8,
+    /// Difference of two symbols (paired with the next relocation).
+    R_MSP430_SYM_DIFF,
+    /// ULEB128 set value (paired with GNU_SUB_ULEB128).
+    R_MSP430_GNU_SET_ULEB128,
+    /// ULEB128 subtract value.
+    R_MSP430_GNU_SUB_ULEB128,
+    /// Unknown
+    Unknown(u32),
+}
+
+impl RelocationTypes {
+    /// Construct new msp430::RelocationTypes
+    pub fn from(typ: u32) -> RelocationTypes {
+        use RelocationTypes::*;
+        match typ {
+            0 => R_MSP430_NONE,
+            1 => R_MSP430_32,
+            2 => R_MSP430_10_PCREL,
+            3 => R_MSP430_16,
+            4 => R_MSP430_16_PCREL,
+            5 => R_MSP430_16_BYTE,
+            6 => R_MSP430_16_PCREL_BYTE,
+            7 => R_MSP430_2X_PCREL,
+            8 => R_MSP430_RL_PCREL,
+            9 => R_MSP430_8,
+            10 => R_MSP430_SYM_DIFF,
+            11 => R_MSP430_GNU_SET_ULEB128,
+            12 => R_MSP430_GNU_SUB_ULEB128,
+            x => Unknown(x),
+        }
+    }
+}
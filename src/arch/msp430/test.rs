@@ -0,0 +1,21 @@
+use crate::arch::msp430::RelocationTypes::{self, *};
+use crate::arch::msp430::EM_MSP430;
+use crate::arch::RelocationType;
+use crate::Machine;
+
+#[test]
+fn check_relocation_types() {
+    assert_eq!(RelocationTypes::from(0), R_MSP430_NONE);
+    assert_eq!(RelocationTypes::from(1), R_MSP430_32);
+    assert_eq!(RelocationTypes::from(3), R_MSP430_16);
+    assert_eq!(RelocationTypes::from(4), R_MSP430_16_PCREL);
+    assert_eq!(RelocationTypes::from(9), R_MSP430_8);
+    assert_eq!(RelocationTypes::from(10), R_MSP430_SYM_DIFF);
+    assert_eq!(RelocationTypes::from(12), R_MSP430_GNU_SUB_ULEB128);
+    assert_eq!(RelocationTypes::from(13), Unknown(13));
+
+    assert!(matches!(
+        RelocationType::from(Machine::Other(EM_MSP430), 3),
+        Ok(RelocationType::Msp430(R_MSP430_16))
+    ));
+}
@@ -0,0 +1,197 @@
+//! OpenRISC 1000 relocation types
+//!
+//! As defined in the "OpenRISC 1000 Architecture Manual" and the OpenRISC
+//! port of binutils (`include/elf/or1k.h`).
+//!
+//! Note: OpenRISC is big-endian, which [`crate::ElfBinary`] does not accept
+//! yet, so these types are currently only reachable through
+//! [`super::RelocationType::from`].
+
+#[cfg(test)]
+mod test;
+
+/// Machine number of OpenRISC 1000 (not known to xmas-elf).
+pub const EM_OPENRISC: u16 = 92;
+
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+#[allow(non_camel_case_types)]
+#[repr(u32)]
+pub enum RelocationTypes {
+    /// No relocation.
+    R_OR1K_NONE,
+    /// Direct 32 bit.
+    R_OR1K_32,
+    /// Direct 16 bit.
+    R_OR1K_16,
+    /// Direct 8 bit.
+    R_OR1K_8,
+    /// Low 16 bits of S + A.
+    R_OR1K_LO_16_IN_INSN,
+    /// High 16 bits of S + A.
+    R_OR1K_HI_16_IN_INSN,
+    /// 26 bit PC relative branch, shifted by 2.
+    R_OR1K_INSN_REL_26,
+    /// GNU C++ vtable member usage.
+    R_OR1K_GNU_VTENTRY,
+    /// GNU C++ vtable hierarchy.
+    R_OR1K_GNU_VTINHERIT,
+    /// 32 bit PC relative.
+    R_OR1K_32_PCREL,
+    /// 16 bit PC relative.
+    R_OR1K_16_PCREL,
+    /// 8 bit PC relative.
+    R_OR1K_8_PCREL,
+    /// High 16 bits of the PC relative offset to the GOT.
+    R_OR1K_GOTPC_HI16,
+    /// Low 16 bits of the PC relative offset to the GOT.
+    R_OR1K_GOTPC_LO16,
+    /// 16 bit GOT entry offset.
+    R_OR1K_GOT16,
+    /// 26 bit PC relative PLT address, shifted by 2.
+    R_OR1K_PLT26,
+    /// High 16 bits of the offset to the GOT.
+    R_OR1K_GOTOFF_HI16,
+    /// Low 16 bits of the offset to the GOT.
+    R_OR1K_GOTOFF_LO16,
+    /// Copy data from shared object.
+    R_OR1K_COPY,
+    /// Set GOT entry to data address.
+    R_OR1K_GLOB_DAT,
+    /// Set GOT entry to code address.
+    R_OR1K_JMP_SLOT,
+    /// Add load address of shared object.
+    R_OR1K_RELATIVE,
+    /// High 16 bits of the GD GOT entry offset.
+    R_OR1K_TLS_GD_HI16,
+    /// Low 16 bits of the GD GOT entry offset.
+    R_OR1K_TLS_GD_LO16,
+    /// High 16 bits of the LDM GOT entry offset.
+    R_OR1K_TLS_LDM_HI16,
+    /// Low 16 bits of the LDM GOT entry offset.
+    R_OR1K_TLS_LDM_LO16,
+    /// High 16 bits of the offset in the TLS block.
+    R_OR1K_TLS_LDO_HI16,
+    /// Low 16 bits of the offset in the TLS block.
+    R_OR1K_TLS_LDO_LO16,
+    /// High 16 bits of the IE GOT entry offset.
+    R_OR1K_TLS_IE_HI16,
+    /// Low 16 bits of the IE GOT entry offset.
+    R_OR1K_TLS_IE_LO16,
+    /// High 16 bits of the offset in the static TLS block.
+    R_OR1K_TLS_LE_HI16,
+    /// Low 16 bits of the offset in the static TLS block.
+    R_OR1K_TLS_LE_LO16,
+    /// Offset in static TLS block.
+    R_OR1K_TLS_TPOFF,
+    /// Offset in TLS block.
+    R_OR1K_TLS_DTPOFF,
+    /// ID of module containing symbol.
+    R_OR1K_TLS_DTPMOD,
+    /// High 16 bits of S + A, adjusted for the sign of the low part.
+    R_OR1K_AHI16,
+    /// Adjusted high 16 bits of the offset to the GOT.
+    R_OR1K_GOTOFF_AHI16,
+    /// Adjusted high 16 bits of the IE GOT entry offset.
+    R_OR1K_TLS_IE_AHI16,
+    /// Adjusted high 16 bits of the offset in the static TLS block.
+    R_OR1K_TLS_LE_AHI16,
+    /// Low 16 bits of S + A for store instructions (split immediate).
+    R_OR1K_SLO16,
+    /// Low 16 bits of the offset to the GOT for store instructions.
+    R_OR1K_GOTOFF_SLO16,
+    /// Low 16 bits of the TP offset for store instructions.
+    R_OR1K_TLS_LE_SLO16,
+    /// 21 bit PC relative page offset (`l.adrp`).
+    R_OR1K_PCREL_PG21,
+    /// 21 bit PC relative page offset of the GOT entry.
+    R_OR1K_GOT_PG21,
+    /// 21 bit PC relative page offset of the GD GOT entry.
+    R_OR1K_TLS_GD_PG21,
+    /// 21 bit PC relative page offset of the LDM GOT entry.
+    R_OR1K_TLS_LDM_PG21,
+    /// 21 bit PC relative page offset of the IE GOT entry.
+    R_OR1K_TLS_IE_PG21,
+    /// Low 13 bits of S + A.
+    R_OR1K_LO13,
+    /// Low 13 bits of the GOT entry address.
+    R_OR1K_GOT_LO13,
+    /// Low 13 bits of the GD GOT entry address.
+    R_OR1K_TLS_GD_LO13,
+    /// Low 13 bits of the LDM GOT entry address.
+    R_OR1K_TLS_LDM_LO13,
+    /// Low 13 bits of the IE GOT entry address.
+    R_OR1K_TLS_IE_LO13,
+    /// Low 13 bits of S + A for store instructions.
+    R_OR1K_SLO13,
+    /// 26 bit PC relative PLT address (absolute PLT entries).
+    R_OR1K_PLTA26,
+    /// Adjusted high 16 bits of the GOT entry offset.
+    R_OR1K_GOT_AHI16,
+    /// Unknown
+    Unknown(u32),
+}
+
+impl RelocationTypes {
+    /// Construct new or1k::RelocationTypes
+    pub fn from(typ: u32) -> RelocationTypes {
+        use RelocationTypes::*;
+        match typ {
+            0 => R_OR1K_NONE,
+            1 => R_OR1K_32,
+            2 => R_OR1K_16,
+            3 => R_OR1K_8,
+            4 => R_OR1K_LO_16_IN_INSN,
+            5 => R_OR1K_HI_16_IN_INSN,
+            6 => R_OR1K_INSN_REL_26,
+            7 => R_OR1K_GNU_VTENTRY,
+            8 => R_OR1K_GNU_VTINHERIT,
+            9 => R_OR1K_32_PCREL,
+            10 => R_OR1K_16_PCREL,
+            11 => R_OR1K_8_PCREL,
+            12 => R_OR1K_GOTPC_HI16,
+            13 => R_OR1K_GOTPC_LO16,
+            14 => R_OR1K_GOT16,
+            15 => R_OR1K_PLT26,
+            16 => R_OR1K_GOTOFF_HI16,
+            17 => R_OR1K_GOTOFF_LO16,
+            18 => R_OR1K_COPY,
+            19 => R_OR1K_GLOB_DAT,
+            20 => R_OR1K_JMP_SLOT,
+            21 => R_OR1K_RELATIVE,
+            22 => R_OR1K_TLS_GD_HI16,
+            23 => R_OR1K_TLS_GD_LO16,
+            24 => R_OR1K_TLS_LDM_HI16,
+            25 => R_OR1K_TLS_LDM_LO16,
+            26 => R_OR1K_TLS_LDO_HI16,
+            27 => R_OR1K_TLS_LDO_LO16,
+            28 => R_OR1K_TLS_IE_HI16,
+            29 => R_OR1K_TLS_IE_LO16,
+            30 => R_OR1K_TLS_LE_HI16,
+            31 => R_OR1K_TLS_LE_LO16,
+            32 => R_OR1K_TLS_TPOFF,
+            33 => R_OR1K_TLS_DTPOFF,
+            34 => R_OR1K_TLS_DTPMOD,
+            35 => R_OR1K_AHI16,
+            36 => R_OR1K_GOTOFF_AHI16,
+            37 => R_OR1K_TLS_IE_AHI16,
+            38 => R_OR1K_TLS_LE_AHI16,
+            39 => R_OR1K_SLO16,
+            40 => R_OR1K_GOTOFF_SLO16,
+            41 => R_OR1K_TLS_LE_SLO16,
+            42 => R_OR1K_PCREL_PG21,
+            43 => R_OR1K_GOT_PG21,
+            44 => R_OR1K_TLS_GD_PG21,
+            45 => R_OR1K_TLS_LDM_PG21,
+            46 => R_OR1K_TLS_IE_PG21,
+            47 => R_OR1K_LO13,
+            48 => R_OR1K_GOT_LO13,
+            49 => R_OR1K_TLS_GD_LO13,
+            50 => R_OR1K_TLS_LDM_LO13,
+            51 => R_OR1K_TLS_IE_LO13,
+            52 => R_OR1K_SLO13,
+            53 => R_OR1K_PLTA26,
+            54 => R_OR1K_GOT_AHI16,
+            x => Unknown(x),
+        }
+    }
+}
@@ -0,0 +1,23 @@
+use crate::arch::or1k::RelocationTypes::{self, *};
+use crate::arch::or1k::EM_OPENRISC;
+use crate::arch::RelocationType;
+use crate::Machine;
+
+#[test]
+fn check_relocation_types() {
+    assert_eq!(RelocationTypes::from(0), R_OR1K_NONE);
+    assert_eq!(RelocationTypes::from(1), R_OR1K_32);
+    assert_eq!(RelocationTypes::from(18), R_OR1K_COPY);
+    assert_eq!(RelocationTypes::from(19), R_OR1K_GLOB_DAT);
+    assert_eq!(RelocationTypes::from(20), R_OR1K_JMP_SLOT);
+    assert_eq!(RelocationTypes::from(21), R_OR1K_RELATIVE);
+    assert_eq!(RelocationTypes::from(32), R_OR1K_TLS_TPOFF);
+    assert_eq!(RelocationTypes::from(33), R_OR1K_TLS_DTPOFF);
+    assert_eq!(RelocationTypes::from(34), R_OR1K_TLS_DTPMOD);
+    assert_eq!(RelocationTypes::from(55), Unknown(55));
+
+    assert!(matches!(
+        RelocationType::from(Machine::Other(EM_OPENRISC), 21),
+        Ok(RelocationType::Or1k(R_OR1K_RELATIVE))
+    ));
+}
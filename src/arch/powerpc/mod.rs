@@ -0,0 +1,298 @@
+//! PowerPC (32-bit) relocation types
+//!
+//! As defined in the "System V Application Binary Interface PowerPC
+//! Processor Supplement" and the "PowerPC Embedded Application Binary
+//! Interface" (EABI) used by e500/QorIQ targets.
+//!
+//! The following nomenclature is used for the operation:
+//! - S is the value of the symbol, A the addend and P the place being relocated.
+//! - B is the base address of the shared object.
+//! - G is the offset of the GOT entry for the symbol, L the address of its PLT entry.
+//! - R is the offset of the symbol within its section.
+//! - #lo/#hi/#ha select 16 bit parts of a value (#ha adjusts for the sign of
+//!   the lower part).
+
+#[cfg(test)]
+mod test;
+
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+#[allow(non_camel_case_types)]
+#[repr(u32)]
+pub enum RelocationTypes {
+    /// No relocation.
+    R_PPC_NONE,
+    /// word32: S + A.
+    R_PPC_ADDR32,
+    /// low24: (S + A) >> 2.
+    R_PPC_ADDR24,
+    /// half16: S + A.
+    R_PPC_ADDR16,
+    /// half16: #lo(S + A).
+    R_PPC_ADDR16_LO,
+    /// half16: #hi(S + A).
+    R_PPC_ADDR16_HI,
+    /// half16: #ha(S + A).
+    R_PPC_ADDR16_HA,
+    /// low14: (S + A) >> 2.
+    R_PPC_ADDR14,
+    /// low14: (S + A) >> 2, branch taken hint.
+    R_PPC_ADDR14_BRTAKEN,
+    /// low14: (S + A) >> 2, branch not taken hint.
+    R_PPC_ADDR14_BRNTAKEN,
+    /// low24: (S + A - P) >> 2.
+    R_PPC_REL24,
+    /// low14: (S + A - P) >> 2.
+    R_PPC_REL14,
+    /// low14: (S + A - P) >> 2, branch taken hint.
+    R_PPC_REL14_BRTAKEN,
+    /// low14: (S + A - P) >> 2, branch not taken hint.
+    R_PPC_REL14_BRNTAKEN,
+    /// half16: G + A.
+    R_PPC_GOT16,
+    /// half16: #lo(G + A).
+    R_PPC_GOT16_LO,
+    /// half16: #hi(G + A).
+    R_PPC_GOT16_HI,
+    /// half16: #ha(G + A).
+    R_PPC_GOT16_HA,
+    /// low24: (L + A - P) >> 2.
+    R_PPC_PLTREL24,
+    /// Copy data from shared object.
+    R_PPC_COPY,
+    /// word32: S + A, set GOT entry to symbol value.
+    R_PPC_GLOB_DAT,
+    /// Set PLT entry to the code address of the symbol.
+    R_PPC_JMP_SLOT,
+    /// word32: B + A.
+    R_PPC_RELATIVE,
+    /// low24: (S + A - P) >> 2, local symbol.
+    R_PPC_LOCAL24PC,
+    /// word32 (unaligned): S + A.
+    R_PPC_UADDR32,
+    /// half16 (unaligned): S + A.
+    R_PPC_UADDR16,
+    /// word32: S + A - P.
+    R_PPC_REL32,
+    /// word32: L + A.
+    R_PPC_PLT32,
+    /// word32: L + A - P.
+    R_PPC_PLTREL32,
+    /// half16: #lo(L + A).
+    R_PPC_PLT16_LO,
+    /// half16: #hi(L + A).
+    R_PPC_PLT16_HI,
+    /// half16: #ha(L + A).
+    R_PPC_PLT16_HA,
+    /// half16: S + A - _SDA_BASE_.
+    R_PPC_SDAREL16,
+    /// half16: R + A.
+    R_PPC_SECTOFF,
+    /// half16: #lo(R + A).
+    R_PPC_SECTOFF_LO,
+    /// half16: #hi(R + A).
+    R_PPC_SECTOFF_HI,
+    /// half16: #ha(R + A).
+    R_PPC_SECTOFF_HA,
+    /// Marks an instruction of a TLS sequence.
+    R_PPC_TLS,
+    /// word32: module id of the symbol.
+    R_PPC_DTPMOD32,
+    /// half16: tprel.
+    R_PPC_TPREL16,
+    /// half16: #lo(tprel).
+    R_PPC_TPREL16_LO,
+    /// half16: #hi(tprel).
+    R_PPC_TPREL16_HI,
+    /// half16: #ha(tprel).
+    R_PPC_TPREL16_HA,
+    /// word32: tprel.
+    R_PPC_TPREL32,
+    /// half16: dtprel.
+    R_PPC_DTPREL16,
+    /// half16: #lo(dtprel).
+    R_PPC_DTPREL16_LO,
+    /// half16: #hi(dtprel).
+    R_PPC_DTPREL16_HI,
+    /// half16: #ha(dtprel).
+    R_PPC_DTPREL16_HA,
+    /// word32: dtprel.
+    R_PPC_DTPREL32,
+    /// half16: GOT entry for the tls_index of the symbol.
+    R_PPC_GOT_TLSGD16,
+    /// half16: #lo of GOT_TLSGD16.
+    R_PPC_GOT_TLSGD16_LO,
+    /// half16: #hi of GOT_TLSGD16.
+    R_PPC_GOT_TLSGD16_HI,
+    /// half16: #ha of GOT_TLSGD16.
+    R_PPC_GOT_TLSGD16_HA,
+    /// half16: GOT entry for the tls_index of the module.
+    R_PPC_GOT_TLSLD16,
+    /// half16: #lo of GOT_TLSLD16.
+    R_PPC_GOT_TLSLD16_LO,
+    /// half16: #hi of GOT_TLSLD16.
+    R_PPC_GOT_TLSLD16_HI,
+    /// half16: #ha of GOT_TLSLD16.
+    R_PPC_GOT_TLSLD16_HA,
+    /// half16: GOT entry for the tprel of the symbol.
+    R_PPC_GOT_TPREL16,
+    /// half16: #lo of GOT_TPREL16.
+    R_PPC_GOT_TPREL16_LO,
+    /// half16: #hi of GOT_TPREL16.
+    R_PPC_GOT_TPREL16_HI,
+    /// half16: #ha of GOT_TPREL16.
+    R_PPC_GOT_TPREL16_HA,
+    /// half16: GOT entry for the dtprel of the symbol.
+    R_PPC_GOT_DTPREL16,
+    /// half16: #lo of GOT_DTPREL16.
+    R_PPC_GOT_DTPREL16_LO,
+    /// half16: #hi of GOT_DTPREL16.
+    R_PPC_GOT_DTPREL16_HI,
+    /// half16: #ha of GOT_DTPREL16.
+    R_PPC_GOT_DTPREL16_HA,
+    /// Marks the call of a general dynamic TLS sequence.
+    R_PPC_TLSGD,
+    /// Marks the call of a local dynamic TLS sequence.
+    R_PPC_TLSLD,
+    /// word32: A - S (embedded ABI).
+    R_PPC_EMB_NADDR32,
+    /// half16: A - S (embedded ABI).
+    R_PPC_EMB_NADDR16,
+    /// half16: #lo(A - S) (embedded ABI).
+    R_PPC_EMB_NADDR16_LO,
+    /// half16: #hi(A - S) (embedded ABI).
+    R_PPC_EMB_NADDR16_HI,
+    /// half16: #ha(A - S) (embedded ABI).
+    R_PPC_EMB_NADDR16_HA,
+    /// half16: offset of a small data pointer (embedded ABI).
+    R_PPC_EMB_SDAI16,
+    /// half16: offset of a small data pointer in .sdata2 (embedded ABI).
+    R_PPC_EMB_SDA2I16,
+    /// half16: S + A - _SDA2_BASE_ (embedded ABI).
+    R_PPC_EMB_SDA2REL,
+    /// low21: small data area relative with base register (embedded ABI).
+    R_PPC_EMB_SDA21,
+    /// Marks a reference to a section (embedded ABI).
+    R_PPC_EMB_MRKREF,
+    /// half16: address relative to the section (embedded ABI).
+    R_PPC_EMB_RELSEC16,
+    /// half16: #lo of the section start (embedded ABI).
+    R_PPC_EMB_RELST_LO,
+    /// half16: #hi of the section start (embedded ABI).
+    R_PPC_EMB_RELST_HI,
+    /// half16: #ha of the section start (embedded ABI).
+    R_PPC_EMB_RELST_HA,
+    /// Bit field relocation (embedded ABI).
+    R_PPC_EMB_BIT_FLD,
+    /// half16: relative to the small data area of the symbol (embedded ABI).
+    R_PPC_EMB_RELSDA,
+    /// word32: result of calling the resolver at B + A.
+    R_PPC_IRELATIVE,
+    /// half16: S + A - P.
+    R_PPC_REL16,
+    /// half16: #lo(S + A - P).
+    R_PPC_REL16_LO,
+    /// half16: #hi(S + A - P).
+    R_PPC_REL16_HI,
+    /// half16: #ha(S + A - P).
+    R_PPC_REL16_HA,
+    /// Unknown
+    Unknown(u32),
+}
+
+impl RelocationTypes {
+    /// Construct new powerpc::RelocationTypes
+    pub fn from(typ: u32) -> RelocationTypes {
+        use RelocationTypes::*;
+        match typ {
+            0 => R_PPC_NONE,
+            1 => R_PPC_ADDR32,
+            2 => R_PPC_ADDR24,
+            3 => R_PPC_ADDR16,
+            4 => R_PPC_ADDR16_LO,
+            5 => R_PPC_ADDR16_HI,
+            6 => R_PPC_ADDR16_HA,
+            7 => R_PPC_ADDR14,
+            8 => R_PPC_ADDR14_BRTAKEN,
+            9 => R_PPC_ADDR14_BRNTAKEN,
+            10 => R_PPC_REL24,
+            11 => R_PPC_REL14,
+            12 => R_PPC_REL14_BRTAKEN,
+            13 => R_PPC_REL14_BRNTAKEN,
+            14 => R_PPC_GOT16,
+            15 => R_PPC_GOT16_LO,
+            16 => R_PPC_GOT16_HI,
+            17 => R_PPC_GOT16_HA,
+            18 => R_PPC_PLTREL24,
+            19 => R_PPC_COPY,
+            20 => R_PPC_GLOB_DAT,
+            21 => R_PPC_JMP_SLOT,
+            22 => R_PPC_RELATIVE,
+            23 => R_PPC_LOCAL24PC,
+            24 => R_PPC_UADDR32,
+            25 => R_PPC_UADDR16,
+            26 => R_PPC_REL32,
+            27 => R_PPC_PLT32,
+            28 => R_PPC_PLTREL32,
+            29 => R_PPC_PLT16_LO,
+            30 => R_PPC_PLT16_HI,
+            31 => R_PPC_PLT16_HA,
+            32 => R_PPC_SDAREL16,
+            33 => R_PPC_SECTOFF,
+            34 => R_PPC_SECTOFF_LO,
+            35 => R_PPC_SECTOFF_HI,
+            36 => R_PPC_SECTOFF_HA,
+            67 => R_PPC_TLS,
+            68 => R_PPC_DTPMOD32,
+            69 => R_PPC_TPREL16,
+            70 => R_PPC_TPREL16_LO,
+            71 => R_PPC_TPREL16_HI,
+            72 => R_PPC_TPREL16_HA,
+            73 => R_PPC_TPREL32,
+            74 => R_PPC_DTPREL16,
+            75 => R_PPC_DTPREL16_LO,
+            76 => R_PPC_DTPREL16_HI,
+            77 => R_PPC_DTPREL16_HA,
+            78 => R_PPC_DTPREL32,
+            79 => R_PPC_GOT_TLSGD16,
+            80 => R_PPC_GOT_TLSGD16_LO,
+            81 => R_PPC_GOT_TLSGD16_HI,
+            82 => R_PPC_GOT_TLSGD16_HA,
+            83 => R_PPC_GOT_TLSLD16,
+            84 => R_PPC_GOT_TLSLD16_LO,
+            85 => R_PPC_GOT_TLSLD16_HI,
+            86 => R_PPC_GOT_TLSLD16_HA,
+            87 => R_PPC_GOT_TPREL16,
+            88 => R_PPC_GOT_TPREL16_LO,
+            89 => R_PPC_GOT_TPREL16_HI,
+            90 => R_PPC_GOT_TPREL16_HA,
+            91 => R_PPC_GOT_DTPREL16,
+            92 => R_PPC_GOT_DTPREL16_LO,
+            93 => R_PPC_GOT_DTPREL16_HI,
+            94 => R_PPC_GOT_DTPREL16_HA,
+            95 => R_PPC_TLSGD,
+            96 => R_PPC_TLSLD,
+            101 => R_PPC_EMB_NADDR32,
+            102 => R_PPC_EMB_NADDR16,
+            103 => R_PPC_EMB_NADDR16_LO,
+            104 => R_PPC_EMB_NADDR16_HI,
+            105 => R_PPC_EMB_NADDR16_HA,
+            106 => R_PPC_EMB_SDAI16,
+            107 => R_PPC_EMB_SDA2I16,
+            108 => R_PPC_EMB_SDA2REL,
+            109 => R_PPC_EMB_SDA21,
+            110 => R_PPC_EMB_MRKREF,
+            111 => R_PPC_EMB_RELSEC16,
+            112 => R_PPC_EMB_RELST_LO,
+            113 => R_PPC_EMB_RELST_HI,
+            114 => R_PPC_EMB_RELST_HA,
+            115 => R_PPC_EMB_BIT_FLD,
+            116 => R_PPC_EMB_RELSDA,
+            248 => R_PPC_IRELATIVE,
+            249 => R_PPC_REL16,
+            250 => R_PPC_REL16_LO,
+            251 => R_PPC_REL16_HI,
+            252 => R_PPC_REL16_HA,
+            x => Unknown(x),
+        }
+    }
+}
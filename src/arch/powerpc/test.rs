@@ -0,0 +1,23 @@
+use crate::arch::powerpc::RelocationTypes::{self, *};
+use crate::arch::RelocationType;
+use crate::Machine;
+
+#[test]
+fn check_relocation_types() {
+    assert_eq!(RelocationTypes::from(0), R_PPC_NONE);
+    assert_eq!(RelocationTypes::from(1), R_PPC_ADDR32);
+    assert_eq!(RelocationTypes::from(19), R_PPC_COPY);
+    assert_eq!(RelocationTypes::from(20), R_PPC_GLOB_DAT);
+    assert_eq!(RelocationTypes::from(21), R_PPC_JMP_SLOT);
+    assert_eq!(RelocationTypes::from(22), R_PPC_RELATIVE);
+    assert_eq!(RelocationTypes::from(68), R_PPC_DTPMOD32);
+    assert_eq!(RelocationTypes::from(73), R_PPC_TPREL32);
+    assert_eq!(RelocationTypes::from(78), R_PPC_DTPREL32);
+    assert_eq!(RelocationTypes::from(248), R_PPC_IRELATIVE);
+    assert_eq!(RelocationTypes::from(37), Unknown(37));
+
+    assert!(matches!(
+        RelocationType::from(Machine::PowerPC, 22),
+        Ok(RelocationType::PowerPc(R_PPC_RELATIVE))
+    ));
+}
@@ -0,0 +1,400 @@
+//! PowerPC64 relocation types
+//!
+//! As defined in the "64-Bit ELF V2 ABI Specification: Power Architecture"
+//! (OpenPOWER, revision 1.5).
+//!
+//! The following nomenclature is used for the operation:
+//! - S is the value of the symbol, A the addend and P the place being relocated.
+//! - B is the base address of the shared object.
+//! - G is the offset of the GOT entry for the symbol from the TOC base.
+//! - L is the address of the PLT entry, M the offset of it from the TOC base.
+//! - R is the offset of the symbol within its section.
+//! - #lo/#hi/#ha/#higher(a)/#highest(a) select 16 bit parts of a value
+//!   (the `a` variants adjust for the sign of the lower part).
+
+#[cfg(test)]
+mod test;
+
+/// Machine number of PowerPC64 (not known to xmas-elf).
+pub const EM_PPC64: u16 = 21;
+
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+#[allow(non_camel_case_types)]
+#[repr(u32)]
+pub enum RelocationTypes {
+    /// No relocation.
+    R_PPC64_NONE,
+    /// word32: S + A.
+    R_PPC64_ADDR32,
+    /// low24: (S + A) >> 2.
+    R_PPC64_ADDR24,
+    /// half16: S + A.
+    R_PPC64_ADDR16,
+    /// half16: #lo(S + A).
+    R_PPC64_ADDR16_LO,
+    /// half16: #hi(S + A).
+    R_PPC64_ADDR16_HI,
+    /// half16: #ha(S + A).
+    R_PPC64_ADDR16_HA,
+    /// low14: (S + A) >> 2.
+    R_PPC64_ADDR14,
+    /// low14: (S + A) >> 2, branch taken hint.
+    R_PPC64_ADDR14_BRTAKEN,
+    /// low14: (S + A) >> 2, branch not taken hint.
+    R_PPC64_ADDR14_BRNTAKEN,
+    /// low24: (S + A - P) >> 2.
+    R_PPC64_REL24,
+    /// low14: (S + A - P) >> 2.
+    R_PPC64_REL14,
+    /// low14: (S + A - P) >> 2, branch taken hint.
+    R_PPC64_REL14_BRTAKEN,
+    /// low14: (S + A - P) >> 2, branch not taken hint.
+    R_PPC64_REL14_BRNTAKEN,
+    /// half16: G.
+    R_PPC64_GOT16,
+    /// half16: #lo(G).
+    R_PPC64_GOT16_LO,
+    /// half16: #hi(G).
+    R_PPC64_GOT16_HI,
+    /// half16: #ha(G).
+    R_PPC64_GOT16_HA,
+    /// Copy data from shared object.
+    R_PPC64_COPY,
+    /// doubleword64: S + A, set GOT entry to symbol value.
+    R_PPC64_GLOB_DAT,
+    /// doubleword64: function descriptor/entry point of the symbol.
+    R_PPC64_JMP_SLOT,
+    /// doubleword64: B + A.
+    R_PPC64_RELATIVE,
+    /// word32 (unaligned): S + A.
+    R_PPC64_UADDR32,
+    /// half16 (unaligned): S + A.
+    R_PPC64_UADDR16,
+    /// word32: S + A - P.
+    R_PPC64_REL32,
+    /// word32: L.
+    R_PPC64_PLT32,
+    /// word32: L - P.
+    R_PPC64_PLTREL32,
+    /// half16: #lo(L).
+    R_PPC64_PLT16_LO,
+    /// half16: #hi(L).
+    R_PPC64_PLT16_HI,
+    /// half16: #ha(L).
+    R_PPC64_PLT16_HA,
+    /// half16: R + A.
+    R_PPC64_SECTOFF,
+    /// half16: #lo(R + A).
+    R_PPC64_SECTOFF_LO,
+    /// half16: #hi(R + A).
+    R_PPC64_SECTOFF_HI,
+    /// half16: #ha(R + A).
+    R_PPC64_SECTOFF_HA,
+    /// word30: (S + A - P) >> 2.
+    R_PPC64_ADDR30,
+    /// doubleword64: S + A.
+    R_PPC64_ADDR64,
+    /// half16: #higher(S + A).
+    R_PPC64_ADDR16_HIGHER,
+    /// half16: #highera(S + A).
+    R_PPC64_ADDR16_HIGHERA,
+    /// half16: #highest(S + A).
+    R_PPC64_ADDR16_HIGHEST,
+    /// half16: #highesta(S + A).
+    R_PPC64_ADDR16_HIGHESTA,
+    /// doubleword64 (unaligned): S + A.
+    R_PPC64_UADDR64,
+    /// doubleword64: S + A - P.
+    R_PPC64_REL64,
+    /// doubleword64: L.
+    R_PPC64_PLT64,
+    /// doubleword64: L - P.
+    R_PPC64_PLTREL64,
+    /// half16: S + A - .TOC.
+    R_PPC64_TOC16,
+    /// half16: #lo(S + A - .TOC.).
+    R_PPC64_TOC16_LO,
+    /// half16: #hi(S + A - .TOC.).
+    R_PPC64_TOC16_HI,
+    /// half16: #ha(S + A - .TOC.).
+    R_PPC64_TOC16_HA,
+    /// doubleword64: .TOC.
+    R_PPC64_TOC,
+    /// half16: M.
+    R_PPC64_PLTGOT16,
+    /// half16: #lo(M).
+    R_PPC64_PLTGOT16_LO,
+    /// half16: #hi(M).
+    R_PPC64_PLTGOT16_HI,
+    /// half16: #ha(M).
+    R_PPC64_PLTGOT16_HA,
+    /// half16ds: (S + A) >> 2.
+    R_PPC64_ADDR16_DS,
+    /// half16ds: #lo(S + A) >> 2.
+    R_PPC64_ADDR16_LO_DS,
+    /// half16ds: G >> 2.
+    R_PPC64_GOT16_DS,
+    /// half16ds: #lo(G) >> 2.
+    R_PPC64_GOT16_LO_DS,
+    /// half16ds: #lo(L) >> 2.
+    R_PPC64_PLT16_LO_DS,
+    /// half16ds: (R + A) >> 2.
+    R_PPC64_SECTOFF_DS,
+    /// half16ds: #lo(R + A) >> 2.
+    R_PPC64_SECTOFF_LO_DS,
+    /// half16ds: (S + A - .TOC.) >> 2.
+    R_PPC64_TOC16_DS,
+    /// half16ds: #lo(S + A - .TOC.) >> 2.
+    R_PPC64_TOC16_LO_DS,
+    /// half16ds: M >> 2.
+    R_PPC64_PLTGOT16_DS,
+    /// half16ds: #lo(M) >> 2.
+    R_PPC64_PLTGOT16_LO_DS,
+    /// Marks an instruction of a TLS sequence.
+    R_PPC64_TLS,
+    /// doubleword64: module id of the symbol.
+    R_PPC64_DTPMOD64,
+    /// half16: tprel.
+    R_PPC64_TPREL16,
+    /// half16: #lo(tprel).
+    R_PPC64_TPREL16_LO,
+    /// half16: #hi(tprel).
+    R_PPC64_TPREL16_HI,
+    /// half16: #ha(tprel).
+    R_PPC64_TPREL16_HA,
+    /// doubleword64: tprel.
+    R_PPC64_TPREL64,
+    /// half16: dtprel.
+    R_PPC64_DTPREL16,
+    /// half16: #lo(dtprel).
+    R_PPC64_DTPREL16_LO,
+    /// half16: #hi(dtprel).
+    R_PPC64_DTPREL16_HI,
+    /// half16: #ha(dtprel).
+    R_PPC64_DTPREL16_HA,
+    /// doubleword64: dtprel.
+    R_PPC64_DTPREL64,
+    /// half16: GOT entry for the tls_index of the symbol.
+    R_PPC64_GOT_TLSGD16,
+    /// half16: #lo of GOT_TLSGD16.
+    R_PPC64_GOT_TLSGD16_LO,
+    /// half16: #hi of GOT_TLSGD16.
+    R_PPC64_GOT_TLSGD16_HI,
+    /// half16: #ha of GOT_TLSGD16.
+    R_PPC64_GOT_TLSGD16_HA,
+    /// half16: GOT entry for the tls_index of the module.
+    R_PPC64_GOT_TLSLD16,
+    /// half16: #lo of GOT_TLSLD16.
+    R_PPC64_GOT_TLSLD16_LO,
+    /// half16: #hi of GOT_TLSLD16.
+    R_PPC64_GOT_TLSLD16_HI,
+    /// half16: #ha of GOT_TLSLD16.
+    R_PPC64_GOT_TLSLD16_HA,
+    /// half16ds: GOT entry for the tprel of the symbol.
+    R_PPC64_GOT_TPREL16_DS,
+    /// half16ds: #lo of GOT_TPREL16_DS.
+    R_PPC64_GOT_TPREL16_LO_DS,
+    /// half16: #hi of GOT_TPREL16_DS.
+    R_PPC64_GOT_TPREL16_HI,
+    /// half16: #ha of GOT_TPREL16_DS.
+    R_PPC64_GOT_TPREL16_HA,
+    /// half16ds: GOT entry for the dtprel of the symbol.
+    R_PPC64_GOT_DTPREL16_DS,
+    /// half16ds: #lo of GOT_DTPREL16_DS.
+    R_PPC64_GOT_DTPREL16_LO_DS,
+    /// half16: #hi of GOT_DTPREL16_DS.
+    R_PPC64_GOT_DTPREL16_HI,
+    /// half16: #ha of GOT_DTPREL16_DS.
+    R_PPC64_GOT_DTPREL16_HA,
+    /// half16ds: tprel >> 2.
+    R_PPC64_TPREL16_DS,
+    /// half16ds: #lo(tprel) >> 2.
+    R_PPC64_TPREL16_LO_DS,
+    /// half16: #higher(tprel).
+    R_PPC64_TPREL16_HIGHER,
+    /// half16: #highera(tprel).
+    R_PPC64_TPREL16_HIGHERA,
+    /// half16: #highest(tprel).
+    R_PPC64_TPREL16_HIGHEST,
+    /// half16: #highesta(tprel).
+    R_PPC64_TPREL16_HIGHESTA,
+    /// half16ds: dtprel >> 2.
+    R_PPC64_DTPREL16_DS,
+    /// half16ds: #lo(dtprel) >> 2.
+    R_PPC64_DTPREL16_LO_DS,
+    /// half16: #higher(dtprel).
+    R_PPC64_DTPREL16_HIGHER,
+    /// half16: #highera(dtprel).
+    R_PPC64_DTPREL16_HIGHERA,
+    /// half16: #highest(dtprel).
+    R_PPC64_DTPREL16_HIGHEST,
+    /// half16: #highesta(dtprel).
+    R_PPC64_DTPREL16_HIGHESTA,
+    /// Marks the call of a general dynamic TLS sequence.
+    R_PPC64_TLSGD,
+    /// Marks the call of a local dynamic TLS sequence.
+    R_PPC64_TLSLD,
+    /// Marks a TOC save slot of a call.
+    R_PPC64_TOCSAVE,
+    /// half16: #hi(S + A) without overflow check.
+    R_PPC64_ADDR16_HIGH,
+    /// half16: #ha(S + A) without overflow check.
+    R_PPC64_ADDR16_HIGHA,
+    /// half16: #hi(tprel) without overflow check.
+    R_PPC64_TPREL16_HIGH,
+    /// half16: #ha(tprel) without overflow check.
+    R_PPC64_TPREL16_HIGHA,
+    /// half16: #hi(dtprel) without overflow check.
+    R_PPC64_DTPREL16_HIGH,
+    /// half16: #ha(dtprel) without overflow check.
+    R_PPC64_DTPREL16_HIGHA,
+    /// low24: (S + A - P) >> 2, call that does not preserve r2.
+    R_PPC64_REL24_NOTOC,
+    /// doubleword64: S + A, using the local entry point.
+    R_PPC64_ADDR64_LOCAL,
+    /// Marks the global entry point sequence of a function.
+    R_PPC64_ENTRY,
+    /// doubleword64: result of calling the resolver at B + A.
+    R_PPC64_IRELATIVE,
+    /// half16: S + A - P.
+    R_PPC64_REL16,
+    /// half16: #lo(S + A - P).
+    R_PPC64_REL16_LO,
+    /// half16: #hi(S + A - P).
+    R_PPC64_REL16_HI,
+    /// half16: #ha(S + A - P).
+    R_PPC64_REL16_HA,
+    /// Unknown
+    Unknown(u32),
+}
+
+impl RelocationTypes {
+    /// Construct new powerpc64::RelocationTypes
+    pub fn from(typ: u32) -> RelocationTypes {
+        use RelocationTypes::*;
+        match typ {
+            0 => R_PPC64_NONE,
+            1 => R_PPC64_ADDR32,
+            2 => R_PPC64_ADDR24,
+            3 => R_PPC64_ADDR16,
+            4 => R_PPC64_ADDR16_LO,
+            5 => R_PPC64_ADDR16_HI,
+            6 => R_PPC64_ADDR16_HA,
+            7 => R_PPC64_ADDR14,
+            8 => R_PPC64_ADDR14_BRTAKEN,
+            9 => R_PPC64_ADDR14_BRNTAKEN,
+            10 => R_PPC64_REL24,
+            11 => R_PPC64_REL14,
+            12 => R_PPC64_REL14_BRTAKEN,
+            13 => R_PPC64_REL14_BRNTAKEN,
+            14 => R_PPC64_GOT16,
+            15 => R_PPC64_GOT16_LO,
+            16 => R_PPC64_GOT16_HI,
+            17 => R_PPC64_GOT16_HA,
+            19 => R_PPC64_COPY,
+            20 => R_PPC64_GLOB_DAT,
+            21 => R_PPC64_JMP_SLOT,
+            22 => R_PPC64_RELATIVE,
+            24 => R_PPC64_UADDR32,
+            25 => R_PPC64_UADDR16,
+            26 => R_PPC64_REL32,
+            27 => R_PPC64_PLT32,
+            28 => R_PPC64_PLTREL32,
+            29 => R_PPC64_PLT16_LO,
+            30 => R_PPC64_PLT16_HI,
+            31 => R_PPC64_PLT16_HA,
+            33 => R_PPC64_SECTOFF,
+            34 => R_PPC64_SECTOFF_LO,
+            35 => R_PPC64_SECTOFF_HI,
+            36 => R_PPC64_SECTOFF_HA,
+            37 => R_PPC64_ADDR30,
+            38 => R_PPC64_ADDR64,
+            39 => R_PPC64_ADDR16_HIGHER,
+            40 => R_PPC64_ADDR16_HIGHERA,
+            41 => R_PPC64_ADDR16_HIGHEST,
+            42 => R_PPC64_ADDR16_HIGHESTA,
+            43 => R_PPC64_UADDR64,
+            44 => R_PPC64_REL64,
+            45 => R_PPC64_PLT64,
+            46 => R_PPC64_PLTREL64,
+            47 => R_PPC64_TOC16,
+            48 => R_PPC64_TOC16_LO,
+            49 => R_PPC64_TOC16_HI,
+            50 => R_PPC64_TOC16_HA,
+            51 => R_PPC64_TOC,
+            52 => R_PPC64_PLTGOT16,
+            53 => R_PPC64_PLTGOT16_LO,
+            54 => R_PPC64_PLTGOT16_HI,
+            55 => R_PPC64_PLTGOT16_HA,
+            56 => R_PPC64_ADDR16_DS,
+            57 => R_PPC64_ADDR16_LO_DS,
+            58 => R_PPC64_GOT16_DS,
+            59 => R_PPC64_GOT16_LO_DS,
+            60 => R_PPC64_PLT16_LO_DS,
+            61 => R_PPC64_SECTOFF_DS,
+            62 => R_PPC64_SECTOFF_LO_DS,
+            63 => R_PPC64_TOC16_DS,
+            64 => R_PPC64_TOC16_LO_DS,
+            65 => R_PPC64_PLTGOT16_DS,
+            66 => R_PPC64_PLTGOT16_LO_DS,
+            67 => R_PPC64_TLS,
+            68 => R_PPC64_DTPMOD64,
+            69 => R_PPC64_TPREL16,
+            70 => R_PPC64_TPREL16_LO,
+            71 => R_PPC64_TPREL16_HI,
+            72 => R_PPC64_TPREL16_HA,
+            73 => R_PPC64_TPREL64,
+            74 => R_PPC64_DTPREL16,
+            75 => R_PPC64_DTPREL16_LO,
+            76 => R_PPC64_DTPREL16_HI,
+            77 => R_PPC64_DTPREL16_HA,
+            78 => R_PPC64_DTPREL64,
+            79 => R_PPC64_GOT_TLSGD16,
+            80 => R_PPC64_GOT_TLSGD16_LO,
+            81 => R_PPC64_GOT_TLSGD16_HI,
+            82 => R_PPC64_GOT_TLSGD16_HA,
+            83 => R_PPC64_GOT_TLSLD16,
+            84 => R_PPC64_GOT_TLSLD16_LO,
+            85 => R_PPC64_GOT_TLSLD16_HI,
+            86 => R_PPC64_GOT_TLSLD16_HA,
+            87 => R_PPC64_GOT_TPREL16_DS,
+            88 => R_PPC64_GOT_TPREL16_LO_DS,
+            89 => R_PPC64_GOT_TPREL16_HI,
+            90 => R_PPC64_GOT_TPREL16_HA,
+            91 => R_PPC64_GOT_DTPREL16_DS,
+            92 => R_PPC64_GOT_DTPREL16_LO_DS,
+            93 => R_PPC64_GOT_DTPREL16_HI,
+            94 => R_PPC64_GOT_DTPREL16_HA,
+            95 => R_PPC64_TPREL16_DS,
+            96 => R_PPC64_TPREL16_LO_DS,
+            97 => R_PPC64_TPREL16_HIGHER,
+            98 => R_PPC64_TPREL16_HIGHERA,
+            99 => R_PPC64_TPREL16_HIGHEST,
+            100 => R_PPC64_TPREL16_HIGHESTA,
+            101 => R_PPC64_DTPREL16_DS,
+            102 => R_PPC64_DTPREL16_LO_DS,
+            103 => R_PPC64_DTPREL16_HIGHER,
+            104 => R_PPC64_DTPREL16_HIGHERA,
+            105 => R_PPC64_DTPREL16_HIGHEST,
+            106 => R_PPC64_DTPREL16_HIGHESTA,
+            107 => R_PPC64_TLSGD,
+            108 => R_PPC64_TLSLD,
+            109 => R_PPC64_TOCSAVE,
+            110 => R_PPC64_ADDR16_HIGH,
+            111 => R_PPC64_ADDR16_HIGHA,
+            112 => R_PPC64_TPREL16_HIGH,
+            113 => R_PPC64_TPREL16_HIGHA,
+            114 => R_PPC64_DTPREL16_HIGH,
+            115 => R_PPC64_DTPREL16_HIGHA,
+            116 => R_PPC64_REL24_NOTOC,
+            117 => R_PPC64_ADDR64_LOCAL,
+            118 => R_PPC64_ENTRY,
+            248 => R_PPC64_IRELATIVE,
+            249 => R_PPC64_REL16,
+            250 => R_PPC64_REL16_LO,
+            251 => R_PPC64_REL16_HI,
+            252 => R_PPC64_REL16_HA,
+            x => Unknown(x),
+        }
+    }
+}
@@ -0,0 +1,26 @@
+use crate::arch::powerpc64::RelocationTypes::{self, *};
+use crate::arch::powerpc64::EM_PPC64;
+use crate::arch::RelocationType;
+use crate::Machine;
+
+#[test]
+fn check_relocation_types() {
+    assert_eq!(RelocationTypes::from(0), R_PPC64_NONE);
+    assert_eq!(RelocationTypes::from(1), R_PPC64_ADDR32);
+    assert_eq!(RelocationTypes::from(19), R_PPC64_COPY);
+    assert_eq!(RelocationTypes::from(20), R_PPC64_GLOB_DAT);
+    assert_eq!(RelocationTypes::from(21), R_PPC64_JMP_SLOT);
+    assert_eq!(RelocationTypes::from(22), R_PPC64_RELATIVE);
+    assert_eq!(RelocationTypes::from(38), R_PPC64_ADDR64);
+    assert_eq!(RelocationTypes::from(68), R_PPC64_DTPMOD64);
+    assert_eq!(RelocationTypes::from(73), R_PPC64_TPREL64);
+    assert_eq!(RelocationTypes::from(78), R_PPC64_DTPREL64);
+    assert_eq!(RelocationTypes::from(248), R_PPC64_IRELATIVE);
+    assert_eq!(RelocationTypes::from(18), Unknown(18));
+    assert_eq!(RelocationTypes::from(253), Unknown(253));
+
+    assert!(matches!(
+        RelocationType::from(Machine::Other(EM_PPC64), 22),
+        Ok(RelocationType::PowerPc64(R_PPC64_RELATIVE))
+    ));
+}
@@ -1,6 +1,12 @@
 //! RISCV relocation types
 //!
 
+use super::{align_up, TlsSegment};
+use crate::reloc::{tp_relative, RelocationResolver};
+use crate::{elf, ElfLoaderErr, RelocationEntry};
+#[cfg(feature = "apply")]
+use crate::{RelocationType, VAddr};
+
 #[cfg(test)]
 mod test;
 
@@ -114,6 +120,26 @@ pub enum RelocationTypes {
     R_RISCV_SET16,
     /// Local label subtraction
     R_RISCV_SET32,
+    /// 32-bit PC relative
+    R_RISCV_32_PCREL,
+    /// Relocation against a non-preemptible ifunc symbol
+    R_RISCV_IRELATIVE,
+    /// 32-bit relative offset to a function or its PLT entry
+    R_RISCV_PLT32,
+    /// Local label assignment (ULEB128)
+    R_RISCV_SET_ULEB128,
+    /// Local label subtraction (ULEB128)
+    R_RISCV_SUB_ULEB128,
+    /// TLS descriptor
+    R_RISCV_TLSDESC,
+    /// PC-relative TLS descriptor reference, high 20 bits
+    R_RISCV_TLSDESC_HI20,
+    /// Load of the TLS descriptor resolver, low 12 bits
+    R_RISCV_TLSDESC_LOAD_LO12,
+    /// Address of the TLS descriptor, low 12 bits
+    R_RISCV_TLSDESC_ADD_LO12,
+    /// Call of the TLS descriptor resolver
+    R_RISCV_TLSDESC_CALL,
 
     /// Unknown
     Unknown(u32),
@@ -136,6 +162,7 @@ impl RelocationTypes {
             9 => R_RISCV_TLS_DTPREL64,
             10 => R_RISCV_TLS_TPREL32,
             11 => R_RISCV_TLS_TPREL64,
+            12 => R_RISCV_TLSDESC,
             16 => R_RISCV_BRANCH,
             17 => R_RISCV_JAL,
             18 => R_RISCV_CALL,
@@ -177,7 +204,103 @@ impl RelocationTypes {
             54 => R_RISCV_SET8,
             55 => R_RISCV_SET16,
             56 => R_RISCV_SET32,
+            57 => R_RISCV_32_PCREL,
+            58 => R_RISCV_IRELATIVE,
+            59 => R_RISCV_PLT32,
+            60 => R_RISCV_SET_ULEB128,
+            61 => R_RISCV_SUB_ULEB128,
+            62 => R_RISCV_TLSDESC_HI20,
+            63 => R_RISCV_TLSDESC_LOAD_LO12,
+            64 => R_RISCV_TLSDESC_ADD_LO12,
+            65 => R_RISCV_TLSDESC_CALL,
             x => Unknown(x),
         }
     }
 }
+
+/// Set if the binary references symbols using the variant calling
+/// convention (DT_RISCV_VARIANT_CC).
+pub const DT_RISCV_VARIANT_CC: u64 = 0x70000001;
+/// `st_other` flag of symbols using a variant calling convention, e.g.,
+/// passing arguments in vector registers (STO_RISCV_VARIANT_CC).
+pub const STO_RISCV_VARIANT_CC: u8 = 0x80;
+
+/// Whether `symbol` follows a variant calling convention: calls to it must
+/// not go through a lazy binding trampoline, which may clobber the registers
+/// holding its arguments.
+pub fn is_variant_cc(symbol: &elf::Symbol) -> bool {
+    symbol.other & STO_RISCV_VARIANT_CC != 0
+}
+
+/// Size of the thread control block the thread pointer points to (the TLS blocks start right at
+/// the thread pointer).
+pub const TCB_SIZE: u64 = 0;
+
+/// Thread pointer relative offset of `value` in the TLS block of the main
+/// executable (TLS variant I: the block follows the TCB).
+pub fn tp_offset(tls: &TlsSegment, value: u64) -> i64 {
+    (align_up(TCB_SIZE, tls.align) + value) as i64
+}
+
+/// Computes the value and width (in bytes) of a RISC-V relocation, see
+/// [`RelocationEntry::evaluate`].
+///
+/// Word sized relocations (`R_RISCV_RELATIVE`, `R_RISCV_JUMP_SLOT`) are
+/// evaluated for RV64.
+pub(crate) fn evaluate<R: RelocationResolver>(
+    typ: RelocationTypes,
+    entry: &RelocationEntry,
+    resolver: &mut R,
+) -> Result<Option<(u64, usize)>, ElfLoaderErr> {
+    use RelocationTypes::*;
+
+    let i = entry.index;
+    let a = || entry.required_addend();
+    let b = resolver.load_bias();
+    let p = b.wrapping_add(entry.offset);
+    let word = entry.class.word_size();
+    let value = match typ {
+        R_RISCV_NONE => return Ok(None),
+        R_RISCV_32 => (resolver.symbol_value(i)?.wrapping_add(a()?), 4),
+        R_RISCV_64 => (resolver.symbol_value(i)?.wrapping_add(a()?), 8),
+        R_RISCV_32_PCREL => (
+            resolver.symbol_value(i)?.wrapping_add(a()?).wrapping_sub(p),
+            4,
+        ),
+        R_RISCV_JUMP_SLOT => (resolver.symbol_value(i)?, word),
+        R_RISCV_RELATIVE => (b.wrapping_add(a()?), word),
+        R_RISCV_TLS_DTPMOD32 => (resolver.tls_module(i)? as u64, 4),
+        R_RISCV_TLS_DTPMOD64 => (resolver.tls_module(i)? as u64, 8),
+        R_RISCV_TLS_DTPREL32 => (resolver.symbol_value(i)?.wrapping_add(a()?), 4),
+        R_RISCV_TLS_DTPREL64 => (resolver.symbol_value(i)?.wrapping_add(a()?), 8),
+        R_RISCV_TLS_TPREL32 => (tp_relative(resolver, i, a()?)?, 4),
+        R_RISCV_TLS_TPREL64 => (tp_relative(resolver, i, a()?)?, 8),
+        _ => return Err(ElfLoaderErr::UnsupportedRelocationEntry),
+    };
+    Ok(Some(value))
+}
+
+/// Applies a RISC-V dynamic relocation of a binary loaded at `base`.
+///
+/// `resolve_symbol` returns the (already relocated) address S of the symbol
+/// with the given index, `write` stores the little-endian value (32 or 64
+/// bits wide) at the given (relocated) address.
+///
+/// Supports the types [`RelocationEntry::evaluate`] handles, except for TLS
+/// relocations. Other types return `UnsupportedRelocationEntry`.
+#[cfg(feature = "apply")]
+pub fn apply<S, W>(
+    entry: RelocationEntry,
+    base: VAddr,
+    resolve_symbol: S,
+    write: W,
+) -> Result<(), ElfLoaderErr>
+where
+    S: FnMut(u32) -> Result<u64, ElfLoaderErr>,
+    W: FnMut(VAddr, &[u8]) -> Result<(), ElfLoaderErr>,
+{
+    match entry.rtype {
+        RelocationType::RiscV(_) => super::apply(entry, base, resolve_symbol, write),
+        _ => Err(ElfLoaderErr::UnsupportedRelocationEntry),
+    }
+}
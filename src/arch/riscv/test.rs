@@ -1,4 +1,5 @@
 use std::fs;
+use std::vec::Vec;
 
 use crate::arch::test::*;
 use crate::*;
@@ -29,11 +30,19 @@ fn load_pie_elf() {
     // DYNAMIC        0x000e30 0x0000000000001e30 0x0000000000001e30 0x0001d0 0x0001d0 RW  0x8
     assert_eq!(
         loader.actions[0],
-        LoaderAction::Allocate(VAddr::from(0x0u64), 0x780, Flags(1 | 4))
+        LoaderAction::Allocate(
+            VAddr::from(0x0u64),
+            0x780,
+            SegmentPermissions::READ | SegmentPermissions::EXECUTE
+        )
     );
     assert_eq!(
         loader.actions[1],
-        LoaderAction::Allocate(VAddr::from(0x1e20u64), 0x288, Flags(0b110))
+        LoaderAction::Allocate(
+            VAddr::from(0x1e20u64),
+            0x288,
+            SegmentPermissions::READ | SegmentPermissions::WRITE
+        )
     );
     assert_eq!(
         loader.actions[2],
@@ -81,7 +90,10 @@ fn load_pie_elf() {
         LoaderAction::Relocate(0x1000_0000 + 0x2058, 0x1000_06e0)
     );
 
-    assert_eq!(loader.actions.len(), 8);
+    // GNU_RELRO is made read-only last
+    assert_eq!(loader.actions[8], LoaderAction::Relro(0x1e20, 0x1e0));
+
+    assert_eq!(loader.actions.len(), 9);
 }
 
 #[test]
@@ -125,3 +137,259 @@ fn check_tls() {
         .find(|&&x| x == LoaderAction::Tls(VAddr::from(0x1e0cu64), 0x4, 0x8, 0x4))
         .is_some());
 }
+
+#[test]
+fn check_textrel() {
+    init();
+
+    let binary_blob = fs::read("test/tls.riscv64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+    assert!(binary.has_textrel());
+    // readelf -d: TEXTREL, but no DF_TEXTREL in FLAGS
+    let dynamic = binary.dynamic().expect("Has a dynamic section");
+    assert!(dynamic.textrel);
+    assert!(!dynamic.flags.contains(DynamicFlags::TEXTREL));
+
+    let mut loader = TestLoader::new(0x1000_0000);
+    binary.load(&mut loader).expect("Can't load?");
+
+    // The R E segment is made writable before the first relocation and
+    // restored after the last one:
+    // LOAD 0x000000 0x0000000000000000 0x0000000000000000 0x000808 0x000808 R E 0x1000
+    let writable = loader
+        .actions
+        .iter()
+        .position(|&x| x == LoaderAction::MakeWritable(0x0, 0x808))
+        .expect("No make_writable call?");
+    let restored = loader
+        .actions
+        .iter()
+        .position(|&x| {
+            x == LoaderAction::RestorePermissions(
+                0x0,
+                0x808,
+                SegmentPermissions::READ | SegmentPermissions::EXECUTE,
+            )
+        })
+        .expect("No restore_permissions call?");
+    let relocations: Vec<usize> = loader
+        .actions
+        .iter()
+        .enumerate()
+        .filter(|(_, x)| matches!(x, LoaderAction::Relocate(_, _)))
+        .map(|(idx, _)| idx)
+        .collect();
+    assert!(!relocations.is_empty());
+    assert!(relocations
+        .iter()
+        .all(|&idx| writable < idx && idx < restored));
+
+    // Binaries without DT_TEXTREL don't get these calls
+    let binary_blob = fs::read("test/test.riscv64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+    assert!(!binary.has_textrel());
+    let mut loader = TestLoader::new(0x1000_0000);
+    binary.load(&mut loader).expect("Can't load?");
+    assert!(!loader
+        .actions
+        .iter()
+        .any(|x| matches!(x, LoaderAction::MakeWritable(_, _))));
+}
+
+#[test]
+fn check_lazy_binding_slots() {
+    init();
+    let binary_blob = fs::read("test/test.riscv64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+
+    // readelf -d: 0x0000000000000003 (PLTGOT) 0x2008
+    assert_eq!(binary.pltgot(), Some(0x2008));
+    assert_eq!(
+        binary.lazy_binding_slots(),
+        Ok(Some(LazyBindingSlots {
+            link_map: 0x2010,
+            resolver: 0x2008
+        }))
+    );
+
+    let binary_blob = fs::read("test/test_nopie.riscv64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+    assert_eq!(binary.lazy_binding_slots(), Ok(None));
+}
+
+#[test]
+fn check_relocation_types() {
+    use crate::arch::riscv::RelocationTypes::{self, *};
+
+    assert_eq!(RelocationTypes::from(12), R_RISCV_TLSDESC);
+    assert_eq!(RelocationTypes::from(57), R_RISCV_32_PCREL);
+    assert_eq!(RelocationTypes::from(58), R_RISCV_IRELATIVE);
+    assert_eq!(RelocationTypes::from(59), R_RISCV_PLT32);
+    assert_eq!(RelocationTypes::from(60), R_RISCV_SET_ULEB128);
+    assert_eq!(RelocationTypes::from(61), R_RISCV_SUB_ULEB128);
+    assert_eq!(RelocationTypes::from(65), R_RISCV_TLSDESC_CALL);
+    assert_eq!(RelocationTypes::from(66), Unknown(66));
+}
+
+#[test]
+fn check_bind_lazy() {
+    init();
+    let binary_blob = fs::read("test/test.riscv64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+
+    // t1 holds the offset of the GOT slot: __libc_start_main
+    let fixup = binary.bind_lazy(0x1000_0000, 8, |entry| {
+        assert_eq!(entry.index, 9);
+        Ok(0x1234)
+    });
+    assert_eq!(fixup.map(|fixup| fixup.address), Ok(0x1000_2020));
+}
+
+#[test]
+fn check_got_fixups() {
+    init();
+    let binary_blob = fs::read("test/test.riscv64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+
+    // readelf -r: 4 RELATIVE, 7 R_RISCV_64 and 2 JUMP_SLOT
+    let fixups: Vec<GotFixup> = binary.got_fixups().map(|f| f.unwrap()).collect();
+    let count = |kind| fixups.iter().filter(|f| f.kind == kind).count();
+    assert_eq!(count(GotFixupKind::Relative), 4);
+    assert_eq!(count(GotFixupKind::GlobDat), 7);
+    assert_eq!(count(GotFixupKind::JumpSlot), 2);
+    assert_eq!((fixups[4].address, fixups[4].symbol), (0x2030, 3));
+}
+
+#[test]
+fn check_scatter_load() {
+    init();
+    let mut binary_blob = fs::read("test/test_nopie.riscv64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+
+    // readelf -l: both LOAD segments run where they are stored
+    let steps: Vec<ScatterLoad> = binary.scatter_load().collect();
+    assert_eq!(
+        steps,
+        [ScatterLoad::Zero {
+            segment: 2,
+            vma: 0x18188,
+            size: 0x678
+        }]
+    );
+
+    // Store .data after .text
+    binary_blob[0xc8..0xd0].copy_from_slice(&0x16b00u64.to_le_bytes());
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+    let steps: Vec<ScatterLoad> = binary.scatter_load().collect();
+    assert_eq!(
+        steps[0],
+        ScatterLoad::Copy {
+            segment: 2,
+            lma: 0x16b00,
+            vma: 0x17fe0,
+            size: 0x1a8
+        }
+    );
+    assert_eq!(steps.len(), 2);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn check_exports() {
+    init();
+    let binary_blob = fs::read("test/test.riscv64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+
+    // readelf --dyn-syms: `main` is the only defined global symbol, the
+    // undefined imports and the local .text section symbol are left out
+    let exports = binary.exports(0x1000_0000).expect("Has .dynsym");
+    assert_eq!(exports.len(), 1);
+    assert_eq!(exports.get("main"), Some(&0x1000_06e0));
+
+    let binary_blob = fs::read("test/static_pie.x86_64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+    assert!(binary.exports(0x1000_0000).map_or(true, |e| e.is_empty()));
+}
+
+#[test]
+fn check_variant_cc() {
+    init();
+    let mut binary_blob = fs::read("test/test.riscv64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+    assert!(!binary.dynamic().expect("Has .dynamic").riscv_variant_cc);
+    assert_eq!(binary.riscv_variant_cc_symbols().count(), 0);
+
+    // Mark printf (.dynsym entry 2 at 0x258) and replace DT_RELACOUNT by
+    // DT_RISCV_VARIANT_CC
+    binary_blob[0x258 + 2 * 24 + 5] |= arch::riscv::STO_RISCV_VARIANT_CC;
+    let dynamic = 0xe30..0xe30 + 0x1d0;
+    let relacount = binary_blob[dynamic.clone()]
+        .chunks(16)
+        .position(|entry| entry[..8] == 0x6ffffff9u64.to_le_bytes())
+        .expect("Has DT_RELACOUNT");
+    let tag = dynamic.start + relacount * 16;
+    binary_blob[tag..tag + 8].copy_from_slice(&arch::riscv::DT_RISCV_VARIANT_CC.to_le_bytes());
+
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+    assert!(binary.dynamic().expect("Has .dynamic").riscv_variant_cc);
+    let symbols: Vec<u32> = binary.riscv_variant_cc_symbols().collect();
+    assert_eq!(symbols, [2]);
+}
+
+#[test]
+fn check_evaluate() {
+    use crate::arch::riscv::RelocationTypes::{self, *};
+    use crate::elf::Class;
+
+    struct Resolver;
+    impl RelocationResolver for Resolver {
+        fn symbol_value(&mut self, index: u32) -> Result<u64, ElfLoaderErr> {
+            Ok(0x100 * index as u64)
+        }
+        fn load_bias(&self) -> u64 {
+            0x1000_0000
+        }
+    }
+
+    let evaluate = |rtype: RelocationTypes, class, index: u32, addend| {
+        let type_num = (0..).find(|&n| RelocationTypes::from(n) == rtype).unwrap();
+        RelocationEntry {
+            rtype: RelocationType::RiscV(rtype),
+            type_num,
+            info: type_num as u64,
+            offset: 0x2000,
+            index,
+            addend: Some(addend),
+            class,
+            segment: None,
+        }
+        .evaluate(&mut Resolver)
+        .map(|value| value.map(|value| (value.value, value.width)))
+    };
+
+    // Word-sized relocations are as wide as the addresses of the binary
+    assert_eq!(
+        evaluate(R_RISCV_RELATIVE, Class::Elf64, 0, 0x10),
+        Ok(Some((0x1000_0010, 8)))
+    );
+    assert_eq!(
+        evaluate(R_RISCV_RELATIVE, Class::Elf32, 0, 0x10),
+        Ok(Some((0x1000_0010, 4)))
+    );
+    assert_eq!(
+        evaluate(R_RISCV_JUMP_SLOT, Class::Elf64, 2, 0),
+        Ok(Some((0x200, 8)))
+    );
+    assert_eq!(
+        evaluate(R_RISCV_JUMP_SLOT, Class::Elf32, 2, 0),
+        Ok(Some((0x200, 4)))
+    );
+    assert_eq!(
+        evaluate(R_RISCV_64, Class::Elf64, 2, 8),
+        Ok(Some((0x208, 8)))
+    );
+    assert_eq!(
+        evaluate(R_RISCV_32, Class::Elf32, 2, 8),
+        Ok(Some((0x208, 4)))
+    );
+}
@@ -0,0 +1,229 @@
+//! s390x relocation types
+//!
+//! As defined in the "zSeries ELF Application Binary Interface Supplement".
+//!
+//! Note: s390x is big-endian, which [`crate::ElfBinary`] does not accept
+//! yet, so these types are currently only reachable through
+//! [`super::RelocationType::from`].
+
+#[cfg(test)]
+mod test;
+
+/// Machine number of S/390 and zSeries (not known to xmas-elf).
+pub const EM_S390: u16 = 22;
+
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+#[allow(non_camel_case_types)]
+#[repr(u32)]
+pub enum RelocationTypes {
+    /// No relocation.
+    R_390_NONE,
+    /// Direct 8 bit.
+    R_390_8,
+    /// Direct 12 bit.
+    R_390_12,
+    /// Direct 16 bit.
+    R_390_16,
+    /// Direct 32 bit.
+    R_390_32,
+    /// PC relative 32 bit.
+    R_390_PC32,
+    /// 12 bit GOT offset.
+    R_390_GOT12,
+    /// 32 bit GOT offset.
+    R_390_GOT32,
+    /// 32 bit PC relative PLT address.
+    R_390_PLT32,
+    /// Copy data from shared object.
+    R_390_COPY,
+    /// Set GOT entry to data address.
+    R_390_GLOB_DAT,
+    /// Set GOT entry to code address.
+    R_390_JMP_SLOT,
+    /// Add load address of shared object.
+    R_390_RELATIVE,
+    /// 32 bit offset to GOT.
+    R_390_GOTOFF32,
+    /// 32 bit PC relative offset to GOT.
+    R_390_GOTPC,
+    /// 16 bit GOT offset.
+    R_390_GOT16,
+    /// PC relative 16 bit.
+    R_390_PC16,
+    /// PC relative 16 bit shifted by 1.
+    R_390_PC16DBL,
+    /// 16 bit PC relative PLT address shifted by 1.
+    R_390_PLT16DBL,
+    /// PC relative 32 bit shifted by 1.
+    R_390_PC32DBL,
+    /// 32 bit PC relative PLT address shifted by 1.
+    R_390_PLT32DBL,
+    /// 32 bit PC relative offset to GOT shifted by 1.
+    R_390_GOTPCDBL,
+    /// Direct 64 bit.
+    R_390_64,
+    /// PC relative 64 bit.
+    R_390_PC64,
+    /// 64 bit GOT offset.
+    R_390_GOT64,
+    /// 64 bit PC relative PLT address.
+    R_390_PLT64,
+    /// 32 bit PC relative offset to the GOT entry shifted by 1.
+    R_390_GOTENT,
+    /// 16 bit offset to GOT.
+    R_390_GOTOFF16,
+    /// 64 bit offset to GOT.
+    R_390_GOTOFF64,
+    /// 12 bit offset to the jump slot.
+    R_390_GOTPLT12,
+    /// 16 bit offset to the jump slot.
+    R_390_GOTPLT16,
+    /// 32 bit offset to the jump slot.
+    R_390_GOTPLT32,
+    /// 64 bit offset to the jump slot.
+    R_390_GOTPLT64,
+    /// 32 bit PC relative offset to the jump slot shifted by 1.
+    R_390_GOTPLTENT,
+    /// 16 bit offset from GOT to PLT.
+    R_390_PLTOFF16,
+    /// 32 bit offset from GOT to PLT.
+    R_390_PLTOFF32,
+    /// 64 bit offset from GOT to PLT.
+    R_390_PLTOFF64,
+    /// Marks a TLS load instruction.
+    R_390_TLS_LOAD,
+    /// Marks the call of a general dynamic TLS sequence.
+    R_390_TLS_GDCALL,
+    /// Marks the call of a local dynamic TLS sequence.
+    R_390_TLS_LDCALL,
+    /// 32 bit GOT offset for the tls_index of the symbol.
+    R_390_TLS_GD32,
+    /// 64 bit GOT offset for the tls_index of the symbol.
+    R_390_TLS_GD64,
+    /// 12 bit GOT offset for the TP offset of the symbol.
+    R_390_TLS_GOTIE12,
+    /// 32 bit GOT offset for the TP offset of the symbol.
+    R_390_TLS_GOTIE32,
+    /// 64 bit GOT offset for the TP offset of the symbol.
+    R_390_TLS_GOTIE64,
+    /// 32 bit GOT offset for the tls_index of the module.
+    R_390_TLS_LDM32,
+    /// 64 bit GOT offset for the tls_index of the module.
+    R_390_TLS_LDM64,
+    /// 32 bit address of the GOT entry for the TP offset.
+    R_390_TLS_IE32,
+    /// 64 bit address of the GOT entry for the TP offset.
+    R_390_TLS_IE64,
+    /// 32 bit PC relative offset to the GOT entry for the TP offset shifted by 1.
+    R_390_TLS_IEENT,
+    /// 32 bit negated TP offset of the symbol.
+    R_390_TLS_LE32,
+    /// 64 bit negated TP offset of the symbol.
+    R_390_TLS_LE64,
+    /// 32 bit offset of the symbol in its TLS block.
+    R_390_TLS_LDO32,
+    /// 64 bit offset of the symbol in its TLS block.
+    R_390_TLS_LDO64,
+    /// ID of module containing symbol.
+    R_390_TLS_DTPMOD,
+    /// Offset in TLS block.
+    R_390_TLS_DTPOFF,
+    /// Negated offset in static TLS block.
+    R_390_TLS_TPOFF,
+    /// Direct 20 bit.
+    R_390_20,
+    /// 20 bit GOT offset.
+    R_390_GOT20,
+    /// 20 bit offset to the jump slot.
+    R_390_GOTPLT20,
+    /// 20 bit GOT offset for the TP offset of the symbol.
+    R_390_TLS_GOTIE20,
+    /// Set GOT entry to the result of calling the resolver at B + A.
+    R_390_IRELATIVE,
+    /// PC relative 12 bit shifted by 1.
+    R_390_PC12DBL,
+    /// 12 bit PC relative PLT address shifted by 1.
+    R_390_PLT12DBL,
+    /// PC relative 24 bit shifted by 1.
+    R_390_PC24DBL,
+    /// 24 bit PC relative PLT address shifted by 1.
+    R_390_PLT24DBL,
+    /// Unknown
+    Unknown(u32),
+}
+
+impl RelocationTypes {
+    /// Construct new s390x::RelocationTypes
+    pub fn from(typ: u32) -> RelocationTypes {
+        use RelocationTypes::*;
+        match typ {
+            0 => R_390_NONE,
+            1 => R_390_8,
+            2 => R_390_12,
+            3 => R_390_16,
+            4 => R_390_32,
+            5 => R_390_PC32,
+            6 => R_390_GOT12,
+            7 => R_390_GOT32,
+            8 => R_390_PLT32,
+            9 => R_390_COPY,
+            10 => R_390_GLOB_DAT,
+            11 => R_390_JMP_SLOT,
+            12 => R_390_RELATIVE,
+            13 => R_390_GOTOFF32,
+            14 => R_390_GOTPC,
+            15 => R_390_GOT16,
+            16 => R_390_PC16,
+            17 => R_390_PC16DBL,
+            18 => R_390_PLT16DBL,
+            19 => R_390_PC32DBL,
+            20 => R_390_PLT32DBL,
+            21 => R_390_GOTPCDBL,
+            22 => R_390_64,
+            23 => R_390_PC64,
+            24 => R_390_GOT64,
+            25 => R_390_PLT64,
+            26 => R_390_GOTENT,
+            27 => R_390_GOTOFF16,
+            28 => R_390_GOTOFF64,
+            29 => R_390_GOTPLT12,
+            30 => R_390_GOTPLT16,
+            31 => R_390_GOTPLT32,
+            32 => R_390_GOTPLT64,
+            33 => R_390_GOTPLTENT,
+            34 => R_390_PLTOFF16,
+            35 => R_390_PLTOFF32,
+            36 => R_390_PLTOFF64,
+            37 => R_390_TLS_LOAD,
+            38 => R_390_TLS_GDCALL,
+            39 => R_390_TLS_LDCALL,
+            40 => R_390_TLS_GD32,
+            41 => R_390_TLS_GD64,
+            42 => R_390_TLS_GOTIE12,
+            43 => R_390_TLS_GOTIE32,
+            44 => R_390_TLS_GOTIE64,
+            45 => R_390_TLS_LDM32,
+            46 => R_390_TLS_LDM64,
+            47 => R_390_TLS_IE32,
+            48 => R_390_TLS_IE64,
+            49 => R_390_TLS_IEENT,
+            50 => R_390_TLS_LE32,
+            51 => R_390_TLS_LE64,
+            52 => R_390_TLS_LDO32,
+            53 => R_390_TLS_LDO64,
+            54 => R_390_TLS_DTPMOD,
+            55 => R_390_TLS_DTPOFF,
+            56 => R_390_TLS_TPOFF,
+            57 => R_390_20,
+            58 => R_390_GOT20,
+            59 => R_390_GOTPLT20,
+            60 => R_390_TLS_GOTIE20,
+            61 => R_390_IRELATIVE,
+            62 => R_390_PC12DBL,
+            63 => R_390_PLT12DBL,
+            64 => R_390_PC24DBL,
+            65 => R_390_PLT24DBL,
+            x => Unknown(x),
+        }
+    }
+}
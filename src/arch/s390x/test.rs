@@ -0,0 +1,24 @@
+use crate::arch::s390x::RelocationTypes::{self, *};
+use crate::arch::s390x::EM_S390;
+use crate::arch::RelocationType;
+use crate::Machine;
+
+#[test]
+fn check_relocation_types() {
+    assert_eq!(RelocationTypes::from(0), R_390_NONE);
+    assert_eq!(RelocationTypes::from(9), R_390_COPY);
+    assert_eq!(RelocationTypes::from(10), R_390_GLOB_DAT);
+    assert_eq!(RelocationTypes::from(11), R_390_JMP_SLOT);
+    assert_eq!(RelocationTypes::from(12), R_390_RELATIVE);
+    assert_eq!(RelocationTypes::from(22), R_390_64);
+    assert_eq!(RelocationTypes::from(54), R_390_TLS_DTPMOD);
+    assert_eq!(RelocationTypes::from(55), R_390_TLS_DTPOFF);
+    assert_eq!(RelocationTypes::from(56), R_390_TLS_TPOFF);
+    assert_eq!(RelocationTypes::from(61), R_390_IRELATIVE);
+    assert_eq!(RelocationTypes::from(66), Unknown(66));
+
+    assert!(matches!(
+        RelocationType::from(Machine::Other(EM_S390), 12),
+        Ok(RelocationType::S390x(R_390_RELATIVE))
+    ));
+}
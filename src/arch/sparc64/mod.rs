@@ -0,0 +1,329 @@
+//! SPARC relocation types
+//!
+//! As defined in the "SPARC Compliance Definition 2.4" and the
+//! "System V Application Binary Interface SPARC Version 9 Processor
+//! Supplement".
+//!
+//! On SPARC V9 the type field of `r_info` is split into an 8 bit type id
+//! and 24 bits of type specific data (the secondary addend of
+//! R_SPARC_OLO10), use [`type_id`] and [`type_data`] to take it apart.
+//!
+//! Note: SPARC is big-endian, which [`crate::ElfBinary`] does not accept
+//! yet, so these types are currently only reachable through
+//! [`super::RelocationType::from`].
+
+#[cfg(test)]
+mod test;
+
+/// Machine number of SPARC V9 (not known to xmas-elf).
+pub const EM_SPARCV9: u16 = 43;
+
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+#[allow(non_camel_case_types)]
+#[repr(u32)]
+pub enum RelocationTypes {
+    /// No relocation.
+    R_SPARC_NONE,
+    /// Direct 8 bit.
+    R_SPARC_8,
+    /// Direct 16 bit.
+    R_SPARC_16,
+    /// Direct 32 bit.
+    R_SPARC_32,
+    /// PC relative 8 bit.
+    R_SPARC_DISP8,
+    /// PC relative 16 bit.
+    R_SPARC_DISP16,
+    /// PC relative 32 bit.
+    R_SPARC_DISP32,
+    /// PC relative 30 bit shifted.
+    R_SPARC_WDISP30,
+    /// PC relative 22 bit shifted.
+    R_SPARC_WDISP22,
+    /// High 22 bit.
+    R_SPARC_HI22,
+    /// Direct 22 bit.
+    R_SPARC_22,
+    /// Direct 13 bit.
+    R_SPARC_13,
+    /// Truncated 10 bit.
+    R_SPARC_LO10,
+    /// Truncated 10 bit GOT entry.
+    R_SPARC_GOT10,
+    /// 13 bit GOT entry.
+    R_SPARC_GOT13,
+    /// 22 bit GOT entry shifted.
+    R_SPARC_GOT22,
+    /// PC relative 10 bit truncated.
+    R_SPARC_PC10,
+    /// PC relative 22 bit shifted.
+    R_SPARC_PC22,
+    /// 30 bit PC relative PLT address.
+    R_SPARC_WPLT30,
+    /// Copy symbol at runtime.
+    R_SPARC_COPY,
+    /// Create GOT entry.
+    R_SPARC_GLOB_DAT,
+    /// Create PLT entry.
+    R_SPARC_JMP_SLOT,
+    /// Adjust by program base.
+    R_SPARC_RELATIVE,
+    /// Direct 32 bit unaligned.
+    R_SPARC_UA32,
+    /// Direct 32 bit ref to PLT entry.
+    R_SPARC_PLT32,
+    /// High 22 bit PLT entry.
+    R_SPARC_HIPLT22,
+    /// Truncated 10 bit PLT entry.
+    R_SPARC_LOPLT10,
+    /// PC relative 32 bit PLT entry.
+    R_SPARC_PCPLT32,
+    /// PC relative high 22 bit PLT entry.
+    R_SPARC_PCPLT22,
+    /// PC relative truncated 10 bit PLT entry.
+    R_SPARC_PCPLT10,
+    /// Direct 10 bit.
+    R_SPARC_10,
+    /// Direct 11 bit.
+    R_SPARC_11,
+    /// Direct 64 bit.
+    R_SPARC_64,
+    /// 10 bit with secondary 13 bit addend (in the upper bits of the type).
+    R_SPARC_OLO10,
+    /// Top 22 bits of direct 64 bit.
+    R_SPARC_HH22,
+    /// High middle 10 bits of direct 64 bit.
+    R_SPARC_HM10,
+    /// Low middle 22 bits of direct 64 bit.
+    R_SPARC_LM22,
+    /// Top 22 bits of PC relative 64 bit.
+    R_SPARC_PC_HH22,
+    /// High middle 10 bits of PC relative 64 bit.
+    R_SPARC_PC_HM10,
+    /// Low middle 22 bits of PC relative 64 bit.
+    R_SPARC_PC_LM22,
+    /// PC relative 16 bit shifted.
+    R_SPARC_WDISP16,
+    /// PC relative 19 bit shifted.
+    R_SPARC_WDISP19,
+    /// Was part of v9 ABI but was removed.
+    R_SPARC_GLOB_JMP,
+    /// Direct 7 bit.
+    R_SPARC_7,
+    /// Direct 5 bit.
+    R_SPARC_5,
+    /// Direct 6 bit.
+    R_SPARC_6,
+    /// PC relative 64 bit.
+    R_SPARC_DISP64,
+    /// Direct 64 bit ref to PLT entry.
+    R_SPARC_PLT64,
+    /// High 22 bit complemented.
+    R_SPARC_HIX22,
+    /// Truncated 11 bit complemented.
+    R_SPARC_LOX10,
+    /// Direct high 12 of 44 bit.
+    R_SPARC_H44,
+    /// Direct mid 22 of 44 bit.
+    R_SPARC_M44,
+    /// Direct low 10 of 44 bit.
+    R_SPARC_L44,
+    /// Global register usage.
+    R_SPARC_REGISTER,
+    /// Direct 64 bit unaligned.
+    R_SPARC_UA64,
+    /// Direct 16 bit unaligned.
+    R_SPARC_UA16,
+    /// General dynamic TLS, high 22 bit of the GOT offset.
+    R_SPARC_TLS_GD_HI22,
+    /// General dynamic TLS, low 10 bit of the GOT offset.
+    R_SPARC_TLS_GD_LO10,
+    /// General dynamic TLS, marks the add instruction.
+    R_SPARC_TLS_GD_ADD,
+    /// General dynamic TLS, marks the call to __tls_get_addr.
+    R_SPARC_TLS_GD_CALL,
+    /// Local dynamic TLS, high 22 bit of the GOT offset.
+    R_SPARC_TLS_LDM_HI22,
+    /// Local dynamic TLS, low 10 bit of the GOT offset.
+    R_SPARC_TLS_LDM_LO10,
+    /// Local dynamic TLS, marks the add instruction.
+    R_SPARC_TLS_LDM_ADD,
+    /// Local dynamic TLS, marks the call to __tls_get_addr.
+    R_SPARC_TLS_LDM_CALL,
+    /// Local dynamic TLS, high 22 bit of the offset in the block.
+    R_SPARC_TLS_LDO_HIX22,
+    /// Local dynamic TLS, low 10 bit of the offset in the block.
+    R_SPARC_TLS_LDO_LOX10,
+    /// Local dynamic TLS, marks the add instruction.
+    R_SPARC_TLS_LDO_ADD,
+    /// Initial exec TLS, high 22 bit of the GOT offset.
+    R_SPARC_TLS_IE_HI22,
+    /// Initial exec TLS, low 10 bit of the GOT offset.
+    R_SPARC_TLS_IE_LO10,
+    /// Initial exec TLS, marks the 32 bit load.
+    R_SPARC_TLS_IE_LD,
+    /// Initial exec TLS, marks the 64 bit load.
+    R_SPARC_TLS_IE_LDX,
+    /// Initial exec TLS, marks the add instruction.
+    R_SPARC_TLS_IE_ADD,
+    /// Local exec TLS, high 22 bit of the complemented TP offset.
+    R_SPARC_TLS_LE_HIX22,
+    /// Local exec TLS, low 10 bit of the TP offset.
+    R_SPARC_TLS_LE_LOX10,
+    /// 32 bit ID of module containing symbol.
+    R_SPARC_TLS_DTPMOD32,
+    /// 64 bit ID of module containing symbol.
+    R_SPARC_TLS_DTPMOD64,
+    /// 32 bit offset in TLS block.
+    R_SPARC_TLS_DTPOFF32,
+    /// 64 bit offset in TLS block.
+    R_SPARC_TLS_DTPOFF64,
+    /// 32 bit offset in static TLS block.
+    R_SPARC_TLS_TPOFF32,
+    /// 64 bit offset in static TLS block.
+    R_SPARC_TLS_TPOFF64,
+    /// High 22 bit of a GOT relative data reference.
+    R_SPARC_GOTDATA_HIX22,
+    /// Low 10 bit of a GOT relative data reference.
+    R_SPARC_GOTDATA_LOX10,
+    /// High 22 bit of a GOT relative data reference (optimizable).
+    R_SPARC_GOTDATA_OP_HIX22,
+    /// Low 10 bit of a GOT relative data reference (optimizable).
+    R_SPARC_GOTDATA_OP_LOX10,
+    /// Marks the load of a GOT relative data reference.
+    R_SPARC_GOTDATA_OP,
+    /// Direct high 22 of 34 bit.
+    R_SPARC_H34,
+    /// Size of the symbol, 32 bit.
+    R_SPARC_SIZE32,
+    /// Size of the symbol, 64 bit.
+    R_SPARC_SIZE64,
+    /// PC relative 10 bit shifted.
+    R_SPARC_WDISP10,
+    /// Create PLT entry for an indirect function.
+    R_SPARC_JMP_IREL,
+    /// Set GOT entry to the result of calling the resolver at B + A.
+    R_SPARC_IRELATIVE,
+    /// GNU C++ vtable hierarchy.
+    R_SPARC_GNU_VTINHERIT,
+    /// GNU C++ vtable member usage.
+    R_SPARC_GNU_VTENTRY,
+    /// Direct 32 bit byte swapped.
+    R_SPARC_REV32,
+    /// Unknown
+    Unknown(u32),
+}
+
+impl RelocationTypes {
+    /// Construct new sparc64::RelocationTypes (ignores the type data)
+    pub fn from(typ: u32) -> RelocationTypes {
+        use RelocationTypes::*;
+        match type_id(typ) {
+            0 => R_SPARC_NONE,
+            1 => R_SPARC_8,
+            2 => R_SPARC_16,
+            3 => R_SPARC_32,
+            4 => R_SPARC_DISP8,
+            5 => R_SPARC_DISP16,
+            6 => R_SPARC_DISP32,
+            7 => R_SPARC_WDISP30,
+            8 => R_SPARC_WDISP22,
+            9 => R_SPARC_HI22,
+            10 => R_SPARC_22,
+            11 => R_SPARC_13,
+            12 => R_SPARC_LO10,
+            13 => R_SPARC_GOT10,
+            14 => R_SPARC_GOT13,
+            15 => R_SPARC_GOT22,
+            16 => R_SPARC_PC10,
+            17 => R_SPARC_PC22,
+            18 => R_SPARC_WPLT30,
+            19 => R_SPARC_COPY,
+            20 => R_SPARC_GLOB_DAT,
+            21 => R_SPARC_JMP_SLOT,
+            22 => R_SPARC_RELATIVE,
+            23 => R_SPARC_UA32,
+            24 => R_SPARC_PLT32,
+            25 => R_SPARC_HIPLT22,
+            26 => R_SPARC_LOPLT10,
+            27 => R_SPARC_PCPLT32,
+            28 => R_SPARC_PCPLT22,
+            29 => R_SPARC_PCPLT10,
+            30 => R_SPARC_10,
+            31 => R_SPARC_11,
+            32 => R_SPARC_64,
+            33 => R_SPARC_OLO10,
+            34 => R_SPARC_HH22,
+            35 => R_SPARC_HM10,
+            36 => R_SPARC_LM22,
+            37 => R_SPARC_PC_HH22,
+            38 => R_SPARC_PC_HM10,
+            39 => R_SPARC_PC_LM22,
+            40 => R_SPARC_WDISP16,
+            41 => R_SPARC_WDISP19,
+            42 => R_SPARC_GLOB_JMP,
+            43 => R_SPARC_7,
+            44 => R_SPARC_5,
+            45 => R_SPARC_6,
+            46 => R_SPARC_DISP64,
+            47 => R_SPARC_PLT64,
+            48 => R_SPARC_HIX22,
+            49 => R_SPARC_LOX10,
+            50 => R_SPARC_H44,
+            51 => R_SPARC_M44,
+            52 => R_SPARC_L44,
+            53 => R_SPARC_REGISTER,
+            54 => R_SPARC_UA64,
+            55 => R_SPARC_UA16,
+            56 => R_SPARC_TLS_GD_HI22,
+            57 => R_SPARC_TLS_GD_LO10,
+            58 => R_SPARC_TLS_GD_ADD,
+            59 => R_SPARC_TLS_GD_CALL,
+            60 => R_SPARC_TLS_LDM_HI22,
+            61 => R_SPARC_TLS_LDM_LO10,
+            62 => R_SPARC_TLS_LDM_ADD,
+            63 => R_SPARC_TLS_LDM_CALL,
+            64 => R_SPARC_TLS_LDO_HIX22,
+            65 => R_SPARC_TLS_LDO_LOX10,
+            66 => R_SPARC_TLS_LDO_ADD,
+            67 => R_SPARC_TLS_IE_HI22,
+            68 => R_SPARC_TLS_IE_LO10,
+            69 => R_SPARC_TLS_IE_LD,
+            70 => R_SPARC_TLS_IE_LDX,
+            71 => R_SPARC_TLS_IE_ADD,
+            72 => R_SPARC_TLS_LE_HIX22,
+            73 => R_SPARC_TLS_LE_LOX10,
+            74 => R_SPARC_TLS_DTPMOD32,
+            75 => R_SPARC_TLS_DTPMOD64,
+            76 => R_SPARC_TLS_DTPOFF32,
+            77 => R_SPARC_TLS_DTPOFF64,
+            78 => R_SPARC_TLS_TPOFF32,
+            79 => R_SPARC_TLS_TPOFF64,
+            80 => R_SPARC_GOTDATA_HIX22,
+            81 => R_SPARC_GOTDATA_LOX10,
+            82 => R_SPARC_GOTDATA_OP_HIX22,
+            83 => R_SPARC_GOTDATA_OP_LOX10,
+            84 => R_SPARC_GOTDATA_OP,
+            85 => R_SPARC_H34,
+            86 => R_SPARC_SIZE32,
+            87 => R_SPARC_SIZE64,
+            88 => R_SPARC_WDISP10,
+            248 => R_SPARC_JMP_IREL,
+            249 => R_SPARC_IRELATIVE,
+            250 => R_SPARC_GNU_VTINHERIT,
+            251 => R_SPARC_GNU_VTENTRY,
+            252 => R_SPARC_REV32,
+            x => Unknown(x),
+        }
+    }
+}
+
+/// The relocation type encoded in the low 8 bits of `typ`.
+pub fn type_id(typ: u32) -> u32 {
+    typ & 0xff
+}
+
+/// The signed 24 bit type specific data in the upper bits of `typ`.
+pub fn type_data(typ: u32) -> i32 {
+    (typ as i32) >> 8
+}
@@ -0,0 +1,28 @@
+use crate::arch::sparc64::RelocationTypes::{self, *};
+use crate::arch::sparc64::EM_SPARCV9;
+use crate::arch::RelocationType;
+use crate::Machine;
+
+#[test]
+fn check_relocation_types() {
+    assert_eq!(RelocationTypes::from(0), R_SPARC_NONE);
+    assert_eq!(RelocationTypes::from(3), R_SPARC_32);
+    assert_eq!(RelocationTypes::from(19), R_SPARC_COPY);
+    assert_eq!(RelocationTypes::from(20), R_SPARC_GLOB_DAT);
+    assert_eq!(RelocationTypes::from(21), R_SPARC_JMP_SLOT);
+    assert_eq!(RelocationTypes::from(22), R_SPARC_RELATIVE);
+    assert_eq!(RelocationTypes::from(32), R_SPARC_64);
+    assert_eq!(RelocationTypes::from(75), R_SPARC_TLS_DTPMOD64);
+    assert_eq!(RelocationTypes::from(77), R_SPARC_TLS_DTPOFF64);
+    assert_eq!(RelocationTypes::from(79), R_SPARC_TLS_TPOFF64);
+    assert_eq!(RelocationTypes::from(248), R_SPARC_JMP_IREL);
+    assert_eq!(RelocationTypes::from(249), R_SPARC_IRELATIVE);
+    assert_eq!(RelocationTypes::from(89), Unknown(89));
+
+    for machine in [Machine::Sparc, Machine::Other(EM_SPARCV9)] {
+        assert!(matches!(
+            RelocationType::from(machine, 22),
+            Ok(RelocationType::Sparc64(R_SPARC_RELATIVE))
+        ));
+    }
+}
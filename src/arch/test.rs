@@ -4,14 +4,24 @@ use std::vec::Vec;
 
 #[derive(Eq, Clone, PartialEq, Copy, Debug)]
 pub(crate) enum LoaderAction {
-    Allocate(VAddr, usize, Flags),
+    Allocate(VAddr, usize, SegmentPermissions),
+    AllocateCoalesced(VAddr, u64, u64),
     Load(VAddr, usize),
     Relocate(VAddr, u64),
+    Write(VAddr, u64),
     Tls(VAddr, u64, u64, u64),
+    MakeWritable(VAddr, usize),
+    RestorePermissions(VAddr, usize, SegmentPermissions),
+    Relro(VAddr, u64),
 }
 pub(crate) struct TestLoader {
     pub(crate) vbase: VAddr,
     pub(crate) actions: Vec<LoaderAction>,
+    pub(crate) tdata: Vec<u8>,
+    /// Virtual addresses of the LOAD segments to skip.
+    pub(crate) skip: Vec<VAddr>,
+    /// Number of `disposition` calls.
+    pub(crate) dispositions: usize,
 }
 
 impl TestLoader {
@@ -19,29 +29,52 @@ impl TestLoader {
         TestLoader {
             vbase: offset,
             actions: Vec::with_capacity(12),
+            tdata: Vec::new(),
+            skip: Vec::new(),
+            dispositions: 0,
         }
     }
 }
 
 impl ElfLoader for TestLoader {
-    fn allocate(&mut self, load_headers: LoadableHeaders) -> Result<(), ElfLoaderErr> {
-        for header in load_headers {
+    fn allocate(&mut self, requests: AllocateRequests) -> Result<(), ElfLoaderErr> {
+        for request in requests {
             info!(
                 "allocate base = {:#x} size = {:#x} flags = {}",
-                header.virtual_addr(),
-                header.mem_size(),
-                header.flags()
+                request.vaddr, request.mem_size, request.flags
             );
 
             self.actions.push(LoaderAction::Allocate(
-                header.virtual_addr(),
-                header.mem_size() as usize,
-                header.flags(),
+                request.vaddr,
+                request.mem_size as usize,
+                request.flags,
             ));
         }
         Ok(())
     }
 
+    fn allocate_coalesced(&mut self, allocation: CoalescedAllocation) -> Result<(), ElfLoaderErr> {
+        info!(
+            "allocate_coalesced base = {:#x} size = {:#x} align = {:#x}",
+            allocation.vaddr, allocation.mem_size, allocation.align
+        );
+        self.actions.push(LoaderAction::AllocateCoalesced(
+            allocation.vaddr,
+            allocation.mem_size,
+            allocation.align,
+        ));
+        Ok(())
+    }
+
+    fn disposition(&mut self, header: ProgramHeader) -> Result<Disposition, ElfLoaderErr> {
+        self.dispositions += 1;
+        if self.skip.contains(&header.virtual_addr()) {
+            info!("skip base = {:#x}", header.virtual_addr());
+            return Ok(Disposition::Skip);
+        }
+        Ok(Disposition::Load)
+    }
+
     fn relocate(&mut self, entry: RelocationEntry) -> Result<(), ElfLoaderErr> {
         use crate::arch::aarch64::RelocationTypes::*;
         use crate::arch::riscv::RelocationTypes::*;
@@ -63,9 +96,13 @@ impl ElfLoader for TestLoader {
             // x86
             x86(R_386_32) => Ok(()),
             x86(R_386_RELATIVE) => {
-                info!("R_RELATIVE {:p} ", addr);
+                // The addend is implicit (read from the file)
+                let addend = entry
+                    .addend
+                    .ok_or(ElfLoaderErr::UnsupportedRelocationEntry)?;
+                info!("R_RELATIVE *{:p} = {:#x}", addr, self.vbase + addend);
                 self.actions
-                    .push(LoaderAction::Relocate(addr as u64, self.vbase));
+                    .push(LoaderAction::Relocate(addr as u64, self.vbase + addend));
                 Ok(())
             }
             x86(R_386_GLOB_DAT) => {
@@ -137,29 +174,79 @@ impl ElfLoader for TestLoader {
         }
     }
 
-    fn load(&mut self, _flags: Flags, base: VAddr, region: &[u8]) -> Result<(), ElfLoaderErr> {
+    fn write_u64(&mut self, vaddr: VAddr, value: u64) -> Result<(), ElfLoaderErr> {
+        self.actions
+            .push(LoaderAction::Write(self.vbase + vaddr, value));
+        Ok(())
+    }
+
+    fn write_u32(&mut self, vaddr: VAddr, value: u32) -> Result<(), ElfLoaderErr> {
+        self.write_u64(vaddr, value as u64)
+    }
+
+    fn load(
+        &mut self,
+        _flags: SegmentPermissions,
+        base: VAddr,
+        region: &[u8],
+    ) -> Result<(), ElfLoaderErr> {
         info!("load base = {:#x} size = {:#x} region", base, region.len());
         self.actions.push(LoaderAction::Load(base, region.len()));
         Ok(())
     }
 
+    fn make_writable(&mut self, base: VAddr, size: usize) -> Result<(), ElfLoaderErr> {
+        info!("make_writable base = {:#x} size = {:#x}", base, size);
+        self.actions.push(LoaderAction::MakeWritable(base, size));
+        Ok(())
+    }
+
+    fn restore_permissions(
+        &mut self,
+        base: VAddr,
+        size: usize,
+        flags: SegmentPermissions,
+    ) -> Result<(), ElfLoaderErr> {
+        info!(
+            "restore_permissions base = {:#x} size = {:#x} flags = {}",
+            base, size, flags
+        );
+        self.actions
+            .push(LoaderAction::RestorePermissions(base, size, flags));
+        Ok(())
+    }
+
+    fn relro(&mut self, segment: RelroSegment) -> Result<(), ElfLoaderErr> {
+        info!(
+            "relro vaddr = {:#x} mem_size = {:#x}",
+            segment.vaddr, segment.mem_size
+        );
+        self.actions
+            .push(LoaderAction::Relro(segment.vaddr, segment.mem_size));
+        Ok(())
+    }
+
     fn tls(
         &mut self,
         tdata_start: VAddr,
-        tdata_length: u64,
-        total_size: u64,
+        tdata: &[u8],
+        tbss_length: u64,
         alignment: u64,
     ) -> Result<(), ElfLoaderErr> {
         info!(
-            "tdata_start = {:#x} tdata_length = {:#x} total_size = {:#x} alignment = {:#}",
-            tdata_start, tdata_length, total_size, alignment
+            "tdata_start = {:#x} tdata_length = {:#x} tbss_length = {:#x} alignment = {:#}",
+            tdata_start,
+            tdata.len(),
+            tbss_length,
+            alignment
         );
         self.actions.push(LoaderAction::Tls(
             tdata_start,
-            tdata_length,
-            total_size,
+            tdata.len() as u64,
+            tdata.len() as u64 + tbss_length,
             alignment,
         ));
+        self.tdata.extend_from_slice(tdata);
         Ok(())
     }
 }
@@ -167,3 +254,44 @@ impl ElfLoader for TestLoader {
 pub(crate) fn init() {
     let _ = env_logger::builder().is_test(true).try_init();
 }
+
+/// A big-endian ELF32 shared object for `machine` with `data` at offset (and
+/// address) 0x100. The first program header is a LOAD segment mapping the
+/// whole file at address 0, followed by `headers` (`p_type`, `p_offset`,
+/// `p_filesz`).
+pub(crate) fn big_endian_elf32(machine: u16, headers: &[(u32, u32, u32)], data: &[u8]) -> Vec<u8> {
+    let mut file = vec![0u8; 0x100 + data.len()];
+    let put16 = |file: &mut [u8], offset: usize, value: u16| {
+        file[offset..offset + 2].copy_from_slice(&value.to_be_bytes())
+    };
+    let put32 = |file: &mut [u8], offset: usize, value: u32| {
+        file[offset..offset + 4].copy_from_slice(&value.to_be_bytes())
+    };
+
+    file[..7].copy_from_slice(&[0x7f, b'E', b'L', b'F', 1, 2, 1]);
+    put16(&mut file, 16, 3); // ET_DYN
+    put16(&mut file, 18, machine);
+    put32(&mut file, 20, 1);
+    put32(&mut file, 28, 52); // e_phoff
+    put16(&mut file, 40, 52); // e_ehsize
+    put16(&mut file, 42, 32); // e_phentsize
+    put16(&mut file, 44, 1 + headers.len() as u16);
+    put16(&mut file, 46, 40); // e_shentsize
+
+    let size = file.len() as u32;
+    let load = (1, 0, size);
+    for (i, &(typ, offset, file_size)) in core::iter::once(&load).chain(headers).enumerate() {
+        let header = 52 + 32 * i;
+        put32(&mut file, header, typ);
+        put32(&mut file, header + 4, offset);
+        put32(&mut file, header + 8, offset); // p_vaddr
+        put32(&mut file, header + 12, offset); // p_paddr
+        put32(&mut file, header + 16, file_size);
+        put32(&mut file, header + 20, file_size);
+        put32(&mut file, header + 24, 0b110); // PF_R | PF_W
+        put32(&mut file, header + 28, if typ == 1 { 0x1000 } else { 4 });
+    }
+    assert!(52 + 32 * (1 + headers.len()) <= 0x100);
+    file[0x100..].copy_from_slice(data);
+    file
+}
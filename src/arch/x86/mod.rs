@@ -1,3 +1,9 @@
+use super::{align_up, TlsSegment};
+use crate::reloc::RelocationResolver;
+use crate::{ElfLoaderErr, RelocationEntry};
+#[cfg(feature = "apply")]
+use crate::{RelocationType, VAddr};
+
 #[cfg(test)]
 mod test;
 
@@ -30,6 +36,18 @@ pub enum RelocationTypes {
     R_386_GOTPC,
     /// Direct 32 bit PLT address
     R_386_32PLT,
+    /// Negative offset in static TLS block
+    R_386_TLS_TPOFF,
+    /// Absolute address of GOT entry for negative static TLS block offset
+    R_386_TLS_IE,
+    /// GOT entry for negative static TLS block offset
+    R_386_TLS_GOTIE,
+    /// Negative offset relative to static TLS
+    R_386_TLS_LE,
+    /// Direct 32 bit for GNU version of general dynamic thread local data
+    R_386_TLS_GD,
+    /// Direct 32 bit for GNU version of local dynamic thread local data
+    R_386_TLS_LDM,
     /// Direct 16 bit zero extended
     R_386_16,
     /// 16 bit sign extended pc relative
@@ -38,8 +56,46 @@ pub enum RelocationTypes {
     R_386_8,
     /// 8 bit sign extended pc relative
     R_386_PC8,
+    /// Direct 32 bit for general dynamic thread local data
+    R_386_TLS_GD_32,
+    /// Tag for pushl in GD TLS code
+    R_386_TLS_GD_PUSH,
+    /// Relocation for call to __tls_get_addr()
+    R_386_TLS_GD_CALL,
+    /// Tag for popl in GD TLS code
+    R_386_TLS_GD_POP,
+    /// Direct 32 bit for local dynamic thread local data in LE code
+    R_386_TLS_LDM_32,
+    /// Tag for pushl in LDM TLS code
+    R_386_TLS_LDM_PUSH,
+    /// Relocation for call to __tls_get_addr() in LDM code
+    R_386_TLS_LDM_CALL,
+    /// Tag for popl in LDM TLS code
+    R_386_TLS_LDM_POP,
+    /// Offset relative to TLS block
+    R_386_TLS_LDO_32,
+    /// GOT entry for static TLS block offset
+    R_386_TLS_IE_32,
+    /// Offset relative to static TLS block
+    R_386_TLS_LE_32,
+    /// ID of module containing symbol
+    R_386_TLS_DTPMOD32,
+    /// Offset in TLS block
+    R_386_TLS_DTPOFF32,
+    /// Negated offset in static TLS block
+    R_386_TLS_TPOFF32,
     /// 32-bit symbol size
     R_386_SIZE32,
+    /// GOT offset for TLS descriptor
+    R_386_TLS_GOTDESC,
+    /// Marker of call through TLS descriptor for relaxation
+    R_386_TLS_DESC_CALL,
+    /// TLS descriptor
+    R_386_TLS_DESC,
+    /// Adjust indirectly by program base
+    R_386_IRELATIVE,
+    /// Load from 32 bit GOT entry, relaxable
+    R_386_GOT32X,
     /// Unknown
     Unknown(u32),
 }
@@ -49,8 +105,8 @@ impl RelocationTypes {
         use RelocationTypes::*;
         match typ {
             0 => R_386_NONE,
-            1 => R_386_PC32,
-            2 => R_386_32,
+            1 => R_386_32,
+            2 => R_386_PC32,
             3 => R_386_GOT32,
             4 => R_386_PLT32,
             5 => R_386_COPY,
@@ -60,12 +116,95 @@ impl RelocationTypes {
             9 => R_386_GOTOFF,
             10 => R_386_GOTPC,
             11 => R_386_32PLT,
+            14 => R_386_TLS_TPOFF,
+            15 => R_386_TLS_IE,
+            16 => R_386_TLS_GOTIE,
+            17 => R_386_TLS_LE,
+            18 => R_386_TLS_GD,
+            19 => R_386_TLS_LDM,
             20 => R_386_16,
             21 => R_386_PC16,
             22 => R_386_8,
             23 => R_386_PC8,
+            24 => R_386_TLS_GD_32,
+            25 => R_386_TLS_GD_PUSH,
+            26 => R_386_TLS_GD_CALL,
+            27 => R_386_TLS_GD_POP,
+            28 => R_386_TLS_LDM_32,
+            29 => R_386_TLS_LDM_PUSH,
+            30 => R_386_TLS_LDM_CALL,
+            31 => R_386_TLS_LDM_POP,
+            32 => R_386_TLS_LDO_32,
+            33 => R_386_TLS_IE_32,
+            34 => R_386_TLS_LE_32,
+            35 => R_386_TLS_DTPMOD32,
+            36 => R_386_TLS_DTPOFF32,
+            37 => R_386_TLS_TPOFF32,
             38 => R_386_SIZE32,
+            39 => R_386_TLS_GOTDESC,
+            40 => R_386_TLS_DESC_CALL,
+            41 => R_386_TLS_DESC,
+            42 => R_386_IRELATIVE,
+            43 => R_386_GOT32X,
             x => Unknown(x),
         }
     }
 }
+
+/// Thread pointer relative offset of `value` in the TLS block of the main
+/// executable (TLS variant II: the block ends at the thread pointer).
+pub fn tp_offset(tls: &TlsSegment, value: u64) -> i64 {
+    value as i64 - align_up(tls.mem_size, tls.align) as i64
+}
+
+/// Computes the value and width (in bytes) of an x86 relocation, see
+/// [`RelocationEntry::evaluate`].
+pub(crate) fn evaluate<R: RelocationResolver>(
+    typ: RelocationTypes,
+    entry: &RelocationEntry,
+    resolver: &mut R,
+) -> Result<Option<(u64, usize)>, ElfLoaderErr> {
+    use RelocationTypes::*;
+
+    let i = entry.index;
+    let a = || entry.required_addend();
+    let b = resolver.load_bias();
+    let p = b.wrapping_add(entry.offset);
+    let value = match typ {
+        R_386_NONE => return Ok(None),
+        R_386_32 => (resolver.symbol_value(i)?.wrapping_add(a()?), 4),
+        R_386_PC32 => (
+            resolver.symbol_value(i)?.wrapping_add(a()?).wrapping_sub(p),
+            4,
+        ),
+        R_386_GLOB_DAT | R_386_JMP_SLOT => (resolver.symbol_value(i)?, 4),
+        R_386_RELATIVE => (b.wrapping_add(a()?), 4),
+        _ => return Err(ElfLoaderErr::UnsupportedRelocationEntry),
+    };
+    Ok(Some(value))
+}
+
+/// Applies an x86 dynamic relocation of a binary loaded at `base`.
+///
+/// `resolve_symbol` returns the (already relocated) address S of the symbol
+/// with the given index, `write` stores the 32-bit little-endian value at
+/// the given (relocated) address.
+///
+/// Supports the types [`RelocationEntry::evaluate`] handles, except for TLS
+/// relocations. Other types return `UnsupportedRelocationEntry`.
+#[cfg(feature = "apply")]
+pub fn apply<S, W>(
+    entry: RelocationEntry,
+    base: VAddr,
+    resolve_symbol: S,
+    write: W,
+) -> Result<(), ElfLoaderErr>
+where
+    S: FnMut(u32) -> Result<u64, ElfLoaderErr>,
+    W: FnMut(VAddr, &[u8]) -> Result<(), ElfLoaderErr>,
+{
+    match entry.rtype {
+        RelocationType::x86(_) => super::apply(entry, base, resolve_symbol, write),
+        _ => Err(ElfLoaderErr::UnsupportedRelocationEntry),
+    }
+}
@@ -1,4 +1,5 @@
 use std::fs;
+use std::vec::Vec;
 
 use crate::arch::test::*;
 use crate::*;
@@ -27,22 +28,34 @@ fn load_pie_elf() {
     assert!(loader
         .actions
         .iter()
-        .find(|&&x| x == LoaderAction::Allocate(VAddr::from(0x0u64), 0x003bc, Flags(4)))
+        .find(|&&x| x
+            == LoaderAction::Allocate(VAddr::from(0x0u64), 0x003bc, SegmentPermissions::READ))
         .is_some());
     assert!(loader
         .actions
         .iter()
-        .find(|&&x| x == LoaderAction::Allocate(VAddr::from(0x1000u64), 0x288, Flags(1 | 4)))
+        .find(|&&x| x
+            == LoaderAction::Allocate(
+                VAddr::from(0x1000u64),
+                0x288,
+                SegmentPermissions::READ | SegmentPermissions::EXECUTE
+            ))
         .is_some());
     assert!(loader
         .actions
         .iter()
-        .find(|&&x| x == LoaderAction::Allocate(VAddr::from(0x002000u64), 0x0016c, Flags(4)))
+        .find(|&&x| x
+            == LoaderAction::Allocate(VAddr::from(0x002000u64), 0x0016c, SegmentPermissions::READ))
         .is_some());
     assert!(loader
         .actions
         .iter()
-        .find(|&&x| x == LoaderAction::Allocate(VAddr::from(0x3ef4u64), 0x12c, Flags(2 | 4)))
+        .find(|&&x| x
+            == LoaderAction::Allocate(
+                VAddr::from(0x3ef4u64),
+                0x12c,
+                SegmentPermissions::READ | SegmentPermissions::WRITE
+            ))
         .is_some());
     assert!(loader
         .actions
@@ -71,15 +84,17 @@ fn load_pie_elf() {
     // 00003ef8  00000008 R_386_RELATIVE
     // 00003ff8  00000008 R_386_RELATIVE
     // 00004018  00000008 R_386_RELATIVE
+    //
+    // The addends are stored in the file at the relocated locations
     assert!(loader
         .actions
         .iter()
-        .find(|&&x| x == LoaderAction::Relocate(0x1000_0000 + 0x00003ef4, 0x1000_0000))
+        .find(|&&x| x == LoaderAction::Relocate(0x1000_0000 + 0x00003ef4, 0x1000_0000 + 0x1190))
         .is_some());
     assert!(loader
         .actions
         .iter()
-        .find(|&&x| x == LoaderAction::Relocate(0x1000_0000 + 0x00003ef8, 0x1000_0000))
+        .find(|&&x| x == LoaderAction::Relocate(0x1000_0000 + 0x00003ef8, 0x1000_0000 + 0x1140))
         .is_some());
 }
 
@@ -123,3 +138,298 @@ fn check_tls() {
         .find(|&&x| x == LoaderAction::Tls(VAddr::from(0x3ef0u64), 0x4, 0x8, 0x4))
         .is_some());
 }
+
+#[test]
+fn check_dynamic_flags() {
+    init();
+    let binary_blob = fs::read("test/test.x86").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+
+    let dynamic = binary.dynamic().expect("Has a .dynamic section");
+    assert!(dynamic.flags.is_empty());
+    assert_eq!(dynamic.flags1, DynamicFlags1::PIE);
+}
+
+#[cfg(feature = "apply")]
+#[test]
+fn check_apply() {
+    use crate::arch::x86::{apply, RelocationTypes::*};
+
+    let entry = RelocationEntry {
+        rtype: RelocationType::x86(R_386_RELATIVE),
+        type_num: 8,
+        info: 8,
+        offset: 0x3ef4,
+        index: 0,
+        addend: Some(0x1190),
+        class: crate::elf::Class::Elf32,
+        segment: None,
+    };
+    let mut written = None;
+    apply(
+        entry,
+        0x1000_0000,
+        |_| Ok(0),
+        |addr, bytes| {
+            written = Some((addr, bytes.to_vec()));
+            Ok(())
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        written,
+        Some((0x1000_3ef4, 0x1000_1190u32.to_le_bytes().to_vec()))
+    );
+}
+
+#[test]
+fn check_stripped_section_headers() {
+    init();
+    let binary_blob = fs::read("test/test.x86").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+    let mut loader = TestLoader::new(0x1000_0000);
+    binary.load(&mut loader).expect("Can't load?");
+
+    // Drop the section headers (e_shoff, e_shnum and e_shstrndx), the DT_REL
+    // table of the dynamic segment still describes the relocations
+    let mut stripped_blob = binary_blob.clone();
+    stripped_blob[0x20..0x24].copy_from_slice(&0u32.to_le_bytes());
+    stripped_blob[0x30..0x34].copy_from_slice(&0u32.to_le_bytes());
+    let stripped = ElfBinary::new(stripped_blob.as_slice()).expect("Got proper ELF file");
+    assert!(stripped.file.section_iter().next().is_none());
+    let dynamic = stripped.dynamic().expect("Has dynamic segment");
+    assert_eq!((dynamic.rela, dynamic.rela_size), (0, 0));
+    assert_ne!(dynamic.rel_size, 0);
+
+    let mut stripped_loader = TestLoader::new(0x1000_0000);
+    stripped
+        .load(&mut stripped_loader)
+        .expect("Can't load stripped binary?");
+    assert_eq!(stripped_loader.actions, loader.actions);
+    assert!(loader
+        .actions
+        .contains(&LoaderAction::Relocate(0x1000_3ef4, 0x1000_1190)));
+}
+
+#[test]
+fn check_dynamic_entries() {
+    init();
+    let binary_blob = fs::read("test/test.x86").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+
+    let entries: Vec<(Tag<u64>, u64)> = binary
+        .dynamic_entries()
+        .collect::<Result<_, _>>()
+        .expect("Valid dynamic entries");
+    // readelf -d: 26 entries including the terminating NULL
+    assert_eq!(entries.len(), 25);
+    assert_eq!(entries[1], (Tag::Init, 0x1000));
+    assert_eq!(entries[7], (Tag::OsSpecific(0x6fff_fef5), 0x1ec));
+    assert_eq!(entries[17], (Tag::Rel, 0x36c));
+    assert_eq!(entries[18], (Tag::RelSize, 64));
+    assert_eq!(entries[20], (Tag::Flags1, DynamicFlags1::PIE.bits()));
+}
+
+#[test]
+fn check_coalesced_allocation() {
+    init();
+    let binary_blob = fs::read("test/test.x86").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+
+    let allocation = binary
+        .coalesced_allocation()
+        .expect("Valid segments")
+        .expect("Has LOAD segments");
+    // The last LOAD segment ends at 0x3ef4 + 0x12c
+    assert_eq!((allocation.vaddr, allocation.mem_size), (0x0, 0x5000));
+    assert_eq!(allocation.align, 0x1000);
+    assert_eq!(allocation.segments.count(), 4);
+
+    let config = LoadConfig {
+        coalesce_allocation: true,
+        ..Default::default()
+    };
+    let mut loader = TestLoader::new(0x1000_0000);
+    binary
+        .load_with_config(&mut loader, &config)
+        .expect("Can't load?");
+    assert_eq!(
+        loader.actions[0],
+        LoaderAction::AllocateCoalesced(0x0, 0x5000, 0x1000)
+    );
+    assert!(!loader
+        .actions
+        .iter()
+        .any(|action| matches!(action, LoaderAction::Allocate(..))));
+    assert!(loader.actions.contains(&LoaderAction::Load(0x3ef4, 0x128)));
+}
+
+#[test]
+fn check_load32() {
+    use crate::addr32::{ElfLoader32, RelocationEntry32, VAddr32};
+
+    #[derive(Default)]
+    struct Recorder {
+        loads: Vec<(VAddr32, usize)>,
+        relocations: Vec<(VAddr32, u32, Option<u32>)>,
+    }
+
+    impl ElfLoader32 for Recorder {
+        fn allocate(&mut self, _requests: AllocateRequests) -> Result<(), ElfLoaderErr> {
+            Ok(())
+        }
+
+        fn load(
+            &mut self,
+            _flags: SegmentPermissions,
+            base: VAddr32,
+            region: &[u8],
+        ) -> Result<(), ElfLoaderErr> {
+            self.loads.push((base, region.len()));
+            Ok(())
+        }
+
+        fn relocate(&mut self, entry: RelocationEntry32) -> Result<(), ElfLoaderErr> {
+            self.relocations
+                .push((entry.offset, entry.type_num, entry.addend));
+            Ok(())
+        }
+    }
+
+    init();
+    let binary_blob = fs::read("test/test.x86").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+    let mut recorder = Recorder::default();
+    binary.load32(&mut recorder).expect("Can't load?");
+
+    let mut loader = TestLoader::new(0x1000_0000);
+    binary.load(&mut loader).expect("Can't load?");
+    let loads: Vec<(VAddr32, usize)> = loader
+        .actions
+        .iter()
+        .filter_map(|action| match *action {
+            LoaderAction::Load(base, len) => Some((base as u32, len)),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(recorder.loads, loads);
+    let relocations: Vec<(VAddr32, u32, Option<u32>)> = binary
+        .relocations()
+        .map(|entry| entry.expect("Valid relocation"))
+        .filter(|entry| entry.type_num != 7)
+        .map(|entry| {
+            (
+                entry.offset as u32,
+                entry.type_num,
+                entry.addend.map(|a| a as u32),
+            )
+        })
+        .collect();
+    assert!(!relocations.is_empty());
+    assert_eq!(recorder.relocations, relocations);
+
+    // Move the second LOAD segment of a 64-bit binary (program header 3)
+    // beyond 4 GiB: the allocation doesn't fit
+    let mut binary_blob = fs::read("test/test.x86_64").expect("Can't read binary");
+    binary_blob[0xf8..0x100].copy_from_slice(&0x1_0020_0db8u64.to_le_bytes());
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+    let mut recorder = Recorder::default();
+    assert_eq!(
+        binary.load32(&mut recorder),
+        Err(ElfLoaderErr::AddressOverflow {
+            source: "LOAD segment"
+        })
+    );
+    assert!(recorder.loads.is_empty());
+}
+
+#[test]
+fn check_bind_lazy() {
+    init();
+    let binary_blob = fs::read("test/test.x86").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+
+    // PLT entries push the byte offset of their .rel.plt entry
+    let fixup = binary.bind_lazy(0x1000_0000, 8, |entry| {
+        assert_eq!(entry.index, 5);
+        Ok(0x1234)
+    });
+    assert_eq!(
+        fixup.map(|fixup| (fixup.address, fixup.size)),
+        Ok((0x1000_4010, 4))
+    );
+    assert_eq!(
+        binary.bind_lazy(0x1000_0000, 4, |_| Ok(0)),
+        Err(ElfLoaderErr::InvalidPltSelector { selector: 4 })
+    );
+}
+
+#[test]
+fn check_relocation_types() {
+    use crate::arch::x86::RelocationTypes::{self, *};
+
+    // Numbers of the i386 psABI
+    assert_eq!(RelocationTypes::from(1), R_386_32);
+    assert_eq!(RelocationTypes::from(2), R_386_PC32);
+    assert_eq!(RelocationTypes::from(14), R_386_TLS_TPOFF);
+    assert_eq!(RelocationTypes::from(18), R_386_TLS_GD);
+    assert_eq!(RelocationTypes::from(19), R_386_TLS_LDM);
+    assert_eq!(RelocationTypes::from(35), R_386_TLS_DTPMOD32);
+    assert_eq!(RelocationTypes::from(36), R_386_TLS_DTPOFF32);
+    assert_eq!(RelocationTypes::from(37), R_386_TLS_TPOFF32);
+    assert_eq!(RelocationTypes::from(41), R_386_TLS_DESC);
+    assert_eq!(RelocationTypes::from(42), R_386_IRELATIVE);
+    assert_eq!(RelocationTypes::from(43), R_386_GOT32X);
+    assert_eq!(RelocationTypes::from(12), Unknown(12));
+    assert_eq!(RelocationTypes::from(44), Unknown(44));
+}
+
+#[test]
+fn check_evaluate() {
+    use crate::arch::x86::RelocationTypes::{self, *};
+
+    struct Resolver;
+    impl RelocationResolver for Resolver {
+        fn symbol_value(&mut self, index: u32) -> Result<u64, ElfLoaderErr> {
+            Ok(0x100 * index as u64)
+        }
+        fn load_bias(&self) -> u64 {
+            0x1000_0000
+        }
+    }
+
+    let evaluate = |rtype: RelocationTypes, index: u32, addend| {
+        let type_num = (0..).find(|&n| RelocationTypes::from(n) == rtype).unwrap();
+        RelocationEntry {
+            rtype: RelocationType::x86(rtype),
+            type_num,
+            info: (index as u64) << 8 | type_num as u64,
+            offset: 0x3ef4,
+            index,
+            addend: Some(addend),
+            class: crate::elf::Class::Elf32,
+            segment: None,
+        }
+        .evaluate(&mut Resolver)
+    };
+    let value = |value, width| {
+        Ok(Some(RelocationValue {
+            target: 0x1000_3ef4,
+            value,
+            width,
+        }))
+    };
+
+    assert_eq!(evaluate(R_386_NONE, 0, 0), Ok(None));
+    assert_eq!(evaluate(R_386_RELATIVE, 0, 0x1199), value(0x1000_1199, 4));
+    assert_eq!(evaluate(R_386_32, 2, 8), value(0x208, 4));
+    assert_eq!(
+        evaluate(R_386_PC32, 2, 0),
+        value(0x200u64.wrapping_sub(0x1000_3ef4), 4)
+    );
+    assert_eq!(evaluate(R_386_GLOB_DAT, 2, 0), value(0x200, 4));
+    assert_eq!(
+        evaluate(R_386_COPY, 2, 0),
+        Err(ElfLoaderErr::UnsupportedRelocationEntry)
+    );
+}
@@ -1,3 +1,9 @@
+use super::{align_up, TlsSegment};
+use crate::reloc::{tp_relative, RelocationResolver};
+use crate::{ElfLoaderErr, RelocationEntry};
+#[cfg(feature = "apply")]
+use crate::{RelocationType, VAddr};
+
 #[cfg(test)]
 mod test;
 
@@ -54,6 +60,62 @@ pub enum RelocationTypes {
     R_AMD64_GOTTPOFF,
     /// Offset in static TLS block
     R_AMD64_TPOFF32,
+    /// PC relative 64 bit
+    R_AMD64_PC64,
+    /// 64 bit offset to GOT
+    R_AMD64_GOTOFF64,
+    /// 32 bit signed PC relative offset to GOT
+    R_AMD64_GOTPC32,
+    /// 64-bit GOT entry offset
+    R_AMD64_GOT64,
+    /// 64-bit PC relative offset to GOT entry
+    R_AMD64_GOTPCREL64,
+    /// 64-bit PC relative offset to GOT
+    R_AMD64_GOTPC64,
+    /// Like GOT64, says PLT entry needed
+    R_AMD64_GOTPLT64,
+    /// 64-bit GOT relative offset to PLT entry
+    R_AMD64_PLTOFF64,
+    /// Size of symbol plus 32-bit addend
+    R_AMD64_SIZE32,
+    /// Size of symbol plus 64-bit addend
+    R_AMD64_SIZE64,
+    /// GOT offset for TLS descriptor
+    R_AMD64_GOTPC32_TLSDESC,
+    /// Marker for call through TLS descriptor
+    R_AMD64_TLSDESC_CALL,
+    /// TLS descriptor
+    R_AMD64_TLSDESC,
+    /// Adjust indirectly by program base
+    R_AMD64_IRELATIVE,
+    /// 64-bit adjust by program base
+    R_AMD64_RELATIVE64,
+    /// PC relative 32 bit with BND prefix (deprecated)
+    R_AMD64_PC32_BND,
+    /// PC relative 32 bit PLT offset with BND prefix (deprecated)
+    R_AMD64_PLT32_BND,
+    /// Load from 32 bit signed pc relative offset to GOT entry without REX prefix, relaxable
+    R_AMD64_GOTPCRELX,
+    /// Load from 32 bit signed pc relative offset to GOT entry with REX prefix, relaxable
+    R_AMD64_REX_GOTPCRELX,
+    /// Like REX_GOTPCRELX, with a REX2 or 4 byte prefix (APX)
+    R_AMD64_CODE_4_GOTPCRELX,
+    /// Like GOTTPOFF, with a REX2 or 4 byte prefix (APX)
+    R_AMD64_CODE_4_GOTTPOFF,
+    /// Like GOTPC32_TLSDESC, with a REX2 or 4 byte prefix (APX)
+    R_AMD64_CODE_4_GOTPC32_TLSDESC,
+    /// Like REX_GOTPCRELX, with a 5 byte prefix (APX)
+    R_AMD64_CODE_5_GOTPCRELX,
+    /// Like GOTTPOFF, with a 5 byte prefix (APX)
+    R_AMD64_CODE_5_GOTTPOFF,
+    /// Like GOTPC32_TLSDESC, with a 5 byte prefix (APX)
+    R_AMD64_CODE_5_GOTPC32_TLSDESC,
+    /// Like REX_GOTPCRELX, with an EVEX prefix (APX)
+    R_AMD64_CODE_6_GOTPCRELX,
+    /// Like GOTTPOFF, with an EVEX prefix (APX)
+    R_AMD64_CODE_6_GOTTPOFF,
+    /// Like GOTPC32_TLSDESC, with an EVEX prefix (APX)
+    R_AMD64_CODE_6_GOTPC32_TLSDESC,
     /// Unknown
     Unknown(u32),
 }
@@ -87,7 +149,103 @@ impl RelocationTypes {
             21 => R_AMD64_DTPOFF32,
             22 => R_AMD64_GOTTPOFF,
             23 => R_AMD64_TPOFF32,
+            24 => R_AMD64_PC64,
+            25 => R_AMD64_GOTOFF64,
+            26 => R_AMD64_GOTPC32,
+            27 => R_AMD64_GOT64,
+            28 => R_AMD64_GOTPCREL64,
+            29 => R_AMD64_GOTPC64,
+            30 => R_AMD64_GOTPLT64,
+            31 => R_AMD64_PLTOFF64,
+            32 => R_AMD64_SIZE32,
+            33 => R_AMD64_SIZE64,
+            34 => R_AMD64_GOTPC32_TLSDESC,
+            35 => R_AMD64_TLSDESC_CALL,
+            36 => R_AMD64_TLSDESC,
+            37 => R_AMD64_IRELATIVE,
+            38 => R_AMD64_RELATIVE64,
+            39 => R_AMD64_PC32_BND,
+            40 => R_AMD64_PLT32_BND,
+            41 => R_AMD64_GOTPCRELX,
+            42 => R_AMD64_REX_GOTPCRELX,
+            43 => R_AMD64_CODE_4_GOTPCRELX,
+            44 => R_AMD64_CODE_4_GOTTPOFF,
+            45 => R_AMD64_CODE_4_GOTPC32_TLSDESC,
+            46 => R_AMD64_CODE_5_GOTPCRELX,
+            47 => R_AMD64_CODE_5_GOTTPOFF,
+            48 => R_AMD64_CODE_5_GOTPC32_TLSDESC,
+            49 => R_AMD64_CODE_6_GOTPCRELX,
+            50 => R_AMD64_CODE_6_GOTTPOFF,
+            51 => R_AMD64_CODE_6_GOTPC32_TLSDESC,
             x => Unknown(x),
         }
     }
 }
+
+/// Thread pointer relative offset of `value` in the TLS block of the main
+/// executable (TLS variant II: the block ends at the thread pointer).
+pub fn tp_offset(tls: &TlsSegment, value: u64) -> i64 {
+    value as i64 - align_up(tls.mem_size, tls.align) as i64
+}
+
+/// Computes the value and width (in bytes) of an x86-64 relocation, see
+/// [`RelocationEntry::evaluate`].
+pub(crate) fn evaluate<R: RelocationResolver>(
+    typ: RelocationTypes,
+    entry: &RelocationEntry,
+    resolver: &mut R,
+) -> Result<Option<(u64, usize)>, ElfLoaderErr> {
+    use RelocationTypes::*;
+
+    let i = entry.index;
+    let a = || entry.required_addend();
+    let b = resolver.load_bias();
+    let p = b.wrapping_add(entry.offset);
+    let value = match typ {
+        R_AMD64_NONE => return Ok(None),
+        R_AMD64_64 => (resolver.symbol_value(i)?.wrapping_add(a()?), 8),
+        R_AMD64_32 => (resolver.symbol_value(i)?.wrapping_add(a()?), 4),
+        R_AMD64_PC32 => (
+            resolver.symbol_value(i)?.wrapping_add(a()?).wrapping_sub(p),
+            4,
+        ),
+        R_AMD64_PC64 => (
+            resolver.symbol_value(i)?.wrapping_add(a()?).wrapping_sub(p),
+            8,
+        ),
+        R_AMD64_GLOB_DAT | R_AMD64_JMP_SLOT => (resolver.symbol_value(i)?, 8),
+        R_AMD64_RELATIVE | R_AMD64_RELATIVE64 => (b.wrapping_add(a()?), 8),
+        R_AMD64_DTPMOD64 => (resolver.tls_module(i)? as u64, 8),
+        R_AMD64_DTPOFF64 => (resolver.symbol_value(i)?.wrapping_add(a()?), 8),
+        R_AMD64_DTPOFF32 => (resolver.symbol_value(i)?.wrapping_add(a()?), 4),
+        R_AMD64_TPOFF64 => (tp_relative(resolver, i, a()?)?, 8),
+        R_AMD64_TPOFF32 => (tp_relative(resolver, i, a()?)?, 4),
+        _ => return Err(ElfLoaderErr::UnsupportedRelocationEntry),
+    };
+    Ok(Some(value))
+}
+
+/// Applies an x86-64 dynamic relocation of a binary loaded at `base`.
+///
+/// `resolve_symbol` returns the (already relocated) address S of the symbol
+/// with the given index, `write` stores the 64-bit little-endian value at
+/// the given (relocated) address.
+///
+/// Supports the types [`RelocationEntry::evaluate`] handles, except for TLS
+/// relocations. Other types return `UnsupportedRelocationEntry`.
+#[cfg(feature = "apply")]
+pub fn apply<S, W>(
+    entry: RelocationEntry,
+    base: VAddr,
+    resolve_symbol: S,
+    write: W,
+) -> Result<(), ElfLoaderErr>
+where
+    S: FnMut(u32) -> Result<u64, ElfLoaderErr>,
+    W: FnMut(VAddr, &[u8]) -> Result<(), ElfLoaderErr>,
+{
+    match entry.rtype {
+        RelocationType::x86_64(_) => super::apply(entry, base, resolve_symbol, write),
+        _ => Err(ElfLoaderErr::UnsupportedRelocationEntry),
+    }
+}
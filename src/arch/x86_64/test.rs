@@ -1,4 +1,5 @@
 use std::fs;
+use std::vec::Vec;
 
 use crate::arch::test::*;
 use crate::*;
@@ -21,12 +22,22 @@ fn load_pie_elf() {
     assert!(loader
         .actions
         .iter()
-        .find(|&&x| x == LoaderAction::Allocate(VAddr::from(0x0u64), 0x888, Flags(1 | 4)))
+        .find(|&&x| x
+            == LoaderAction::Allocate(
+                VAddr::from(0x0u64),
+                0x888,
+                SegmentPermissions::READ | SegmentPermissions::EXECUTE
+            ))
         .is_some());
     assert!(loader
         .actions
         .iter()
-        .find(|&&x| x == LoaderAction::Allocate(VAddr::from(0x200db8u64), 0x260, Flags(2 | 4)))
+        .find(|&&x| x
+            == LoaderAction::Allocate(
+                VAddr::from(0x200db8u64),
+                0x260,
+                SegmentPermissions::READ | SegmentPermissions::WRITE
+            ))
         .is_some());
     assert!(loader
         .actions
@@ -89,4 +100,1671 @@ fn check_tls() {
         .iter()
         .find(|&&x| x == LoaderAction::Tls(VAddr::from(0x200db4u64), 0x4, 0x8, 0x4))
         .is_some());
+    // tdata_entry = 0xdeadbeef
+    assert_eq!(loader.tdata, 0xdeadbeefu32.to_le_bytes());
+
+    // The same image is available without loading
+    let template = binary
+        .tls_template()
+        .expect("Valid TLS segment")
+        .expect("Has TLS");
+    assert_eq!(template.vaddr, 0x200db4);
+    assert_eq!(template.tdata, loader.tdata.as_slice());
+    assert_eq!(template.tbss_size, 0x4);
+    assert_eq!(template.mem_size, 0x8);
+    assert_eq!(template.align, 0x4);
+
+    let binary_blob = fs::read("test/test.x86_64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+    assert_eq!(binary.tls_template(), Ok(None));
+}
+
+#[test]
+fn check_runpath() {
+    init();
+    let binary_blob = fs::read("test/runpath.x86_64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+
+    assert_eq!(binary.runpath(), Some("$ORIGIN/../lib:/opt/${LIB}"));
+    assert_eq!(binary.rpath(), None);
+
+    // Without section headers (e_shoff, e_shnum and e_shstrndx cleared) the
+    // string is found through DT_STRTAB
+    let mut stripped = binary_blob.clone();
+    stripped[0x28..0x30].fill(0);
+    stripped[0x3c..0x40].fill(0);
+    let binary = ElfBinary::new(stripped.as_slice()).expect("Got proper ELF file");
+    assert!(binary.file.find_section_by_name(".dynstr").is_none());
+    assert_eq!(binary.runpath(), Some("$ORIGIN/../lib:/opt/${LIB}"));
+
+    let binary_blob = fs::read("test/test.x86_64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+    assert_eq!(binary.runpath(), None);
+}
+
+#[test]
+fn check_dynamic_flags() {
+    init();
+    let binary_blob = fs::read("test/test.x86_64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+
+    let dynamic = binary.dynamic().expect("Has a .dynamic section");
+    assert_eq!(dynamic.flags, DynamicFlags::BIND_NOW);
+    assert!(dynamic
+        .flags1
+        .contains(DynamicFlags1::NOW | DynamicFlags1::PIE));
+}
+
+#[test]
+fn check_segment_cache() {
+    use crate::segments::LoadSegment;
+    use xmas_elf::program::Flags;
+
+    init();
+    let binary_blob = fs::read("test/test.x86_64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+    assert_eq!(binary.segments.dynamic, Some(4));
+    assert_eq!(binary.segments.interp, Some(1));
+    let load = binary
+        .segments
+        .load_segments()
+        .expect("LOAD segments are cached");
+    assert_eq!(load.len(), 2);
+    assert_eq!(
+        load[1],
+        LoadSegment {
+            index: 3,
+            flags: Flags(6),
+            offset: 0xdb8,
+            vaddr: 0x200db8,
+            paddr: 0x200db8,
+            file_size: 0x258,
+            mem_size: 0x260,
+            align: 0x200000,
+        }
+    );
+
+    // Without the cache the program headers are decoded, with the same result
+    let mut loader = TestLoader::new(0x1000_0000);
+    binary.load(&mut loader).expect("Can't load?");
+    let mut uncached = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+    uncached.segments.clear_load_segments();
+    let mut uncached_loader = TestLoader::new(0x1000_0000);
+    uncached.load(&mut uncached_loader).expect("Can't load?");
+    assert_eq!(loader.actions, uncached_loader.actions);
+    assert_eq!(
+        binary
+            .coalesced_allocation()
+            .unwrap()
+            .map(|a| (a.vaddr, a.mem_size)),
+        uncached
+            .coalesced_allocation()
+            .unwrap()
+            .map(|a| (a.vaddr, a.mem_size))
+    );
+}
+
+#[test]
+fn check_lazy_dynamic() {
+    init();
+    let binary_blob = fs::read("test/test.x86_64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+    let first = binary.dynamic().expect("Has a .dynamic section") as *const DynamicInfo;
+    let second = binary.dynamic().expect("Has a .dynamic section") as *const DynamicInfo;
+    assert_eq!(first, second, "Parsed only once");
+
+    // Move PT_DYNAMIC (program header 4) out of the file: only using the
+    // dynamic segment fails, not parsing the binary
+    let mut binary_blob = binary_blob.clone();
+    binary_blob[0x128..0x130].copy_from_slice(&0xffff_0000u64.to_le_bytes());
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+    assert_eq!(binary.entry_point(), 0x540);
+    assert!(binary.dynamic().is_none());
+    assert_eq!(
+        binary.try_dynamic().err(),
+        Some(ElfLoaderErr::SegmentOutOfBounds { index: 4 })
+    );
+    let mut loader = TestLoader::new(0x1000_0000);
+    assert_eq!(
+        binary.load(&mut loader).err(),
+        Some(ElfLoaderErr::SegmentOutOfBounds { index: 4 })
+    );
+}
+
+#[test]
+fn check_lazy_binding_slots() {
+    init();
+    let binary_blob = fs::read("test/test.x86_64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+
+    // readelf -d: 0x0000000000000003 (PLTGOT) 0x200fb8
+    assert_eq!(binary.pltgot(), Some(0x200fb8));
+    assert_eq!(
+        binary.lazy_binding_slots(),
+        Ok(Some(LazyBindingSlots {
+            link_map: 0x200fc0,
+            resolver: 0x200fc8
+        }))
+    );
+}
+
+#[test]
+fn check_debug_slot() {
+    init();
+    let binary_blob = fs::read("test/test.x86_64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+
+    // DYNAMIC is at 0x200dc8 and DT_DEBUG is its 13th entry (16 bytes each),
+    // the value follows the 8 byte tag.
+    assert_eq!(
+        binary.debug_slot(0x1000_0000),
+        Some(0x1000_0000 + 0x200dc8 + 12 * 16 + 8)
+    );
+
+    assert_eq!(core::mem::size_of::<debug::RDebug>(), 40);
+    assert_eq!(core::mem::size_of::<debug::LinkMap>(), 40);
+}
+
+#[test]
+fn check_mappings() {
+    init();
+    let binary_blob = fs::read("test/test.x86_64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+
+    let report = format!("{}", binary.mappings(0x1000_0000));
+    let lines: Vec<&str> = report.lines().collect();
+    assert_eq!(lines.len(), 3);
+    assert!(lines[0].starts_with("0000000010000000-0000000010000888 r-xp 00000000 LOAD  .interp"));
+    assert!(lines[0].contains(" .text "));
+    assert_eq!(
+        lines[1],
+        "0000000010200db8-0000000010201018 rw-p 00000db8 LOAD  .init_array .fini_array .dynamic .got .data .bss"
+    );
+    assert_eq!(
+        lines[2],
+        "0000000010200db8-0000000010201000 r--p 00000db8 RELRO .init_array .fini_array .dynamic .got"
+    );
+
+    // A base at the top of the address space wraps instead of panicking
+    let report = format!("{}", binary.mappings(u64::MAX - 0xfff));
+    assert!(report.starts_with("fffffffffffff000-fffffffffffff888 r-xp 00000000 LOAD  .interp"));
+    assert!(report.contains("\n00000000001ffdb8-0000000000200018 rw-p 00000db8 LOAD  .init_array"));
+}
+
+#[test]
+fn check_sections() {
+    init();
+    let binary_blob = fs::read("test/test.x86_64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+
+    assert_eq!(
+        binary.find_section_data(".interp"),
+        Some(&b"/lib64/ld-linux-x86-64.so.2\0"[..])
+    );
+    assert_eq!(binary.find_section_data(".bss"), None);
+    assert_eq!(binary.find_section_data(".does-not-exist"), None);
+
+    // readelf -S: 29 section headers, the first one is NULL
+    let sections: Vec<Section> = binary.sections().collect();
+    assert_eq!(sections.len(), 28);
+    assert_eq!(
+        sections[8],
+        Section {
+            name: ".rela.dyn",
+            address: 0x418,
+            offset: 0x418,
+            size: 0xc0,
+            flags: SectionFlags::ALLOC,
+            typ: ShType::Rela,
+        }
+    );
+    let text = sections.iter().find(|s| s.name == ".text").unwrap();
+    assert_eq!(text.flags, SectionFlags::ALLOC | SectionFlags::EXECINSTR);
+}
+
+#[test]
+fn check_string_tables() {
+    init();
+    let binary_blob = fs::read("test/test.x86_64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+
+    // readelf -d: 0x0000000000000001 (NEEDED) Shared library: [libc.so.6]
+    let dynstr = binary.dynstr().expect("Has .dynstr");
+    assert_eq!(dynstr.get(1), Some("libc.so.6"));
+    assert_eq!(dynstr.get(0), Some(""));
+    assert_eq!(dynstr.get(dynstr.data().len() as u64), None);
+    assert_eq!(dynstr.get(u64::MAX), None);
+
+    let shstrtab = binary.shstrtab().expect("Has .shstrtab");
+    assert_eq!(shstrtab.get(1), Some(".symtab"));
+
+    let strtab = binary.strtab().expect("Has .strtab");
+    assert!(strtab.data().windows(5).any(|w| w == b"main\0"));
+
+    // A table without a terminating NUL yields nothing
+    let table = StringTable::new(b"abc\0def");
+    assert_eq!(table.get(0), Some("abc"));
+    assert_eq!(table.get(2), Some("c"));
+    assert_eq!(table.get(4), None);
+    assert_eq!(table.get_bytes(3), Some(&b""[..]));
+}
+
+#[test]
+fn check_debuglink() {
+    init();
+    let binary_blob = fs::read("test/debuglink.x86_64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+
+    // readelf -x .gnu_debuglink: test.x86_64.0...
+    let link = binary.debuglink().expect("Has .gnu_debuglink");
+    assert_eq!(link.filename, "test.x86_64");
+    assert_eq!(link.crc, 0xdff2cb30);
+
+    let debug_file = fs::read("test/test.x86_64").expect("Can't read binary");
+    assert!(link.matches(&debug_file));
+    assert!(!link.matches(&binary_blob));
+
+    let binary_blob = fs::read("test/test.x86_64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+    assert_eq!(binary.debuglink(), None);
+
+    assert_eq!(crc32(b"123456789"), 0xcbf43926);
+    assert_eq!(DebugLink::parse(b"a.debug\0\x01\x02"), None);
+}
+
+#[test]
+fn check_debug_sections() {
+    init();
+    let binary_blob = fs::read("test/test.x86_64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+
+    let sections = binary.debug_sections(0x1000_0000);
+    assert_eq!(sections.bias, 0x1000_0000);
+    // readelf -S: .eh_frame_hdr at 0x740 (0x3c bytes), .eh_frame at 0x780 (0x108 bytes)
+    assert_eq!(sections.eh_frame_hdr().len(), 0x3c);
+    assert_eq!(sections.eh_frame().len(), 0x108);
+    assert_eq!(sections.section_address(".eh_frame"), Some(0x1000_0780));
+    // Built without -g
+    assert!(sections.debug_info().is_empty());
+    assert_eq!(sections.section_address(".debug_info"), None);
+    assert_eq!(sections.to_load_address(0x64a), 0x1000_064a);
+
+    let binary_blob = fs::read("test/test_nopie.x86_64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+    assert_eq!(binary.debug_sections(0x1000_0000).bias, 0);
+}
+
+#[test]
+fn check_eh_frame_hdr() {
+    use crate::dwarf::{EhFrameHdr, FdeEntry};
+
+    init();
+    let binary_blob = fs::read("test/test.x86_64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+
+    // readelf -x .eh_frame_hdr: pcrel sdata4 pointer to .eh_frame, udata4
+    // count and a datarel sdata4 table with 6 entries
+    let hdr = binary
+        .debug_sections(0x1000_0000)
+        .eh_frame_hdr_table()
+        .expect("Has .eh_frame_hdr")
+        .expect("Valid .eh_frame_hdr");
+    assert_eq!(hdr.version, 1);
+    assert_eq!(hdr.eh_frame_ptr_encoding, 0x1b);
+    assert_eq!(hdr.fde_count_encoding, 0x03);
+    assert_eq!(hdr.table_encoding, 0x3b);
+    assert_eq!(hdr.eh_frame, 0x1000_0780);
+    assert_eq!(hdr.fde_count, 6);
+
+    let initial_locations: Vec<u64> = hdr
+        .entries()
+        .map(|entry| entry.initial_location - 0x1000_0000)
+        .collect();
+    assert_eq!(
+        initial_locations,
+        [0x510, 0x530, 0x540, 0x64a, 0x6b0, 0x720]
+    );
+    let main = FdeEntry {
+        initial_location: 0x1000_064a,
+        fde_address: 0x1000_0808,
+    };
+    assert_eq!(hdr.entry(3), Some(main));
+    assert_eq!(hdr.entry(6), None);
+    assert_eq!(hdr.lookup(0x1000_064a), Some(main));
+    assert_eq!(hdr.lookup(0x1000_06a1), Some(main));
+    assert_eq!(hdr.lookup(0x1000_050f), None);
+    assert_eq!(
+        hdr.lookup(u64::MAX).map(|entry| entry.initial_location),
+        Some(0x1000_0720)
+    );
+
+    // A table running past the end of the section
+    let data = binary.debug_sections(0).eh_frame_hdr();
+    assert!(EhFrameHdr::parse(&data[..0x30], 0x740, 0, binary.header.encoding).is_err());
+    let mut data = data.to_vec();
+    data[0] = 2;
+    assert!(EhFrameHdr::parse(&data, 0x740, 0, binary.header.encoding).is_err());
+}
+
+#[test]
+fn check_tp_offset() {
+    // PT_TLS of tls.x86_64: memory size 0x8, align 0x4
+    let tls = TlsSegment {
+        mem_size: 0x8,
+        align: 0x4,
+    };
+    // objdump -d: main reads tdata_entry (st_value 0) from %fs:-8 and
+    // tbss_entry (st_value 4) from %fs:-4
+    assert_eq!(tls.tp_offset(Machine::X86_64, 0), Ok(-8));
+    assert_eq!(tls.tp_offset(Machine::X86_64, 4), Ok(-4));
+
+    let tls = TlsSegment {
+        mem_size: 0x14,
+        align: 0x10,
+    };
+    assert_eq!(tls.tp_offset(Machine::X86_64, 0), Ok(-0x20));
+}
+
+#[test]
+fn check_relocation_types() {
+    use crate::arch::x86_64::RelocationTypes::{self, *};
+
+    assert_eq!(RelocationTypes::from(23), R_AMD64_TPOFF32);
+    assert_eq!(RelocationTypes::from(24), R_AMD64_PC64);
+    assert_eq!(RelocationTypes::from(36), R_AMD64_TLSDESC);
+    assert_eq!(RelocationTypes::from(37), R_AMD64_IRELATIVE);
+    assert_eq!(RelocationTypes::from(38), R_AMD64_RELATIVE64);
+    assert_eq!(RelocationTypes::from(41), R_AMD64_GOTPCRELX);
+    assert_eq!(RelocationTypes::from(42), R_AMD64_REX_GOTPCRELX);
+    assert_eq!(RelocationTypes::from(51), R_AMD64_CODE_6_GOTPC32_TLSDESC);
+    assert_eq!(RelocationTypes::from(52), Unknown(52));
+}
+
+/// A RELA entry at offset 0x3de8, with the raw fields derived from `rtype`.
+fn rela_entry(
+    rtype: crate::arch::x86_64::RelocationTypes,
+    index: u32,
+    addend: u64,
+) -> RelocationEntry {
+    let type_num = (0..)
+        .find(|&n| crate::arch::x86_64::RelocationTypes::from(n) == rtype)
+        .unwrap();
+    RelocationEntry {
+        rtype: RelocationType::x86_64(rtype),
+        type_num,
+        info: (index as u64) << 32 | type_num as u64,
+        offset: 0x3de8,
+        index,
+        addend: Some(addend),
+        class: crate::elf::Class::Elf64,
+        segment: None,
+    }
+}
+
+#[cfg(feature = "apply")]
+#[test]
+fn check_apply() {
+    use crate::arch::x86_64::{apply, RelocationTypes::*};
+    use core::convert::TryInto;
+
+    let entry = |rtype, addend| rela_entry(rtype, 5, addend);
+    let resolve = |index| {
+        assert_eq!(index, 5);
+        Ok(0x4000)
+    };
+
+    let mut writes = Vec::new();
+    let mut write = |addr, bytes: &[u8]| {
+        writes.push((addr, u64::from_le_bytes(bytes.try_into().unwrap())));
+        Ok(())
+    };
+    apply(
+        entry(R_AMD64_RELATIVE, 0x1139),
+        0x1000_0000,
+        resolve,
+        &mut write,
+    )
+    .unwrap();
+    apply(entry(R_AMD64_64, 0x8), 0x1000_0000, resolve, &mut write).unwrap();
+    apply(
+        entry(R_AMD64_GLOB_DAT, 0x8),
+        0x1000_0000,
+        resolve,
+        &mut write,
+    )
+    .unwrap();
+    apply(entry(R_AMD64_NONE, 0), 0x1000_0000, resolve, &mut write).unwrap();
+    assert_eq!(
+        apply(entry(R_AMD64_COPY, 0), 0x1000_0000, resolve, &mut write),
+        Err(ElfLoaderErr::UnsupportedRelocationEntry)
+    );
+    assert_eq!(
+        writes,
+        [
+            (0x1000_3de8, 0x1000_1139),
+            (0x1000_3de8, 0x4008),
+            (0x1000_3de8, 0x4000)
+        ]
+    );
+}
+
+#[test]
+fn check_evaluate() {
+    use crate::arch::x86_64::RelocationTypes::*;
+
+    struct Resolver;
+    impl RelocationResolver for Resolver {
+        fn symbol_value(&mut self, index: u32) -> Result<u64, ElfLoaderErr> {
+            Ok(0x100 * index as u64)
+        }
+        fn load_bias(&self) -> u64 {
+            0x1000_0000
+        }
+        fn tls_module(&mut self, _index: u32) -> Result<usize, ElfLoaderErr> {
+            Ok(1)
+        }
+        fn tls_offset(&mut self, module: usize) -> Result<i64, ElfLoaderErr> {
+            assert_eq!(module, 1);
+            Ok(-0x40)
+        }
+    }
+
+    let evaluate = |rtype, index, addend| rela_entry(rtype, index, addend).evaluate(&mut Resolver);
+    let value = |value, width| {
+        Ok(Some(RelocationValue {
+            target: 0x1000_3de8,
+            value,
+            width,
+        }))
+    };
+
+    assert_eq!(evaluate(R_AMD64_NONE, 0, 0), Ok(None));
+    assert_eq!(evaluate(R_AMD64_RELATIVE, 0, 0x1139), value(0x1000_1139, 8));
+    assert_eq!(evaluate(R_AMD64_64, 2, 8), value(0x208, 8));
+    assert_eq!(evaluate(R_AMD64_JMP_SLOT, 2, 8), value(0x200, 8));
+    assert_eq!(
+        evaluate(R_AMD64_PC32, 2, 0),
+        value(0x200u64.wrapping_sub(0x1000_3de8), 4)
+    );
+    assert_eq!(evaluate(R_AMD64_DTPMOD64, 2, 0), value(1, 8));
+    assert_eq!(evaluate(R_AMD64_DTPOFF64, 2, 4), value(0x204, 8));
+    assert_eq!(evaluate(R_AMD64_TPOFF64, 2, 4), value(0x1c4, 8));
+    assert_eq!(
+        evaluate(R_AMD64_IRELATIVE, 0, 0),
+        Err(ElfLoaderErr::UnsupportedRelocationEntry)
+    );
+}
+
+#[test]
+fn check_raw_relocation_fields() {
+    init();
+    struct RawLoader(Vec<(u32, u64, u32)>);
+    impl ElfLoader for RawLoader {
+        fn allocate(&mut self, _requests: AllocateRequests) -> Result<(), ElfLoaderErr> {
+            Ok(())
+        }
+        fn load(
+            &mut self,
+            _flags: SegmentPermissions,
+            _base: VAddr,
+            _region: &[u8],
+        ) -> Result<(), ElfLoaderErr> {
+            Ok(())
+        }
+        fn relocate(&mut self, entry: RelocationEntry) -> Result<(), ElfLoaderErr> {
+            self.0.push((entry.type_num, entry.info, entry.index));
+            Ok(())
+        }
+    }
+
+    let binary_blob = fs::read("test/test.x86_64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+    let mut loader = RawLoader(Vec::new());
+    binary.load(&mut loader).expect("Can't load?");
+
+    // 000000200db8  000000000008 R_X86_64_RELATIVE
+    // 000000200fe0  000300000006 R_X86_64_GLOB_DAT __libc_start_main@GLIBC_2.2.5 + 0
+    assert_eq!(loader.0[0], (8, 0x8, 0));
+    assert_eq!(loader.0[4], (6, 0x3_0000_0006, 3));
+}
+
+#[test]
+fn check_relro() {
+    init();
+    let binary_blob = fs::read("test/test.x86_64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+
+    let segment = RelroSegment {
+        vaddr: 0x200db8,
+        mem_size: 0x248,
+        align: 1,
+        page_size: 0x1000,
+    };
+    assert_eq!(binary.relro_segments().collect::<Vec<_>>(), [segment]);
+    assert_eq!(segment.page_range(0x1000), Ok((0x200000, 0x201000)));
+    assert_eq!(segment.pages(), Ok((0x200000, 0x201000)));
+    let wrapping = RelroSegment {
+        vaddr: u64::MAX - 0xfff,
+        mem_size: 0x2000,
+        ..segment
+    };
+    assert_eq!(
+        wrapping.pages(),
+        Err(ElfLoaderErr::AddressOverflow {
+            source: "p_vaddr + p_memsz"
+        })
+    );
+
+    let mut loader = TestLoader::new(0x1000_0000);
+    binary.load(&mut loader).expect("Can't load?");
+    assert_eq!(
+        loader.actions.last(),
+        Some(&LoaderAction::Relro(0x200db8, 0x248))
+    );
+
+    let mut loader = TestLoader::new(0x1000_0000);
+    let config = LoadConfig {
+        defer_relro: true,
+        ..Default::default()
+    };
+    binary
+        .load_with_config(&mut loader, &config)
+        .expect("Can't load?");
+    assert!(!loader
+        .actions
+        .iter()
+        .any(|a| matches!(a, LoaderAction::Relro(..))));
+    binary
+        .finalize(&mut loader, &config)
+        .expect("Can't finalize?");
+    assert_eq!(
+        loader.actions.last(),
+        Some(&LoaderAction::Relro(0x200db8, 0x248))
+    );
+}
+
+#[test]
+fn check_validate_load_segments() {
+    init();
+    for file in [
+        "test/test.x86_64",
+        "test/test.x86",
+        "test/test_nopie.riscv64",
+    ]
+    .iter()
+    {
+        let binary_blob = fs::read(file).expect("Can't read binary");
+        let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+        assert_eq!(binary.validate_load_segments(), Ok(()));
+    }
+
+    // Program header 3 is the second LOAD segment
+    let phdr = 64 + 3 * 56;
+    let patched = |field: usize, value: u64| {
+        let mut binary_blob = fs::read("test/test.x86_64").expect("Can't read binary");
+        binary_blob[phdr + field..phdr + field + 8].copy_from_slice(&value.to_le_bytes());
+        binary_blob
+    };
+
+    let binary_blob = patched(8, 0xdb0);
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+    assert_eq!(
+        binary.validate_load_segments(),
+        Err(ElfLoaderErr::InvalidSegment {
+            index: 3,
+            reason: "p_vaddr and p_offset are not congruent modulo p_align"
+        })
+    );
+
+    let binary_blob = patched(16, 0xdb8);
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+    assert_eq!(
+        binary.validate_load_segments(),
+        Err(ElfLoaderErr::InvalidSegment {
+            index: 3,
+            reason: "LOAD segment overlaps the previous one"
+        })
+    );
+
+    let mut loader = TestLoader::new(0x1000_0000);
+    let config = LoadConfig {
+        validate_segments: true,
+        ..Default::default()
+    };
+    assert!(binary.load_with_config(&mut loader, &config).is_err());
+    assert!(loader.actions.is_empty());
+}
+
+#[test]
+fn check_overlapping_segments() {
+    init();
+    let mut binary_blob = fs::read("test/test.x86_64").expect("Can't read binary");
+
+    // Move the second LOAD segment (program header 3) into the first one
+    let phdr = 64 + 3 * 56;
+    binary_blob[phdr + 16..phdr + 24].copy_from_slice(&0x800u64.to_le_bytes());
+
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+    let overlap = Err(ElfLoaderErr::OverlappingSegments {
+        first: 2,
+        second: 3,
+    });
+    assert_eq!(binary.check_overlapping_segments(), overlap);
+
+    let mut loader = TestLoader::new(0x1000_0000);
+    assert_eq!(binary.load(&mut loader), overlap);
+    assert!(loader.actions.is_empty());
+}
+
+#[test]
+fn check_segment_overflow() {
+    init();
+    // Program header 3 is the second LOAD segment
+    let phdr = 64 + 3 * 56;
+    let patched = |field: usize, value: u64| {
+        let mut binary_blob = fs::read("test/test.x86_64").expect("Can't read binary");
+        binary_blob[phdr + field..phdr + field + 8].copy_from_slice(&value.to_le_bytes());
+        binary_blob
+    };
+
+    let binary_blob = patched(40, u64::MAX);
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+    let mut loader = TestLoader::new(0x1000_0000);
+    assert_eq!(
+        binary.load(&mut loader),
+        Err(ElfLoaderErr::AddressOverflow {
+            source: "p_vaddr + p_memsz"
+        })
+    );
+    assert!(loader.actions.is_empty());
+
+    let binary_blob = patched(8, u64::MAX - 0x10);
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+    assert_eq!(
+        binary.load(&mut loader),
+        Err(ElfLoaderErr::AddressOverflow {
+            source: "p_offset + p_filesz"
+        })
+    );
+
+    let binary_blob = patched(8, 0x10_0000);
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+    assert_eq!(
+        binary.load(&mut loader),
+        Err(ElfLoaderErr::SegmentOutOfBounds { index: 3 })
+    );
+    assert!(loader.actions.is_empty());
+}
+
+#[test]
+fn check_limits() {
+    init();
+    let binary_blob = fs::read("test/test.x86_64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+
+    let limits = Limits {
+        max_segment_size: Some(0x888),
+        max_total_size: Some(0x888 + 0x260),
+        max_relocations: Some(8),
+    };
+    assert_eq!(binary.check_limits(&limits), Ok(()));
+
+    let exceeded = |limits| {
+        let mut loader = TestLoader::new(0x1000_0000);
+        let config = LoadConfig {
+            limits,
+            ..Default::default()
+        };
+        let result = binary.load_with_config(&mut loader, &config);
+        assert!(loader.actions.is_empty());
+        match result {
+            Err(ElfLoaderErr::LimitsExceeded { limit }) => limit,
+            _ => panic!("Limits not enforced"),
+        }
+    };
+    assert_eq!(
+        exceeded(Limits {
+            max_segment_size: Some(0x887),
+            ..limits
+        }),
+        "max_segment_size"
+    );
+    assert_eq!(
+        exceeded(Limits {
+            max_total_size: Some(0x888 + 0x25f),
+            ..limits
+        }),
+        "max_total_size"
+    );
+    assert_eq!(
+        exceeded(Limits {
+            max_relocations: Some(7),
+            ..limits
+        }),
+        "max_relocations"
+    );
+}
+
+#[test]
+fn check_rebased_addresses() {
+    init();
+    let binary_blob = fs::read("test/test.x86_64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+    assert_eq!(binary.entry_point_rebased(0x1000_0000), Ok(0x1000_0540));
+
+    let mut symbols = Vec::new();
+    binary
+        .for_each_symbol(|sym| {
+            let name = binary.symbol_name(sym);
+            if name == "main" || name == "printf@@GLIBC_2.2.5" || name == "test.c" {
+                symbols.push((name, binary.symbol_address_rebased(sym, 0x1000_0000)));
+            }
+        })
+        .expect("Got symbols");
+    symbols.sort();
+    assert_eq!(
+        symbols,
+        [
+            ("main", Some(0x1000_064a)),
+            ("printf@@GLIBC_2.2.5", None),
+            ("test.c", Some(0))
+        ]
+    );
+
+    let binary_blob = fs::read("test/test_nopie.x86_64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+    assert_eq!(binary.entry_point_rebased(0x1000_0000), Ok(0x400400));
+}
+
+#[test]
+fn check_binary_kind() {
+    init();
+    let binary_blob = fs::read("test/test.x86_64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+    assert_eq!(binary.kind(), Some(BinaryKind::Pie));
+
+    let binary_blob = fs::read("test/test_nopie.x86_64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+    assert_eq!(binary.kind(), Some(BinaryKind::Executable));
+
+    // Clear DF_1_PIE in DT_FLAGS_1 (.dynamic is at 0xdc8)
+    let mut binary_blob = fs::read("test/test.x86_64").expect("Can't read binary");
+    let flags1 = (0xdc8..0xdc8 + 0x1f0)
+        .step_by(16)
+        .find(|&off| binary_blob[off..off + 8] == 0x6fff_fffbu64.to_le_bytes())
+        .expect("Has DT_FLAGS_1");
+    binary_blob[flags1 + 8..flags1 + 16].copy_from_slice(&0u64.to_le_bytes());
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+    assert_eq!(binary.kind(), Some(BinaryKind::Pie));
+
+    // Turn PT_INTERP (program header 1) into PT_NULL
+    binary_blob[64 + 56..64 + 56 + 4].copy_from_slice(&0u32.to_le_bytes());
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+    assert!(binary.is_pie());
+
+    // Without an entry point it's a shared library
+    binary_blob[0x18..0x20].copy_from_slice(&0u64.to_le_bytes());
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+    assert_eq!(binary.kind(), Some(BinaryKind::SharedLibrary));
+    assert!(!binary.is_pie());
+}
+
+#[test]
+fn check_load_static_pie() {
+    init();
+    let binary_blob = fs::read("test/static_pie.x86_64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+    assert_eq!(binary.kind(), Some(BinaryKind::Pie));
+
+    let mut loader = TestLoader::new(0x1000_0000);
+    binary
+        .load_static_pie(0x1000_0000, &mut loader)
+        .expect("Can't load?");
+    assert!(loader
+        .actions
+        .contains(&LoaderAction::Write(0x1000_4008, 0x1000_2000)));
+    assert!(loader
+        .actions
+        .contains(&LoaderAction::Write(0x1000_4010, 0x1000_4000)));
+    assert!(!loader
+        .actions
+        .iter()
+        .any(|action| matches!(action, LoaderAction::Relocate(..))));
+
+    // Turn the second RELATIVE (.rela.dyn at 0x240) into a symbol-less TPOFF64
+    let mut tpoff_blob = binary_blob.clone();
+    tpoff_blob[0x240 + 24 + 8..0x240 + 24 + 16].copy_from_slice(&18u64.to_le_bytes());
+    let tpoff = ElfBinary::new(tpoff_blob.as_slice()).expect("Got proper ELF file");
+    let mut loader = TestLoader::new(0x1000_0000);
+    assert_eq!(
+        tpoff.load_static_pie(0x1000_0000, &mut loader),
+        Err(ElfLoaderErr::NotStaticPie {
+            reason: "has relocations other than RELATIVE and IRELATIVE"
+        })
+    );
+
+    let mut loader = TestLoader::new(0x1000_0800);
+    assert_eq!(
+        binary.load_static_pie(0x1000_0800, &mut loader),
+        Err(ElfLoaderErr::NotStaticPie {
+            reason: "base is not aligned to the LOAD segments"
+        })
+    );
+    assert!(loader.actions.is_empty());
+
+    let binary_blob = fs::read("test/test.x86_64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+    assert_eq!(
+        binary.load_static_pie(0x1000_0000, &mut loader),
+        Err(ElfLoaderErr::NotStaticPie {
+            reason: "requests an interpreter"
+        })
+    );
+
+    // Turn PT_INTERP (program header 1) into PT_NULL, libc is still needed
+    let mut binary_blob = binary_blob.clone();
+    binary_blob[64 + 56..64 + 56 + 4].copy_from_slice(&0u32.to_le_bytes());
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+    assert_eq!(
+        binary.load_static_pie(0x1000_0000, &mut loader),
+        Err(ElfLoaderErr::NotStaticPie {
+            reason: "depends on shared libraries"
+        })
+    );
+    assert!(loader.actions.is_empty());
+
+    let binary_blob = fs::read("test/test_nopie.x86_64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+    assert_eq!(
+        binary.load_static_pie(0, &mut loader),
+        Err(ElfLoaderErr::NotStaticPie {
+            reason: "not a PIE"
+        })
+    );
+}
+
+#[test]
+fn check_relocations() {
+    init();
+    let raw = |binary: &ElfBinary| {
+        binary
+            .relocations()
+            .map(|entry| {
+                let entry = entry.expect("Valid relocation");
+                (entry.offset, entry.type_num, entry.index, entry.addend)
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let binary_blob = fs::read("test/test.x86_64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+    let relocations = raw(&binary);
+    // readelf -r: .rela.dyn contains 8 entries, .rela.plt contains 1 entry
+    assert_eq!(relocations.len(), 9);
+    assert_eq!(relocations[0], (0x200db8, 8, 0, Some(0x640)));
+    assert_eq!(relocations[3], (0x200fd8, 6, 1, Some(0)));
+    assert_eq!(relocations[8], (0x200fd0, 7, 2, Some(0)));
+
+    // Without section headers the same entries come from DT_RELA and DT_JMPREL
+    let mut stripped_blob = binary_blob.clone();
+    stripped_blob[0x28..0x30].copy_from_slice(&0u64.to_le_bytes());
+    stripped_blob[0x3c..0x40].copy_from_slice(&0u32.to_le_bytes());
+    let stripped = ElfBinary::new(stripped_blob.as_slice()).expect("Got proper ELF file");
+    assert!(stripped.file.section_iter().next().is_none());
+    assert_eq!(raw(&stripped), relocations);
+}
+
+#[test]
+fn check_relocations_nth() {
+    init();
+    let binary_blob = fs::read("test/test.x86_64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+    let all = binary
+        .relocations()
+        .map(|entry| entry.expect("Valid relocation").offset)
+        .collect::<Vec<_>>();
+    for start in 0..=all.len() {
+        let skipped = binary
+            .relocations()
+            .skip(start)
+            .map(|entry| entry.expect("Valid relocation").offset)
+            .collect::<Vec<_>>();
+        assert_eq!(skipped, all[start..]);
+    }
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn check_load_parallel() {
+    use std::sync::Mutex;
+
+    struct Sink(Mutex<Vec<(u64, u32)>>);
+
+    impl RelocationSink for Sink {
+        fn relocate(&self, entry: RelocationEntry) -> Result<(), ElfLoaderErr> {
+            self.0.lock().unwrap().push((entry.offset, entry.type_num));
+            Ok(())
+        }
+    }
+
+    init();
+    let binary_blob = fs::read("test/test.x86_64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+    let mut expected = binary
+        .relocations()
+        .map(|entry| entry.expect("Valid relocation"))
+        .filter(|entry| entry.type_num != 7)
+        .map(|entry| (entry.offset, entry.type_num))
+        .collect::<Vec<_>>();
+    expected.sort_unstable();
+
+    for threads in [0, 1, 3, 16] {
+        let mut loader = TestLoader::new(0x1000_0000);
+        let sink = Sink(Mutex::new(Vec::new()));
+        let stats = binary
+            .load_parallel(&mut loader, &sink, threads, &LoadConfig::default())
+            .expect("Can't load?");
+        assert_eq!(stats.segments, 2);
+        assert_eq!(stats.relocations.get(8), 3);
+        assert_eq!(stats.relocations.get(6), 5);
+        assert_eq!(stats.relocations.total(), 8);
+
+        let mut applied = sink.0.into_inner().unwrap();
+        applied.sort_unstable();
+        assert_eq!(applied, expected);
+        // The sink handles all relocations, the loader none
+        assert!(!loader
+            .actions
+            .iter()
+            .any(|action| matches!(action, LoaderAction::Relocate(..))));
+    }
+}
+
+#[test]
+fn check_interpreter() {
+    init();
+    let binary_blob = fs::read("test/test.x86_64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+    assert_eq!(binary.interpreter(), Some("/lib64/ld-linux-x86-64.so.2"));
+
+    // Without section headers the path comes from PT_INTERP
+    let mut binary_blob = binary_blob.clone();
+    binary_blob[0x28..0x30].copy_from_slice(&0u64.to_le_bytes());
+    binary_blob[0x3c..0x40].copy_from_slice(&0u32.to_le_bytes());
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+    assert_eq!(binary.interpreter(), Some("/lib64/ld-linux-x86-64.so.2"));
+
+    // A missing NUL terminator is tolerated (PT_INTERP is at 0x238, 0x1c bytes)
+    binary_blob[0x238 + 0x1b] = b'X';
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+    assert_eq!(binary.interpreter(), Some("/lib64/ld-linux-x86-64.so.2X"));
+
+    let binary_blob = fs::read("test/static_pie.x86_64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+    assert_eq!(binary.interpreter(), None);
+}
+
+#[test]
+fn check_load_stats() {
+    init();
+    let binary_blob = fs::read("test/test.x86_64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+    let mut loader = TestLoader::new(0x1000_0000);
+    let stats = binary
+        .load_with_stats(&mut loader, &LoadConfig::default())
+        .expect("Can't load?");
+
+    assert_eq!(stats.segments, 2);
+    assert_eq!(stats.bytes_copied, 0x888 + 0x258);
+    assert_eq!(stats.bytes_zeroed, 0x8);
+    assert_eq!(stats.tls_size, 0);
+    // readelf -r: 3 R_X86_64_RELATIVE and 5 R_X86_64_GLOB_DAT in .rela.dyn
+    assert_eq!(stats.relocations.get(8), 3);
+    assert_eq!(stats.relocations.get(6), 5);
+    assert_eq!(stats.relocations.get(7), 0);
+    assert_eq!(stats.relocations.total(), 8);
+    assert_eq!(
+        stats.relocations.iter().collect::<Vec<_>>(),
+        [(8, 3), (6, 5)]
+    );
+
+    let binary_blob = fs::read("test/tls.x86_64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+    let mut loader = TestLoader::new(0x1000_0000);
+    let stats = binary
+        .load_with_stats(&mut loader, &LoadConfig::default())
+        .expect("Can't load?");
+    assert_eq!(stats.tls_size, 0x8);
+}
+
+#[test]
+fn check_relocation_counts() {
+    let mut counts = RelocationCounts::default();
+    for typ in 0..RelocationCounts::CAPACITY as u32 + 2 {
+        counts.add(typ);
+    }
+    counts.add(0);
+    assert_eq!(counts.get(0), 2);
+    assert_eq!(counts.get(RelocationCounts::CAPACITY as u32), 0);
+    assert_eq!(counts.other, 2);
+    assert_eq!(counts.total(), RelocationCounts::CAPACITY as u64 + 3);
+}
+
+#[test]
+fn check_progress() {
+    init();
+    struct ProgressLoader {
+        progress: Vec<LoadProgress>,
+        abort_after: usize,
+    }
+
+    impl ElfLoader for ProgressLoader {
+        fn allocate(&mut self, _requests: AllocateRequests) -> Result<(), ElfLoaderErr> {
+            Ok(())
+        }
+
+        fn load(
+            &mut self,
+            _flags: SegmentPermissions,
+            _base: VAddr,
+            _region: &[u8],
+        ) -> Result<(), ElfLoaderErr> {
+            Ok(())
+        }
+
+        fn relocate(&mut self, _entry: RelocationEntry) -> Result<(), ElfLoaderErr> {
+            Ok(())
+        }
+
+        fn progress(&mut self, progress: LoadProgress) -> Result<(), ElfLoaderErr> {
+            self.progress.push(progress);
+            if self.progress.len() == self.abort_after {
+                return Err(ElfLoaderErr::OutOfMemory);
+            }
+            Ok(())
+        }
+    }
+
+    let binary_blob = fs::read("test/test.x86_64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+    let config = LoadConfig {
+        progress_interval: 3,
+        ..Default::default()
+    };
+    let mut loader = ProgressLoader {
+        progress: Vec::new(),
+        abort_after: 0,
+    };
+    binary
+        .load_with_config(&mut loader, &config)
+        .expect("Can't load?");
+    assert_eq!(
+        loader.progress,
+        [
+            LoadProgress::Segments {
+                completed: 1,
+                total: 2
+            },
+            LoadProgress::Segments {
+                completed: 2,
+                total: 2
+            },
+            LoadProgress::Relocations {
+                completed: 3,
+                total: 8
+            },
+            LoadProgress::Relocations {
+                completed: 6,
+                total: 8
+            },
+            LoadProgress::Relocations {
+                completed: 8,
+                total: 8
+            },
+        ]
+    );
+
+    // Errors from the callback abort the load
+    let mut loader = ProgressLoader {
+        progress: Vec::new(),
+        abort_after: 1,
+    };
+    assert_eq!(binary.load(&mut loader), Err(ElfLoaderErr::OutOfMemory));
+    assert_eq!(loader.progress.len(), 1);
+}
+
+#[test]
+fn check_skip_segment() {
+    init();
+    let binary_blob = fs::read("test/test.x86_64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+
+    // All relocations and the RELRO segment are in the second (RW) segment
+    let mut loader = TestLoader::new(0x1000_0000);
+    loader.skip.push(0x200db8);
+    let stats = binary
+        .load_with_stats(&mut loader, &LoadConfig::default())
+        .expect("Can't load?");
+    assert_eq!(stats.segments, 1);
+    assert_eq!(stats.segments_skipped, 1);
+    assert_eq!(stats.relocations.total(), 0);
+    assert_eq!(
+        loader.actions,
+        [
+            LoaderAction::Allocate(
+                0x0,
+                0x888,
+                SegmentPermissions::READ | SegmentPermissions::EXECUTE
+            ),
+            LoaderAction::Allocate(
+                0x200db8,
+                0x260,
+                SegmentPermissions::READ | SegmentPermissions::WRITE
+            ),
+            LoaderAction::Load(0x0, 0x888),
+        ]
+    );
+
+    let mut loader = TestLoader::new(0x1000_0000);
+    loader.skip.push(0x0);
+    let stats = binary
+        .load_with_stats(&mut loader, &LoadConfig::default())
+        .expect("Can't load?");
+    assert_eq!(stats.segments_skipped, 1);
+    assert_eq!(stats.relocations.total(), 8);
+    // The decisions are recorded, not asked for again while relocating
+    assert_eq!(loader.dispositions, 2);
+    assert!(!loader.actions.contains(&LoaderAction::Load(0x0, 0x888)));
+    assert!(loader
+        .actions
+        .contains(&LoaderAction::Relro(0x200db8, 0x248)));
+}
+
+#[test]
+fn check_page_size() {
+    init();
+    struct ReadonlyLoader(Vec<(VAddr, usize)>);
+
+    impl ElfLoader for ReadonlyLoader {
+        fn allocate(&mut self, _requests: AllocateRequests) -> Result<(), ElfLoaderErr> {
+            Ok(())
+        }
+
+        fn load(
+            &mut self,
+            _flags: SegmentPermissions,
+            _base: VAddr,
+            _region: &[u8],
+        ) -> Result<(), ElfLoaderErr> {
+            Ok(())
+        }
+
+        fn relocate(&mut self, _entry: RelocationEntry) -> Result<(), ElfLoaderErr> {
+            Ok(())
+        }
+
+        fn make_readonly(&mut self, base: VAddr, size: usize) -> Result<(), ElfLoaderErr> {
+            self.0.push((base, size));
+            Ok(())
+        }
+    }
+
+    let binary_blob = fs::read("test/test.x86_64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+    let config = |page_size| LoadConfig {
+        validate_segments: true,
+        page_size,
+        ..Default::default()
+    };
+
+    // RELRO covers 0x200db8..0x201000, the default relro() passes the exact
+    // range for any page size
+    for page_size in [0x1000, 0x10000] {
+        let mut loader = ReadonlyLoader(Vec::new());
+        binary
+            .load_with_config(&mut loader, &config(page_size))
+            .expect("Can't load?");
+        assert_eq!(loader.0, [(0x200db8, 0x248)]);
+    }
+
+    // The LOAD segments are 2 MiB apart
+    let mut loader = ReadonlyLoader(Vec::new());
+    assert_eq!(
+        binary.validate_load_segments_for_page_size(0x20_0000),
+        Ok(())
+    );
+    assert_eq!(
+        binary.load_with_config(&mut loader, &config(0x40_0000)),
+        Err(ElfLoaderErr::InvalidSegment {
+            index: 3,
+            reason: "LOAD segment overlaps the previous one"
+        })
+    );
+    assert_eq!(
+        binary.load_with_config(&mut loader, &config(0x1800)),
+        Err(ElfLoaderErr::InvalidPageSize { page_size: 0x1800 })
+    );
+}
+
+#[test]
+fn check_concurrent_loads() {
+    init();
+    let binary_blob = fs::read("test/test.x86_64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+
+    let bases = [0x1000_0000, 0x2000_0000, 0x3000_0000, 0x4000_0000];
+    let loaders: Vec<TestLoader> = std::thread::scope(|scope| {
+        let threads: Vec<_> = bases
+            .iter()
+            .map(|&base| {
+                let binary = &binary;
+                scope.spawn(move || {
+                    let mut loader = TestLoader::new(base);
+                    binary.load(&mut loader).expect("Can't load?");
+                    loader
+                })
+            })
+            .collect();
+        threads
+            .into_iter()
+            .map(|thread| thread.join().expect("Load panicked"))
+            .collect()
+    });
+
+    for (loader, base) in loaders.iter().zip(bases) {
+        assert!(loader
+            .actions
+            .contains(&LoaderAction::Relocate(base + 0x200db8, base + 0x640)));
+    }
+}
+
+#[test]
+fn check_bind_lazy() {
+    init();
+    let binary_blob = fs::read("test/test.x86_64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+
+    assert_eq!(
+        binary.lazy_binding_abi().map(|abi| abi.selector_kind),
+        Ok(PltSelector::Index)
+    );
+    // readelf -r: printf@GLIBC_2.2.5 is the only .rela.plt entry
+    let fixup = binary.bind_lazy(0x1000_0000, 0, |entry| {
+        assert_eq!(entry.index, 2);
+        Ok(0xdead_beef)
+    });
+    assert_eq!(
+        fixup,
+        Ok(JumpSlotFixup {
+            address: 0x1020_0fd0,
+            value: 0xdead_beef,
+            size: 8
+        })
+    );
+    assert_eq!(
+        binary.bind_lazy(0x1000_0000, 1, |_| Ok(0)),
+        Err(ElfLoaderErr::InvalidPltSelector { selector: 1 })
+    );
+}
+
+#[test]
+fn check_got_fixups() {
+    init();
+    let binary_blob = fs::read("test/test.x86_64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+
+    // readelf -r: 3 RELATIVE, 5 GLOB_DAT and 1 JUMP_SLOT
+    let fixups: Vec<GotFixup> = binary.got_fixups().map(|f| f.unwrap()).collect();
+    assert_eq!(fixups.len(), 9);
+    let count = |kind| fixups.iter().filter(|f| f.kind == kind).count();
+    assert_eq!(count(GotFixupKind::Relative), 3);
+    assert_eq!(count(GotFixupKind::GlobDat), 5);
+    assert_eq!(count(GotFixupKind::JumpSlot), 1);
+    assert_eq!(
+        fixups[0],
+        GotFixup {
+            address: 0x200db8,
+            kind: GotFixupKind::Relative,
+            symbol: 0,
+            addend: Some(0x640)
+        }
+    );
+    assert_eq!(
+        fixups[8],
+        GotFixup {
+            address: 0x200fd0,
+            kind: GotFixupKind::JumpSlot,
+            symbol: 2,
+            addend: Some(0)
+        }
+    );
+}
+
+#[test]
+fn check_abi_version() {
+    init();
+    let mut binary_blob = fs::read("test/test.x86_64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+    assert_eq!(binary.abi_version(), 0);
+
+    // EI_ABIVERSION 1, as used by glibc for binaries with IFUNCs
+    binary_blob[8] = 1;
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+    assert_eq!(binary.abi_version(), 1);
+    let mut loader = TestLoader::new(0x1000_0000);
+    binary.load(&mut loader).expect("Can't load?");
+
+    let config = LoadConfig {
+        max_abi_version: Some(0),
+        ..Default::default()
+    };
+    let mut loader = TestLoader::new(0x1000_0000);
+    assert_eq!(
+        binary.load_with_config(&mut loader, &config),
+        Err(ElfLoaderErr::UnsupportedAbiVersion { version: 1 })
+    );
+    assert!(loader.actions.is_empty());
+}
+
+#[test]
+fn check_load_validated() {
+    init();
+    let binary_blob = fs::read("test/test.x86_64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+    let token = binary
+        .validate(&LoadConfig::default())
+        .expect("Valid binary");
+
+    let mut loader = TestLoader::new(0x1000_0000);
+    binary.load(&mut loader).expect("Can't load?");
+    let expected = loader.actions;
+
+    // Another copy of the binary, parsed without the section header checks
+    let copy = binary_blob.clone();
+    let binary = ElfBinary::new_unchecked(copy.as_slice()).expect("Got proper ELF file");
+    let mut loader = TestLoader::new(0x1000_0000);
+    binary
+        .load_validated(&mut loader, &token)
+        .expect("Can't load?");
+    assert_eq!(loader.actions, expected);
+
+    // The token doesn't apply to a different binary, which is checked again
+    let mut blob = binary_blob.clone();
+    blob[0xf8..0x100].copy_from_slice(&0x100u64.to_le_bytes());
+    let binary = ElfBinary::new(blob.as_slice()).expect("Got proper ELF file");
+    let mut loader = TestLoader::new(0x1000_0000);
+    assert_eq!(
+        binary.load_validated(&mut loader, &token).err(),
+        Some(ElfLoaderErr::OverlappingSegments {
+            first: 2,
+            second: 3
+        })
+    );
+    assert!(loader.actions.is_empty());
+
+    // Same program headers, but a larger .rela.dyn (sh_size of section 9)
+    let config = LoadConfig {
+        limits: Limits {
+            max_relocations: Some(8),
+            ..Limits::default()
+        },
+        ..LoadConfig::default()
+    };
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+    let token = binary.validate(&config).expect("Valid binary");
+    let mut blob = binary_blob.clone();
+    blob[0x1b90..0x1b98].copy_from_slice(&0xd8u64.to_le_bytes());
+    let binary = ElfBinary::new(blob.as_slice()).expect("Got proper ELF file");
+    let mut loader = TestLoader::new(0x1000_0000);
+    assert_eq!(
+        binary.load_validated(&mut loader, &token).err(),
+        Some(ElfLoaderErr::LimitsExceeded {
+            limit: "max_relocations"
+        })
+    );
+    assert!(loader.actions.is_empty());
+}
+
+#[test]
+fn check_memory_image() {
+    init();
+    let binary_blob = fs::read("test/test.x86_64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+
+    // readelf -l: the second LOAD segment has 8 bytes of .bss
+    let regions: Vec<MemoryRegion> = binary
+        .memory_image(0x1000_0000)
+        .expect("Valid segments")
+        .collect();
+    assert_eq!(regions.len(), 2);
+    assert_eq!(regions[0].segment, 2);
+    assert_eq!(regions[0].vaddr, 0x1000_0000);
+    assert_eq!(
+        regions[0].flags,
+        SegmentPermissions::READ | SegmentPermissions::EXECUTE
+    );
+    assert_eq!(regions[0].data, &binary_blob[..0x888]);
+    assert_eq!(regions[0].zero_size, 0);
+    assert_eq!(regions[1].segment, 3);
+    assert_eq!(regions[1].vaddr, 0x1020_0db8);
+    assert_eq!(regions[1].mem_size, 0x260);
+    assert_eq!(
+        regions[1].flags,
+        SegmentPermissions::READ | SegmentPermissions::WRITE
+    );
+    assert_eq!(regions[1].data, &binary_blob[0xdb8..0x1010]);
+    assert_eq!(regions[1].zero_start, 0x1020_1010);
+    assert_eq!(regions[1].zero_size, 8);
+
+    // p_filesz of the second LOAD segment past the end of the file
+    let mut binary_blob = binary_blob.clone();
+    binary_blob[0x108..0x110].copy_from_slice(&0x10_0000u64.to_le_bytes());
+    let binary = ElfBinary::new_unchecked(binary_blob.as_slice()).expect("Got proper ELF file");
+    assert_eq!(
+        binary.memory_image(0).err(),
+        Some(ElfLoaderErr::SegmentOutOfBounds { index: 3 })
+    );
+}
+
+#[test]
+fn check_relative_base() {
+    init();
+    let binary_blob = fs::read("test/test.x86_64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+
+    let mut loader = TestLoader::new(0x1000_0000);
+    binary.load(&mut loader).expect("Can't load?");
+    let expected = loader.actions;
+
+    let config = LoadConfig {
+        relative_base: Some(0x1000_0000),
+        ..Default::default()
+    };
+    let mut loader = TestLoader::new(0x1000_0000);
+    binary
+        .load_with_config(&mut loader, &config)
+        .expect("Can't load?");
+
+    // The RELATIVE relocations are written with the same values instead of
+    // being passed to `relocate`
+    assert!(loader
+        .actions
+        .iter()
+        .any(|action| matches!(action, LoaderAction::Write(..))));
+    assert!(!loader
+        .actions
+        .iter()
+        .any(|action| matches!(action, LoaderAction::Relocate(..))));
+    let writes: Vec<LoaderAction> = loader
+        .actions
+        .iter()
+        .map(|action| match *action {
+            LoaderAction::Write(addr, value) => LoaderAction::Relocate(addr, value),
+            action => action,
+        })
+        .collect();
+    assert_eq!(writes, expected);
+}
+
+#[test]
+fn check_relocation_segment() {
+    init();
+    let binary_blob = fs::read("test/test.x86_64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+
+    let segment = |offset| {
+        binary
+            .relocations()
+            .map(|entry| entry.expect("Valid relocation"))
+            .find(|entry| entry.offset == offset)
+            .expect("Relocation exists")
+            .segment
+    };
+    let data = RelocationSegment {
+        index: 3,
+        vaddr: 0x200db8,
+        mem_size: 0x260,
+        flags: SegmentPermissions::READ | SegmentPermissions::WRITE,
+        relro: true,
+    };
+    // .init_array is covered by GNU_RELRO, .data is not
+    assert_eq!(segment(0x200db8), Some(data));
+    assert_eq!(
+        segment(0x201008),
+        Some(RelocationSegment {
+            relro: false,
+            ..data
+        })
+    );
+}
+
+#[test]
+fn check_segment_containing() {
+    init();
+    let binary_blob = fs::read("test/test.x86_64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+
+    let vaddr = |addr| binary.segment_containing(addr).map(|p| p.virtual_addr());
+    assert_eq!(vaddr(0x0), Some(0x0));
+    assert_eq!(vaddr(0x887), Some(0x0));
+    assert_eq!(vaddr(0x888), None);
+    assert_eq!(vaddr(0x200db8), Some(0x200db8));
+    // The .bss part of the segment is covered as well
+    assert_eq!(vaddr(0x201017), Some(0x200db8));
+    assert_eq!(vaddr(0x201018), None);
+
+    let segment = binary.segment_containing(0x201000).expect("Mapped");
+    assert_eq!(segment.get_type(), Ok(xmas_elf::program::Type::Load));
+    assert_eq!(segment.flags(), Flags(2 | 4));
+    assert_eq!(segment.offset(), 0xdb8);
+}
+
+#[test]
+fn check_segment_permissions() {
+    init();
+    let binary_blob = fs::read("test/test.x86_64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+
+    let permissions: Vec<SegmentPermissions> = binary
+        .program_headers()
+        .filter(|p| p.get_type() == Ok(xmas_elf::program::Type::Load))
+        .map(|p| p.flags().into())
+        .collect();
+    assert_eq!(
+        permissions,
+        [
+            SegmentPermissions::READ | SegmentPermissions::EXECUTE,
+            SegmentPermissions::READ | SegmentPermissions::WRITE
+        ]
+    );
+    assert_eq!(std::format!("{}", permissions[0]), "r-x");
+    assert_eq!(std::format!("{}", permissions[1]), "rw-");
+    assert!(permissions[0].is_execute() && !permissions[0].is_write());
+
+    // PROT_READ | PROT_EXEC
+    assert_eq!(permissions[0].to_prot(), 0x5);
+    assert_eq!(SegmentPermissions::from_prot(0x3), permissions[1]);
+    // RISC-V page table entry: R = 1 << 1, W = 1 << 2, X = 1 << 3
+    assert_eq!(permissions[0].to_bits(1 << 1, 1 << 2, 1 << 3), 0b1010);
+
+    // OS specific bits of p_flags are dropped
+    assert_eq!(
+        SegmentPermissions::from(Flags(0x0ff0_0000 | 4)),
+        SegmentPermissions::READ
+    );
+    assert_eq!(Flags::from(SegmentPermissions::WRITE).0, 2);
+}
+
+#[test]
+fn check_allocate_requests() {
+    init();
+    let binary_blob = fs::read("test/test.x86_64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+
+    let requests: Vec<AllocateRequest> = binary.iter_loadable_headers().collect();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(
+        requests[1],
+        AllocateRequest {
+            vaddr: 0x200db8,
+            paddr: 0x200db8,
+            mem_size: 0x260,
+            file_size: 0x258,
+            align: 0x200000,
+            flags: SegmentPermissions::READ | SegmentPermissions::WRITE,
+        }
+    );
+}
+
+#[test]
+fn check_program_header_table() {
+    init();
+    let binary_blob = fs::read("test/test.x86_64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+
+    // readelf -h / -l: 9 headers of 56 bytes at offset 64, PHDR at 0x40
+    assert_eq!(binary.program_header_offset(), 0x40);
+    assert_eq!(binary.program_header_entry_size(), 56);
+    assert_eq!(binary.program_header_count(), 9);
+    assert_eq!(binary.program_header_vaddr(), Some(0x40));
+
+    // Shrink p_filesz of the first LOAD segment so it ends in the table
+    let mut binary_blob = binary_blob.clone();
+    binary_blob[0xd0..0xd8].copy_from_slice(&0x100u64.to_le_bytes());
+    let binary = ElfBinary::new_unchecked(binary_blob.as_slice()).expect("Got proper ELF file");
+    assert_eq!(binary.program_header_vaddr(), None);
+}
+
+#[test]
+fn check_segments() {
+    init();
+    let binary_blob = fs::read("test/test.x86_64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+
+    let segments: Vec<Segment> = binary.segments().collect();
+    assert_eq!(segments.len(), 9);
+    let data = segments[3];
+    assert_eq!(data.index(), 3);
+    assert_eq!(data.typ(), Ok(xmas_elf::program::Type::Load));
+    assert_eq!(
+        data.flags(),
+        SegmentPermissions::READ | SegmentPermissions::WRITE
+    );
+    assert_eq!(data.vaddr(), 0x200db8);
+    assert_eq!(data.paddr(), 0x200db8);
+    assert_eq!(data.offset(), 0xdb8);
+    assert_eq!(data.file_size(), 0x258);
+    assert_eq!(data.mem_size(), 0x260);
+    assert_eq!(data.align(), 0x200000);
+    assert_eq!(data.data(), Ok(&binary_blob[0xdb8..0x1010]));
+
+    // p_filesz of the second LOAD segment past the end of the file
+    let mut binary_blob = binary_blob.clone();
+    binary_blob[0x108..0x110].copy_from_slice(&0x10_0000u64.to_le_bytes());
+    let binary = ElfBinary::new_unchecked(binary_blob.as_slice()).expect("Got proper ELF file");
+    let data = binary.segments().nth(3).expect("Has segment");
+    assert_eq!(
+        data.data(),
+        Err(ElfLoaderErr::SegmentOutOfBounds { index: 3 })
+    );
 }
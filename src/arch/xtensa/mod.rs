@@ -0,0 +1,211 @@
+//! Xtensa relocation types
+//!
+//! As defined by the Xtensa port of binutils (`include/elf/xtensa.h`),
+//! there is no separate ABI document.
+
+#[cfg(test)]
+mod test;
+
+/// Machine number of Tensilica Xtensa (not known to xmas-elf).
+pub const EM_XTENSA: u16 = 94;
+
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+#[allow(non_camel_case_types)]
+#[repr(u32)]
+pub enum RelocationTypes {
+    /// No relocation.
+    R_XTENSA_NONE,
+    /// Direct 32 bit.
+    R_XTENSA_32,
+    /// Dynamic linker specific fixup (unused by most loaders).
+    R_XTENSA_RTLD,
+    /// Set GOT entry to data address.
+    R_XTENSA_GLOB_DAT,
+    /// Set GOT entry to code address.
+    R_XTENSA_JMP_SLOT,
+    /// Add load address of shared object.
+    R_XTENSA_RELATIVE,
+    /// 32 bit PLT address.
+    R_XTENSA_PLT,
+    /// Operand 0 of an instruction (obsolete).
+    R_XTENSA_OP0,
+    /// Operand 1 of an instruction (obsolete).
+    R_XTENSA_OP1,
+    /// Operand 2 of an instruction (obsolete).
+    R_XTENSA_OP2,
+    /// Assembler macro that may be expanded by the linker.
+    R_XTENSA_ASM_EXPAND,
+    /// Assembler macro that may be simplified by the linker.
+    R_XTENSA_ASM_SIMPLIFY,
+    /// 32 bit PC relative.
+    R_XTENSA_32_PCREL,
+    /// GNU C++ vtable hierarchy.
+    R_XTENSA_GNU_VTINHERIT,
+    /// GNU C++ vtable member usage.
+    R_XTENSA_GNU_VTENTRY,
+    /// 8 bit difference of two symbols.
+    R_XTENSA_DIFF8,
+    /// 16 bit difference of two symbols.
+    R_XTENSA_DIFF16,
+    /// 32 bit difference of two symbols.
+    R_XTENSA_DIFF32,
+    /// PC relative or absolute operand in slot 0 of an instruction bundle.
+    R_XTENSA_SLOT0_OP,
+    /// PC relative or absolute operand in slot 1 of an instruction bundle.
+    R_XTENSA_SLOT1_OP,
+    /// PC relative or absolute operand in slot 2 of an instruction bundle.
+    R_XTENSA_SLOT2_OP,
+    /// PC relative or absolute operand in slot 3 of an instruction bundle.
+    R_XTENSA_SLOT3_OP,
+    /// PC relative or absolute operand in slot 4 of an instruction bundle.
+    R_XTENSA_SLOT4_OP,
+    /// PC relative or absolute operand in slot 5 of an instruction bundle.
+    R_XTENSA_SLOT5_OP,
+    /// PC relative or absolute operand in slot 6 of an instruction bundle.
+    R_XTENSA_SLOT6_OP,
+    /// PC relative or absolute operand in slot 7 of an instruction bundle.
+    R_XTENSA_SLOT7_OP,
+    /// PC relative or absolute operand in slot 8 of an instruction bundle.
+    R_XTENSA_SLOT8_OP,
+    /// PC relative or absolute operand in slot 9 of an instruction bundle.
+    R_XTENSA_SLOT9_OP,
+    /// PC relative or absolute operand in slot 10 of an instruction bundle.
+    R_XTENSA_SLOT10_OP,
+    /// PC relative or absolute operand in slot 11 of an instruction bundle.
+    R_XTENSA_SLOT11_OP,
+    /// PC relative or absolute operand in slot 12 of an instruction bundle.
+    R_XTENSA_SLOT12_OP,
+    /// PC relative or absolute operand in slot 13 of an instruction bundle.
+    R_XTENSA_SLOT13_OP,
+    /// PC relative or absolute operand in slot 14 of an instruction bundle.
+    R_XTENSA_SLOT14_OP,
+    /// Alternate operand in slot 0 (e.g. the high part of a `const16` pair).
+    R_XTENSA_SLOT0_ALT,
+    /// Alternate operand in slot 1 (e.g. the high part of a `const16` pair).
+    R_XTENSA_SLOT1_ALT,
+    /// Alternate operand in slot 2 (e.g. the high part of a `const16` pair).
+    R_XTENSA_SLOT2_ALT,
+    /// Alternate operand in slot 3 (e.g. the high part of a `const16` pair).
+    R_XTENSA_SLOT3_ALT,
+    /// Alternate operand in slot 4 (e.g. the high part of a `const16` pair).
+    R_XTENSA_SLOT4_ALT,
+    /// Alternate operand in slot 5 (e.g. the high part of a `const16` pair).
+    R_XTENSA_SLOT5_ALT,
+    /// Alternate operand in slot 6 (e.g. the high part of a `const16` pair).
+    R_XTENSA_SLOT6_ALT,
+    /// Alternate operand in slot 7 (e.g. the high part of a `const16` pair).
+    R_XTENSA_SLOT7_ALT,
+    /// Alternate operand in slot 8 (e.g. the high part of a `const16` pair).
+    R_XTENSA_SLOT8_ALT,
+    /// Alternate operand in slot 9 (e.g. the high part of a `const16` pair).
+    R_XTENSA_SLOT9_ALT,
+    /// Alternate operand in slot 10 (e.g. the high part of a `const16` pair).
+    R_XTENSA_SLOT10_ALT,
+    /// Alternate operand in slot 11 (e.g. the high part of a `const16` pair).
+    R_XTENSA_SLOT11_ALT,
+    /// Alternate operand in slot 12 (e.g. the high part of a `const16` pair).
+    R_XTENSA_SLOT12_ALT,
+    /// Alternate operand in slot 13 (e.g. the high part of a `const16` pair).
+    R_XTENSA_SLOT13_ALT,
+    /// Alternate operand in slot 14 (e.g. the high part of a `const16` pair).
+    R_XTENSA_SLOT14_ALT,
+    /// TLS descriptor function.
+    R_XTENSA_TLSDESC_FN,
+    /// TLS descriptor argument.
+    R_XTENSA_TLSDESC_ARG,
+    /// Offset in TLS block.
+    R_XTENSA_TLS_DTPOFF,
+    /// Offset in static TLS block.
+    R_XTENSA_TLS_TPOFF,
+    /// Marks the load of the TLS descriptor function.
+    R_XTENSA_TLS_FUNC,
+    /// Marks the load of the TLS descriptor argument.
+    R_XTENSA_TLS_ARG,
+    /// Marks the call of the TLS descriptor function.
+    R_XTENSA_TLS_CALL,
+    /// 8 bit unsigned difference of two symbols.
+    R_XTENSA_PDIFF8,
+    /// 16 bit unsigned difference of two symbols.
+    R_XTENSA_PDIFF16,
+    /// 32 bit unsigned difference of two symbols.
+    R_XTENSA_PDIFF32,
+    /// 8 bit negated difference of two symbols.
+    R_XTENSA_NDIFF8,
+    /// 16 bit negated difference of two symbols.
+    R_XTENSA_NDIFF16,
+    /// 32 bit negated difference of two symbols.
+    R_XTENSA_NDIFF32,
+    /// Unknown
+    Unknown(u32),
+}
+
+impl RelocationTypes {
+    /// Construct new xtensa::RelocationTypes
+    pub fn from(typ: u32) -> RelocationTypes {
+        use RelocationTypes::*;
+        match typ {
+            0 => R_XTENSA_NONE,
+            1 => R_XTENSA_32,
+            2 => R_XTENSA_RTLD,
+            3 => R_XTENSA_GLOB_DAT,
+            4 => R_XTENSA_JMP_SLOT,
+            5 => R_XTENSA_RELATIVE,
+            6 => R_XTENSA_PLT,
+            8 => R_XTENSA_OP0,
+            9 => R_XTENSA_OP1,
+            10 => R_XTENSA_OP2,
+            11 => R_XTENSA_ASM_EXPAND,
+            12 => R_XTENSA_ASM_SIMPLIFY,
+            14 => R_XTENSA_32_PCREL,
+            15 => R_XTENSA_GNU_VTINHERIT,
+            16 => R_XTENSA_GNU_VTENTRY,
+            17 => R_XTENSA_DIFF8,
+            18 => R_XTENSA_DIFF16,
+            19 => R_XTENSA_DIFF32,
+            20 => R_XTENSA_SLOT0_OP,
+            21 => R_XTENSA_SLOT1_OP,
+            22 => R_XTENSA_SLOT2_OP,
+            23 => R_XTENSA_SLOT3_OP,
+            24 => R_XTENSA_SLOT4_OP,
+            25 => R_XTENSA_SLOT5_OP,
+            26 => R_XTENSA_SLOT6_OP,
+            27 => R_XTENSA_SLOT7_OP,
+            28 => R_XTENSA_SLOT8_OP,
+            29 => R_XTENSA_SLOT9_OP,
+            30 => R_XTENSA_SLOT10_OP,
+            31 => R_XTENSA_SLOT11_OP,
+            32 => R_XTENSA_SLOT12_OP,
+            33 => R_XTENSA_SLOT13_OP,
+            34 => R_XTENSA_SLOT14_OP,
+            35 => R_XTENSA_SLOT0_ALT,
+            36 => R_XTENSA_SLOT1_ALT,
+            37 => R_XTENSA_SLOT2_ALT,
+            38 => R_XTENSA_SLOT3_ALT,
+            39 => R_XTENSA_SLOT4_ALT,
+            40 => R_XTENSA_SLOT5_ALT,
+            41 => R_XTENSA_SLOT6_ALT,
+            42 => R_XTENSA_SLOT7_ALT,
+            43 => R_XTENSA_SLOT8_ALT,
+            44 => R_XTENSA_SLOT9_ALT,
+            45 => R_XTENSA_SLOT10_ALT,
+            46 => R_XTENSA_SLOT11_ALT,
+            47 => R_XTENSA_SLOT12_ALT,
+            48 => R_XTENSA_SLOT13_ALT,
+            49 => R_XTENSA_SLOT14_ALT,
+            50 => R_XTENSA_TLSDESC_FN,
+            51 => R_XTENSA_TLSDESC_ARG,
+            52 => R_XTENSA_TLS_DTPOFF,
+            53 => R_XTENSA_TLS_TPOFF,
+            54 => R_XTENSA_TLS_FUNC,
+            55 => R_XTENSA_TLS_ARG,
+            56 => R_XTENSA_TLS_CALL,
+            57 => R_XTENSA_PDIFF8,
+            58 => R_XTENSA_PDIFF16,
+            59 => R_XTENSA_PDIFF32,
+            60 => R_XTENSA_NDIFF8,
+            61 => R_XTENSA_NDIFF16,
+            62 => R_XTENSA_NDIFF32,
+            x => Unknown(x),
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use crate::arch::xtensa::RelocationTypes::{self, *};
+use crate::arch::xtensa::EM_XTENSA;
+use crate::arch::RelocationType;
+use crate::Machine;
+
+#[test]
+fn check_relocation_types() {
+    assert_eq!(RelocationTypes::from(0), R_XTENSA_NONE);
+    assert_eq!(RelocationTypes::from(1), R_XTENSA_32);
+    assert_eq!(RelocationTypes::from(3), R_XTENSA_GLOB_DAT);
+    assert_eq!(RelocationTypes::from(4), R_XTENSA_JMP_SLOT);
+    assert_eq!(RelocationTypes::from(5), R_XTENSA_RELATIVE);
+    assert_eq!(RelocationTypes::from(13), Unknown(13));
+    assert_eq!(RelocationTypes::from(14), R_XTENSA_32_PCREL);
+    assert_eq!(RelocationTypes::from(20), R_XTENSA_SLOT0_OP);
+    assert_eq!(RelocationTypes::from(35), R_XTENSA_SLOT0_ALT);
+    assert_eq!(RelocationTypes::from(50), R_XTENSA_TLSDESC_FN);
+    assert_eq!(RelocationTypes::from(52), R_XTENSA_TLS_DTPOFF);
+    assert_eq!(RelocationTypes::from(53), R_XTENSA_TLS_TPOFF);
+    assert_eq!(RelocationTypes::from(7), Unknown(7));
+    assert_eq!(RelocationTypes::from(63), Unknown(63));
+
+    assert!(matches!(
+        RelocationType::from(Machine::Other(EM_XTENSA), 5),
+        Ok(RelocationType::Xtensa(R_XTENSA_RELATIVE))
+    ));
+}
@@ -1,26 +1,61 @@
+use crate::addr32::{ElfLoader32, Loader32};
+use crate::arch::{aarch64, mips, riscv};
+use crate::debuglink::crc32;
+use crate::dwarf::DebugSections;
+use crate::elf;
+use crate::note::{GnuAbiTag, GnuProperties, Note, Notes, GNU_OWNER, NT_GNU_BUILD_ID};
+use crate::once::Once;
+use crate::openbsd;
+use crate::segments::{LoadSegment, Segment, SegmentCache};
 use crate::{
-    DynamicFlags1, DynamicInfo, ElfLoader, ElfLoaderErr, LoadableHeaders, RelocationEntry,
-    RelocationType,
+    AllocateRequests, BinaryKind, CoalescedAllocation, DebugLink, Disposition, DynamicFlags,
+    DynamicFlags1, DynamicInfo, ElfLoader, ElfLoaderErr, ElfSummary, FunctionRange, GotFixup,
+    GotFixupKind, JumpSlotFixup, LazyBindingAbi, LazyBindingSlots, Limits, LoadConfig,
+    LoadProgress, LoadStats, Machine, Mappings, MemoryRegion, PltSelector, RelocationCounts,
+    RelocationEntry, RelocationSegment, Relocations, RelroSegment, ScatterLoad, Section,
+    SectionFlags, SegmentPermissions, ShType, SliceLoader, StringTable, Symbol, SymbolTableKind,
+    Symbols, TlsTemplate, VAddr, ValidationToken,
 };
+use core::convert::TryFrom;
 use core::fmt;
 #[cfg(log)]
 use log::*;
 use xmas_elf::dynamic::Tag;
 use xmas_elf::program::ProgramHeader::{self, Ph32, Ph64};
-use xmas_elf::program::{ProgramIter, SegmentData, Type};
+use xmas_elf::program::{ProgramIter, Type};
 use xmas_elf::sections::SectionData;
-pub use xmas_elf::symbol_table::{Entry, Entry64};
+pub use xmas_elf::symbol_table::Entry;
 use xmas_elf::ElfFile;
 use xmas_elf::*;
 
 /// Abstract representation of a loadable ELF binary.
+///
+/// An `ElfBinary` only borrows the file contents and is never modified
+/// after parsing: it is `Send + Sync` and all methods take `&self`, so a
+/// binary can be parsed once and loaded into several address spaces
+/// concurrently, with one [`ElfLoader`] per load.
 pub struct ElfBinary<'s> {
     /// The ELF file in question.
     pub file: ElfFile<'s>,
-    /// Parsed information from the .dynamic section (if the binary has it).
-    pub dynamic: Option<DynamicInfo>,
+    /// Parsed information from the .dynamic section, see [`ElfBinary::try_dynamic`].
+    dynamic: Once<Result<Option<DynamicInfo>, ElfLoaderErr>>,
+    /// The file header, decoded by the in-crate parser.
+    pub(crate) header: elf::FileHeader,
+    /// The decoded LOAD, DYNAMIC and INTERP program headers.
+    pub(crate) segments: SegmentCache,
 }
 
+// Keep the guarantee documented on `ElfBinary` (and for the iterators that
+// borrow it) from being broken by accident.
+const _: () = {
+    const fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<ElfBinary<'static>>();
+    assert_send_sync::<Relocations<'static, 'static>>();
+    assert_send_sync::<Mappings<'static, 'static>>();
+    assert_send_sync::<DebugSections<'static, 'static>>();
+    assert_send_sync::<Notes<'static>>();
+};
+
 impl<'s> fmt::Debug for ElfBinary<'s> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "ElfBinary{{ [")?;
@@ -33,58 +68,392 @@ impl<'s> fmt::Debug for ElfBinary<'s> {
 
 impl<'s> ElfBinary<'s> {
     /// Create a new ElfBinary.
+    ///
+    /// Only the ELF header, program header table and section header table
+    /// are decoded here, the dynamic segment is parsed the first time it is
+    /// needed (see [`ElfBinary::try_dynamic`]). A truncated or otherwise
+    /// malformed file, program header table or section header table results
+    /// in [`ElfLoaderErr::Parse`].
     pub fn new(region: &'s [u8]) -> Result<ElfBinary<'s>, ElfLoaderErr> {
-        let file = ElfFile::new(region)?;
-
-        // Parse relevant parts out of the the .dynamic section
-        let mut dynamic = None;
-        for p in file.program_iter() {
-            let typ = match p {
-                Ph64(header) => header.get_type()?,
-                Ph32(header) => header.get_type()?,
-            };
+        let header = elf::FileHeader::parse(region)?;
+        let segments = SegmentCache::new(header.program_headers(region)?);
+        check_structure(&header, region)?;
+        Ok(ElfBinary {
+            file: ElfFile::new(region)?,
+            dynamic: Once::new(),
+            header,
+            segments,
+        })
+    }
+
+    /// Like [`ElfBinary::new`], but without checking the section header
+    /// table, for images that were accepted by [`ElfBinary::new`] before
+    /// (e.g., when spawning many processes from the same binary).
+    ///
+    /// The ELF header and the program header table are still decoded.
+    ///
+    /// # Panics
+    /// The section based accessors may panic instead of returning an error
+    /// if the section header table of `region` is malformed.
+    pub fn new_unchecked(region: &'s [u8]) -> Result<ElfBinary<'s>, ElfLoaderErr> {
+        let header = elf::FileHeader::parse(region)?;
+        let segments = SegmentCache::new(header.program_headers(region)?);
+        Ok(ElfBinary {
+            file: ElfFile::new(region)?,
+            dynamic: Once::new(),
+            header,
+            segments,
+        })
+    }
+
+    /// Parsed information from the dynamic segment, or None if the binary
+    /// has no dynamic segment or it is malformed.
+    pub fn dynamic(&self) -> Option<&DynamicInfo> {
+        self.try_dynamic().ok().flatten()
+    }
+
+    /// Parsed information from the dynamic segment (if the binary has one).
+    ///
+    /// The segment is parsed on the first call and the result is cached.
+    /// Loading the binary fails with the same error if the segment is
+    /// malformed.
+    pub fn try_dynamic(&self) -> Result<Option<&DynamicInfo>, ElfLoaderErr> {
+        let dynamic = self.dynamic.get_or_init(|| {
+            // Parse relevant parts out of the .dynamic section
+            match self.segments.dynamic {
+                Some(index) => {
+                    let p = self.file.program_header(index as u16)?;
+                    let segment = ElfBinary::segment_data(&self.file, index, &p)?;
+                    self.parse_dynamic(segment, p.virtual_addr())
+                }
+                None => Ok(None),
+            }
+        });
+        dynamic.as_ref().map(Option::as_ref).map_err(Clone::clone)
+    }
+
+    /// Returns true if the binary is a position independent executable or false otherwise.
+    ///
+    /// See [`ElfBinary::kind`] for how PIEs are told apart from shared libraries.
+    pub fn is_pie(&self) -> bool {
+        self.kind() == Some(BinaryKind::Pie)
+    }
 
-            if typ == Type::Dynamic {
-                dynamic = ElfBinary::parse_dynamic(&file, &p)?;
-                break;
+    /// Classify the binary as executable, PIE or shared library.
+    ///
+    /// Both PIEs and shared libraries are ET_DYN. A binary is considered a
+    /// PIE if it has DF_1_PIE set in DT_FLAGS_1, if it requests an
+    /// interpreter (PT_INTERP), or if it has an entry point but no
+    /// DT_SONAME. Returns None for other ELF types (e.g., relocatable
+    /// objects).
+    pub fn kind(&self) -> Option<BinaryKind> {
+        match self.file.header.pt2.type_().as_type() {
+            header::Type::Executable => Some(BinaryKind::Executable),
+            header::Type::SharedObject => {
+                let dynamic = self.dynamic();
+                let pie_flag =
+                    dynamic.is_some_and(|d: &DynamicInfo| d.flags1.contains(DynamicFlags1::PIE));
+                let soname = dynamic.is_some_and(|d| d.soname.is_some());
+                if pie_flag || self.has_interp_segment() || (!soname && self.entry_point() != 0) {
+                    Some(BinaryKind::Pie)
+                } else {
+                    Some(BinaryKind::SharedLibrary)
+                }
             }
+            _ => None,
         }
+    }
+
+    /// Does the binary have a PT_INTERP segment?
+    fn has_interp_segment(&self) -> bool {
+        self.segments.interp.is_some()
+    }
+
+    /// Returns true if the binary has relocations against non-writable
+    /// segments (DT_TEXTREL or DF_TEXTREL is present).
+    pub fn has_textrel(&self) -> bool {
+        self.dynamic()
+            .is_some_and(|d: &DynamicInfo| d.textrel || d.flags.contains(DynamicFlags::TEXTREL))
+    }
 
-        Ok(ElfBinary { file, dynamic })
+    /// Returns the address of the PLT GOT (DT_PLTGOT) if present.
+    pub fn pltgot(&self) -> Option<VAddr> {
+        self.dynamic().and_then(|d| d.pltgot)
     }
 
-    /// Returns true if the binary is compiled as position independent code or false otherwise.
+    /// Returns the GOT layout of a MIPS binary.
     ///
-    /// For the binary to be PIE it needs to have a .dynamic section with PIE set in the flags1
-    /// field.
-    pub fn is_pie(&self) -> bool {
-        self.dynamic.as_ref().map_or(false, |d: &DynamicInfo| {
-            d.flags1.contains(DynamicFlags1::PIE)
+    /// Returns None for binaries without DT_PLTGOT and the DT_MIPS_* GOT
+    /// entries (e.g. non-MIPS binaries).
+    pub fn mips_got(&self) -> Option<mips::GotInfo> {
+        let dynamic = self.dynamic()?;
+        Some(mips::GotInfo {
+            address: dynamic.pltgot?,
+            local_gotno: dynamic.mips_local_gotno?,
+            gotsym: dynamic.mips_gotsym?,
+            symtabno: dynamic.mips_symtabno?,
+        })
+    }
+
+    /// Returns the reserved PLT GOT slots the loader has to fill with its
+    /// object handle and resolver stub to implement lazy binding.
+    ///
+    /// The addresses are not rebased. Returns `Ok(None)` in case the binary
+    /// has no DT_PLTGOT entry.
+    pub fn lazy_binding_slots(&self) -> Result<Option<LazyBindingSlots>, ElfLoaderErr> {
+        let word_size = match self.file.header.pt1.class() {
+            header::Class::ThirtyTwo => 4,
+            header::Class::SixtyFour => 8,
+            _ => return Err(ElfLoaderErr::UnsupportedElfFormat),
+        };
+        self.pltgot()
+            .map(|pltgot| LazyBindingSlots::new(self.get_arch(), pltgot, word_size))
+            .transpose()
+    }
+
+    /// Returns how the PLT stubs of the binary pass the entry to bind to the
+    /// lazy resolver.
+    pub fn lazy_binding_abi(&self) -> Result<LazyBindingAbi, ElfLoaderErr> {
+        LazyBindingAbi::new(self.get_arch())
+    }
+
+    /// Binds the PLT entry identified by `selector` for a binary loaded at
+    /// `base`, to be called from the lazy resolver.
+    ///
+    /// `selector` is the value PLT0 passed to the resolver (see
+    /// [`ElfBinary::lazy_binding_abi`]). `resolve` receives the JUMP_SLOT
+    /// relocation of the entry and returns the address of the function,
+    /// which the returned fixup stores in the GOT slot.
+    pub fn bind_lazy<F>(
+        &self,
+        base: VAddr,
+        selector: u64,
+        resolve: F,
+    ) -> Result<JumpSlotFixup, ElfLoaderErr>
+    where
+        F: FnOnce(&RelocationEntry) -> Result<u64, ElfLoaderErr>,
+    {
+        let abi = self.lazy_binding_abi()?;
+        let word_size = self.word_size();
+        let (mut relocations, entry_size) = Relocations::plt(self)?;
+        let invalid = ElfLoaderErr::InvalidPltSelector { selector };
+
+        let index = match abi.selector_kind {
+            PltSelector::Index => Some(selector),
+            PltSelector::JmprelOffset if entry_size != 0 => Some(selector)
+                .filter(|offset| offset.is_multiple_of(entry_size))
+                .map(|offset| offset / entry_size),
+            PltSelector::GotSlot { reserved } => self
+                .pltgot()
+                .and_then(|pltgot| base.checked_add(pltgot)?.checked_add(reserved * word_size))
+                .and_then(|first| selector.checked_sub(first))
+                .filter(|offset| offset.is_multiple_of(word_size))
+                .map(|offset| offset / word_size),
+            PltSelector::GotOffset => Some(selector)
+                .filter(|offset| offset.is_multiple_of(word_size))
+                .map(|offset| offset / word_size),
+            PltSelector::JmprelOffset => None,
+        };
+        let entry = index
+            .and_then(|index| usize::try_from(index).ok())
+            .and_then(|index| relocations.nth(index))
+            .ok_or(invalid)??;
+        if !entry.rtype.is_jump_slot() {
+            return Err(ElfLoaderErr::UnsupportedRelocationEntry);
+        }
+
+        Ok(JumpSlotFixup {
+            address: base
+                .checked_add(entry.offset)
+                .ok_or(ElfLoaderErr::AddressOverflow {
+                    source: "JUMP_SLOT offset",
+                })?,
+            value: resolve(&entry)?,
+            size: word_size,
         })
     }
 
+    /// Returns the address of the DT_DEBUG slot for a binary loaded at `base`.
+    ///
+    /// A dynamic linker writes the address of its `r_debug` structure (see
+    /// [`crate::debug::RDebug`]) into this slot so debuggers can find the list
+    /// of loaded objects. Returns None in case the binary has no DT_DEBUG entry.
+    pub fn debug_slot(&self, base: VAddr) -> Option<VAddr> {
+        self.dynamic()
+            .and_then(|d| d.debug)
+            .and_then(|slot| base.checked_add(slot))
+    }
+
     /// Returns the dynamic loader if present.
     ///
-    /// readelf -x .interp <binary>
+    /// readelf -l <binary>
+    ///
+    /// The path is read from the PT_INTERP segment, or from the `.interp`
+    /// section if the binary has no such segment. It ends at the first NUL
+    /// byte (or the end of the segment if the terminator is missing).
     ///
     /// For a statically compiled binary this will return None
     pub fn interpreter(&'s self) -> Option<&'s str> {
-        let section = self.file.find_section_by_name(".interp");
-        section.and_then(|interp_section| {
-            let data = interp_section.get_data(&self.file).ok()?;
-            let cstr = match data {
-                SectionData::Undefined(val) => val,
-                _ => return None,
-            };
-
-            // Validate there is room for a null terminator
-            if cstr.len() < 2 {
-                return None;
+        let data = self.segment_data_at(self.segments.interp).or_else(|| {
+            let section = self.file.find_section_by_name(".interp")?;
+            match section.get_data(&self.file).ok()? {
+                SectionData::Undefined(val) => Some(val),
+                _ => None,
             }
+        })?;
 
-            // Ensure it is a valid utf8 string
-            core::str::from_utf8(&cstr[..cstr.len() - 1]).ok()
-        })
+        let path = data.split(|&b| b == 0).next().unwrap_or(&[]);
+        if path.is_empty() {
+            return None;
+        }
+
+        // Ensure it is a valid utf8 string
+        core::str::from_utf8(path).ok()
+    }
+
+    /// Iterate over the notes of all PT_NOTE segments.
+    pub fn notes(&self) -> impl Iterator<Item = Note<'s>> + '_ {
+        let input = self.file.input;
+        let encoding = self.header.encoding;
+        self.header
+            .program_headers(input)
+            .into_iter()
+            .flatten()
+            .filter(|p| p.typ == elf::PT_NOTE)
+            .filter_map(move |p| Some(Notes::new(p.data(input).ok()?, p.align, encoding)))
+            .flatten()
+    }
+
+    /// Returns the OS and minimum kernel version from the GNU ABI tag note
+    /// (`.note.ABI-tag`) if present.
+    pub fn gnu_abi_tag(&self) -> Option<GnuAbiTag> {
+        self.notes()
+            .find_map(|note| GnuAbiTag::parse(&note, self.header.encoding))
+    }
+
+    /// Returns the build ID from the GNU build ID note
+    /// (`.note.gnu.build-id`) if present.
+    pub fn build_id(&self) -> Option<&'s [u8]> {
+        self.notes()
+            .find(|note| note.name == GNU_OWNER.as_bytes() && note.typ == NT_GNU_BUILD_ID)
+            .map(|note| note.desc)
+    }
+
+    /// Returns the AArch64 properties of the GNU property note
+    /// (`.note.gnu.property`): whether the binary supports BTI, PAC and GCS
+    /// and its pointer authentication ABI.
+    ///
+    /// Returns None for other architectures and binaries without the note.
+    pub fn aarch64_properties(&self) -> Option<aarch64::Properties> {
+        if self.get_arch() != Machine::AArch64 {
+            return None;
+        }
+        let encoding = self.header.encoding;
+        let note = self
+            .notes()
+            .find(|note| GnuProperties::new(note, encoding).is_some())?;
+        GnuProperties::new(&note, encoding)
+            .map(|properties| aarch64::Properties::parse(properties, encoding))
+    }
+
+    /// The PT_AARCH64_MEMTAG_MTE segments of an AArch64 binary, the ranges
+    /// which have to be mapped with MTE tagged memory.
+    pub fn aarch64_memtag_segments(&self) -> impl Iterator<Item = aarch64::MemtagSegment> + '_ {
+        let is_aarch64 = self.get_arch() == Machine::AArch64;
+        self.file
+            .program_iter()
+            .filter(move |header| {
+                is_aarch64
+                    && header.get_type()
+                        == Ok(Type::ProcessorSpecific(aarch64::PT_AARCH64_MEMTAG_MTE))
+            })
+            .map(|header| aarch64::MemtagSegment {
+                vaddr: header.virtual_addr(),
+                mem_size: header.mem_size(),
+            })
+    }
+
+    /// Checks that the build ID of the binary is `expected`, e.g., before
+    /// loading an image that was deployed for a known build.
+    ///
+    /// A binary without build ID doesn't match any expected value.
+    pub fn check_build_id(&self, expected: &[u8]) -> Result<(), ElfLoaderErr> {
+        match self.build_id() {
+            Some(id) if id == expected => Ok(()),
+            _ => Err(ElfLoaderErr::BuildIdMismatch),
+        }
+    }
+
+    /// Returns the DT_RPATH library search path if present.
+    ///
+    /// readelf -d <binary>
+    ///
+    /// The returned string is unexpanded, see [`crate::runpath`] for helpers
+    /// to split it and substitute `$ORIGIN`/`$LIB`.
+    pub fn rpath(&self) -> Option<&'s str> {
+        self.dynamic()
+            .and_then(|d| d.rpath)
+            .and_then(|offset| self.dynamic_string(offset))
+    }
+
+    /// Returns the DT_RUNPATH library search path if present.
+    ///
+    /// readelf -d <binary>
+    ///
+    /// The returned string is unexpanded, see [`crate::runpath`] for helpers
+    /// to split it and substitute `$ORIGIN`/`$LIB`.
+    pub fn runpath(&self) -> Option<&'s str> {
+        self.dynamic()
+            .and_then(|d| d.runpath)
+            .and_then(|offset| self.dynamic_string(offset))
+    }
+
+    /// Reads the NUL terminated string at `offset` in the dynamic string
+    /// table.
+    fn dynamic_string(&self, offset: u64) -> Option<&'s str> {
+        self.dynstr()?.get(offset)
+    }
+
+    /// Returns the symbol string table (`.strtab`) if present.
+    pub fn strtab(&self) -> Option<StringTable<'s>> {
+        self.find_section_data(".strtab").map(StringTable::new)
+    }
+
+    /// Returns the dynamic string table if present: the DT_STRTAB range of
+    /// the dynamic segment, or the `.dynstr` section for binaries without
+    /// one. The former also works for binaries without section headers.
+    pub fn dynstr(&self) -> Option<StringTable<'s>> {
+        self.dynamic()
+            .and_then(|d| self.virtual_data(d.strtab?, d.strtab_size))
+            .or_else(|| self.find_section_data(".dynstr"))
+            .map(StringTable::new)
+    }
+
+    /// Returns the section name string table (`.shstrtab`) if present.
+    ///
+    /// This is located through the `e_shstrndx` field of the ELF header.
+    pub fn shstrtab(&self) -> Option<StringTable<'s>> {
+        // xmas-elf doesn't check the index against the section count
+        let index = self.header.shstrndx;
+        if index >= self.header.shnum {
+            return None;
+        }
+        let section = self.file.section_header(index).ok()?;
+        if section.get_type().ok()? != ShType::StrTab {
+            return None;
+        }
+        let start = section.offset() as usize;
+        let end = start.checked_add(section.size() as usize)?;
+        self.file.input.get(start..end).map(StringTable::new)
+    }
+
+    /// Returns the ABI version (`EI_ABIVERSION`) of the binary.
+    ///
+    /// Its meaning depends on the OS ABI: for glibc binaries (System V or
+    /// Linux) it is raised when the binary relies on newer dynamic linker
+    /// features, e.g., to 1 for binaries using IFUNCs or unique symbols.
+    pub fn abi_version(&self) -> u8 {
+        self.header.abi_version
     }
 
     /// Returns the target architecture
@@ -92,6 +461,28 @@ impl<'s> ElfBinary<'s> {
         self.file.header.pt2.machine().as_machine()
     }
 
+    /// Number of bytes [`ElfBinary::probe`] needs: the size of an ELF64
+    /// header (an ELF32 header is smaller).
+    pub const PROBE_SIZE: usize = 64;
+
+    /// Identifies a binary from the start of the file, without parsing (or
+    /// needing) the rest of it.
+    ///
+    /// `prefix` has to hold the ELF header, i.e. [`ElfBinary::PROBE_SIZE`]
+    /// bytes suffice. Fails with [`ElfLoaderErr::Parse`] if `prefix` is too
+    /// short or doesn't start with a valid ELF identification.
+    pub fn probe(prefix: &[u8]) -> Result<ElfSummary, ElfLoaderErr> {
+        let header = elf::FileHeader::parse(prefix)?;
+        Ok(ElfSummary {
+            class: header.encoding.class,
+            endianness: header.encoding.endianness,
+            machine: header.machine,
+            typ: header.typ,
+            entry: header.entry,
+            phnum: header.phnum,
+        })
+    }
+
     /// Return the entry point of the ELF file.
     ///
     /// Note this may be zero in case of position independent executables.
@@ -99,24 +490,391 @@ impl<'s> ElfBinary<'s> {
         self.file.header.pt2.entry_point()
     }
 
+    /// Return the entry point of the binary loaded at `base`.
+    ///
+    /// Position independent binaries (ET_DYN) are moved to `base`,
+    /// executables (ET_EXEC) keep their link-time entry point.
+    pub fn entry_point_rebased(&self, base: VAddr) -> Result<VAddr, ElfLoaderErr> {
+        self.load_bias(base)
+            .checked_add(self.entry_point())
+            .ok_or(ElfLoaderErr::AddressOverflow { source: "e_entry" })
+    }
+
+    /// Returns the address of `symbol` in the binary loaded at `base`.
+    ///
+    /// Absolute symbols (SHN_ABS) are not moved. Returns None for undefined
+    /// symbols and TLS symbols (whose value is an offset in the TLS block).
+    pub fn symbol_address_rebased(&self, symbol: &dyn Entry, base: VAddr) -> Option<VAddr> {
+        match symbol.shndx() {
+            sections::SHN_UNDEF => None,
+            sections::SHN_ABS => Some(symbol.value()),
+            _ if symbol.get_type() == Ok(symbol_table::Type::Tls) => None,
+            _ => self.load_bias(base).checked_add(symbol.value()),
+        }
+    }
+
     /// Create a slice of the program headers.
-    pub fn program_headers(&self) -> ProgramIter {
+    pub fn program_headers(&self) -> ProgramIter<'_, 's> {
         self.file.program_iter()
     }
 
+    /// Iterate over the program headers, see [`Segment`].
+    pub fn segments(&self) -> impl Iterator<Item = Segment<'s>> + '_ {
+        let input = self.file.input;
+        self.file
+            .program_iter()
+            .enumerate()
+            .map(move |(index, header)| Segment::new(index, header, input))
+    }
+
+    /// File offset of the program header table (`e_phoff`).
+    pub fn program_header_offset(&self) -> u64 {
+        self.header.phoff
+    }
+
+    /// Size of a program header table entry (`e_phentsize`), e.g., for
+    /// AT_PHENT of the aux vector.
+    pub fn program_header_entry_size(&self) -> u16 {
+        self.header.phentsize
+    }
+
+    /// Number of program headers (`e_phnum`), e.g., for AT_PHNUM of the aux
+    /// vector.
+    pub fn program_header_count(&self) -> u16 {
+        self.header.phnum
+    }
+
+    /// The (unrelocated) address the program header table is mapped at,
+    /// e.g., for AT_PHDR of the aux vector.
+    ///
+    /// Returns None unless the whole table lies in the file-backed part of
+    /// a LOAD segment.
+    pub fn program_header_vaddr(&self) -> Option<VAddr> {
+        let start = self.header.phoff;
+        let size = u64::from(self.header.phentsize) * u64::from(self.header.phnum);
+        let end = start.checked_add(size)?;
+        self.load_segments().find_map(|segment| {
+            let segment_end = segment.offset.checked_add(segment.file_size)?;
+            (start >= segment.offset && end <= segment_end)
+                .then(|| segment.vaddr.wrapping_add(start - segment.offset))
+        })
+    }
+
+    /// The LOAD segment whose memory range covers the (unrelocated) address
+    /// `vaddr`, None if `vaddr` is not mapped.
+    ///
+    /// Only LOAD segments are considered; other segments (TLS, GNU_RELRO,
+    /// DYNAMIC etc.) lie within the LOAD segments anyways.
+    pub fn segment_containing(&self, vaddr: VAddr) -> Option<ProgramHeader<'s>> {
+        let segment = self
+            .load_segments()
+            .find(|segment| segment.contains(vaddr))?;
+        self.file
+            .program_header(u16::try_from(segment.index).ok()?)
+            .ok()
+    }
+
+    /// Describe the LOAD, TLS and GNU_RELRO segments as they would be mapped
+    /// at `base` (address range, permissions, file offset and the sections
+    /// they contain). Use the `Display` implementation to print the report.
+    pub fn mappings(&self, base: VAddr) -> Mappings<'_, 's> {
+        Mappings { binary: self, base }
+    }
+
+    /// Iterate over all named sections of the file.
+    pub fn sections(&self) -> impl Iterator<Item = Section<'s>> + '_ {
+        self.file.section_iter().filter_map(move |section| {
+            Some(Section {
+                name: section.get_name(&self.file).ok()?,
+                address: section.address(),
+                offset: section.offset(),
+                size: section.size(),
+                flags: SectionFlags::from_bits_truncate(section.flags()),
+                typ: section.get_type().ok()?,
+            })
+        })
+    }
+
+    /// Returns the file contents of the section `name`.
+    ///
+    /// Returns None if there is no such section or it has no data in the
+    /// file (e.g., `.bss`).
+    pub fn find_section_data(&self, name: &str) -> Option<&'s [u8]> {
+        let section = self.file.find_section_by_name(name)?;
+        match section.get_type().ok()? {
+            ShType::Null | ShType::NoBits => None,
+            _ => {
+                let start = section.offset() as usize;
+                let end = start.checked_add(section.size() as usize)?;
+                self.file.input.get(start..end)
+            }
+        }
+    }
+
+    /// Returns the file name and CRC of the separate debug file referenced
+    /// by the `.gnu_debuglink` section, if present.
+    ///
+    /// Use [`DebugLink::matches`] to validate a candidate debug file.
+    pub fn debuglink(&self) -> Option<DebugLink<'s>> {
+        self.find_section_data(".gnu_debuglink")
+            .and_then(DebugLink::parse)
+    }
+
+    /// Returns the DWARF and unwind sections together with the bias needed
+    /// to translate their addresses for a binary loaded at `base`.
+    pub fn debug_sections(&self, base: VAddr) -> DebugSections<'_, 's> {
+        DebugSections {
+            binary: self,
+            bias: self.load_bias(base),
+        }
+    }
+
+    /// The offset between the addresses in the file and the load addresses.
+    ///
+    /// Only position independent (ET_DYN) binaries are moved to `base`,
+    /// executables (ET_EXEC) always run at their link-time addresses.
+    pub(crate) fn load_bias(&self, base: VAddr) -> u64 {
+        match self.file.header.pt2.type_().as_type() {
+            header::Type::SharedObject => base,
+            _ => 0,
+        }
+    }
+
     /// Get the name of the sectione
     pub fn symbol_name(&self, symbol: &'s dyn Entry) -> &'s str {
-        symbol.get_name(&self.file).unwrap_or("unknown")
+        self.strtab()
+            .and_then(|strtab| strtab.get(symbol.name().into()))
+            .unwrap_or("unknown")
+    }
+
+    /// Iterate over the symbol table (`.symtab`).
+    ///
+    /// Fails with `SymbolTableNotFound` for stripped binaries.
+    pub fn symbols(&self) -> Result<Symbols<'s>, ElfLoaderErr> {
+        let table = self
+            .find_section_data(".symtab")
+            .ok_or(ElfLoaderErr::SymbolTableNotFound)?;
+        Ok(Symbols::new(
+            table,
+            self.strtab(),
+            self.header.encoding,
+            SymbolTableKind::Symtab,
+        ))
+    }
+
+    /// Iterate over the dynamic symbol table (`.dynsym`).
+    pub fn dynamic_symbols(&self) -> Result<Symbols<'s>, ElfLoaderErr> {
+        let table = self
+            .find_section_data(".dynsym")
+            .ok_or(ElfLoaderErr::SymbolTableNotFound)?;
+        Ok(Symbols::new(
+            table,
+            self.dynstr(),
+            self.header.encoding,
+            SymbolTableKind::Dynsym,
+        ))
+    }
+
+    /// Iterate over the symbol table, or the dynamic symbol table for
+    /// stripped binaries. [`Symbols::kind`] tells which one is used.
+    pub fn any_symbols(&self) -> Result<Symbols<'s>, ElfLoaderErr> {
+        self.symbols().or_else(|_| self.dynamic_symbols())
     }
 
     /// Enumerate all the symbols in the file
-    pub fn for_each_symbol<F: FnMut(&'s dyn Entry)>(
+    pub fn for_each_symbol<F: FnMut(&'s dyn Entry)>(&self, func: F) -> Result<(), ElfLoaderErr> {
+        self.for_each_symbol_in(".symtab", func)
+    }
+
+    /// The exported functions ([`Symbol::is_exported`] and
+    /// [`Symbol::is_function`]) of the symbol table, or the dynamic symbol
+    /// table for stripped binaries.
+    pub fn exported_functions(
+        &self,
+    ) -> Result<impl Iterator<Item = Symbol<'s>> + 's, ElfLoaderErr> {
+        Ok(self
+            .any_symbols()?
+            .filter(|symbol| symbol.is_exported() && symbol.is_function()))
+    }
+
+    /// The defined global data objects ([`Symbol::is_global`] and
+    /// [`Symbol::is_object`]) of the symbol table, or the dynamic symbol
+    /// table for stripped binaries.
+    pub fn global_data_symbols(
+        &self,
+    ) -> Result<impl Iterator<Item = Symbol<'s>> + 's, ElfLoaderErr> {
+        Ok(self
+            .any_symbols()?
+            .filter(|symbol| symbol.is_defined() && symbol.is_global() && symbol.is_object()))
+    }
+
+    /// The function or data object symbol containing `vaddr` (an
+    /// unrelocated address), searched in the symbol table or the dynamic
+    /// symbol table for stripped binaries.
+    ///
+    /// If symbols overlap the one with the highest address is returned.
+    /// Every call scans the whole table, use [`ElfBinary::symbol_index`] to
+    /// look up many addresses.
+    pub fn symbol_for_address(&self, vaddr: VAddr) -> Result<Option<Symbol<'s>>, ElfLoaderErr> {
+        Ok(self
+            .any_symbols()?
+            .filter(|symbol| symbol.contains(vaddr))
+            .fold(None, |found: Option<Symbol<'s>>, symbol| match found {
+                Some(found) if found.value >= symbol.value => Some(found),
+                _ => Some(symbol),
+            }))
+    }
+
+    /// Sorts the function and data object symbols of the symbol table (or
+    /// the dynamic symbol table for stripped binaries) by address for
+    /// [`crate::SymbolIndex::lookup`].
+    #[cfg(feature = "alloc")]
+    pub fn symbol_index(&self) -> Result<crate::SymbolIndex<'s>, ElfLoaderErr> {
+        Ok(crate::SymbolIndex::new(self.any_symbols()?))
+    }
+
+    /// The address ranges of the defined functions (`STT_FUNC` symbols with
+    /// a size) in the symbol table, or the dynamic symbol table for
+    /// stripped binaries. The addresses are unrelocated.
+    pub fn function_ranges(
+        &self,
+    ) -> Result<impl Iterator<Item = FunctionRange<'s>> + 's, ElfLoaderErr> {
+        Ok(self
+            .any_symbols()?
+            .filter(|symbol| symbol.is_defined() && symbol.is_function() && symbol.size > 0)
+            .map(|symbol| FunctionRange {
+                name: symbol.name,
+                start: symbol.value,
+                size: symbol.size,
+            }))
+    }
+
+    /// The `function_ranges` of the binary loaded at `base`, sorted by
+    /// address for [`FunctionRange::lookup`].
+    ///
+    /// Of the aliases of a function (symbols with the same start address)
+    /// only the first one is kept. A function overlapping the previous one
+    /// is cut to start at its end.
+    #[cfg(feature = "alloc")]
+    pub fn function_range_table(
+        &self,
+        base: VAddr,
+    ) -> Result<alloc::vec::Vec<FunctionRange<'s>>, ElfLoaderErr> {
+        let bias = self.load_bias(base);
+        let mut functions: alloc::vec::Vec<FunctionRange<'s>> = self
+            .function_ranges()?
+            .map(|function| FunctionRange {
+                start: function.start.wrapping_add(bias),
+                ..function
+            })
+            .collect();
+        // Stable, so the first alias stays in front
+        functions.sort_by_key(|function| function.start);
+
+        let mut table: alloc::vec::Vec<FunctionRange<'s>> =
+            alloc::vec::Vec::with_capacity(functions.len());
+        for mut function in functions {
+            if let Some(previous) = table.last() {
+                let end = previous.end();
+                if function.end() <= end {
+                    continue;
+                }
+                if function.start < end {
+                    function.size -= end - function.start;
+                    function.start = end;
+                }
+            }
+            table.push(function);
+        }
+        Ok(table)
+    }
+
+    /// Enumerate all the symbols in the file like `for_each_symbol`, but
+    /// fall back to the dynamic symbol table if the binary is stripped.
+    ///
+    /// Returns the table the symbols came from.
+    pub fn for_each_symbol_any<F: FnMut(Symbol<'s>)>(
         &self,
+        func: F,
+    ) -> Result<SymbolTableKind, ElfLoaderErr> {
+        let symbols = self.any_symbols()?;
+        let kind = symbols.kind();
+        symbols.for_each(func);
+        Ok(kind)
+    }
+
+    /// Returns the name → address table of the global (and weak) symbols
+    /// the binary loaded at `base` defines in its dynamic symbol table
+    /// (`.dynsym`), e.g., to call into a plugin once it is loaded.
+    ///
+    /// Symbols with hidden or internal visibility and TLS symbols are left
+    /// out. For a `STT_GNU_IFUNC` symbol the address is the one of its
+    /// resolver function. If a name is defined multiple times (in different
+    /// versions) the first definition is used.
+    #[cfg(feature = "alloc")]
+    pub fn exports(
+        &self,
+        base: VAddr,
+    ) -> Result<alloc::collections::BTreeMap<&'s str, VAddr>, ElfLoaderErr> {
+        use symbol_table::{Binding, Visibility};
+
+        let dynstr = self.dynstr().ok_or(ElfLoaderErr::SymbolTableNotFound)?;
+        let mut exports = alloc::collections::BTreeMap::new();
+        self.for_each_symbol_in(".dynsym", |symbol| {
+            let global = matches!(symbol.get_binding(), Ok(Binding::Global | Binding::Weak));
+            let visible = matches!(
+                symbol.get_other(),
+                Visibility::Default | Visibility::Protected
+            );
+            if !global || !visible {
+                return;
+            }
+            let name = dynstr.get(symbol.name().into());
+            let address = self.symbol_address_rebased(symbol, base);
+            if let (Some(name), Some(address)) = (name, address) {
+                exports.entry(name).or_insert(address);
+            }
+        })?;
+        Ok(exports)
+    }
+
+    /// Indices of the dynamic symbols (in `.dynsym`) of a RISC-V binary that
+    /// use a variant calling convention (see [`riscv::is_variant_cc`]).
+    ///
+    /// PLT relocations referring to these symbols have to be resolved when
+    /// the binary is loaded instead of lazily. Binaries which contain such
+    /// relocations have [`DynamicInfo::riscv_variant_cc`] set.
+    pub fn riscv_variant_cc_symbols(&self) -> impl Iterator<Item = u32> + '_ {
+        let encoding = self.header.encoding;
+        let input = self.file.input;
+        let table = match self.get_arch() {
+            Machine::RISC_V => self
+                .header
+                .section_headers(input)
+                .ok()
+                .and_then(|mut sections| sections.find(|s| s.typ == elf::SHT_DYNSYM))
+                .and_then(|section| section.data(input).ok())
+                .unwrap_or(&[]),
+            _ => &[],
+        };
+        table
+            .chunks_exact(elf::Symbol::size(encoding.class))
+            .enumerate()
+            .filter_map(move |(index, entry)| {
+                let symbol = elf::Symbol::parse(entry, encoding).ok()?;
+                riscv::is_variant_cc(&symbol).then_some(index as u32)
+            })
+    }
+
+    /// Enumerate the symbols in the symbol table section `name`.
+    fn for_each_symbol_in<F: FnMut(&'s dyn Entry)>(
+        &self,
+        name: &str,
         mut func: F,
     ) -> Result<(), ElfLoaderErr> {
         let symbol_section = self
             .file
-            .find_section_by_name(".symtab")
+            .find_section_by_name(name)
             .ok_or(ElfLoaderErr::SymbolTableNotFound)?;
         let symbol_table = symbol_section.get_data(&self.file)?;
         match symbol_table {
@@ -132,12 +890,45 @@ impl<'s> ElfBinary<'s> {
                 }
                 Ok(())
             }
+            SectionData::DynSymbolTable32(entries) => {
+                for entry in entries {
+                    func(entry);
+                }
+                Ok(())
+            }
+            SectionData::DynSymbolTable64(entries) => {
+                for entry in entries {
+                    func(entry);
+                }
+                Ok(())
+            }
             _ => Err(ElfLoaderErr::SymbolTableNotFound),
         }
     }
 
+    /// Iterate over the (tag, value) pairs of the dynamic segment.
+    ///
+    /// Works the same for ELF32 and ELF64 binaries, so tags that
+    /// [`DynamicInfo`] doesn't model can be read without matching on the
+    /// segment data. Stops at the terminating DT_NULL entry. Tags outside of
+    /// the ranges defined by the ELF specification yield an error.
+    pub fn dynamic_entries(
+        &self,
+    ) -> impl Iterator<Item = Result<(Tag<u64>, u64), ElfLoaderErr>> + '_ {
+        self.raw_dynamic_entries()
+            .map(|(tag, value)| Ok((dynamic_tag(tag)?, value)))
+    }
+
+    /// Iterate over the raw (tag, value) pairs of the dynamic segment.
+    ///
+    /// Stops at the terminating DT_NULL entry.
+    pub(crate) fn raw_dynamic_entries(&self) -> impl Iterator<Item = (u64, u64)> + '_ {
+        let data = self.segment_data_at(self.segments.dynamic).unwrap_or(&[]);
+        elf::DynamicEntries::new(data, self.header.encoding).map(|entry| (entry.tag, entry.value))
+    }
+
     /// Can we load this binary on our platform?
-    fn is_loadable(&self) -> Result<(), ElfLoaderErr> {
+    fn is_loadable(&self, config: &LoadConfig) -> Result<(), ElfLoaderErr> {
         let header = self.file.header;
         let typ = header.pt2.type_().as_type();
 
@@ -149,6 +940,13 @@ impl<'s> ElfBinary<'s> {
             || header.pt1.os_abi() == header::OsAbi::Linux)
         {
             Err(ElfLoaderErr::UnsupportedAbi)
+        } else if config
+            .max_abi_version
+            .is_some_and(|max| self.abi_version() > max)
+        {
+            Err(ElfLoaderErr::UnsupportedAbiVersion {
+                version: self.abi_version(),
+            })
         } else if !(typ == header::Type::Executable || typ == header::Type::SharedObject) {
             #[cfg(log)]
             error!("Invalid ELF type {:?}", typ);
@@ -158,68 +956,302 @@ impl<'s> ElfBinary<'s> {
         }
     }
 
-    /// Process the relocation entries for the ELF file.
-    ///
-    /// Issues call to `loader.relocate` and passes the relocation entry.
-    fn maybe_relocate(&self, loader: &mut dyn ElfLoader) -> Result<(), ElfLoaderErr> {
-        // Relocation types are architecture specific
-        let arch = self.get_arch();
+    /// The file contents of the program header `header` with index `index`.
+    fn segment_data(
+        file: &ElfFile<'s>,
+        index: usize,
+        header: &ProgramHeader<'s>,
+    ) -> Result<&'s [u8], ElfLoaderErr> {
+        Segment::new(index, *header, file.input).data()
+    }
 
-        // It's easier to just locate the section by name, either:
-        // - .rela.dyn
-        // - .rel.dyn
-        let relocation_section = self
-            .file
-            .find_section_by_name(".rela.dyn")
-            .or_else(|| self.file.find_section_by_name(".rel.dyn"));
-
-        // Helper macro to call loader.relocate() on all entries
-        macro_rules! iter_entries_and_relocate {
-            ($rela_entries:expr, $create_addend:ident) => {
-                for entry in $rela_entries {
-                    loader.relocate(RelocationEntry {
-                        rtype: RelocationType::from(arch, entry.get_type() as u32)?,
-                        offset: entry.get_offset() as u64,
-                        index: entry.get_symbol_table_index(),
-                        addend: $create_addend!(entry),
-                    })?;
-                }
-            };
+    /// The file contents of program header `index`, if it is in bounds.
+    fn segment_data_at(&self, index: Option<usize>) -> Option<&'s [u8]> {
+        let index = index?;
+        let header = self.file.program_header(u16::try_from(index).ok()?).ok()?;
+        ElfBinary::segment_data(&self.file, index, &header).ok()
+    }
+
+    /// Makes sure the file and memory ranges of all program headers are
+    /// valid, so none of the values handed to the loader callbacks wrap.
+    pub(crate) fn check_segment_ranges(&self) -> Result<(), ElfLoaderErr> {
+        for (index, header) in self.file.program_iter().enumerate() {
+            if header.get_type()? == Type::Null {
+                continue;
+            }
+            ElfBinary::segment_data(&self.file, index, &header)?;
+            header.virtual_addr().checked_add(header.mem_size()).ok_or(
+                ElfLoaderErr::AddressOverflow {
+                    source: "p_vaddr + p_memsz",
+                },
+            )?;
+            to_usize(header.mem_size())?;
         }
+        Ok(())
+    }
 
-        // Construct from Rel<T> entries. Does not contain an addend.
-        macro_rules! rel_entry {
-            ($entry:ident) => {
-                None
-            };
+    /// Checks the invariants loaders that map the LOAD segments by walking
+    /// the program headers rely on:
+    ///
+    /// - the segments are sorted by `p_vaddr`,
+    /// - they don't overlap, even after rounding them to 4 KiB pages (see
+    ///   [`ElfBinary::validate_load_segments_for_page_size`] for other page
+    ///   sizes),
+    /// - `p_align` is a power of two and `p_vaddr` ≡ `p_offset` (mod `p_align`),
+    /// - `p_filesz` is not larger than `p_memsz`.
+    ///
+    /// Returns `InvalidSegment` with the index of the first offending
+    /// program header otherwise.
+    pub fn validate_load_segments(&self) -> Result<(), ElfLoaderErr> {
+        self.validate_load_segments_for_page_size(LoadConfig::DEFAULT_PAGE_SIZE)
+    }
+
+    /// Like [`ElfBinary::validate_load_segments`], with the overlap check
+    /// done for pages of `page_size` bytes (a power of two).
+    pub fn validate_load_segments_for_page_size(&self, page_size: u64) -> Result<(), ElfLoaderErr> {
+        if !page_size.is_power_of_two() {
+            return Err(ElfLoaderErr::InvalidPageSize { page_size });
         }
 
-        // Construct from Rela<T> entries. Contains an addend.
-        macro_rules! rela_entry {
-            ($entry:ident) => {
-                Some($entry.get_addend() as u64)
+        let mut previous_end: Option<u64> = None;
+        let mut previous_start = 0;
+        for segment in self.load_segments() {
+            let index = segment.index;
+            let invalid = |reason| Err(ElfLoaderErr::InvalidSegment { index, reason });
+
+            let start = segment.vaddr;
+            let align = segment.align;
+            if segment.file_size > segment.mem_size {
+                return invalid("p_filesz is larger than p_memsz");
+            }
+            if align > 1 && !align.is_power_of_two() {
+                return invalid("p_align is not a power of two");
+            }
+            if align > 1 && start.wrapping_sub(segment.offset) & (align - 1) != 0 {
+                return invalid("p_vaddr and p_offset are not congruent modulo p_align");
+            }
+            let end = match start.checked_add(segment.mem_size) {
+                Some(end) => end,
+                None => return invalid("p_vaddr + p_memsz overflows"),
             };
-        }
 
-        // If either section exists apply the relocations
-        relocation_section.map_or(Ok(()), |rela_section_dyn| {
-            let data = rela_section_dyn.get_data(&self.file)?;
-            match data {
-                SectionData::Rel32(rel_entries) => {
-                    iter_entries_and_relocate!(rel_entries, rel_entry);
+            if let Some(previous_end) = previous_end {
+                if start < previous_start {
+                    return invalid("LOAD segments are not sorted by p_vaddr");
                 }
-                SectionData::Rela32(rela_entries) => {
-                    iter_entries_and_relocate!(rela_entries, rela_entry);
+                let page_end = previous_end.saturating_add(page_size - 1) & !(page_size - 1);
+                if start & !(page_size - 1) < page_end {
+                    return invalid("LOAD segment overlaps the previous one");
                 }
-                SectionData::Rel64(rel_entries) => {
-                    iter_entries_and_relocate!(rel_entries, rel_entry);
+            }
+            previous_start = start;
+            previous_end = Some(end);
+        }
+
+        Ok(())
+    }
+
+    /// Makes sure no two LOAD segments cover the same memory, which would
+    /// let the `load` of one segment overwrite the contents of another.
+    ///
+    /// Returns `OverlappingSegments` with the program header indices of the
+    /// first conflicting pair otherwise.
+    pub fn check_overlapping_segments(&self) -> Result<(), ElfLoaderErr> {
+        let range = |segment: &LoadSegment| {
+            (
+                segment.vaddr,
+                segment.vaddr.saturating_add(segment.mem_size),
+            )
+        };
+        let loadable = || self.load_segments().filter(|segment| segment.mem_size > 0);
+
+        for (ordinal, a) in loadable().enumerate() {
+            let (a_start, a_end) = range(&a);
+            for b in loadable().skip(ordinal + 1) {
+                let (b_start, b_end) = range(&b);
+                if a_start < b_end && b_start < a_end {
+                    return Err(ElfLoaderErr::OverlappingSegments {
+                        first: a.index,
+                        second: b.index,
+                    });
                 }
-                SectionData::Rela64(rela_entries) => {
-                    iter_entries_and_relocate!(rela_entries, rela_entry);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Makes sure loading the binary stays within `limits`.
+    pub fn check_limits(&self, limits: &Limits) -> Result<(), ElfLoaderErr> {
+        let exceeds = |value: u64, limit: Option<u64>| limit.is_some_and(|max| value > max);
+
+        let mut total: u64 = 0;
+        for segment in self.load_segments() {
+            if exceeds(segment.mem_size, limits.max_segment_size) {
+                return Err(ElfLoaderErr::LimitsExceeded {
+                    limit: "max_segment_size",
+                });
+            }
+            total = total.saturating_add(segment.mem_size);
+        }
+        if exceeds(total, limits.max_total_size) {
+            return Err(ElfLoaderErr::LimitsExceeded {
+                limit: "max_total_size",
+            });
+        }
+        if exceeds(self.relocation_count(), limits.max_relocations) {
+            return Err(ElfLoaderErr::LimitsExceeded {
+                limit: "max_relocations",
+            });
+        }
+
+        Ok(())
+    }
+
+    /// The section holding the dynamic relocations.
+    ///
+    /// It's easier to just locate the section by name, either:
+    /// - .rela.dyn
+    /// - .rel.dyn
+    pub(crate) fn relocation_section(&self) -> Option<sections::SectionHeader<'s>> {
+        self.file
+            .find_section_by_name(".rela.dyn")
+            .or_else(|| self.file.find_section_by_name(".rel.dyn"))
+    }
+
+    /// Number of entries in the dynamic relocation tables applied by `load`.
+    pub(crate) fn relocation_count(&self) -> u64 {
+        Relocations::new(self, false).count() as u64
+    }
+
+    /// Size of an address in the binary (4 for ELF32, 8 for ELF64).
+    pub(crate) fn word_size(&self) -> u64 {
+        self.header.encoding.class.word_size() as u64
+    }
+
+    /// Lists the word sized slots the dynamic relocations patch (GOT entries
+    /// and RELATIVE/IRELATIVE pointers in data), including the PLT GOT.
+    ///
+    /// Unlike [`ElfBinary::load`] this doesn't involve an [`ElfLoader`], so
+    /// tools can compute the fixups of a binary ahead of time. Relocations
+    /// of other types are skipped; fails with `UnsupportedArchitecture` on
+    /// architectures without GOT fixup classification.
+    pub fn got_fixups(&self) -> impl Iterator<Item = Result<GotFixup, ElfLoaderErr>> + '_ {
+        let supported = matches!(
+            self.get_arch(),
+            Machine::X86 | Machine::X86_64 | Machine::Arm | Machine::AArch64 | Machine::RISC_V
+        );
+        let unsupported = (!supported).then_some(Err(ElfLoaderErr::UnsupportedArchitecture));
+        let fixups = self
+            .relocations()
+            .take_while(move |_| supported)
+            .filter_map(|entry| match entry {
+                Ok(entry) => Some(Ok(GotFixup {
+                    address: entry.offset,
+                    kind: entry.rtype.got_fixup_kind()?,
+                    symbol: entry.index,
+                    addend: entry.addend,
+                })),
+                Err(e) => Some(Err(e)),
+            });
+        unsupported.into_iter().chain(fixups)
+    }
+
+    /// Iterate over the dynamic relocation entries, including the PLT
+    /// relocations.
+    ///
+    /// [`ElfBinary::load`] passes the same entries to
+    /// [`ElfLoader::relocate`], except for the PLT relocations.
+    pub fn relocations(&self) -> Relocations<'_, 's> {
+        Relocations::new(self, true)
+    }
+
+    /// Process the relocation entries for the ELF file.
+    ///
+    /// Issues call to `loader.relocate` and passes the relocation entry,
+    /// or writes the value of RELATIVE relocations itself if
+    /// `config.relative_base` is set.
+    ///
+    /// Progress is reported every `config.progress_interval` relocations and
+    /// after the last one. Relocations targeting `skipped` segments are
+    /// dropped.
+    fn maybe_relocate(
+        &self,
+        loader: &mut dyn ElfLoader,
+        config: &LoadConfig,
+        skipped: SkippedSegments,
+    ) -> Result<RelocationCounts, ElfLoaderErr> {
+        let progress_interval = config.progress_interval;
+        let total = self.relocation_count();
+        let mut counts = RelocationCounts::default();
+        for (index, entry) in Relocations::new(self, false).enumerate() {
+            let entry = entry?;
+            if !skipped.covers(self, entry.offset) {
+                counts.add(entry.type_num);
+                match (config.relative_base, entry.addend) {
+                    (Some(base), Some(addend)) if entry.rtype.is_relative() => {
+                        let value = self.load_bias(base).wrapping_add(addend);
+                        if self.word_size() == 8 {
+                            loader.write_u64(entry.offset, value)?;
+                        } else {
+                            loader.write_u32(entry.offset, value as u32)?;
+                        }
+                    }
+                    _ => loader.relocate(entry)?,
                 }
-                _ => return Err(ElfLoaderErr::UnsupportedSectionData),
             }
-            Ok(())
+
+            let completed = index as u64 + 1;
+            if completed == total
+                || (progress_interval > 0 && completed.is_multiple_of(progress_interval))
+            {
+                loader.progress(LoadProgress::Relocations { completed, total })?;
+            }
+        }
+        Ok(counts)
+    }
+
+    /// The LOAD segment containing the relocated location `offset`.
+    pub(crate) fn relocation_segment(&self, offset: VAddr) -> Option<RelocationSegment> {
+        let segment = self
+            .load_segments()
+            .find(|segment| segment.contains(offset))?;
+        Some(RelocationSegment {
+            index: segment.index,
+            vaddr: segment.vaddr,
+            mem_size: segment.mem_size,
+            flags: segment.flags.into(),
+            relro: self
+                .relro_segments()
+                .any(|relro| offset >= relro.vaddr && offset - relro.vaddr < relro.mem_size),
+        })
+    }
+
+    /// Reads the implicit addend of a REL entry relocating `offset`.
+    ///
+    /// This is the word (32 bit for ELF32, 64 bit for ELF64, in the byte
+    /// order of the binary) at `offset` in the file-backed part of the LOAD
+    /// segment containing it. Returns None if `offset` is not file-backed
+    /// (e.g., in .bss).
+    pub(crate) fn implicit_addend(&self, offset: u64) -> Option<u64> {
+        let data = self.virtual_data(offset, self.word_size())?;
+        self.header.encoding.word(data, 0).ok()
+    }
+
+    /// The file contents of the `size` bytes at virtual address `vaddr`.
+    ///
+    /// Returns None unless the range lies in the file-backed part of a
+    /// LOAD segment.
+    pub(crate) fn virtual_data(&self, vaddr: u64, size: u64) -> Option<&'s [u8]> {
+        self.load_segments().find_map(|segment| {
+            let start = vaddr.checked_sub(segment.vaddr)?;
+            if start.checked_add(size)? > segment.file_size {
+                return None;
+            }
+            let start = usize::try_from(segment.offset.checked_add(start)?).ok()?;
+            self.file
+                .input
+                .get(start..start.checked_add(usize::try_from(size).ok()?)?)
         })
     }
 
@@ -229,82 +1261,133 @@ impl<'s> ElfBinary<'s> {
     /// At the moment this just does sanity checking for relocation later.
     ///
     /// A human readable version of the dynamic section is best obtained with `readelf -d <binary>`.
-    fn parse_dynamic<'a>(
-        file: &ElfFile,
-        dynamic_header: &'a ProgramHeader<'a>,
+    fn parse_dynamic(
+        &self,
+        segment: &[u8],
+        vaddr: VAddr,
     ) -> Result<Option<DynamicInfo>, ElfLoaderErr> {
         #[cfg(log)]
-        trace!("load dynamic segement {:?}", dynamic_header);
-
-        // Walk through the dynamic program header and find the rela and sym_tab section offsets:
-        let segment = dynamic_header.get_data(file)?;
+        trace!("load dynamic segment at {:#x}", vaddr);
 
         // Init result
         let mut info = DynamicInfo {
+            flags: Default::default(),
             flags1: Default::default(),
             rela: 0,
             rela_size: 0,
+            rel: 0,
+            rel_size: 0,
+            jmprel: 0,
+            pltrel_size: 0,
+            pltrel: 0,
+            pltgot: None,
+            debug: None,
+            strtab: None,
+            strtab_size: 0,
+            rpath: None,
+            runpath: None,
+            soname: None,
+            needed: 0,
+            mips_local_gotno: None,
+            mips_gotsym: None,
+            mips_symtabno: None,
+            riscv_variant_cc: false,
+            textrel: false,
         };
+        // Processor specific tags are only meaningful for their architecture
+        let is_mips = self.get_arch() == Machine::Mips;
+        let is_riscv = self.get_arch() == Machine::RISC_V;
+        let entry_size = 2 * self.word_size();
 
-        // Each entry/section is parsed for the same information currently
-        macro_rules! parse_entry_tags {
-            ($info:ident, $entry:ident, $tag:ident) => {
-                match $tag {
-                    // Trace required libs
-                    Tag::Needed => {
-                        #[cfg(log)]
-                        trace!(
-                            "Required library {:?}",
-                            file.get_dyn_string($entry.get_val()? as _)
-                        )
-                    }
+        // Walk through the dynamic entries and find the relocation tables etc.
+        for (idx, entry) in elf::DynamicEntries::new(segment, self.header.encoding).enumerate() {
+            let val = entry.value;
+            match dynamic_tag(entry.tag)? {
+                // Trace required libs
+                Tag::Needed => {
+                    #[cfg(log)]
+                    trace!("Required library at .dynstr offset {:#x}", val);
+                    info.needed += 1;
+                }
 
-                    // Rel<T>
-                    Tag::Rel => $info.rela = $entry.get_ptr()?.into(),
-                    Tag::RelSize => $info.rela_size = $entry.get_val()?.into(),
+                // Rel<T>
+                Tag::Rel => info.rel = val,
+                Tag::RelSize => info.rel_size = val,
 
-                    // Rela<T>
-                    Tag::Rela => $info.rela = $entry.get_ptr()?.into(),
-                    Tag::RelaSize => $info.rela_size = $entry.get_val()?.into(),
-                    Tag::Flags1 => {
-                        $info.flags1 =
-                            unsafe { DynamicFlags1::from_bits_unchecked($entry.get_val()? as _) };
-                    }
-                    _ => {
-                        #[cfg(log)]
-                        trace!("unsupported {:?}", $entry)
-                    }
+                // Rela<T>
+                Tag::Rela => info.rela = val,
+                Tag::RelaSize => info.rela_size = val,
+
+                // PLT relocations
+                Tag::JmpRel => info.jmprel = val,
+                Tag::PltRelSize => info.pltrel_size = val,
+                Tag::PltRel => info.pltrel = val,
+
+                Tag::Pltgot => info.pltgot = Some(val),
+
+                // The value of the DT_DEBUG entry follows its tag, both are one word
+                Tag::Debug => {
+                    info.debug = Some(
+                        (idx as u64 * entry_size + entry_size / 2)
+                            .checked_add(vaddr)
+                            .ok_or(ElfLoaderErr::AddressOverflow { source: "DT_DEBUG" })?,
+                    );
                 }
-            };
-        }
 
-        // Helper macro to iterate all entries
-        macro_rules! iter_entries_and_parse {
-            ($info:ident, $dyn_entries:expr) => {
-                for dyn_entry in $dyn_entries {
-                    let tag = dyn_entry.get_tag()?;
-                    parse_entry_tags!($info, dyn_entry, tag);
+                Tag::StrTab => info.strtab = Some(val),
+                Tag::StrSize => info.strtab_size = val,
+
+                // Library search paths (offsets into .dynstr)
+                Tag::RPath => info.rpath = Some(val),
+                Tag::RunPath => info.runpath = Some(val),
+                Tag::SoName => info.soname = Some(val),
+                Tag::Flags => {
+                    info.flags |= unsafe { DynamicFlags::from_bits_unchecked(val as _) };
                 }
-            };
+                Tag::TextRel => info.textrel = true,
+                Tag::Flags1 => {
+                    info.flags1 = unsafe { DynamicFlags1::from_bits_unchecked(val as _) };
+                }
+                Tag::ProcessorSpecific(tag) if is_mips => match tag {
+                    mips::DT_MIPS_LOCAL_GOTNO => info.mips_local_gotno = Some(val),
+                    mips::DT_MIPS_GOTSYM => info.mips_gotsym = Some(val),
+                    mips::DT_MIPS_SYMTABNO => info.mips_symtabno = Some(val),
+                    _ => {}
+                },
+                Tag::ProcessorSpecific(riscv::DT_RISCV_VARIANT_CC) if is_riscv => {
+                    info.riscv_variant_cc = true
+                }
+                _tag => {
+                    #[cfg(log)]
+                    trace!("unsupported {:?}", _tag)
+                }
+            }
         }
 
-        match segment {
-            SegmentData::Dynamic32(dyn_entries) => {
-                iter_entries_and_parse!(info, dyn_entries);
-            }
-            SegmentData::Dynamic64(dyn_entries) => {
-                iter_entries_and_parse!(info, dyn_entries);
-            }
-            _ => {
-                return Err(ElfLoaderErr::UnsupportedSectionData);
-            }
-        };
+        if info.rela.checked_add(info.rela_size).is_none() {
+            return Err(ElfLoaderErr::AddressOverflow {
+                source: "DT_RELA + DT_RELASZ",
+            });
+        }
+        if info.rel.checked_add(info.rel_size).is_none() {
+            return Err(ElfLoaderErr::AddressOverflow {
+                source: "DT_REL + DT_RELSZ",
+            });
+        }
+        if info.jmprel.checked_add(info.pltrel_size).is_none() {
+            return Err(ElfLoaderErr::AddressOverflow {
+                source: "DT_JMPREL + DT_PLTRELSZ",
+            });
+        }
 
         #[cfg(log)]
         trace!(
-            "rela size {:?} rela off {:?} flags1 {:?}",
+            "rela size {:?} rela off {:?} rel size {:?} rel off {:?} flags {:?} flags1 {:?}",
             info.rela_size,
             info.rela,
+            info.rel_size,
+            info.rel,
+            info.flags,
             info.flags1
         );
 
@@ -315,31 +1398,172 @@ impl<'s> ElfBinary<'s> {
     ///
     /// Will tell loader to create space in the address space / region where the
     /// header is supposed to go, then copy it there, and finally relocate it.
+    ///
+    /// Binaries with overlapping LOAD segments are rejected before any
+    /// callback is issued (see [`ElfBinary::check_overlapping_segments`]).
     pub fn load(&self, loader: &mut dyn ElfLoader) -> Result<(), ElfLoaderErr> {
-        self.is_loadable()?;
+        self.load_with_config(loader, &LoadConfig::default())
+    }
+
+    /// Like [`ElfBinary::load`], for a loader of a 32-bit address space
+    /// (see [`crate::addr32`]).
+    pub fn load32(&self, loader: &mut dyn ElfLoader32) -> Result<(), ElfLoaderErr> {
+        self.load(&mut Loader32(loader))
+    }
 
-        loader.allocate(self.iter_loadable_headers())?;
+    /// Like [`ElfBinary::load`], with the behavior controlled by `config`.
+    pub fn load_with_config(
+        &self,
+        loader: &mut dyn ElfLoader,
+        config: &LoadConfig,
+    ) -> Result<(), ElfLoaderErr> {
+        self.load_with_stats(loader, config).map(|_| ())
+    }
+
+    /// Like [`ElfBinary::load_with_config`], also reporting what the loader
+    /// was asked to do.
+    pub fn load_with_stats(
+        &self,
+        loader: &mut dyn ElfLoader,
+        config: &LoadConfig,
+    ) -> Result<LoadStats, ElfLoaderErr> {
+        self.load_impl(loader, config, false, |loader, skipped| {
+            self.maybe_relocate(loader, config, skipped)
+        })
+    }
+
+    /// Like [`ElfBinary::load_with_stats`], but the relocations are passed
+    /// to `sink` from `threads` threads (each handling a contiguous part of
+    /// the relocation table) instead of to [`ElfLoader::relocate`].
+    ///
+    /// The other loader callbacks are issued from the calling thread as
+    /// usual. [`LoadConfig::progress_interval`] and
+    /// [`LoadConfig::relative_base`] are ignored for relocations.
+    #[cfg(feature = "parallel")]
+    pub fn load_parallel<S: crate::RelocationSink>(
+        &self,
+        loader: &mut dyn ElfLoader,
+        sink: &S,
+        threads: usize,
+        config: &LoadConfig,
+    ) -> Result<LoadStats, ElfLoaderErr> {
+        self.load_impl(loader, config, false, |_, skipped| {
+            crate::parallel::relocate(self, sink, threads, |offset| skipped.covers(self, offset))
+        })
+    }
+
+    /// Checks everything [`ElfBinary::load_with_config`] checks before
+    /// issuing the first callback, and returns a token to skip these checks
+    /// in later loads of the same binary with [`ElfBinary::load_validated`].
+    pub fn validate(&self, config: &LoadConfig) -> Result<ValidationToken, ElfLoaderErr> {
+        self.check_loadable(config)?;
+        Ok(ValidationToken {
+            config: *config,
+            fingerprint: self.fingerprint(),
+        })
+    }
+
+    /// Like [`ElfBinary::load_with_stats`] with the configuration of `token`,
+    /// without checking the binary again.
+    ///
+    /// The checks are only skipped if `token` was returned by
+    /// [`ElfBinary::validate`] for this binary (or another copy of it);
+    /// tokens of other binaries are detected by the size and the CRC32 of
+    /// the whole file, and lead to a full validation.
+    pub fn load_validated(
+        &self,
+        loader: &mut dyn ElfLoader,
+        token: &ValidationToken,
+    ) -> Result<LoadStats, ElfLoaderErr> {
+        let config = &token.config;
+        let validated = token.fingerprint == self.fingerprint();
+        self.load_impl(loader, config, validated, |loader, skipped| {
+            self.maybe_relocate(loader, config, skipped)
+        })
+    }
+
+    /// The size and the CRC32 of the file.
+    pub(crate) fn fingerprint(&self) -> (usize, u32) {
+        let input = self.file.input;
+        (input.len(), crc32(input))
+    }
+
+    /// The checks done before loading the binary with `config`.
+    fn check_loadable(&self, config: &LoadConfig) -> Result<(), ElfLoaderErr> {
+        if !config.page_size.is_power_of_two() {
+            return Err(ElfLoaderErr::InvalidPageSize {
+                page_size: config.page_size,
+            });
+        }
+        self.is_loadable(config)?;
+        self.try_dynamic()?;
+        self.check_segment_ranges()?;
+        self.check_overlapping_segments()?;
+        if config.validate_segments {
+            self.validate_load_segments_for_page_size(config.page_size)?;
+        }
+        self.check_limits(&config.limits)
+    }
+
+    /// Loads the binary, with `relocate` applying the relocations (except
+    /// for those in the given skipped segments). The checks are skipped if
+    /// the binary was `validated` for `config` before.
+    fn load_impl<F>(
+        &self,
+        loader: &mut dyn ElfLoader,
+        config: &LoadConfig,
+        validated: bool,
+        relocate: F,
+    ) -> Result<LoadStats, ElfLoaderErr>
+    where
+        F: FnOnce(&mut dyn ElfLoader, SkippedSegments) -> Result<RelocationCounts, ElfLoaderErr>,
+    {
+        let mut stats = LoadStats::default();
+        let segments = self.load_segments().count() as u64;
+        if !validated {
+            self.check_loadable(config)?;
+        }
+
+        match self.coalesced_allocation()? {
+            Some(allocation) if config.coalesce_allocation => {
+                loader.allocate_coalesced(allocation)?
+            }
+            _ => loader.allocate(self.iter_loadable_headers())?,
+        }
 
         // Load all headers
-        for header in self.file.program_iter() {
+        let mut skipped = SkippedSegments::NONE;
+        let mut ordinal = 0;
+        for (index, header) in self.file.program_iter().enumerate() {
             if header.get_type() == Ok(Type::Null) {
                 continue;
             }
 
-            let raw = match header {
-                Ph32(inner) => inner.raw_data(&self.file),
-                Ph64(inner) => inner.raw_data(&self.file),
-            };
+            let raw = ElfBinary::segment_data(&self.file, index, &header)?;
             let typ = header.get_type()?;
             match typ {
                 Type::Load => {
-                    loader.load(header.flags(), header.virtual_addr(), raw)?;
+                    if loader.disposition(header)? == Disposition::Skip {
+                        skipped.insert(ordinal, index)?;
+                        stats.segments_skipped += 1;
+                    } else {
+                        loader.load(header.flags().into(), header.virtual_addr(), raw)?;
+                        stats.segments += 1;
+                        stats.bytes_copied += raw.len() as u64;
+                        stats.bytes_zeroed += header.mem_size().saturating_sub(raw.len() as u64);
+                    }
+                    ordinal += 1;
+                    loader.progress(LoadProgress::Segments {
+                        completed: ordinal as u64,
+                        total: segments,
+                    })?;
                 }
                 Type::Tls => {
+                    stats.tls_size = header.mem_size();
                     loader.tls(
                         header.virtual_addr(),
-                        header.file_size(),
-                        header.mem_size(),
+                        raw,
+                        header.mem_size().saturating_sub(raw.len() as u64),
                         header.align(),
                     )?;
                 }
@@ -347,20 +1571,351 @@ impl<'s> ElfBinary<'s> {
             }
         }
 
-        // Relocate headers
-        self.maybe_relocate(loader)?;
-
-        // Process .data.rel.ro
         for header in self.file.program_iter() {
-            if header.get_type()? == Type::GnuRelro {
-                loader.make_readonly(header.virtual_addr(), header.mem_size() as usize)?
+            if let Ok(Type::OsSpecific(typ)) = header.get_type() {
+                if let Some(kind) = openbsd::SegmentKind::from(typ) {
+                    loader.openbsd_segment(
+                        kind,
+                        header.virtual_addr(),
+                        to_usize(header.mem_size())?,
+                    )?;
+                }
+            }
+        }
+
+        // Relocate headers, text relocations need write access to read-only segments
+        let textrel = self.has_textrel();
+        if textrel {
+            for (ordinal, segment) in self.load_segments().enumerate() {
+                if !segment.flags.is_write() && !skipped.contains(ordinal) {
+                    loader.make_writable(segment.vaddr, to_usize(segment.mem_size)?)?;
+                }
+            }
+        }
+
+        stats.relocations = relocate(loader, skipped)?;
+
+        if textrel {
+            for (ordinal, segment) in self.load_segments().enumerate() {
+                if !segment.flags.is_write() && !skipped.contains(ordinal) {
+                    loader.restore_permissions(
+                        segment.vaddr,
+                        to_usize(segment.mem_size)?,
+                        segment.flags.into(),
+                    )?;
+                }
+            }
+        }
+
+        if !config.defer_relro {
+            for segment in self.relro_segments_for_page_size(config.page_size) {
+                if !skipped.covers(self, segment.vaddr) {
+                    loader.relro(segment)?;
+                }
             }
         }
 
+        Ok(stats)
+    }
+
+    /// The (relocated) start address and size of the flat image of the
+    /// binary loaded at `base`: the range from the lowest to the highest
+    /// address of its LOAD segments.
+    ///
+    /// Returns None if the binary has no LOAD segments.
+    pub fn flat_image_span(&self, base: VAddr) -> Result<Option<(VAddr, u64)>, ElfLoaderErr> {
+        let overflow = ElfLoaderErr::AddressOverflow {
+            source: "flat image",
+        };
+        let mut span: Option<(u64, u64)> = None;
+        for segment in self.load_segments() {
+            let start = segment.vaddr;
+            let end = start
+                .checked_add(segment.mem_size)
+                .ok_or_else(|| overflow.clone())?;
+            span = Some(match span {
+                Some((lo, hi)) => (lo.min(start), hi.max(end)),
+                None => (start, end),
+            });
+        }
+        span.map(|(start, end)| {
+            let address = self.load_bias(base).checked_add(start);
+            address
+                .map(|address| (address, end - start))
+                .ok_or_else(|| overflow.clone())
+        })
+        .transpose()
+    }
+
+    /// Writes the flat memory image of the binary loaded at `base` to
+    /// `image` (like `objcopy -O binary`) and returns its size.
+    ///
+    /// `image[0]` corresponds to the start of
+    /// [`ElfBinary::flat_image_span`]. The LOAD segments are copied to their
+    /// offsets, everything else (`.bss` and the gaps between segments) is
+    /// zeroed and the relocations are applied as by [`SliceLoader`], so
+    /// only binaries without symbol references can be written. Fails with
+    /// `OutOfMemory` if `image` is too small.
+    pub fn write_flat_image(&self, base: VAddr, image: &mut [u8]) -> Result<usize, ElfLoaderErr> {
+        let (start, size) = match self.flat_image_span(base)? {
+            Some(span) => span,
+            None => return Ok(0),
+        };
+        let image = image
+            .get_mut(..to_usize(size)?)
+            .ok_or(ElfLoaderErr::OutOfMemory)?;
+        image.fill(0);
+        self.load(&mut SliceLoader::with_bias(
+            self,
+            image,
+            start,
+            self.load_bias(base),
+        ))?;
+        Ok(image.len())
+    }
+
+    /// Returns the flat memory image of the binary loaded at `base`, see
+    /// [`ElfBinary::write_flat_image`].
+    #[cfg(feature = "alloc")]
+    pub fn flat_image(&self, base: VAddr) -> Result<alloc::vec::Vec<u8>, ElfLoaderErr> {
+        let size = self.flat_image_span(base)?.map_or(0, |(_, size)| size);
+        let mut image = alloc::vec![0; to_usize(size)?];
+        self.write_flat_image(base, &mut image)?;
+        Ok(image)
+    }
+
+    /// The region spanning all LOAD segments, with start and end rounded to
+    /// the largest segment alignment.
+    ///
+    /// Returns None if the binary has no LOAD segments.
+    pub fn coalesced_allocation(
+        &self,
+    ) -> Result<Option<CoalescedAllocation<'_, 's>>, ElfLoaderErr> {
+        let overflow = ElfLoaderErr::AddressOverflow {
+            source: "LOAD segment span",
+        };
+        let mut span: Option<(u64, u64, u64)> = None;
+        for segment in self.load_segments() {
+            let start = segment.vaddr;
+            let end = start
+                .checked_add(segment.mem_size)
+                .ok_or_else(|| overflow.clone())?;
+            let align = segment.align.max(1);
+            span = Some(match span {
+                Some((lo, hi, a)) => (lo.min(start), hi.max(end), a.max(align)),
+                None => (start, end, align),
+            });
+        }
+
+        let (start, end, align) = match span {
+            Some(span) => span,
+            None => return Ok(None),
+        };
+        let start = start - start % align;
+        let end = match end % align {
+            0 => end,
+            rem => end.checked_add(align - rem).ok_or(overflow)?,
+        };
+        Ok(Some(CoalescedAllocation {
+            vaddr: start,
+            mem_size: end - start,
+            align,
+            segments: self.iter_loadable_headers(),
+        }))
+    }
+
+    /// Loads a static PIE: a position independent executable that relocates
+    /// itself and depends on no other objects.
+    ///
+    /// The binary must not request an interpreter or shared libraries and all
+    /// of its relocations must be symbol independent (i.e., RELATIVE and
+    /// IRELATIVE), so `loader` can apply them without resolving any symbols.
+    /// `base` is the address `loader` places the binary at; it has to satisfy
+    /// the alignment of the LOAD segments.
+    ///
+    /// The binary is loaded with [`LoadConfig::relative_base`] set to `base`:
+    /// RELATIVE relocations are written through [`ElfLoader::write_u64`] (or
+    /// [`ElfLoader::write_u32`] for ELF32), IRELATIVE ones are passed to
+    /// [`ElfLoader::relocate`].
+    ///
+    /// Returns `NotStaticPie` before issuing any callback if the binary isn't a
+    /// PIE, needs other objects or `base` is misaligned. Any other relocation
+    /// type fails with `NotStaticPie` once loading reaches it.
+    pub fn load_static_pie(
+        &self,
+        base: VAddr,
+        loader: &mut dyn ElfLoader,
+    ) -> Result<(), ElfLoaderErr> {
+        let not_static_pie = |reason| Err(ElfLoaderErr::NotStaticPie { reason });
+        if self.kind() != Some(BinaryKind::Pie) {
+            return not_static_pie("not a PIE");
+        }
+        if self.has_interp_segment() {
+            return not_static_pie("requests an interpreter");
+        }
+        if self.try_dynamic()?.is_some_and(|d| d.needed > 0) {
+            return not_static_pie("depends on shared libraries");
+        }
+        let align = self
+            .load_segments()
+            .map(|segment| segment.align)
+            .max()
+            .unwrap_or(1)
+            .max(1);
+        if !base.is_multiple_of(align) {
+            return not_static_pie("base is not aligned to the LOAD segments");
+        }
+
+        let config = LoadConfig {
+            relative_base: Some(base),
+            ..LoadConfig::default()
+        };
+        self.load_with_config(&mut SymbolIndependent(loader), &config)
+    }
+
+    /// Makes the GNU_RELRO segments (`.data.rel.ro` etc.) read-only by
+    /// calling [`ElfLoader::relro`] for each of them.
+    ///
+    /// Only needs to be called if the binary was loaded with
+    /// [`LoadConfig::defer_relro`] set, `config` should be the same.
+    pub fn finalize(
+        &self,
+        loader: &mut dyn ElfLoader,
+        config: &LoadConfig,
+    ) -> Result<(), ElfLoaderErr> {
+        for segment in self.relro_segments_for_page_size(config.page_size) {
+            loader.relro(segment)?;
+        }
         Ok(())
     }
 
-    fn iter_loadable_headers(&self) -> LoadableHeaders {
+    /// The copy and zero-fill steps that place the LOAD segments at their
+    /// VMA, in program header order.
+    ///
+    /// The file-backed part of a segment is copied from its LMA if that
+    /// differs from the VMA (segments with equal addresses are executed in
+    /// place), the remainder up to `p_memsz` (`.bss`) is zeroed. Overlays
+    /// result in several copies to the same VMA, the boot code picks the
+    /// ones it needs by `segment`.
+    pub fn scatter_load(&self) -> impl Iterator<Item = ScatterLoad> + '_ {
+        self.load_segments().flat_map(|segment| {
+            let copy = (segment.file_size > 0 && segment.paddr != segment.vaddr).then_some(
+                ScatterLoad::Copy {
+                    segment: segment.index,
+                    lma: segment.paddr,
+                    vma: segment.vaddr,
+                    size: segment.file_size,
+                },
+            );
+            let zero = (segment.mem_size > segment.file_size).then(|| ScatterLoad::Zero {
+                segment: segment.index,
+                vma: segment.vaddr.wrapping_add(segment.file_size),
+                size: segment.mem_size - segment.file_size,
+            });
+            copy.into_iter().chain(zero)
+        })
+    }
+
+    /// The TLS initialization image, None if the binary has no PT_TLS
+    /// segment.
+    ///
+    /// Describes the same data as the [`ElfLoader::tls`] callback, so
+    /// thread libraries can set up the TLS blocks of new threads without
+    /// holding on to what `load` passed them.
+    pub fn tls_template(&self) -> Result<Option<TlsTemplate<'s>>, ElfLoaderErr> {
+        let (index, header) = match self
+            .file
+            .program_iter()
+            .enumerate()
+            .find(|(_, header)| header.get_type() == Ok(Type::Tls))
+        {
+            Some(tls) => tls,
+            None => return Ok(None),
+        };
+        let tdata = ElfBinary::segment_data(&self.file, index, &header)?;
+        Ok(Some(TlsTemplate {
+            vaddr: header.virtual_addr(),
+            tdata,
+            tbss_size: header.mem_size().saturating_sub(tdata.len() as u64),
+            mem_size: header.mem_size(),
+            align: header.align(),
+        }))
+    }
+
+    /// The LOAD segments of the binary loaded at `base`, with their file
+    /// contents and zero-filled ranges, e.g., to set up the memory of an
+    /// emulator without implementing [`ElfLoader`].
+    ///
+    /// The data is the unmodified file contents: relocations have to be
+    /// applied separately (or use [`ElfBinary::write_flat_image`]).
+    pub fn memory_image(
+        &self,
+        base: VAddr,
+    ) -> Result<impl Iterator<Item = MemoryRegion<'s>> + '_, ElfLoaderErr> {
+        self.check_segment_ranges()?;
+        let bias = self.load_bias(base);
+        let input = self.file.input;
+        Ok(self.load_segments().map(move |segment| {
+            let vaddr = bias.wrapping_add(segment.vaddr);
+            // The ranges were checked above
+            let start = segment.offset as usize;
+            let data = &input[start..start + segment.file_size as usize];
+            MemoryRegion {
+                segment: segment.index,
+                vaddr,
+                mem_size: segment.mem_size,
+                flags: segment.flags.into(),
+                data,
+                zero_start: vaddr.wrapping_add(segment.file_size),
+                zero_size: segment.mem_size.saturating_sub(segment.file_size),
+            }
+        }))
+    }
+
+    /// The GNU_RELRO segments of the binary, for the default page size.
+    pub fn relro_segments(&self) -> impl Iterator<Item = RelroSegment> + '_ {
+        self.relro_segments_for_page_size(LoadConfig::DEFAULT_PAGE_SIZE)
+    }
+
+    fn relro_segments_for_page_size(
+        &self,
+        page_size: u64,
+    ) -> impl Iterator<Item = RelroSegment> + '_ {
+        self.file
+            .program_iter()
+            .filter(|header| header.get_type() == Ok(Type::GnuRelro))
+            .map(move |header| RelroSegment {
+                vaddr: header.virtual_addr(),
+                mem_size: header.mem_size(),
+                align: header.align(),
+                page_size,
+            })
+    }
+
+    /// The LOAD segments, from the segment cache unless the binary has too
+    /// many of them.
+    pub(crate) fn load_segments(&self) -> impl Iterator<Item = LoadSegment> + '_ {
+        let cached = self.segments.load_segments();
+        let decoded = match cached {
+            Some(_) => None,
+            None => Some(
+                self.header
+                    .program_headers(self.file.input)
+                    .into_iter()
+                    .flatten()
+                    .enumerate()
+                    .filter(|(_, header)| header.typ == elf::PT_LOAD)
+                    .map(|(index, header)| LoadSegment::new(index, &header)),
+            ),
+        };
+        cached
+            .unwrap_or(&[])
+            .iter()
+            .copied()
+            .chain(decoded.into_iter().flatten())
+    }
+
+    pub(crate) fn iter_loadable_headers(&self) -> AllocateRequests<'_, 's> {
         // Trying to determine loadeable headers
         fn select_load(pheader: &ProgramHeader) -> bool {
             match pheader {
@@ -379,6 +1934,302 @@ impl<'s> ElfBinary<'s> {
         // headers and pass it to the loader
         // TODO: This is pretty ugly, maybe we can do something with impl Trait?
         // https://stackoverflow.com/questions/27535289/what-is-the-correct-way-to-return-an-iterator-or-any-other-trait
-        self.file.program_iter().filter(select_load)
+        AllocateRequests::new(self.file.program_iter().filter(select_load))
+    }
+}
+
+/// Checks the structure of the file beyond the file and program headers,
+/// so none of the xmas-elf accessors this crate uses on `ElfBinary::file`
+/// can panic: the tables xmas-elf reinterprets in place must be aligned and
+/// within the file, section names must be terminated UTF-8 strings and the
+/// data of the typed sections must hold a whole number of entries.
+fn check_structure(header: &elf::FileHeader, data: &[u8]) -> Result<(), elf::ParseError> {
+    let class = header.encoding.class;
+    let word = class.word_size();
+    let aligned = |offset: u64, align: usize| {
+        (data.as_ptr() as u64)
+            .wrapping_add(offset)
+            .is_multiple_of(align as u64)
+    };
+
+    if !aligned(0, word) {
+        return Err(elf::ParseError::Unaligned { what: "ELF file" });
+    }
+    if header.phnum > 0
+        && !(aligned(header.phoff, word) && usize::from(header.phentsize).is_multiple_of(word))
+    {
+        return Err(elf::ParseError::Unaligned {
+            what: "program header table",
+        });
+    }
+    if header.shnum == 0 {
+        return Ok(());
+    }
+
+    let sections = header.section_headers(data)?;
+    if !(aligned(header.shoff, word) && usize::from(header.shentsize).is_multiple_of(word)) {
+        return Err(elf::ParseError::Unaligned {
+            what: "section header table",
+        });
+    }
+    let invalid = |index: usize, reason| elf::ParseError::InvalidSection {
+        index: index as u16,
+        reason,
+    };
+    let shstrtab = sections
+        .clone()
+        .nth(usize::from(header.shstrndx))
+        .ok_or_else(|| invalid(usize::from(header.shstrndx), "e_shstrndx is not a section"))?;
+    let names = usize::try_from(shstrtab.offset)
+        .ok()
+        .and_then(|offset| data.get(offset..))
+        .ok_or_else(|| {
+            invalid(
+                usize::from(header.shstrndx),
+                "section data is outside of the file",
+            )
+        })?;
+
+    for (index, section) in sections.enumerate() {
+        if section.typ == elf::SHT_NULL {
+            continue;
+        }
+        if !elf::is_valid_section_type(section.typ) {
+            return Err(invalid(index, "invalid section type"));
+        }
+
+        let name = usize::try_from(section.name)
+            .ok()
+            .and_then(|name| names.get(name..))
+            .ok_or_else(|| invalid(index, "section name is outside of the string table"))?;
+        let end = name
+            .iter()
+            .position(|&b| b == 0)
+            .ok_or_else(|| invalid(index, "section name is not terminated"))?;
+        let name = &name[..end];
+        if core::str::from_utf8(name).is_err() {
+            return Err(invalid(index, "section name is not valid UTF-8"));
+        }
+
+        if section.typ == elf::SHT_NOBITS {
+            continue;
+        }
+        let contents = section
+            .data(data)
+            .map_err(|_| invalid(index, "section data is outside of the file"))?;
+
+        // (entry size, alignment, header size) of the data xmas-elf reads in place
+        let layout = match section.typ {
+            elf::SHT_SYMTAB | elf::SHT_DYNSYM => Some((elf::Symbol::size(class), word, 0)),
+            elf::SHT_RELA => Some((elf::Relocation::size(class, true), word, 0)),
+            elf::SHT_REL => Some((elf::Relocation::size(class, false), word, 0)),
+            elf::SHT_DYNAMIC => Some((2 * word, word, 0)),
+            elf::SHT_INIT_ARRAY | elf::SHT_FINI_ARRAY | elf::SHT_PREINIT_ARRAY => {
+                Some((word, word, 0))
+            }
+            elf::SHT_SYMTAB_SHNDX => Some((4, 4, 0)),
+            elf::SHT_GROUP => Some((4, 4, 4)),
+            elf::SHT_HASH => Some((1, 4, 12)),
+            elf::SHT_NOTE => Some((1, 4, 12)),
+            _ => None,
+        };
+        if let Some((entry_size, align, header_size)) = layout {
+            if !aligned(section.offset, align) {
+                return Err(invalid(index, "section data is misaligned"));
+            }
+            if contents.len() < header_size
+                || !(contents.len() - header_size).is_multiple_of(entry_size)
+            {
+                return Err(invalid(
+                    index,
+                    "section size is not a multiple of the entry size",
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Decodes a raw dynamic tag, following `xmas_elf::dynamic::Tag`.
+fn dynamic_tag(tag: u64) -> Result<Tag<u64>, ElfLoaderErr> {
+    Ok(match tag {
+        0 => Tag::Null,
+        1 => Tag::Needed,
+        2 => Tag::PltRelSize,
+        3 => Tag::Pltgot,
+        4 => Tag::Hash,
+        5 => Tag::StrTab,
+        6 => Tag::SymTab,
+        7 => Tag::Rela,
+        8 => Tag::RelaSize,
+        9 => Tag::RelaEnt,
+        10 => Tag::StrSize,
+        11 => Tag::SymEnt,
+        12 => Tag::Init,
+        13 => Tag::Fini,
+        14 => Tag::SoName,
+        15 => Tag::RPath,
+        16 => Tag::Symbolic,
+        17 => Tag::Rel,
+        18 => Tag::RelSize,
+        19 => Tag::RelEnt,
+        20 => Tag::PltRel,
+        21 => Tag::Debug,
+        22 => Tag::TextRel,
+        23 => Tag::JmpRel,
+        24 => Tag::BindNow,
+        25 => Tag::InitArray,
+        26 => Tag::FiniArray,
+        27 => Tag::InitArraySize,
+        28 => Tag::FiniArraySize,
+        29 => Tag::RunPath,
+        30 => Tag::Flags,
+        32 => Tag::PreInitArray,
+        33 => Tag::PreInitArraySize,
+        34 => Tag::SymTabShIndex,
+        0x6fff_fffb => Tag::Flags1,
+        0x6000_000d..=0x6fff_ffff => Tag::OsSpecific(tag),
+        0x7000_0000..=0x7fff_ffff => Tag::ProcessorSpecific(tag),
+        _ => {
+            return Err(ElfLoaderErr::ElfParser {
+                source: "Invalid tag value",
+            })
+        }
+    })
+}
+
+/// Converts a size from the file to `usize` for the loader callbacks.
+fn to_usize(value: u64) -> Result<usize, ElfLoaderErr> {
+    usize::try_from(value).map_err(|_| ElfLoaderErr::AddressOverflow {
+        source: "size does not fit in usize",
+    })
+}
+
+/// The LOAD segments (by their position among the LOAD segments) that
+/// [`ElfLoader::disposition`] asked to skip.
+///
+/// The decisions are recorded while loading, so `disposition` is called
+/// once per segment. Only the first [`SkippedSegments::CAPACITY`] LOAD
+/// segments can be skipped.
+#[derive(Clone, Copy)]
+struct SkippedSegments(u64);
+
+impl SkippedSegments {
+    const NONE: SkippedSegments = SkippedSegments(0);
+    const CAPACITY: usize = 64;
+
+    /// Skip the `ordinal`-th LOAD segment (program header `index`).
+    fn insert(&mut self, ordinal: usize, index: usize) -> Result<(), ElfLoaderErr> {
+        if ordinal >= SkippedSegments::CAPACITY {
+            return Err(ElfLoaderErr::InvalidSegment {
+                index,
+                reason: "only the first 64 LOAD segments can be skipped",
+            });
+        }
+        self.0 |= 1 << ordinal;
+        Ok(())
+    }
+
+    /// Is the `ordinal`-th LOAD segment skipped?
+    fn contains(&self, ordinal: usize) -> bool {
+        ordinal < SkippedSegments::CAPACITY && self.0 & (1 << ordinal) != 0
+    }
+
+    /// Does `vaddr` lie in a skipped segment of `binary`?
+    fn covers(&self, binary: &ElfBinary, vaddr: VAddr) -> bool {
+        self.0 != 0
+            && binary
+                .load_segments()
+                .enumerate()
+                .any(|(ordinal, segment)| self.contains(ordinal) && segment.contains(vaddr))
+    }
+}
+
+/// Forwards to the wrapped loader, but fails on relocations other than
+/// RELATIVE and IRELATIVE, see [`ElfBinary::load_static_pie`].
+struct SymbolIndependent<'a>(&'a mut dyn ElfLoader);
+
+impl ElfLoader for SymbolIndependent<'_> {
+    fn allocate(&mut self, requests: AllocateRequests) -> Result<(), ElfLoaderErr> {
+        self.0.allocate(requests)
+    }
+
+    fn allocate_coalesced(&mut self, allocation: CoalescedAllocation) -> Result<(), ElfLoaderErr> {
+        self.0.allocate_coalesced(allocation)
+    }
+
+    fn load(
+        &mut self,
+        flags: SegmentPermissions,
+        base: VAddr,
+        region: &[u8],
+    ) -> Result<(), ElfLoaderErr> {
+        self.0.load(flags, base, region)
+    }
+
+    fn relocate(&mut self, entry: RelocationEntry) -> Result<(), ElfLoaderErr> {
+        if !entry.rtype.is_relative() && entry.rtype.got_fixup_kind() != Some(GotFixupKind::Ifunc) {
+            return Err(ElfLoaderErr::NotStaticPie {
+                reason: "has relocations other than RELATIVE and IRELATIVE",
+            });
+        }
+        self.0.relocate(entry)
+    }
+
+    fn write_u64(&mut self, vaddr: VAddr, value: u64) -> Result<(), ElfLoaderErr> {
+        self.0.write_u64(vaddr, value)
+    }
+
+    fn write_u32(&mut self, vaddr: VAddr, value: u32) -> Result<(), ElfLoaderErr> {
+        self.0.write_u32(vaddr, value)
+    }
+
+    fn make_writable(&mut self, base: VAddr, size: usize) -> Result<(), ElfLoaderErr> {
+        self.0.make_writable(base, size)
+    }
+
+    fn restore_permissions(
+        &mut self,
+        base: VAddr,
+        size: usize,
+        flags: SegmentPermissions,
+    ) -> Result<(), ElfLoaderErr> {
+        self.0.restore_permissions(base, size, flags)
+    }
+
+    fn tls(
+        &mut self,
+        tdata_start: VAddr,
+        tdata: &[u8],
+        tbss_length: u64,
+        align: u64,
+    ) -> Result<(), ElfLoaderErr> {
+        self.0.tls(tdata_start, tdata, tbss_length, align)
+    }
+
+    fn openbsd_segment(
+        &mut self,
+        kind: openbsd::SegmentKind,
+        base: VAddr,
+        size: usize,
+    ) -> Result<(), ElfLoaderErr> {
+        self.0.openbsd_segment(kind, base, size)
+    }
+
+    fn make_readonly(&mut self, base: VAddr, size: usize) -> Result<(), ElfLoaderErr> {
+        self.0.make_readonly(base, size)
+    }
+
+    fn relro(&mut self, segment: RelroSegment) -> Result<(), ElfLoaderErr> {
+        self.0.relro(segment)
+    }
+
+    fn disposition(&mut self, header: ProgramHeader) -> Result<Disposition, ElfLoaderErr> {
+        self.0.disposition(header)
+    }
+
+    fn progress(&mut self, progress: LoadProgress) -> Result<(), ElfLoaderErr> {
+        self.0.progress(progress)
     }
 }
@@ -0,0 +1,124 @@
+//! Direct boot of kernel images by virtual machine monitors.
+//!
+//! A VMM that boots a kernel without firmware (e.g., an x86-64 `vmlinux`
+//! through PVH or the 64-bit Linux boot protocol) copies the LOAD segments
+//! to their physical addresses in guest memory, places its boot structures
+//! above the kernel and starts the vCPU at the entry point. [`KernelImage`]
+//! collects all of this in one call:
+//!
+//! ```no_run
+//! # fn boot(bytes: &[u8]) -> Result<(), elfloader::ElfLoaderErr> {
+//! let binary = elfloader::ElfBinary::new(bytes)?;
+//! let kernel = elfloader::boot::KernelImage::new(&binary)?;
+//! for segment in kernel.segments() {
+//!     // Copy `segment.data` to guest memory at `segment.paddr` and zero
+//!     // the rest of the `segment.mem_size` bytes.
+//! }
+//! let entry = kernel.pvh_entry_point.unwrap_or(kernel.entry_point);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::elf::{self, Class};
+use crate::note::Note;
+use crate::{ElfBinary, ElfLoaderErr, PAddr, SegmentPermissions};
+
+#[cfg(test)]
+mod test;
+
+/// Owner name of the notes of Xen guest kernels.
+pub const XEN_OWNER: &str = "Xen";
+/// Xen note holding the 32-bit physical entry point for PVH boot.
+pub const XEN_ELFNOTE_PHYS32_ENTRY: u32 = 18;
+
+/// A LOAD segment placed at its physical address.
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub struct BootSegment<'s> {
+    /// Index of the program header.
+    pub index: usize,
+    /// Physical address of the segment (`p_paddr`).
+    pub paddr: PAddr,
+    /// The file contents placed at `paddr`.
+    pub data: &'s [u8],
+    /// Size of the segment in memory, the bytes following `data` are
+    /// zeroed.
+    pub mem_size: u64,
+    /// Permissions of the segment.
+    pub flags: SegmentPermissions,
+}
+
+/// What a VMM needs to boot a 64-bit kernel image directly.
+pub struct KernelImage<'a, 's> {
+    binary: &'a ElfBinary<'s>,
+    /// The entry point (`e_entry`). Kernels linked for direct boot set it
+    /// to a physical address.
+    pub entry_point: PAddr,
+    /// The PVH entry point (`XEN_ELFNOTE_PHYS32_ENTRY`), if the kernel
+    /// supports PVH boot.
+    pub pvh_entry_point: Option<PAddr>,
+    /// End of the highest LOAD segment in physical memory, the first
+    /// address available for boot structures (command line, initrd etc.).
+    pub highest_address: PAddr,
+}
+
+impl<'a, 's> KernelImage<'a, 's> {
+    /// Collect the boot information of `binary`.
+    ///
+    /// Fails with `UnsupportedElfFormat` for ELF32 files and if the LOAD
+    /// segments are malformed.
+    pub fn new(binary: &'a ElfBinary<'s>) -> Result<KernelImage<'a, 's>, ElfLoaderErr> {
+        if binary.header.encoding.class != Class::Elf64 {
+            return Err(ElfLoaderErr::UnsupportedElfFormat);
+        }
+        // Checks the file ranges `segments` relies on
+        binary.check_segment_ranges()?;
+
+        let mut highest_address = 0;
+        for segment in binary.load_segments() {
+            let end = segment.paddr.checked_add(segment.mem_size).ok_or(
+                ElfLoaderErr::AddressOverflow {
+                    source: "p_paddr + p_memsz",
+                },
+            )?;
+            highest_address = highest_address.max(end);
+        }
+
+        Ok(KernelImage {
+            binary,
+            entry_point: binary.entry_point(),
+            pvh_entry_point: binary
+                .notes()
+                .find_map(|note| pvh_entry_point(&note, binary.header.encoding)),
+            highest_address,
+        })
+    }
+
+    /// The LOAD segments, at their physical addresses.
+    pub fn segments(&self) -> impl Iterator<Item = BootSegment<'s>> + 'a {
+        let input = self.binary.file.input;
+        self.binary.load_segments().map(move |segment| {
+            // The ranges were checked in `new`
+            let start = segment.offset as usize;
+            BootSegment {
+                index: segment.index,
+                paddr: segment.paddr,
+                data: &input[start..start + segment.file_size as usize],
+                mem_size: segment.mem_size,
+                flags: segment.flags.into(),
+            }
+        })
+    }
+}
+
+/// The entry point of a `XEN_ELFNOTE_PHYS32_ENTRY` note, whose descriptor
+/// is a 32-bit or 64-bit address.
+fn pvh_entry_point(note: &Note, encoding: elf::Encoding) -> Option<PAddr> {
+    if note.name != XEN_OWNER.as_bytes() || note.typ != XEN_ELFNOTE_PHYS32_ENTRY {
+        return None;
+    }
+    match note.desc.len() {
+        4 => encoding.u32(note.desc, 0).ok().map(PAddr::from),
+        8 => encoding.u64(note.desc, 0).ok(),
+        _ => None,
+    }
+}
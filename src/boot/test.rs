@@ -0,0 +1,67 @@
+use std::fs;
+use std::vec::Vec;
+
+use super::*;
+use crate::elf::{Encoding, Endianness};
+
+#[test]
+fn kernel_image() {
+    let binary_blob = fs::read("test/test_nopie.x86_64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+
+    let kernel = KernelImage::new(&binary).expect("Valid kernel image");
+    assert_eq!(kernel.entry_point, 0x400400);
+    assert_eq!(kernel.pvh_entry_point, None);
+    // The second LOAD segment ends at 0x600e10 + 0x228
+    assert_eq!(kernel.highest_address, 0x601038);
+
+    let segments: Vec<BootSegment> = kernel.segments().collect();
+    assert_eq!(segments.len(), 2);
+    assert_eq!(segments[0].paddr, 0x400000);
+    assert_eq!(segments[0].data, &binary_blob[..0x710]);
+    assert_eq!(
+        segments[0].flags,
+        SegmentPermissions::READ | SegmentPermissions::EXECUTE
+    );
+    assert_eq!(segments[1].paddr, 0x600e10);
+    assert_eq!(segments[1].data, &binary_blob[0xe10..0x1030]);
+    assert_eq!(segments[1].mem_size, 0x228);
+}
+
+#[test]
+fn reject_elf32() {
+    let binary_blob = fs::read("test/test_nopie.x86").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+    assert_eq!(
+        KernelImage::new(&binary).err(),
+        Some(ElfLoaderErr::UnsupportedElfFormat)
+    );
+}
+
+#[test]
+fn pvh_note() {
+    let encoding = Encoding {
+        class: Class::Elf64,
+        endianness: Endianness::Little,
+    };
+    let note = |name: &'static [u8], typ, desc: &'static [u8]| Note { name, typ, desc };
+
+    assert_eq!(
+        pvh_entry_point(&note(b"Xen", 18, &[0x00, 0x00, 0x00, 0x01]), encoding),
+        Some(0x100_0000)
+    );
+    assert_eq!(
+        pvh_entry_point(&note(b"Xen", 18, &[0x10, 0, 0, 0, 0, 0, 0, 0]), encoding),
+        Some(0x10)
+    );
+    // Other notes and malformed descriptors are ignored
+    assert_eq!(
+        pvh_entry_point(&note(b"GNU", 18, &[0x00, 0x00, 0x00, 0x01]), encoding),
+        None
+    );
+    assert_eq!(
+        pvh_entry_point(&note(b"Xen", 17, &[0x00, 0x00, 0x00, 0x01]), encoding),
+        None
+    );
+    assert_eq!(pvh_entry_point(&note(b"Xen", 18, &[0x01]), encoding), None);
+}
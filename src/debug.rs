@@ -0,0 +1,89 @@
+//! Debugger cooperation through the DT_DEBUG protocol.
+//!
+//! A dynamic linker publishes the list of loaded objects to debuggers by
+//! writing the address of an `r_debug` structure into the DT_DEBUG entry of
+//! the executable's dynamic section (see [`crate::ElfBinary::debug_slot`]).
+//! gdb then walks the `link_map` chain and sets a breakpoint on `r_brk` to
+//! be notified whenever the list changes.
+//!
+//! The types in this module mirror the glibc/SysV definitions using native
+//! word sizes, so they are only meaningful when the loader runs in the same
+//! address space layout as the loaded image (e.g. a user-space linker).
+
+use core::ptr;
+
+/// State of the link map list as reported in [`RDebug::r_state`].
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+#[repr(i32)]
+pub enum RState {
+    /// The list of loaded objects is consistent.
+    Consistent = 0,
+    /// An object is about to be added.
+    Add = 1,
+    /// An object is about to be removed.
+    Delete = 2,
+}
+
+/// Mirror of the `struct r_debug` a debugger expects at the DT_DEBUG address.
+#[derive(Debug)]
+#[repr(C)]
+pub struct RDebug {
+    /// Protocol version, always 1.
+    pub r_version: i32,
+    /// Head of the list of loaded objects.
+    pub r_map: *mut LinkMap,
+    /// Address of a function the debugger breaks on after list changes.
+    pub r_brk: usize,
+    /// Current state of the list.
+    pub r_state: RState,
+    /// Base address the dynamic linker was loaded at.
+    pub r_ldbase: usize,
+}
+
+impl RDebug {
+    /// Create an `RDebug` with an empty object list.
+    ///
+    /// `r_brk` should be the address of a function that is called after
+    /// every modification of the list (it can be empty, but must not be
+    /// inlined away).
+    pub fn new(r_brk: usize, r_ldbase: usize) -> RDebug {
+        RDebug {
+            r_version: 1,
+            r_map: ptr::null_mut(),
+            r_brk,
+            r_state: RState::Consistent,
+            r_ldbase,
+        }
+    }
+}
+
+/// Mirror of a `struct link_map` entry describing one loaded object.
+#[derive(Debug)]
+#[repr(C)]
+pub struct LinkMap {
+    /// Difference between the addresses in the ELF file and in memory.
+    pub l_addr: usize,
+    /// NUL terminated path of the object.
+    pub l_name: *const u8,
+    /// Address of the (rebased) dynamic section of the object.
+    pub l_ld: usize,
+    /// Next object in the list.
+    pub l_next: *mut LinkMap,
+    /// Previous object in the list.
+    pub l_prev: *mut LinkMap,
+}
+
+impl LinkMap {
+    /// Create an unlinked entry for an object loaded with bias `l_addr`.
+    ///
+    /// `l_name` must point to a NUL terminated string.
+    pub fn new(l_addr: usize, l_name: *const u8, l_ld: usize) -> LinkMap {
+        LinkMap {
+            l_addr,
+            l_name,
+            l_ld,
+            l_next: ptr::null_mut(),
+            l_prev: ptr::null_mut(),
+        }
+    }
+}
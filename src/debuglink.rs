@@ -0,0 +1,61 @@
+/// The contents of a `.gnu_debuglink` section.
+///
+/// Points to a separate file containing the debug information of the
+/// binary, together with the CRC32 of that file's contents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DebugLink<'s> {
+    /// File name (without directory) of the debug file.
+    pub filename: &'s str,
+    /// Expected CRC32 of the debug file.
+    pub crc: u32,
+}
+
+impl<'s> DebugLink<'s> {
+    /// Parse the raw contents of a `.gnu_debuglink` section.
+    ///
+    /// The section holds a NUL terminated file name, padded to a multiple
+    /// of four bytes, followed by the CRC32.
+    pub fn parse(data: &'s [u8]) -> Option<DebugLink<'s>> {
+        let len = data.iter().position(|&b| b == 0)?;
+        let filename = core::str::from_utf8(&data[..len]).ok()?;
+        let crc_offset = (len + 1 + 3) & !3;
+        let crc = data.get(crc_offset..crc_offset + 4)?;
+        Some(DebugLink {
+            filename,
+            crc: u32::from_le_bytes([crc[0], crc[1], crc[2], crc[3]]),
+        })
+    }
+
+    /// Returns true if `candidate` has the expected CRC.
+    pub fn matches(&self, candidate: &[u8]) -> bool {
+        crc32(candidate) == self.crc
+    }
+}
+
+/// Lookup table for the CRC32 (IEEE 802.3) polynomial used by gnu_debuglink.
+const CRC32_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 {
+                0xedb8_8320 ^ (crc >> 1)
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+/// Computes the CRC32 of `data` as done by `objcopy --add-gnu-debuglink`.
+pub fn crc32(data: &[u8]) -> u32 {
+    !data.iter().fold(!0u32, |crc, &b| {
+        CRC32_TABLE[((crc ^ b as u32) & 0xff) as usize] ^ (crc >> 8)
+    })
+}
@@ -0,0 +1,347 @@
+//! Access to DWARF and unwind sections for debuggers and symbolizers.
+//!
+//! [`DebugSections`] hands out the raw section contents together with the
+//! bias that translates the addresses recorded in them to the addresses the
+//! binary was loaded at. This is what `gimli`/`addr2line` need, e.g.:
+//!
+//! ```ignore
+//! let sections = binary.debug_sections(base);
+//! let dwarf = gimli::Dwarf::load(|id| {
+//!     Ok::<_, gimli::Error>(gimli::EndianSlice::new(
+//!         sections.section(id.name()),
+//!         gimli::LittleEndian,
+//!     ))
+//! })?;
+//! // Look up `pc - sections.bias` in `dwarf`
+//! ```
+//!
+//! Compressed sections (SHF_COMPRESSED) are returned as they are stored in
+//! the file.
+//!
+//! [`EhFrameHdr`] parses the binary search table of `.eh_frame_hdr`, which
+//! lets unwinders find the FDE of an address without scanning `.eh_frame`.
+
+use core::convert::TryFrom;
+
+use crate::elf::{self, Class};
+use crate::{ElfBinary, ElfLoaderErr, SectionFlags, VAddr};
+
+/// The DWARF and unwind sections of a binary loaded at a given base.
+///
+/// Missing sections are returned as empty slices.
+pub struct DebugSections<'a, 's> {
+    pub(crate) binary: &'a ElfBinary<'s>,
+    /// Difference between load addresses and the addresses in the file.
+    pub bias: u64,
+}
+
+macro_rules! section_accessor {
+    ($fn_name:ident, $name:expr) => {
+        #[doc = concat!("Contents of the `", $name, "` section.")]
+        pub fn $fn_name(&self) -> &'s [u8] {
+            self.section($name)
+        }
+    };
+}
+
+impl<'a, 's> DebugSections<'a, 's> {
+    /// Contents of the section `name`, or an empty slice if it is missing.
+    pub fn section(&self, name: &str) -> &'s [u8] {
+        self.binary.find_section_data(name).unwrap_or(&[])
+    }
+
+    /// The load address of the allocated section `name`.
+    ///
+    /// Returns None if the section is missing or not part of the loaded
+    /// image (as is the case for `.debug_*` sections).
+    pub fn section_address(&self, name: &str) -> Option<VAddr> {
+        self.binary
+            .sections()
+            .find(|s| s.name == name && s.flags.contains(SectionFlags::ALLOC))
+            .map(|s| self.to_load_address(s.address))
+    }
+
+    /// The parsed `.eh_frame_hdr` section, with all addresses translated to
+    /// load addresses.
+    ///
+    /// Returns None if the binary has no `.eh_frame_hdr` section.
+    pub fn eh_frame_hdr_table(&self) -> Option<Result<EhFrameHdr<'s>, ElfLoaderErr>> {
+        let section = self
+            .binary
+            .sections()
+            .find(|s| s.name == ".eh_frame_hdr" && s.flags.contains(SectionFlags::ALLOC))?;
+        Some(EhFrameHdr::parse(
+            self.eh_frame_hdr(),
+            section.address,
+            self.bias,
+            self.binary.header.encoding,
+        ))
+    }
+
+    /// Translate an address from the file to the load address.
+    pub fn to_load_address(&self, addr: VAddr) -> VAddr {
+        addr.wrapping_add(self.bias)
+    }
+
+    section_accessor!(debug_abbrev, ".debug_abbrev");
+    section_accessor!(debug_addr, ".debug_addr");
+    section_accessor!(debug_aranges, ".debug_aranges");
+    section_accessor!(debug_frame, ".debug_frame");
+    section_accessor!(debug_info, ".debug_info");
+    section_accessor!(debug_line, ".debug_line");
+    section_accessor!(debug_line_str, ".debug_line_str");
+    section_accessor!(debug_loc, ".debug_loc");
+    section_accessor!(debug_loclists, ".debug_loclists");
+    section_accessor!(debug_ranges, ".debug_ranges");
+    section_accessor!(debug_rnglists, ".debug_rnglists");
+    section_accessor!(debug_str, ".debug_str");
+    section_accessor!(debug_str_offsets, ".debug_str_offsets");
+    section_accessor!(eh_frame, ".eh_frame");
+    section_accessor!(eh_frame_hdr, ".eh_frame_hdr");
+}
+
+/// `DW_EH_PE_omit`: the value is not present.
+pub const DW_EH_PE_OMIT: u8 = 0xff;
+
+// Formats (low nibble) and applications (high nibble) of pointer
+// encodings, see the LSB "Exception Frames" section.
+const DW_EH_PE_ABSPTR: u8 = 0x00;
+const DW_EH_PE_ULEB128: u8 = 0x01;
+const DW_EH_PE_UDATA2: u8 = 0x02;
+const DW_EH_PE_UDATA4: u8 = 0x03;
+const DW_EH_PE_UDATA8: u8 = 0x04;
+const DW_EH_PE_SLEB128: u8 = 0x09;
+const DW_EH_PE_SDATA2: u8 = 0x0a;
+const DW_EH_PE_SDATA4: u8 = 0x0b;
+const DW_EH_PE_SDATA8: u8 = 0x0c;
+const DW_EH_PE_PCREL: u8 = 0x10;
+const DW_EH_PE_DATAREL: u8 = 0x30;
+const DW_EH_PE_INDIRECT: u8 = 0x80;
+
+/// The entry of an `.eh_frame_hdr` search table.
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub struct FdeEntry {
+    /// The lowest address covered by the FDE.
+    pub initial_location: VAddr,
+    /// The address of the FDE in `.eh_frame`.
+    pub fde_address: VAddr,
+}
+
+/// The contents of an `.eh_frame_hdr` section.
+///
+/// All addresses are load addresses: the addresses encoded relative to the
+/// section are computed from its address in the file and the `bias` is
+/// added.
+#[derive(Debug, Clone, Copy)]
+pub struct EhFrameHdr<'s> {
+    /// Version of the header, always 1.
+    pub version: u8,
+    /// `DW_EH_PE_*` encoding of the `.eh_frame` pointer.
+    pub eh_frame_ptr_encoding: u8,
+    /// `DW_EH_PE_*` encoding of the number of FDEs.
+    pub fde_count_encoding: u8,
+    /// `DW_EH_PE_*` encoding of the search table entries.
+    pub table_encoding: u8,
+    /// Address of the `.eh_frame` section.
+    pub eh_frame: VAddr,
+    /// Number of entries of the search table, sorted by initial location.
+    pub fde_count: usize,
+    data: &'s [u8],
+    table_offset: usize,
+    entry_size: usize,
+    address: VAddr,
+    bias: u64,
+    encoding: elf::Encoding,
+}
+
+impl<'s> EhFrameHdr<'s> {
+    /// Parses the `.eh_frame_hdr` `data` located at `address` in the file,
+    /// of a binary loaded with `bias`.
+    ///
+    /// Fails if the header is truncated or uses an unsupported encoding. A
+    /// header without a search table (an omitted FDE count or table
+    /// encoding) has `fde_count` 0.
+    pub fn parse(
+        data: &'s [u8],
+        address: VAddr,
+        bias: u64,
+        encoding: elf::Encoding,
+    ) -> Result<EhFrameHdr<'s>, ElfLoaderErr> {
+        let version = encoding.u8(data, 0)?;
+        if version != 1 {
+            return Err(ElfLoaderErr::ElfParser {
+                source: "Unsupported .eh_frame_hdr version",
+            });
+        }
+        let eh_frame_ptr_encoding = encoding.u8(data, 1)?;
+        let fde_count_encoding = encoding.u8(data, 2)?;
+        let table_encoding = encoding.u8(data, 3)?;
+
+        let mut hdr = EhFrameHdr {
+            version,
+            eh_frame_ptr_encoding,
+            fde_count_encoding,
+            table_encoding,
+            eh_frame: 0,
+            fde_count: 0,
+            data,
+            table_offset: 0,
+            entry_size: 0,
+            address,
+            bias,
+            encoding,
+        };
+        let (eh_frame, offset) = hdr.read_pointer(4, eh_frame_ptr_encoding)?;
+        hdr.eh_frame = eh_frame.wrapping_add(bias);
+        if fde_count_encoding == DW_EH_PE_OMIT || table_encoding == DW_EH_PE_OMIT {
+            return Ok(hdr);
+        }
+
+        let (fde_count, offset) = hdr.read_value(offset, fde_count_encoding)?;
+        let entry_size = hdr
+            .value_size(table_encoding)
+            .ok_or(ElfLoaderErr::ElfParser {
+                source: "Unsupported .eh_frame_hdr table encoding",
+            })?
+            * 2;
+        usize::try_from(fde_count)
+            .ok()
+            .and_then(|count| count.checked_mul(entry_size))
+            .and_then(|size| data.get(offset..)?.get(..size))
+            .ok_or(elf::ParseError::TableOverflow {
+                table: ".eh_frame_hdr search table",
+            })?;
+        hdr.fde_count = fde_count as usize;
+        hdr.table_offset = offset;
+        hdr.entry_size = entry_size;
+        Ok(hdr)
+    }
+
+    /// The search table entry with the given index.
+    pub fn entry(&self, index: usize) -> Option<FdeEntry> {
+        if index >= self.fde_count {
+            return None;
+        }
+        // The table was checked to lie within the section in `parse`
+        let offset = self.table_offset + index * self.entry_size;
+        let (initial_location, offset) = self.read_pointer(offset, self.table_encoding).ok()?;
+        let (fde_address, _) = self.read_pointer(offset, self.table_encoding).ok()?;
+        Some(FdeEntry {
+            initial_location: initial_location.wrapping_add(self.bias),
+            fde_address: fde_address.wrapping_add(self.bias),
+        })
+    }
+
+    /// The entries of the search table.
+    pub fn entries(&self) -> impl Iterator<Item = FdeEntry> + '_ {
+        (0..self.fde_count).filter_map(move |index| self.entry(index))
+    }
+
+    /// The entry of the FDE that may cover `pc` (a load address): the one
+    /// with the highest initial location at or below `pc`. The FDE itself
+    /// has to be checked for the end of its range.
+    pub fn lookup(&self, pc: VAddr) -> Option<FdeEntry> {
+        let (mut low, mut high) = (0, self.fde_count);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if self.entry(mid)?.initial_location <= pc {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        low.checked_sub(1).and_then(|index| self.entry(index))
+    }
+
+    /// Size of the values with the `DW_EH_PE_*` format of `encoding`, None
+    /// for variable length formats.
+    fn value_size(&self, encoding: u8) -> Option<usize> {
+        match encoding & 0x0f {
+            DW_EH_PE_ABSPTR => Some(self.encoding.class.word_size()),
+            DW_EH_PE_UDATA2 | DW_EH_PE_SDATA2 => Some(2),
+            DW_EH_PE_UDATA4 | DW_EH_PE_SDATA4 => Some(4),
+            DW_EH_PE_UDATA8 | DW_EH_PE_SDATA8 => Some(8),
+            _ => None,
+        }
+    }
+
+    /// Reads the value at `offset` with the format of `encoding`,
+    /// sign-extended to 64 bits for the signed formats. Returns the value
+    /// and the offset after it.
+    fn read_value(&self, offset: usize, encoding: u8) -> Result<(u64, usize), ElfLoaderErr> {
+        let data = self.data;
+        let e = self.encoding;
+        let value = match encoding & 0x0f {
+            DW_EH_PE_ULEB128 => return leb128(data, offset, false),
+            DW_EH_PE_SLEB128 => return leb128(data, offset, true),
+            DW_EH_PE_ABSPTR if self.encoding.class == Class::Elf32 => {
+                u64::from(e.u32(data, offset)?)
+            }
+            DW_EH_PE_ABSPTR | DW_EH_PE_UDATA8 | DW_EH_PE_SDATA8 => e.u64(data, offset)?,
+            DW_EH_PE_UDATA2 => u64::from(e.u16(data, offset)?),
+            DW_EH_PE_UDATA4 => u64::from(e.u32(data, offset)?),
+            DW_EH_PE_SDATA2 => e.u16(data, offset)? as i16 as u64,
+            DW_EH_PE_SDATA4 => e.u32(data, offset)? as i32 as u64,
+            _ => {
+                return Err(ElfLoaderErr::ElfParser {
+                    source: "Unsupported .eh_frame_hdr value format",
+                })
+            }
+        };
+        // Fixed size formats
+        let size = self.value_size(encoding).unwrap_or(0);
+        Ok((value, offset + size))
+    }
+
+    /// Reads the pointer at `offset` with the format and application of
+    /// `encoding` and returns its address in the file and the offset after
+    /// it.
+    fn read_pointer(&self, offset: usize, encoding: u8) -> Result<(VAddr, usize), ElfLoaderErr> {
+        let (value, next) = self.read_value(offset, encoding)?;
+        let base = match encoding & 0x70 {
+            DW_EH_PE_ABSPTR => 0,
+            DW_EH_PE_PCREL => self.address.wrapping_add(offset as u64),
+            DW_EH_PE_DATAREL => self.address,
+            _ => {
+                return Err(ElfLoaderErr::ElfParser {
+                    source: "Unsupported .eh_frame_hdr pointer encoding",
+                })
+            }
+        };
+        if encoding & DW_EH_PE_INDIRECT != 0 {
+            return Err(ElfLoaderErr::ElfParser {
+                source: "Unsupported .eh_frame_hdr pointer encoding",
+            });
+        }
+        let mut address = base.wrapping_add(value);
+        if self.encoding.class == Class::Elf32 {
+            address &= u64::from(u32::MAX);
+        }
+        Ok((address, next))
+    }
+}
+
+/// Reads the (U|S)LEB128 number at `offset` of `data` and returns it and the
+/// offset after it.
+fn leb128(data: &[u8], offset: usize, signed: bool) -> Result<(u64, usize), ElfLoaderErr> {
+    let mut value = 0u64;
+    let mut shift = 0;
+    let mut offset = offset;
+    loop {
+        let byte = *data.get(offset).ok_or(elf::ParseError::Truncated {
+            offset: offset as u64,
+            size: 1,
+        })?;
+        offset += 1;
+        if shift < 64 {
+            value |= u64::from(byte & 0x7f) << shift;
+        }
+        shift += 7;
+        if byte & 0x80 == 0 {
+            if signed && shift < 64 && byte & 0x40 != 0 {
+                value |= u64::MAX << shift;
+            }
+            return Ok((value, offset));
+        }
+    }
+}
@@ -0,0 +1,700 @@
+//! A minimal, zero-copy ELF parser.
+//!
+//! Decodes the parts of an ELF file the loader needs (the file header,
+//! program headers, dynamic entries, symbols and relocations) directly from
+//! the file contents. Both ELF classes and byte orders are supported, and
+//! malformed input results in a [`ParseError`] instead of a panic.
+//!
+//! [`crate::ElfBinary`] validates the file and program header table with
+//! this parser and decodes program headers, dynamic entries and relocations
+//! with it. Sections and symbol tables are still accessed through xmas-elf
+//! (`ElfBinary::file`) for now.
+
+use core::convert::TryFrom;
+use core::fmt;
+
+#[cfg(test)]
+mod test;
+
+/// The first four bytes of every ELF file.
+pub const ELF_MAGIC: [u8; 4] = [0x7f, b'E', b'L', b'F'];
+
+pub const PT_NULL: u32 = 0;
+pub const PT_LOAD: u32 = 1;
+pub const PT_DYNAMIC: u32 = 2;
+pub const PT_INTERP: u32 = 3;
+pub const PT_NOTE: u32 = 4;
+pub const PT_PHDR: u32 = 6;
+pub const PT_TLS: u32 = 7;
+pub const PT_GNU_EH_FRAME: u32 = 0x6474_e550;
+pub const PT_GNU_STACK: u32 = 0x6474_e551;
+pub const PT_GNU_RELRO: u32 = 0x6474_e552;
+
+pub const SHT_NULL: u32 = 0;
+pub const SHT_PROGBITS: u32 = 1;
+pub const SHT_SYMTAB: u32 = 2;
+pub const SHT_STRTAB: u32 = 3;
+pub const SHT_RELA: u32 = 4;
+pub const SHT_HASH: u32 = 5;
+pub const SHT_DYNAMIC: u32 = 6;
+pub const SHT_NOTE: u32 = 7;
+pub const SHT_NOBITS: u32 = 8;
+pub const SHT_REL: u32 = 9;
+pub const SHT_SHLIB: u32 = 10;
+pub const SHT_DYNSYM: u32 = 11;
+pub const SHT_INIT_ARRAY: u32 = 14;
+pub const SHT_FINI_ARRAY: u32 = 15;
+pub const SHT_PREINIT_ARRAY: u32 = 16;
+pub const SHT_GROUP: u32 = 17;
+pub const SHT_SYMTAB_SHNDX: u32 = 18;
+pub const SHT_LOOS: u32 = 0x6000_0000;
+
+/// Section indices from here on have a special meaning (`SHN_LORESERVE`).
+pub const SHN_LORESERVE: u16 = 0xff00;
+
+/// Terminates the dynamic entries.
+pub const DT_NULL: u64 = 0;
+
+/// Is `typ` a section type defined by the gABI or in one of the ranges
+/// reserved for OS, processor or application specific types?
+pub fn is_valid_section_type(typ: u32) -> bool {
+    typ <= SHT_SHLIB.max(SHT_DYNSYM)
+        || (SHT_INIT_ARRAY..=SHT_SYMTAB_SHNDX).contains(&typ)
+        || typ >= SHT_LOOS
+}
+
+/// A precise description of why an ELF file could not be parsed.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ParseError {
+    /// The `size` bytes at `offset` lie (partly) outside of the data.
+    Truncated { offset: u64, size: u64 },
+    /// The file does not start with [`ELF_MAGIC`].
+    BadMagic,
+    /// `EI_CLASS` is neither ELFCLASS32 nor ELFCLASS64.
+    InvalidClass(u8),
+    /// `EI_DATA` is neither ELFDATA2LSB nor ELFDATA2MSB.
+    InvalidEndianness(u8),
+    /// `EI_VERSION` is not EV_CURRENT.
+    InvalidVersion(u8),
+    /// The entries of `table` are smaller than the structure they hold.
+    InvalidEntrySize { table: &'static str, size: u16 },
+    /// The offset and size of `table` overflow.
+    TableOverflow { table: &'static str },
+    /// `table` has more entries than supported.
+    TooManyEntries { table: &'static str },
+    /// `what` is not aligned to the word size of the file.
+    Unaligned { what: &'static str },
+    /// The section header with the given index is malformed.
+    InvalidSection { index: u16, reason: &'static str },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Truncated { offset, size } => write!(
+                f,
+                "{:#x} bytes at offset {:#x} are outside of the data",
+                size, offset
+            ),
+            ParseError::BadMagic => write!(f, "Not an ELF file"),
+            ParseError::InvalidClass(class) => write!(f, "Invalid ELF class {}", class),
+            ParseError::InvalidEndianness(data) => write!(f, "Invalid ELF data encoding {}", data),
+            ParseError::InvalidVersion(version) => write!(f, "Invalid ELF version {}", version),
+            ParseError::InvalidEntrySize { table, size } => {
+                write!(f, "Invalid entry size {} of the {}", size, table)
+            }
+            ParseError::TableOverflow { table } => write!(f, "The {} overflows", table),
+            ParseError::TooManyEntries { table } => write!(f, "The {} has too many entries", table),
+            ParseError::Unaligned { what } => write!(f, "The {} is not aligned", what),
+            ParseError::InvalidSection { index, reason } => {
+                write!(f, "Invalid section header {}: {}", index, reason)
+            }
+        }
+    }
+}
+
+/// The word size of an ELF file.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Class {
+    Elf32,
+    Elf64,
+}
+
+impl Class {
+    /// Size of an address in bytes.
+    pub fn word_size(self) -> usize {
+        match self {
+            Class::Elf32 => 4,
+            Class::Elf64 => 8,
+        }
+    }
+}
+
+/// The byte order of an ELF file.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Endianness {
+    Little,
+    Big,
+}
+
+/// How the fields of an ELF file are encoded.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Encoding {
+    pub class: Class,
+    pub endianness: Endianness,
+}
+
+impl Encoding {
+    /// Reads the `size` byte (at most 8) integer at `offset` of `data`.
+    fn read(self, data: &[u8], offset: usize, size: usize) -> Result<u64, ParseError> {
+        let bytes = offset
+            .checked_add(size)
+            .and_then(|end| data.get(offset..end))
+            .ok_or(ParseError::Truncated {
+                offset: offset as u64,
+                size: size as u64,
+            })?;
+        let mut word = [0u8; 8];
+        match self.endianness {
+            Endianness::Little => {
+                word[..size].copy_from_slice(bytes);
+                Ok(u64::from_le_bytes(word))
+            }
+            Endianness::Big => {
+                word[8 - size..].copy_from_slice(bytes);
+                Ok(u64::from_be_bytes(word))
+            }
+        }
+    }
+
+    pub fn u8(self, data: &[u8], offset: usize) -> Result<u8, ParseError> {
+        self.read(data, offset, 1).map(|value| value as u8)
+    }
+
+    pub fn u16(self, data: &[u8], offset: usize) -> Result<u16, ParseError> {
+        self.read(data, offset, 2).map(|value| value as u16)
+    }
+
+    pub fn u32(self, data: &[u8], offset: usize) -> Result<u32, ParseError> {
+        self.read(data, offset, 4).map(|value| value as u32)
+    }
+
+    pub fn u64(self, data: &[u8], offset: usize) -> Result<u64, ParseError> {
+        self.read(data, offset, 8)
+    }
+
+    /// Reads an address or size, 4 bytes for ELF32 and 8 bytes for ELF64.
+    pub fn word(self, data: &[u8], offset: usize) -> Result<u64, ParseError> {
+        self.read(data, offset, self.class.word_size())
+    }
+}
+
+/// Returns the `size` bytes at `offset` of `data`.
+fn slice(data: &[u8], offset: u64, size: u64) -> Result<&[u8], ParseError> {
+    let truncated = ParseError::Truncated { offset, size };
+    let start = usize::try_from(offset).map_err(|_| truncated)?;
+    let end = usize::try_from(size)
+        .ok()
+        .and_then(|size| start.checked_add(size))
+        .ok_or(truncated)?;
+    data.get(start..end).ok_or(truncated)
+}
+
+/// The ELF file header (`Elf32_Ehdr`/`Elf64_Ehdr`).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct FileHeader {
+    pub encoding: Encoding,
+    /// `EI_OSABI`
+    pub os_abi: u8,
+    /// `EI_ABIVERSION`
+    pub abi_version: u8,
+    /// `e_type`
+    pub typ: u16,
+    /// `e_machine`
+    pub machine: u16,
+    /// `e_version`
+    pub version: u32,
+    /// `e_entry`
+    pub entry: u64,
+    /// `e_phoff`
+    pub phoff: u64,
+    /// `e_shoff`
+    pub shoff: u64,
+    /// `e_flags`
+    pub flags: u32,
+    /// `e_ehsize`
+    pub ehsize: u16,
+    /// `e_phentsize`
+    pub phentsize: u16,
+    /// `e_phnum`
+    pub phnum: u16,
+    /// `e_shentsize`
+    pub shentsize: u16,
+    /// `e_shnum`
+    pub shnum: u16,
+    /// `e_shstrndx`
+    pub shstrndx: u16,
+}
+
+impl FileHeader {
+    /// Parse the file header at the start of `data`.
+    pub fn parse(data: &[u8]) -> Result<FileHeader, ParseError> {
+        let ident = slice(data, 0, 16)?;
+        if ident[..4] != ELF_MAGIC {
+            return Err(ParseError::BadMagic);
+        }
+        let class = match ident[4] {
+            1 => Class::Elf32,
+            2 => Class::Elf64,
+            class => return Err(ParseError::InvalidClass(class)),
+        };
+        let endianness = match ident[5] {
+            1 => Endianness::Little,
+            2 => Endianness::Big,
+            data => return Err(ParseError::InvalidEndianness(data)),
+        };
+        if ident[6] != 1 {
+            return Err(ParseError::InvalidVersion(ident[6]));
+        }
+
+        let encoding = Encoding { class, endianness };
+        let word = class.word_size();
+        // e_entry, e_phoff and e_shoff are words, the rest is fixed size
+        let flags = 24 + 3 * word;
+        Ok(FileHeader {
+            encoding,
+            os_abi: ident[7],
+            abi_version: ident[8],
+            typ: encoding.u16(data, 16)?,
+            machine: encoding.u16(data, 18)?,
+            version: encoding.u32(data, 20)?,
+            entry: encoding.word(data, 24)?,
+            phoff: encoding.word(data, 24 + word)?,
+            shoff: encoding.word(data, 24 + 2 * word)?,
+            flags: encoding.u32(data, flags)?,
+            ehsize: encoding.u16(data, flags + 4)?,
+            phentsize: encoding.u16(data, flags + 6)?,
+            phnum: encoding.u16(data, flags + 8)?,
+            shentsize: encoding.u16(data, flags + 10)?,
+            shnum: encoding.u16(data, flags + 12)?,
+            shstrndx: encoding.u16(data, flags + 14)?,
+        })
+    }
+
+    /// The program headers of the file `data`.
+    ///
+    /// Fails if the program header table does not lie within `data`, so the
+    /// returned iterator yields exactly `e_phnum` headers.
+    pub fn program_headers<'s>(&self, data: &'s [u8]) -> Result<ProgramHeaders<'s>, ParseError> {
+        let (table, entry_size) = self.table(
+            data,
+            "program header table",
+            self.phoff,
+            self.phnum,
+            self.phentsize,
+            ProgramHeader::size(self.encoding.class),
+        )?;
+        Ok(ProgramHeaders {
+            table,
+            entry_size,
+            encoding: self.encoding,
+            index: 0,
+        })
+    }
+
+    /// The section headers of the file `data`.
+    ///
+    /// Fails if the section header table does not lie within `data` or has
+    /// more than `SHN_LORESERVE` entries.
+    pub fn section_headers<'s>(&self, data: &'s [u8]) -> Result<SectionHeaders<'s>, ParseError> {
+        let name = "section header table";
+        if self.shnum >= SHN_LORESERVE {
+            return Err(ParseError::TooManyEntries { table: name });
+        }
+        let (table, entry_size) = self.table(
+            data,
+            name,
+            self.shoff,
+            self.shnum,
+            self.shentsize,
+            SectionHeader::size(self.encoding.class),
+        )?;
+        Ok(SectionHeaders {
+            table,
+            entry_size,
+            encoding: self.encoding,
+            index: 0,
+        })
+    }
+
+    /// The `count` entries of `entry_size` bytes at `offset` of `data`.
+    fn table<'s>(
+        &self,
+        data: &'s [u8],
+        table: &'static str,
+        offset: u64,
+        count: u16,
+        entry_size: u16,
+        min_entry_size: usize,
+    ) -> Result<(&'s [u8], usize), ParseError> {
+        if count == 0 {
+            return Ok((&[], 0));
+        }
+        if usize::from(entry_size) < min_entry_size {
+            return Err(ParseError::InvalidEntrySize {
+                table,
+                size: entry_size,
+            });
+        }
+        let size = u64::from(entry_size) * u64::from(count);
+        offset
+            .checked_add(size)
+            .ok_or(ParseError::TableOverflow { table })?;
+        Ok((slice(data, offset, size)?, usize::from(entry_size)))
+    }
+}
+
+/// A program header (`Elf32_Phdr`/`Elf64_Phdr`).
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ProgramHeader {
+    /// `p_type`
+    pub typ: u32,
+    /// `p_flags`
+    pub flags: u32,
+    /// `p_offset`
+    pub offset: u64,
+    /// `p_vaddr`
+    pub vaddr: u64,
+    /// `p_paddr`
+    pub paddr: u64,
+    /// `p_filesz`
+    pub file_size: u64,
+    /// `p_memsz`
+    pub mem_size: u64,
+    /// `p_align`
+    pub align: u64,
+}
+
+impl ProgramHeader {
+    /// Size of a program header in a file of class `class`.
+    pub fn size(class: Class) -> usize {
+        match class {
+            Class::Elf32 => 32,
+            Class::Elf64 => 56,
+        }
+    }
+
+    /// Decode the program header at the start of `data`.
+    pub fn parse(data: &[u8], encoding: Encoding) -> Result<ProgramHeader, ParseError> {
+        let word = |offset| encoding.word(data, offset);
+        match encoding.class {
+            Class::Elf32 => Ok(ProgramHeader {
+                typ: encoding.u32(data, 0)?,
+                offset: word(4)?,
+                vaddr: word(8)?,
+                paddr: word(12)?,
+                file_size: word(16)?,
+                mem_size: word(20)?,
+                flags: encoding.u32(data, 24)?,
+                align: word(28)?,
+            }),
+            Class::Elf64 => Ok(ProgramHeader {
+                typ: encoding.u32(data, 0)?,
+                flags: encoding.u32(data, 4)?,
+                offset: word(8)?,
+                vaddr: word(16)?,
+                paddr: word(24)?,
+                file_size: word(32)?,
+                mem_size: word(40)?,
+                align: word(48)?,
+            }),
+        }
+    }
+
+    /// The file contents of the segment.
+    pub fn data<'s>(&self, file: &'s [u8]) -> Result<&'s [u8], ParseError> {
+        slice(file, self.offset, self.file_size)
+    }
+}
+
+/// An iterator over the program headers of a file.
+///
+/// Created by [`FileHeader::program_headers`].
+#[derive(Clone, Debug)]
+pub struct ProgramHeaders<'s> {
+    table: &'s [u8],
+    entry_size: usize,
+    encoding: Encoding,
+    index: usize,
+}
+
+impl<'s> Iterator for ProgramHeaders<'s> {
+    type Item = ProgramHeader;
+
+    fn next(&mut self) -> Option<ProgramHeader> {
+        let start = self.index.checked_mul(self.entry_size)?;
+        let entry = self.table.get(start..start.checked_add(self.entry_size)?)?;
+        self.index += 1;
+        ProgramHeader::parse(entry, self.encoding).ok()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = match self.entry_size {
+            0 => 0,
+            size => (self.table.len() / size).saturating_sub(self.index),
+        };
+        (len, Some(len))
+    }
+}
+
+impl<'s> ExactSizeIterator for ProgramHeaders<'s> {}
+
+/// A section header (`Elf32_Shdr`/`Elf64_Shdr`).
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct SectionHeader {
+    /// `sh_name`, an offset into the section name string table.
+    pub name: u32,
+    /// `sh_type`
+    pub typ: u32,
+    /// `sh_flags`
+    pub flags: u64,
+    /// `sh_addr`
+    pub addr: u64,
+    /// `sh_offset`
+    pub offset: u64,
+    /// `sh_size`
+    pub size: u64,
+    /// `sh_link`
+    pub link: u32,
+    /// `sh_info`
+    pub info: u32,
+    /// `sh_addralign`
+    pub addralign: u64,
+    /// `sh_entsize`
+    pub entsize: u64,
+}
+
+impl SectionHeader {
+    /// Size of a section header in a file of class `class`.
+    pub fn size(class: Class) -> usize {
+        match class {
+            Class::Elf32 => 40,
+            Class::Elf64 => 64,
+        }
+    }
+
+    /// Decode the section header at the start of `data`.
+    pub fn parse(data: &[u8], encoding: Encoding) -> Result<SectionHeader, ParseError> {
+        let word = encoding.class.word_size();
+        // sh_name and sh_type are followed by 4 words, sh_link, sh_info and 2 words
+        let link = 8 + 4 * word;
+        Ok(SectionHeader {
+            name: encoding.u32(data, 0)?,
+            typ: encoding.u32(data, 4)?,
+            flags: encoding.word(data, 8)?,
+            addr: encoding.word(data, 8 + word)?,
+            offset: encoding.word(data, 8 + 2 * word)?,
+            size: encoding.word(data, 8 + 3 * word)?,
+            link: encoding.u32(data, link)?,
+            info: encoding.u32(data, link + 4)?,
+            addralign: encoding.word(data, link + 8)?,
+            entsize: encoding.word(data, link + 8 + word)?,
+        })
+    }
+
+    /// The file contents of the section (also for SHT_NOBITS sections,
+    /// which have none).
+    pub fn data<'s>(&self, file: &'s [u8]) -> Result<&'s [u8], ParseError> {
+        slice(file, self.offset, self.size)
+    }
+}
+
+/// An iterator over the section headers of a file.
+///
+/// Created by [`FileHeader::section_headers`].
+#[derive(Clone, Debug)]
+pub struct SectionHeaders<'s> {
+    table: &'s [u8],
+    entry_size: usize,
+    encoding: Encoding,
+    index: usize,
+}
+
+impl<'s> Iterator for SectionHeaders<'s> {
+    type Item = SectionHeader;
+
+    fn next(&mut self) -> Option<SectionHeader> {
+        let start = self.index.checked_mul(self.entry_size)?;
+        let entry = self.table.get(start..start.checked_add(self.entry_size)?)?;
+        self.index += 1;
+        SectionHeader::parse(entry, self.encoding).ok()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = match self.entry_size {
+            0 => 0,
+            size => (self.table.len() / size).saturating_sub(self.index),
+        };
+        (len, Some(len))
+    }
+}
+
+impl<'s> ExactSizeIterator for SectionHeaders<'s> {}
+
+/// A dynamic entry (`Elf32_Dyn`/`Elf64_Dyn`).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Dyn {
+    /// `d_tag`
+    pub tag: u64,
+    /// `d_val` or `d_ptr`
+    pub value: u64,
+}
+
+/// An iterator over the entries of a dynamic segment, up to the
+/// terminating DT_NULL entry.
+#[derive(Clone, Debug)]
+pub struct DynamicEntries<'s> {
+    data: &'s [u8],
+    encoding: Encoding,
+}
+
+impl<'s> DynamicEntries<'s> {
+    /// Iterate over the dynamic entries in `data`, the contents of a
+    /// PT_DYNAMIC segment.
+    pub fn new(data: &'s [u8], encoding: Encoding) -> DynamicEntries<'s> {
+        DynamicEntries { data, encoding }
+    }
+}
+
+impl<'s> Iterator for DynamicEntries<'s> {
+    type Item = Dyn;
+
+    fn next(&mut self) -> Option<Dyn> {
+        let word = self.encoding.class.word_size();
+        let entry = Dyn {
+            tag: self.encoding.word(self.data, 0).ok()?,
+            value: self.encoding.word(self.data, word).ok()?,
+        };
+        if entry.tag == DT_NULL {
+            self.data = &[];
+            return None;
+        }
+        self.data = self.data.get(2 * word..)?;
+        Some(entry)
+    }
+}
+
+/// A symbol table entry (`Elf32_Sym`/`Elf64_Sym`).
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Symbol {
+    /// `st_name`, an offset into the string table.
+    pub name: u32,
+    /// `st_info`
+    pub info: u8,
+    /// `st_other`
+    pub other: u8,
+    /// `st_shndx`
+    pub shndx: u16,
+    /// `st_value`
+    pub value: u64,
+    /// `st_size`
+    pub size: u64,
+}
+
+impl Symbol {
+    /// Size of a symbol table entry in a file of class `class`.
+    pub fn size(class: Class) -> usize {
+        match class {
+            Class::Elf32 => 16,
+            Class::Elf64 => 24,
+        }
+    }
+
+    /// Decode the symbol table entry at the start of `data`.
+    pub fn parse(data: &[u8], encoding: Encoding) -> Result<Symbol, ParseError> {
+        match encoding.class {
+            Class::Elf32 => Ok(Symbol {
+                name: encoding.u32(data, 0)?,
+                value: encoding.word(data, 4)?,
+                size: encoding.word(data, 8)?,
+                info: encoding.u8(data, 12)?,
+                other: encoding.u8(data, 13)?,
+                shndx: encoding.u16(data, 14)?,
+            }),
+            Class::Elf64 => Ok(Symbol {
+                name: encoding.u32(data, 0)?,
+                info: encoding.u8(data, 4)?,
+                other: encoding.u8(data, 5)?,
+                shndx: encoding.u16(data, 6)?,
+                value: encoding.u64(data, 8)?,
+                size: encoding.u64(data, 16)?,
+            }),
+        }
+    }
+
+    /// The symbol binding (`STB_*`).
+    pub fn bind(&self) -> u8 {
+        self.info >> 4
+    }
+
+    /// The symbol type (`STT_*`).
+    pub fn typ(&self) -> u8 {
+        self.info & 0xf
+    }
+}
+
+/// A REL or RELA relocation entry (`Elf*_Rel`/`Elf*_Rela`).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Relocation {
+    /// `r_offset`
+    pub offset: u64,
+    /// `r_info`
+    pub info: u64,
+    /// `r_addend`, None for REL entries.
+    pub addend: Option<u64>,
+}
+
+impl Relocation {
+    /// Size of a REL (or RELA if `has_addend`) entry in a file of class `class`.
+    pub fn size(class: Class, has_addend: bool) -> usize {
+        if has_addend {
+            3 * class.word_size()
+        } else {
+            2 * class.word_size()
+        }
+    }
+
+    /// Decode the REL or RELA entry at the start of `data`.
+    pub fn parse(
+        data: &[u8],
+        encoding: Encoding,
+        has_addend: bool,
+    ) -> Result<Relocation, ParseError> {
+        let word = encoding.class.word_size();
+        Ok(Relocation {
+            offset: encoding.word(data, 0)?,
+            info: encoding.word(data, word)?,
+            addend: if has_addend {
+                Some(encoding.word(data, 2 * word)?)
+            } else {
+                None
+            },
+        })
+    }
+
+    /// The symbol table index of the entry.
+    pub fn symbol(&self, class: Class) -> u32 {
+        match class {
+            Class::Elf32 => (self.info >> 8) as u32,
+            Class::Elf64 => (self.info >> 32) as u32,
+        }
+    }
+
+    /// The (architecture specific) relocation type of the entry.
+    pub fn typ(&self, class: Class) -> u32 {
+        match class {
+            Class::Elf32 => (self.info & 0xff) as u32,
+            Class::Elf64 => self.info as u32,
+        }
+    }
+}
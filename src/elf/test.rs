@@ -0,0 +1,404 @@
+use super::*;
+use crate::{ElfBinary, ElfLoaderErr};
+use std::fs;
+use std::vec::Vec;
+use xmas_elf::sections::SectionData;
+use xmas_elf::symbol_table::Entry;
+
+const LE64: Encoding = Encoding {
+    class: Class::Elf64,
+    endianness: Endianness::Little,
+};
+
+#[test]
+fn check_file_header() {
+    let binary_blob = fs::read("test/test.x86_64").expect("Can't read binary");
+    let header = FileHeader::parse(&binary_blob).expect("Valid header");
+    assert_eq!(header.encoding, LE64);
+    assert_eq!(header.typ, 3);
+    assert_eq!(header.machine, 62);
+    assert_eq!(header.entry, 0x540);
+    assert_eq!(header.phoff, 64);
+    assert_eq!((header.phentsize, header.phnum), (56, 9));
+
+    let binary_blob = fs::read("test/test.x86").expect("Can't read binary");
+    let header = FileHeader::parse(&binary_blob).expect("Valid header");
+    assert_eq!(header.encoding.class, Class::Elf32);
+    assert_eq!(header.machine, 3);
+    assert_eq!(header.entry, 0x1060);
+    assert_eq!(header.phoff, 52);
+    assert_eq!((header.phentsize, header.phnum), (32, 11));
+}
+
+#[test]
+fn check_program_headers() {
+    for file in ["test/test.x86_64", "test/test.x86", "test/tls.aarch64"] {
+        let binary_blob = fs::read(file).expect("Can't read binary");
+        let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+        let header = FileHeader::parse(&binary_blob).expect("Valid header");
+        let headers: Vec<ProgramHeader> = header
+            .program_headers(&binary_blob)
+            .expect("Valid program headers")
+            .collect();
+
+        assert_eq!(headers.len(), binary.program_headers().count());
+        for (ours, theirs) in headers.iter().zip(binary.program_headers()) {
+            assert_eq!(ours.flags, theirs.flags().0);
+            assert_eq!(ours.offset, theirs.offset());
+            assert_eq!(ours.vaddr, theirs.virtual_addr());
+            assert_eq!(ours.paddr, theirs.physical_addr());
+            assert_eq!(ours.file_size, theirs.file_size());
+            assert_eq!(ours.mem_size, theirs.mem_size());
+            assert_eq!(ours.align, theirs.align());
+        }
+    }
+
+    // readelf -l: the last LOAD segment and the dynamic segment
+    let binary_blob = fs::read("test/test.x86").expect("Can't read binary");
+    let header = FileHeader::parse(&binary_blob).expect("Valid header");
+    let mut headers = header.program_headers(&binary_blob).unwrap();
+    assert_eq!(headers.len(), 11);
+    let dynamic = headers.find(|p| p.typ == PT_DYNAMIC).unwrap();
+    assert_eq!((dynamic.offset, dynamic.vaddr), (0x2efc, 0x3efc));
+    assert_eq!(dynamic.data(&binary_blob).unwrap().len(), 0xf0);
+}
+
+#[test]
+fn check_big_endian() {
+    let mut data = [0u8; 64];
+    data[..8].copy_from_slice(&[0x7f, b'E', b'L', b'F', 2, 2, 1, 0]);
+    data[16..18].copy_from_slice(&2u16.to_be_bytes());
+    data[24..32].copy_from_slice(&0x1234_5678u64.to_be_bytes());
+    data[54..56].copy_from_slice(&56u16.to_be_bytes());
+
+    let header = FileHeader::parse(&data).expect("Valid header");
+    assert_eq!(header.encoding.endianness, Endianness::Big);
+    assert_eq!(header.typ, 2);
+    assert_eq!(header.entry, 0x1234_5678);
+    assert_eq!(header.program_headers(&data).unwrap().count(), 0);
+}
+
+#[test]
+fn check_parse_errors() {
+    let binary_blob = fs::read("test/test.x86_64").expect("Can't read binary");
+
+    assert_eq!(
+        FileHeader::parse(&binary_blob[..10]),
+        Err(ParseError::Truncated {
+            offset: 0,
+            size: 16
+        })
+    );
+    assert_eq!(
+        FileHeader::parse(&binary_blob[..40]),
+        Err(ParseError::Truncated {
+            offset: 40,
+            size: 8
+        })
+    );
+    assert_eq!(FileHeader::parse(&[0u8; 64]), Err(ParseError::BadMagic));
+
+    let mut blob = binary_blob.clone();
+    blob[4] = 3;
+    assert_eq!(FileHeader::parse(&blob), Err(ParseError::InvalidClass(3)));
+    let mut blob = binary_blob.clone();
+    blob[5] = 0;
+    assert_eq!(
+        FileHeader::parse(&blob),
+        Err(ParseError::InvalidEndianness(0))
+    );
+    let mut blob = binary_blob.clone();
+    blob[6] = 2;
+    assert_eq!(FileHeader::parse(&blob), Err(ParseError::InvalidVersion(2)));
+
+    // The program header table doesn't fit in the file
+    let header = FileHeader::parse(&binary_blob).unwrap();
+    assert_eq!(
+        header.program_headers(&binary_blob[..0x100]).err(),
+        Some(ParseError::Truncated {
+            offset: 64,
+            size: 9 * 56
+        })
+    );
+    let mut blob = binary_blob.clone();
+    blob[0x20..0x28].copy_from_slice(&u64::MAX.to_le_bytes());
+    let header = FileHeader::parse(&blob).unwrap();
+    assert_eq!(
+        header.program_headers(&blob).err(),
+        Some(ParseError::TableOverflow {
+            table: "program header table"
+        })
+    );
+    assert_eq!(
+        ElfBinary::new(blob.as_slice()).err(),
+        Some(ElfLoaderErr::Parse {
+            error: ParseError::TableOverflow {
+                table: "program header table"
+            }
+        })
+    );
+    let mut blob = binary_blob.clone();
+    blob[0x36..0x38].copy_from_slice(&8u16.to_le_bytes());
+    let header = FileHeader::parse(&blob).unwrap();
+    assert_eq!(
+        header.program_headers(&blob).err(),
+        Some(ParseError::InvalidEntrySize {
+            table: "program header table",
+            size: 8
+        })
+    );
+}
+
+#[test]
+fn check_dynamic_entries() {
+    let binary_blob = fs::read("test/test.x86_64").expect("Can't read binary");
+    let header = FileHeader::parse(&binary_blob).unwrap();
+    let dynamic = header
+        .program_headers(&binary_blob)
+        .unwrap()
+        .find(|p| p.typ == PT_DYNAMIC)
+        .unwrap();
+    let entries: Vec<Dyn> =
+        DynamicEntries::new(dynamic.data(&binary_blob).unwrap(), LE64).collect();
+
+    // readelf -d: 27 entries including the terminating DT_NULL
+    assert_eq!(entries.len(), 26);
+    assert_eq!(entries[0], Dyn { tag: 1, value: 1 });
+    assert_eq!(
+        entries[1],
+        Dyn {
+            tag: 0xc,
+            value: 0x4f0
+        }
+    );
+
+    // A truncated segment ends the iteration
+    let data = &dynamic.data(&binary_blob).unwrap()[..20];
+    assert_eq!(DynamicEntries::new(data, LE64).count(), 1);
+}
+
+#[test]
+fn check_symbols() {
+    for file in ["test/test.x86_64", "test/test.x86"] {
+        let binary_blob = fs::read(file).expect("Can't read binary");
+        let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+        let header = FileHeader::parse(&binary_blob).unwrap();
+        let section = binary.file.find_section_by_name(".dynsym").unwrap();
+        let raw = section.raw_data(&binary.file);
+        let size = Symbol::size(header.encoding.class);
+        let ours: Vec<Symbol> = raw
+            .chunks_exact(size)
+            .map(|entry| Symbol::parse(entry, header.encoding).unwrap())
+            .collect();
+
+        macro_rules! compare {
+            ($theirs:expr) => {{
+                assert_eq!(ours.len(), $theirs.len());
+                for (ours, theirs) in ours.iter().zip($theirs.iter()) {
+                    assert_eq!(ours.name, theirs.name());
+                    assert_eq!(ours.info, theirs.info());
+                    assert_eq!(ours.shndx, theirs.shndx());
+                    assert_eq!(ours.value, theirs.value() as u64);
+                    assert_eq!(ours.size, theirs.size() as u64);
+                }
+            }};
+        }
+        match section.get_data(&binary.file).unwrap() {
+            SectionData::DynSymbolTable64(theirs) => compare!(theirs),
+            SectionData::DynSymbolTable32(theirs) => compare!(theirs),
+            _ => panic!("Not a symbol table"),
+        }
+    }
+
+    // readelf --dyn-syms: printf is a global function
+    let binary_blob = fs::read("test/test.x86_64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).unwrap();
+    let raw = binary
+        .file
+        .find_section_by_name(".dynsym")
+        .unwrap()
+        .raw_data(&binary.file);
+    let printf = Symbol::parse(&raw[2 * 24..], LE64).unwrap();
+    assert_eq!((printf.bind(), printf.typ()), (1, 2));
+    assert!(Symbol::parse(&raw[raw.len() - 8..], LE64).is_err());
+}
+
+#[test]
+fn check_relocations() {
+    let mut rela = [0u8; 24];
+    rela[..8].copy_from_slice(&0x200db8u64.to_le_bytes());
+    rela[8..16].copy_from_slice(&((2u64 << 32) | 7).to_le_bytes());
+    rela[16..].copy_from_slice(&0x640u64.to_le_bytes());
+    let entry = Relocation::parse(&rela, LE64, true).unwrap();
+    assert_eq!(entry.offset, 0x200db8);
+    assert_eq!(entry.addend, Some(0x640));
+    assert_eq!(
+        (entry.symbol(Class::Elf64), entry.typ(Class::Elf64)),
+        (2, 7)
+    );
+    assert!(Relocation::parse(&rela[..16], LE64, true).is_err());
+    assert_eq!(
+        Relocation::parse(&rela[..16], LE64, false).unwrap().addend,
+        None
+    );
+
+    let le32 = Encoding {
+        class: Class::Elf32,
+        endianness: Endianness::Little,
+    };
+    let mut rel = [0u8; 8];
+    rel[..4].copy_from_slice(&0x3ef4u32.to_le_bytes());
+    rel[4..].copy_from_slice(&((5u32 << 8) | 1).to_le_bytes());
+    let entry = Relocation::parse(&rel, le32, false).unwrap();
+    assert_eq!(entry.offset, 0x3ef4);
+    assert_eq!(
+        (entry.symbol(Class::Elf32), entry.typ(Class::Elf32)),
+        (5, 1)
+    );
+    assert_eq!(Relocation::size(Class::Elf32, false), 8);
+}
+
+#[test]
+fn check_section_headers() {
+    for file in ["test/test.x86_64", "test/test.x86"] {
+        let binary_blob = fs::read(file).expect("Can't read binary");
+        let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+        let header = FileHeader::parse(&binary_blob).unwrap();
+        let headers: Vec<SectionHeader> = header.section_headers(&binary_blob).unwrap().collect();
+
+        assert_eq!(headers.len(), binary.file.section_iter().count());
+        for (ours, theirs) in headers.iter().zip(binary.file.section_iter()) {
+            assert_eq!(ours.name, theirs.name());
+            assert_eq!(ours.offset, theirs.offset());
+            assert_eq!(ours.size, theirs.size());
+            assert_eq!(ours.addr, theirs.address());
+            assert_eq!(ours.link, theirs.link());
+            assert_eq!(ours.info, theirs.info());
+            assert_eq!(ours.flags, theirs.flags());
+        }
+    }
+
+    // readelf -S: section 21 is .dynamic
+    let binary_blob = fs::read("test/test.x86_64").expect("Can't read binary");
+    let header = FileHeader::parse(&binary_blob).unwrap();
+    let dynamic = header
+        .section_headers(&binary_blob)
+        .unwrap()
+        .nth(21)
+        .unwrap();
+    assert_eq!(dynamic.typ, SHT_DYNAMIC);
+    assert_eq!(
+        (dynamic.offset, dynamic.size, dynamic.entsize),
+        (0xdc8, 0x1f0, 16)
+    );
+}
+
+/// Inputs found by fuzzing which used to panic, as patches of
+/// `test/test.x86_64` (section headers at 0x1930, 64 bytes each).
+#[test]
+fn check_fuzz_corpus() {
+    let binary_blob = fs::read("test/test.x86_64").expect("Can't read binary");
+    let section = |index: usize, field: usize| 0x1930 + index * 64 + field;
+    let invalid = |index, reason| ParseError::InvalidSection { index, reason };
+
+    let corpus: [(usize, &[u8], ParseError); 8] = [
+        // e_shoff is only 4 byte aligned
+        (
+            0x28,
+            &0x1924u64.to_le_bytes(),
+            ParseError::Unaligned {
+                what: "section header table",
+            },
+        ),
+        // e_shnum is a reserved index
+        (
+            0x3c,
+            &0xff00u16.to_le_bytes(),
+            ParseError::TooManyEntries {
+                table: "section header table",
+            },
+        ),
+        // e_shstrndx is past the section header table
+        (
+            0x3e,
+            &29u16.to_le_bytes(),
+            invalid(29, "e_shstrndx is not a section"),
+        ),
+        // .interp name is outside of .shstrtab
+        (
+            section(1, 0),
+            &0xffff_ff00u32.to_le_bytes(),
+            invalid(1, "section name is outside of the string table"),
+        ),
+        // .text has an undefined section type
+        (
+            section(14, 4),
+            &12u32.to_le_bytes(),
+            invalid(14, "invalid section type"),
+        ),
+        // .note.ABI-tag is shorter than a note header
+        (
+            section(2, 32),
+            &4u64.to_le_bytes(),
+            invalid(2, "section size is not a multiple of the entry size"),
+        ),
+        // .dynsym holds a partial symbol
+        (
+            section(5, 32),
+            &0xa9u64.to_le_bytes(),
+            invalid(5, "section size is not a multiple of the entry size"),
+        ),
+        // .dynamic is misaligned
+        (
+            section(21, 24),
+            &0xdc9u64.to_le_bytes(),
+            invalid(21, "section data is misaligned"),
+        ),
+    ];
+    for (offset, patch, error) in corpus.iter() {
+        let mut blob = binary_blob.clone();
+        blob[*offset..*offset + patch.len()].copy_from_slice(patch);
+        assert_eq!(
+            ElfBinary::new(blob.as_slice()).err(),
+            Some(ElfLoaderErr::Parse { error: *error }),
+            "patch at {:#x}",
+            offset
+        );
+    }
+
+    // Without section headers, e_shstrndx is not checked
+    let mut blob = binary_blob.clone();
+    blob[0x3c..0x3e].copy_from_slice(&0u16.to_le_bytes());
+    let binary = ElfBinary::new(blob.as_slice()).expect("Section headers are optional");
+    assert!(binary.shstrtab().is_none());
+    assert_eq!(binary.sections().count(), 0);
+}
+
+#[test]
+fn check_probe() {
+    let binary_blob = fs::read("test/test.x86_64").expect("Can't read binary");
+    let summary = ElfBinary::probe(&binary_blob[..ElfBinary::PROBE_SIZE]).expect("Valid header");
+    assert_eq!(
+        summary,
+        crate::ElfSummary {
+            class: Class::Elf64,
+            endianness: Endianness::Little,
+            machine: 62,
+            typ: 3,
+            entry: 0x540,
+            phnum: 9,
+        }
+    );
+    assert!(matches!(
+        ElfBinary::probe(&binary_blob[..63]),
+        Err(ElfLoaderErr::Parse {
+            error: ParseError::Truncated { .. }
+        })
+    ));
+
+    // An ELF32 header is only 52 bytes
+    let binary_blob = fs::read("test/test.x86").expect("Can't read binary");
+    let summary = ElfBinary::probe(&binary_blob[..52]).expect("Valid header");
+    assert_eq!((summary.class, summary.machine), (Class::Elf32, 3));
+    assert_eq!((summary.entry, summary.phnum), (0x1060, 11));
+}
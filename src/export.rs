@@ -0,0 +1,165 @@
+//! Intel HEX and Motorola S-record images of a binary, for flashing.
+//!
+//! The records hold the contents of the LOAD segments as the loader places
+//! them in memory (i.e., with the relocations for the base address applied,
+//! see [`ElfBinary::write_flat_image`]), at their load memory addresses
+//! (`p_paddr`). Only the file-backed part of each segment is emitted, the
+//! zero-filled remainder (`.bss`) is left to the startup code.
+//!
+//! ```no_run
+//! # fn export(bytes: &[u8]) -> Result<(), elfloader::ElfLoaderErr> {
+//! let binary = elfloader::ElfBinary::new(bytes)?;
+//! let hex = elfloader::export::intel_hex(&binary, 0)?;
+//! # Ok(())
+//! # }
+//! ```
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::fmt::Write;
+
+use crate::{ElfBinary, ElfLoaderErr, VAddr};
+
+#[cfg(test)]
+mod test;
+
+/// Number of data bytes per record.
+const RECORD_SIZE: usize = 16;
+
+/// The file-backed contents of the LOAD segments of `binary` loaded at
+/// `base`, with their load memory addresses.
+fn segments(binary: &ElfBinary, base: VAddr) -> Result<Vec<(u64, Vec<u8>)>, ElfLoaderErr> {
+    let image = binary.flat_image(base)?;
+    let start = match binary.flat_image_span(base)? {
+        Some((start, _)) => start,
+        None => return Ok(Vec::new()),
+    };
+    let bias = binary.load_bias(base);
+    let mut segments = Vec::new();
+    for segment in binary.load_segments() {
+        if segment.file_size == 0 {
+            continue;
+        }
+        // flat_image_span covers all LOAD segments, so this is in bounds
+        let offset = (bias + segment.vaddr - start) as usize;
+        let data = &image[offset..offset + segment.file_size as usize];
+        segment
+            .paddr
+            .checked_add(segment.file_size)
+            .ok_or(ElfLoaderErr::AddressOverflow {
+                source: "LOAD segment physical address",
+            })?;
+        segments.push((segment.paddr, data.into()));
+    }
+    Ok(segments)
+}
+
+/// Appends the hex encoded `bytes` and `checksum` of a record to `out`.
+fn push_bytes(out: &mut String, bytes: &[u8], checksum: u8) {
+    for byte in bytes {
+        let _ = write!(out, "{:02X}", byte);
+    }
+    let _ = writeln!(out, "{:02X}", checksum);
+}
+
+fn sum(bytes: &[u8]) -> u8 {
+    bytes.iter().fold(0u8, |sum, &b| sum.wrapping_add(b))
+}
+
+/// Formats a single Intel HEX record.
+fn hex_record(out: &mut String, typ: u8, address: u16, data: &[u8]) {
+    let mut bytes = Vec::with_capacity(data.len() + 4);
+    bytes.push(data.len() as u8);
+    bytes.extend_from_slice(&address.to_be_bytes());
+    bytes.push(typ);
+    bytes.extend_from_slice(data);
+    out.push(':');
+    push_bytes(out, &bytes, sum(&bytes).wrapping_neg());
+}
+
+/// Returns `binary` loaded at `base` as an Intel HEX file.
+///
+/// Uses extended linear address records, so all load memory addresses and
+/// the entry point (the start linear address record) must fit in 32 bits,
+/// otherwise `AddressOverflow` is returned.
+pub fn intel_hex(binary: &ElfBinary, base: VAddr) -> Result<String, ElfLoaderErr> {
+    let overflow = |source| move |_| ElfLoaderErr::AddressOverflow { source };
+    let mut out = String::new();
+    let mut upper = 0u16;
+    for (address, data) in segments(binary, base)? {
+        let mut address = u32::try_from(address).map_err(overflow("Intel HEX address"))?;
+        u32::try_from(u64::from(address) + data.len() as u64 - 1)
+            .map_err(overflow("Intel HEX address"))?;
+        let mut data = &data[..];
+        while !data.is_empty() {
+            if (address >> 16) as u16 != upper {
+                upper = (address >> 16) as u16;
+                hex_record(&mut out, 4, 0, &upper.to_be_bytes());
+            }
+            // Records don't wrap around at the end of a 64 KiB segment
+            let room = 0x1_0000 - (address & 0xffff) as usize;
+            let (record, rest) = data.split_at(data.len().min(RECORD_SIZE).min(room));
+            hex_record(&mut out, 0, address as u16, record);
+            address = address.wrapping_add(record.len() as u32);
+            data = rest;
+        }
+    }
+    let entry = binary.entry_point_rebased(base)?;
+    let entry = u32::try_from(entry).map_err(overflow("Intel HEX start address"))?;
+    hex_record(&mut out, 5, 0, &entry.to_be_bytes());
+    hex_record(&mut out, 1, 0, &[]);
+    Ok(out)
+}
+
+/// Formats a single S-record with an address of `address_size` bytes.
+fn srec_record(out: &mut String, typ: u8, address: u32, address_size: usize, data: &[u8]) {
+    let mut bytes = Vec::with_capacity(data.len() + 5);
+    bytes.push((address_size + data.len() + 1) as u8);
+    bytes.extend_from_slice(&address.to_be_bytes()[4 - address_size..]);
+    bytes.extend_from_slice(data);
+    let _ = write!(out, "S{}", typ);
+    push_bytes(out, &bytes, !sum(&bytes));
+}
+
+/// Returns `binary` loaded at `base` as a Motorola S-record file.
+///
+/// Uses S1/S9, S2/S8 or S3/S7 records depending on the highest address
+/// (including the entry point), which must fit in 32 bits, otherwise
+/// `AddressOverflow` is returned. The records are preceded by an S0 header
+/// and followed by an S5 record count if there are at most 65535 data
+/// records.
+pub fn srec(binary: &ElfBinary, base: VAddr) -> Result<String, ElfLoaderErr> {
+    let segments = segments(binary, base)?;
+    let entry = binary.entry_point_rebased(base)?;
+    let highest = segments
+        .iter()
+        .map(|(address, data)| address + data.len() as u64 - 1)
+        .fold(entry, u64::max);
+    let (data_type, end_type, address_size) = match highest {
+        0..=0xffff => (1, 9, 2),
+        0x1_0000..=0xff_ffff => (2, 8, 3),
+        0x100_0000..=0xffff_ffff => (3, 7, 4),
+        _ => {
+            return Err(ElfLoaderErr::AddressOverflow {
+                source: "S-record address",
+            })
+        }
+    };
+
+    let mut out = String::new();
+    srec_record(&mut out, 0, 0, 2, &[]);
+    let mut count = 0usize;
+    for (address, data) in segments {
+        for (index, record) in data.chunks(RECORD_SIZE).enumerate() {
+            let address = (address + (index * RECORD_SIZE) as u64) as u32;
+            srec_record(&mut out, data_type, address, address_size, record);
+            count += 1;
+        }
+    }
+    if let Ok(count) = u16::try_from(count) {
+        srec_record(&mut out, 5, u32::from(count), 2, &[]);
+    }
+    srec_record(&mut out, end_type, entry as u32, address_size, &[]);
+    Ok(out)
+}
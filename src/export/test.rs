@@ -0,0 +1,112 @@
+use super::*;
+use std::collections::BTreeMap;
+use std::fs;
+
+/// Decodes the hex bytes of a record.
+fn decode(record: &str) -> Vec<u8> {
+    (0..record.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&record[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+/// The memory contents and start address described by an Intel HEX file.
+fn parse_intel_hex(hex: &str) -> (BTreeMap<u64, u8>, u32) {
+    let mut memory = BTreeMap::new();
+    let (mut upper, mut start) = (0, None);
+    for line in hex.lines() {
+        let bytes = decode(&line[1..]);
+        assert_eq!(bytes[0] as usize, bytes.len() - 5);
+        assert_eq!(sum(&bytes), 0, "checksum of {}", line);
+        let address = u64::from(u16::from_be_bytes([bytes[1], bytes[2]]));
+        let data = &bytes[4..bytes.len() - 1];
+        match bytes[3] {
+            0 => {
+                for (i, &b) in data.iter().enumerate() {
+                    memory.insert(upper + address + i as u64, b);
+                }
+            }
+            1 => return (memory, start.unwrap()),
+            4 => upper = u64::from(u16::from_be_bytes([data[0], data[1]])) << 16,
+            5 => start = Some(u32::from_be_bytes([data[0], data[1], data[2], data[3]])),
+            typ => panic!("Unexpected record type {}", typ),
+        }
+    }
+    panic!("No end of file record");
+}
+
+/// The expected memory contents: the file-backed part of the LOAD
+/// segments of the static riscv64 executable, at `paddr`.
+fn expected(blob: &[u8], second_paddr: u64) -> BTreeMap<u64, u8> {
+    let mut memory = BTreeMap::new();
+    for (i, &b) in blob[..0x6aec].iter().enumerate() {
+        memory.insert(0x10000 + i as u64, b);
+    }
+    for (i, &b) in blob[0x6fe0..0x6fe0 + 0x1a8].iter().enumerate() {
+        memory.insert(second_paddr + i as u64, b);
+    }
+    memory
+}
+
+#[test]
+fn check_intel_hex() {
+    let mut binary_blob = fs::read("test/test_nopie.riscv64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+    let hex = intel_hex(&binary, 0).expect("Can't export");
+
+    assert!(hex.starts_with(":020000040001F9\n"));
+    assert!(hex.ends_with(":040000050001018075\n:00000001FF\n"));
+    assert_eq!(
+        parse_intel_hex(&hex),
+        (expected(&binary_blob, 0x17fe0), 0x10180)
+    );
+
+    // The data segment is stored after the code (LMA != VMA)
+    binary_blob[0xc8..0xd0].copy_from_slice(&0x16b00u64.to_le_bytes());
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+    let hex = intel_hex(&binary, 0).expect("Can't export");
+    assert_eq!(parse_intel_hex(&hex).0, expected(&binary_blob, 0x16b00));
+
+    binary_blob[0xc8..0xd0].copy_from_slice(&0x1_0000_0000u64.to_le_bytes());
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+    assert_eq!(
+        intel_hex(&binary, 0),
+        Err(ElfLoaderErr::AddressOverflow {
+            source: "Intel HEX address"
+        })
+    );
+}
+
+#[test]
+fn check_srec() {
+    let binary_blob = fs::read("test/test_nopie.riscv64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+    let srec = srec(&binary, 0).expect("Can't export");
+
+    // 24 bit addresses
+    let mut memory = BTreeMap::new();
+    let lines: Vec<&str> = srec.lines().collect();
+    assert_eq!(lines[0], "S0030000FC");
+    for line in &lines {
+        let bytes = decode(&line[2..]);
+        assert_eq!(bytes[0] as usize, bytes.len() - 1);
+        assert_eq!(sum(&bytes), 0xff, "checksum of {}", line);
+        if &line[..2] == "S2" {
+            let address = u64::from_be_bytes([0, 0, 0, 0, 0, bytes[1], bytes[2], bytes[3]]);
+            for (i, &b) in bytes[4..bytes.len() - 1].iter().enumerate() {
+                memory.insert(address + i as u64, b);
+            }
+        }
+    }
+    assert_eq!(memory, expected(&binary_blob, 0x17fe0));
+
+    // 0x6aec and 0x1a8 bytes in records of 16 bytes
+    let records = 0x6aec / 16 + 1 + 0x1a8 / 16 + 1;
+    assert_eq!(lines.len(), records + 3);
+    assert!(lines[lines.len() - 2].starts_with("S503"));
+    assert_eq!(
+        u16::from_str_radix(&lines[lines.len() - 2][4..8], 16).unwrap() as usize,
+        records
+    );
+    assert!(lines[lines.len() - 1].starts_with("S804010180"));
+}
@@ -0,0 +1,416 @@
+//! C interface, for kernels and boot loaders written in C.
+//!
+//! The functions are exported unmangled with the `elfloader_` prefix. To use
+//! them from C, build a `staticlib` crate that depends on this crate with the
+//! `ffi` feature (and provides the panic handler) and link against it. The
+//! interface doesn't allocate: the caller provides the storage for the
+//! parsed binary.
+//!
+//! ```c
+//! typedef struct elfloader_binary elfloader_binary;
+//!
+//! typedef struct {
+//!     uint64_t vaddr, mem_size, offset, file_size, align;
+//!     uint32_t flags;
+//! } elfloader_segment;
+//!
+//! typedef struct {
+//!     uint64_t offset, info, addend;
+//!     uint32_t type_num, index;
+//!     bool has_addend;
+//! } elfloader_relocation;
+//!
+//! typedef struct {
+//!     void *context;
+//!     int (*allocate)(void *context, const elfloader_segment *segment);
+//!     int (*load)(void *context, uint32_t flags, uint64_t vaddr,
+//!                 const uint8_t *data, size_t len);
+//!     int (*relocate)(void *context, const elfloader_relocation *entry);
+//!     /* optional (NULL) */
+//!     int (*tls)(void *context, uint64_t tdata_start, const uint8_t *tdata,
+//!                size_t len, uint64_t tbss_length, uint64_t align);
+//!     int (*relro)(void *context, uint64_t vaddr, uint64_t size);
+//! } elfloader_callbacks;
+//!
+//! size_t elfloader_binary_size(void);
+//! size_t elfloader_binary_align(void);
+//! int elfloader_parse(const uint8_t *data, size_t len, elfloader_binary *out);
+//! void elfloader_release(elfloader_binary *binary);
+//! uint16_t elfloader_machine(const elfloader_binary *binary);
+//! int elfloader_entry_point(const elfloader_binary *binary, uint64_t base,
+//!                           uint64_t *entry);
+//! size_t elfloader_segment_count(const elfloader_binary *binary);
+//! int elfloader_segment(const elfloader_binary *binary, size_t index,
+//!                       elfloader_segment *out);
+//! int elfloader_load(const elfloader_binary *binary,
+//!                    const elfloader_callbacks *callbacks);
+//! ```
+//!
+//! All functions returning `int` return [`ELFLOADER_OK`] or one of the
+//! (negative) `ELFLOADER_ERR_*` codes. If a callback returns a non-zero
+//! value loading stops and `elfloader_load` returns that value.
+
+use core::mem;
+use core::ptr;
+use core::slice;
+
+use crate::segments::LoadSegment;
+use crate::{
+    AllocateRequests, ElfBinary, ElfLoader, ElfLoaderErr, RelocationEntry, RelroSegment,
+    SegmentPermissions, VAddr,
+};
+
+#[cfg(test)]
+mod test;
+
+/// Success.
+pub const ELFLOADER_OK: i32 = 0;
+/// The ELF file is malformed.
+pub const ELFLOADER_ERR_MALFORMED: i32 = -1;
+/// The ELF file uses a format, architecture or relocation this crate
+/// doesn't support.
+pub const ELFLOADER_ERR_UNSUPPORTED: i32 = -2;
+/// An argument is NULL or out of range.
+pub const ELFLOADER_ERR_INVALID_ARGUMENT: i32 = -3;
+/// Any other error.
+pub const ELFLOADER_ERR_OTHER: i32 = -4;
+
+/// A LOAD segment (`elfloader_segment`).
+#[repr(C)]
+#[derive(Eq, PartialEq, Debug, Clone, Copy, Default)]
+pub struct Segment {
+    pub vaddr: u64,
+    pub mem_size: u64,
+    pub offset: u64,
+    pub file_size: u64,
+    pub align: u64,
+    /// The `p_flags` of the segment.
+    pub flags: u32,
+}
+
+impl From<&LoadSegment> for Segment {
+    fn from(segment: &LoadSegment) -> Segment {
+        Segment {
+            vaddr: segment.vaddr,
+            mem_size: segment.mem_size,
+            offset: segment.offset,
+            file_size: segment.file_size,
+            align: segment.align,
+            flags: segment.flags.0,
+        }
+    }
+}
+
+/// A relocation passed to the `relocate` callback
+/// (`elfloader_relocation`), see [`RelocationEntry`].
+#[repr(C)]
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub struct Relocation {
+    pub offset: u64,
+    pub info: u64,
+    /// The addend, if `has_addend` is set.
+    pub addend: u64,
+    pub type_num: u32,
+    pub index: u32,
+    pub has_addend: bool,
+}
+
+/// The loader callbacks (`elfloader_callbacks`), `context` is passed to
+/// every callback.
+///
+/// `allocate` is called once per LOAD segment, `tls` and `relro` may be
+/// NULL.
+#[repr(C)]
+pub struct Callbacks {
+    pub context: *mut core::ffi::c_void,
+    pub allocate: extern "C" fn(context: *mut core::ffi::c_void, segment: *const Segment) -> i32,
+    pub load: extern "C" fn(
+        context: *mut core::ffi::c_void,
+        flags: u32,
+        vaddr: u64,
+        data: *const u8,
+        len: usize,
+    ) -> i32,
+    pub relocate: extern "C" fn(context: *mut core::ffi::c_void, entry: *const Relocation) -> i32,
+    pub tls: Option<
+        extern "C" fn(
+            context: *mut core::ffi::c_void,
+            tdata_start: u64,
+            tdata: *const u8,
+            len: usize,
+            tbss_length: u64,
+            align: u64,
+        ) -> i32,
+    >,
+    pub relro: Option<extern "C" fn(context: *mut core::ffi::c_void, vaddr: u64, size: u64) -> i32>,
+}
+
+/// The error code of `error`.
+fn error_code(error: &ElfLoaderErr) -> i32 {
+    match error {
+        ElfLoaderErr::ElfParser { .. }
+        | ElfLoaderErr::Parse { .. }
+        | ElfLoaderErr::SymbolTableNotFound
+        | ElfLoaderErr::InvalidSegment { .. }
+        | ElfLoaderErr::OverlappingSegments { .. }
+        | ElfLoaderErr::AddressOverflow { .. }
+        | ElfLoaderErr::SegmentOutOfBounds { .. } => ELFLOADER_ERR_MALFORMED,
+        ElfLoaderErr::UnsupportedElfFormat
+        | ElfLoaderErr::UnsupportedElfVersion
+        | ElfLoaderErr::UnsupportedEndianness
+        | ElfLoaderErr::UnsupportedAbi
+        | ElfLoaderErr::UnsupportedAbiVersion { .. }
+        | ElfLoaderErr::UnsupportedElfType
+        | ElfLoaderErr::UnsupportedSectionData
+        | ElfLoaderErr::UnsupportedArchitecture
+        | ElfLoaderErr::UnsupportedRelocationEntry => ELFLOADER_ERR_UNSUPPORTED,
+        ElfLoaderErr::InvalidPageSize { .. } => ELFLOADER_ERR_INVALID_ARGUMENT,
+        _ => ELFLOADER_ERR_OTHER,
+    }
+}
+
+/// Size in bytes of the storage for a parsed binary.
+#[no_mangle]
+pub extern "C" fn elfloader_binary_size() -> usize {
+    mem::size_of::<ElfBinary>()
+}
+
+/// Required alignment of the storage for a parsed binary.
+#[no_mangle]
+pub extern "C" fn elfloader_binary_align() -> usize {
+    mem::align_of::<ElfBinary>()
+}
+
+/// Parses the ELF file of `len` bytes at `data` into `out`.
+///
+/// # Safety
+///
+/// `data` must be valid for reads of `len` bytes and stay unmodified until
+/// the binary is released. `out` must point to writable storage of
+/// [`elfloader_binary_size`] bytes aligned to [`elfloader_binary_align`].
+#[no_mangle]
+pub unsafe extern "C" fn elfloader_parse(data: *const u8, len: usize, out: *mut ElfBinary) -> i32 {
+    if data.is_null() || out.is_null() {
+        return ELFLOADER_ERR_INVALID_ARGUMENT;
+    }
+    let region = slice::from_raw_parts(data, len);
+    match ElfBinary::new(region) {
+        Ok(binary) => {
+            ptr::write(out, binary);
+            ELFLOADER_OK
+        }
+        Err(error) => error_code(&error),
+    }
+}
+
+/// Releases a binary parsed by [`elfloader_parse`].
+///
+/// # Safety
+///
+/// `binary` must be NULL or a binary parsed by [`elfloader_parse`] which
+/// wasn't released yet.
+#[no_mangle]
+pub unsafe extern "C" fn elfloader_release(binary: *mut ElfBinary) {
+    if !binary.is_null() {
+        ptr::drop_in_place(binary);
+    }
+}
+
+/// The `e_machine` field of the binary, to interpret relocation types.
+///
+/// # Safety
+///
+/// `binary` must be a binary parsed by [`elfloader_parse`].
+#[no_mangle]
+pub unsafe extern "C" fn elfloader_machine(binary: *const ElfBinary) -> u16 {
+    match binary.as_ref() {
+        Some(binary) => binary.header.machine,
+        None => 0,
+    }
+}
+
+/// Stores the entry point of the binary loaded at `base` in `entry`, see
+/// [`ElfBinary::entry_point_rebased`].
+///
+/// # Safety
+///
+/// `binary` must be a binary parsed by [`elfloader_parse`], `entry` must be
+/// writable.
+#[no_mangle]
+pub unsafe extern "C" fn elfloader_entry_point(
+    binary: *const ElfBinary,
+    base: VAddr,
+    entry: *mut u64,
+) -> i32 {
+    let binary = match binary.as_ref() {
+        Some(binary) if !entry.is_null() => binary,
+        _ => return ELFLOADER_ERR_INVALID_ARGUMENT,
+    };
+    match binary.entry_point_rebased(base) {
+        Ok(address) => {
+            *entry = address;
+            ELFLOADER_OK
+        }
+        Err(error) => error_code(&error),
+    }
+}
+
+/// Number of LOAD segments of the binary.
+///
+/// # Safety
+///
+/// `binary` must be a binary parsed by [`elfloader_parse`].
+#[no_mangle]
+pub unsafe extern "C" fn elfloader_segment_count(binary: *const ElfBinary) -> usize {
+    match binary.as_ref() {
+        Some(binary) => binary.load_segments().count(),
+        None => 0,
+    }
+}
+
+/// Stores the LOAD segment with the given `index` (counting LOAD segments
+/// only) in `out`.
+///
+/// # Safety
+///
+/// `binary` must be a binary parsed by [`elfloader_parse`], `out` must be
+/// writable.
+#[no_mangle]
+pub unsafe extern "C" fn elfloader_segment(
+    binary: *const ElfBinary,
+    index: usize,
+    out: *mut Segment,
+) -> i32 {
+    let binary = match binary.as_ref() {
+        Some(binary) if !out.is_null() => binary,
+        _ => return ELFLOADER_ERR_INVALID_ARGUMENT,
+    };
+    match binary.load_segments().nth(index) {
+        Some(segment) => {
+            *out = Segment::from(&segment);
+            ELFLOADER_OK
+        }
+        None => ELFLOADER_ERR_INVALID_ARGUMENT,
+    }
+}
+
+/// Loads the binary through `callbacks`, see [`ElfBinary::load`].
+///
+/// # Safety
+///
+/// `binary` must be a binary parsed by [`elfloader_parse`] and `callbacks`
+/// must point to valid callbacks.
+#[no_mangle]
+pub unsafe extern "C" fn elfloader_load(
+    binary: *const ElfBinary,
+    callbacks: *const Callbacks,
+) -> i32 {
+    let (binary, callbacks) = match (binary.as_ref(), callbacks.as_ref()) {
+        (Some(binary), Some(callbacks)) => (binary, callbacks),
+        _ => return ELFLOADER_ERR_INVALID_ARGUMENT,
+    };
+    let mut loader = CallbackLoader {
+        binary,
+        callbacks,
+        status: ELFLOADER_OK,
+    };
+    match binary.load(&mut loader) {
+        Ok(()) => ELFLOADER_OK,
+        Err(_) if loader.status != ELFLOADER_OK => loader.status,
+        Err(error) => error_code(&error),
+    }
+}
+
+/// An [`ElfLoader`] forwarding to C callbacks.
+struct CallbackLoader<'a, 's> {
+    binary: &'a ElfBinary<'s>,
+    callbacks: &'a Callbacks,
+    /// The non-zero return value of the callback which failed.
+    status: i32,
+}
+
+impl CallbackLoader<'_, '_> {
+    fn check(&mut self, status: i32) -> Result<(), ElfLoaderErr> {
+        if status == ELFLOADER_OK {
+            Ok(())
+        } else {
+            self.status = status;
+            Err(ElfLoaderErr::ElfParser {
+                source: "loader callback failed",
+            })
+        }
+    }
+}
+
+impl ElfLoader for CallbackLoader<'_, '_> {
+    fn allocate(&mut self, _requests: AllocateRequests) -> Result<(), ElfLoaderErr> {
+        for segment in self.binary.load_segments() {
+            let segment = Segment::from(&segment);
+            let status = (self.callbacks.allocate)(self.callbacks.context, &segment);
+            self.check(status)?;
+        }
+        Ok(())
+    }
+
+    fn load(
+        &mut self,
+        flags: SegmentPermissions,
+        base: VAddr,
+        region: &[u8],
+    ) -> Result<(), ElfLoaderErr> {
+        let status = (self.callbacks.load)(
+            self.callbacks.context,
+            flags.bits(),
+            base,
+            region.as_ptr(),
+            region.len(),
+        );
+        self.check(status)
+    }
+
+    fn relocate(&mut self, entry: RelocationEntry) -> Result<(), ElfLoaderErr> {
+        let relocation = Relocation {
+            offset: entry.offset,
+            info: entry.info,
+            addend: entry.addend.unwrap_or(0),
+            type_num: entry.type_num,
+            index: entry.index,
+            has_addend: entry.addend.is_some(),
+        };
+        let status = (self.callbacks.relocate)(self.callbacks.context, &relocation);
+        self.check(status)
+    }
+
+    fn tls(
+        &mut self,
+        tdata_start: VAddr,
+        tdata: &[u8],
+        tbss_length: u64,
+        align: u64,
+    ) -> Result<(), ElfLoaderErr> {
+        match self.callbacks.tls {
+            Some(tls) => {
+                let status = tls(
+                    self.callbacks.context,
+                    tdata_start,
+                    tdata.as_ptr(),
+                    tdata.len(),
+                    tbss_length,
+                    align,
+                );
+                self.check(status)
+            }
+            None => Ok(()),
+        }
+    }
+
+    fn relro(&mut self, segment: RelroSegment) -> Result<(), ElfLoaderErr> {
+        match self.callbacks.relro {
+            Some(relro) => {
+                let status = relro(self.callbacks.context, segment.vaddr, segment.mem_size);
+                self.check(status)
+            }
+            None => Ok(()),
+        }
+    }
+}
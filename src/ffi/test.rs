@@ -0,0 +1,117 @@
+use super::*;
+use core::ffi::c_void;
+use core::mem::MaybeUninit;
+use std::fs;
+use std::vec::Vec;
+
+#[derive(Default)]
+struct Context {
+    segments: Vec<Segment>,
+    loaded: Vec<(u64, usize)>,
+    relocations: Vec<Relocation>,
+    relro: Vec<(u64, u64)>,
+    fail_relocate: bool,
+}
+
+fn context<'a>(context: *mut c_void) -> &'a mut Context {
+    unsafe { &mut *(context as *mut Context) }
+}
+
+extern "C" fn allocate(ctx: *mut c_void, segment: *const Segment) -> i32 {
+    context(ctx).segments.push(unsafe { *segment });
+    ELFLOADER_OK
+}
+
+extern "C" fn load(ctx: *mut c_void, _flags: u32, vaddr: u64, _data: *const u8, len: usize) -> i32 {
+    context(ctx).loaded.push((vaddr, len));
+    ELFLOADER_OK
+}
+
+extern "C" fn relocate(ctx: *mut c_void, entry: *const Relocation) -> i32 {
+    let context = context(ctx);
+    if context.fail_relocate {
+        return 42;
+    }
+    context.relocations.push(unsafe { *entry });
+    ELFLOADER_OK
+}
+
+extern "C" fn relro(ctx: *mut c_void, vaddr: u64, size: u64) -> i32 {
+    context(ctx).relro.push((vaddr, size));
+    ELFLOADER_OK
+}
+
+#[test]
+fn load_through_callbacks() {
+    let binary_blob = fs::read("test/test.x86_64").expect("Can't read binary");
+    let mut storage = MaybeUninit::<ElfBinary>::uninit();
+    assert!(elfloader_binary_size() >= mem::size_of::<u64>());
+    assert_eq!(elfloader_binary_align(), mem::align_of::<ElfBinary>());
+
+    unsafe {
+        let binary = storage.as_mut_ptr();
+        assert_eq!(
+            elfloader_parse(binary_blob.as_ptr(), binary_blob.len(), binary),
+            ELFLOADER_OK
+        );
+        assert_eq!(elfloader_machine(binary), 62); // EM_X86_64
+
+        let mut entry = 0;
+        assert_eq!(
+            elfloader_entry_point(binary, 0x1000_0000, &mut entry),
+            ELFLOADER_OK
+        );
+        assert_eq!(entry, 0x1000_0540);
+
+        // readelf -l: two LOAD segments
+        assert_eq!(elfloader_segment_count(binary), 2);
+        let mut segment = Segment::default();
+        assert_eq!(elfloader_segment(binary, 1, &mut segment), ELFLOADER_OK);
+        assert_eq!(segment.vaddr, 0x200db8);
+        assert_eq!(segment.file_size, 0x258);
+        assert_eq!(segment.mem_size, 0x260);
+        assert_eq!(segment.flags, 6);
+        assert_eq!(
+            elfloader_segment(binary, 2, &mut segment),
+            ELFLOADER_ERR_INVALID_ARGUMENT
+        );
+
+        let mut ctx = Context::default();
+        let mut callbacks = Callbacks {
+            context: &mut ctx as *mut Context as *mut c_void,
+            allocate,
+            load,
+            relocate,
+            tls: None,
+            relro: Some(relro),
+        };
+        assert_eq!(elfloader_load(binary, &callbacks), ELFLOADER_OK);
+        assert_eq!(ctx.segments.len(), 2);
+        assert_eq!(ctx.loaded, [(0x0, 0x888), (0x200db8, 0x258)]);
+        assert!(ctx.relocations.iter().all(|r| r.has_addend));
+        assert!(ctx
+            .relocations
+            .iter()
+            .any(|r| r.type_num == 8 && r.offset == 0x200db8));
+        assert_eq!(ctx.relro, [(0x200db8, 0x248)]);
+
+        // The status of a failing callback is returned
+        let mut failing = Context {
+            fail_relocate: true,
+            ..Default::default()
+        };
+        callbacks.context = &mut failing as *mut Context as *mut c_void;
+        assert_eq!(elfloader_load(binary, &callbacks), 42);
+
+        elfloader_release(binary);
+
+        assert_eq!(
+            elfloader_parse(binary_blob.as_ptr(), 16, binary),
+            ELFLOADER_ERR_MALFORMED
+        );
+        assert_eq!(
+            elfloader_parse(ptr::null(), 0, binary),
+            ELFLOADER_ERR_INVALID_ARGUMENT
+        );
+    }
+}
@@ -0,0 +1,258 @@
+//! Registration of loaded images with the GDB JIT interface.
+//!
+//! gdb (and lldb) look for the symbols `__jit_debug_descriptor` and
+//! `__jit_debug_register_code` in the debugged process. Whenever an in-memory
+//! ELF file is added to or removed from the descriptor list and the register
+//! function is called, the debugger reads the ELF file and uses it to
+//! symbolize the code. This makes binaries that were loaded into anonymous
+//! memory through an [`crate::ElfLoader`] visible to the debugger.
+//!
+//! Since this module exports the two symbols it is only available with the
+//! `gdb-jit` feature; enabling it in a process that already provides the
+//! interface (e.g., through LLVM) will lead to duplicate symbol errors.
+
+use alloc::boxed::Box;
+use core::convert::TryFrom;
+use core::ptr::{self, addr_of_mut};
+use core::sync::atomic::{AtomicBool, Ordering};
+
+use crate::elf::{Class, Encoding, Endianness, SHN_LORESERVE, SHT_DYNSYM, SHT_SYMTAB};
+use crate::{ElfBinary, ElfLoaderErr, VAddr};
+
+#[cfg(test)]
+mod test;
+
+const JIT_NOACTION: u32 = 0;
+const JIT_REGISTER_FN: u32 = 1;
+const JIT_UNREGISTER_FN: u32 = 2;
+
+/// An entry in the list of in-memory ELF files (`struct jit_code_entry`).
+#[derive(Debug)]
+#[repr(C)]
+pub struct JitCodeEntry {
+    next_entry: *mut JitCodeEntry,
+    prev_entry: *mut JitCodeEntry,
+    symfile_addr: *const u8,
+    symfile_size: u64,
+}
+
+/// The descriptor the debugger reads (`struct jit_descriptor`).
+#[derive(Debug)]
+#[repr(C)]
+pub struct JitDescriptor {
+    version: u32,
+    action_flag: u32,
+    relevant_entry: *mut JitCodeEntry,
+    first_entry: *mut JitCodeEntry,
+}
+
+/// The debugger reads this descriptor after `__jit_debug_register_code` was called.
+#[no_mangle]
+#[used]
+pub static mut __jit_debug_descriptor: JitDescriptor = JitDescriptor {
+    version: 1,
+    action_flag: JIT_NOACTION,
+    relevant_entry: ptr::null_mut(),
+    first_entry: ptr::null_mut(),
+};
+
+/// The debugger sets a breakpoint on this function.
+#[no_mangle]
+#[inline(never)]
+pub extern "C" fn __jit_debug_register_code() {
+    // Make sure the call is not optimized away.
+    unsafe { core::arch::asm!("", options(nomem, nostack, preserves_flags)) };
+}
+
+/// Serializes modifications of the descriptor list.
+static LOCK: AtomicBool = AtomicBool::new(false);
+
+fn with_descriptor<F: FnOnce(*mut JitDescriptor)>(func: F) {
+    while LOCK
+        .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+        .is_err()
+    {
+        core::hint::spin_loop();
+    }
+    func(addr_of_mut!(__jit_debug_descriptor));
+    LOCK.store(false, Ordering::Release);
+}
+
+/// A loaded image registered with the debugger.
+///
+/// The image stays registered until this is dropped.
+#[derive(Debug)]
+pub struct JitImage {
+    entry: Box<JitCodeEntry>,
+    symfile: Box<[u8]>,
+}
+
+unsafe impl Send for JitImage {}
+unsafe impl Sync for JitImage {}
+
+impl JitImage {
+    /// Registers `binary`, loaded with bias `base`, with the debugger.
+    ///
+    /// This makes a copy of the ELF file with all section, program header
+    /// and symbol addresses moved by `base`, so the debugger sees the
+    /// addresses the code actually runs at. Use a `base` of 0 for binaries
+    /// loaded at their link-time addresses.
+    pub fn register(binary: &ElfBinary, base: VAddr) -> Result<JitImage, ElfLoaderErr> {
+        let mut symfile: Box<[u8]> = binary.file.input.into();
+        if base != 0 {
+            rebase(binary, &mut symfile, base)?;
+        }
+
+        let mut entry = Box::new(JitCodeEntry {
+            next_entry: ptr::null_mut(),
+            prev_entry: ptr::null_mut(),
+            symfile_addr: symfile.as_ptr(),
+            symfile_size: symfile.len() as u64,
+        });
+
+        let entry_ptr: *mut JitCodeEntry = &mut *entry;
+        with_descriptor(|descriptor| unsafe {
+            let first = (*descriptor).first_entry;
+            (*entry_ptr).next_entry = first;
+            if !first.is_null() {
+                (*first).prev_entry = entry_ptr;
+            }
+            (*descriptor).first_entry = entry_ptr;
+            (*descriptor).relevant_entry = entry_ptr;
+            (*descriptor).action_flag = JIT_REGISTER_FN;
+            __jit_debug_register_code();
+        });
+
+        Ok(JitImage { entry, symfile })
+    }
+
+    /// The (rebased) ELF file that was handed to the debugger.
+    pub fn symfile(&self) -> &[u8] {
+        &self.symfile
+    }
+}
+
+impl Drop for JitImage {
+    fn drop(&mut self) {
+        let entry_ptr: *mut JitCodeEntry = &mut *self.entry;
+        with_descriptor(|descriptor| unsafe {
+            let prev = (*entry_ptr).prev_entry;
+            let next = (*entry_ptr).next_entry;
+            if prev.is_null() {
+                (*descriptor).first_entry = next;
+            } else {
+                (*prev).next_entry = next;
+            }
+            if !next.is_null() {
+                (*next).prev_entry = prev;
+            }
+            (*descriptor).relevant_entry = entry_ptr;
+            (*descriptor).action_flag = JIT_UNREGISTER_FN;
+            __jit_debug_register_code();
+        });
+    }
+}
+
+/// Adds `base` to the word at `offset` in `data`, which is encoded with
+/// `encoding`.
+fn add_word(
+    data: &mut [u8],
+    offset: usize,
+    encoding: Encoding,
+    base: VAddr,
+) -> Result<(), ElfLoaderErr> {
+    let err = ElfLoaderErr::ElfParser {
+        source: "Header out of range",
+    };
+    let size = encoding.class.word_size();
+    let value = encoding
+        .word(data, offset)
+        .map_err(|_| err.clone())?
+        .wrapping_add(base);
+    let bytes = match encoding.endianness {
+        Endianness::Little => value.to_le_bytes(),
+        Endianness::Big => value.to_be_bytes(),
+    };
+    let bytes = match encoding.endianness {
+        Endianness::Little => &bytes[..size],
+        Endianness::Big => &bytes[8 - size..],
+    };
+    // `word` checked the range
+    data[offset..offset + size].copy_from_slice(bytes);
+    Ok(())
+}
+
+/// Moves all addresses in the copy `data` of `binary` by `base`.
+fn rebase(binary: &ElfBinary, data: &mut [u8], base: VAddr) -> Result<(), ElfLoaderErr> {
+    const SHF_ALLOC: u64 = 0x2;
+    const SHN_UNDEF: u16 = 0;
+
+    let header = &binary.header;
+    let encoding = header.encoding;
+    let is_64 = encoding.class == Class::Elf64;
+    // The offset of `field` in the `index`-th entry of `size` bytes of the
+    // table at `table`
+    let field = |table: u64, index: usize, size: u16, field: usize| {
+        usize::try_from(table)
+            .ok()
+            .zip(index.checked_mul(usize::from(size)))
+            .and_then(|(table, entry)| table.checked_add(entry)?.checked_add(field))
+            .ok_or(ElfLoaderErr::ElfParser {
+                source: "Header out of range",
+            })
+    };
+
+    // e_entry
+    add_word(data, 0x18, encoding, base)?;
+
+    // p_vaddr
+    for idx in 0..usize::from(header.phnum) {
+        let p_vaddr = field(
+            header.phoff,
+            idx,
+            header.phentsize,
+            if is_64 { 16 } else { 8 },
+        )?;
+        add_word(data, p_vaddr, encoding, base)?;
+    }
+
+    // sh_addr of allocated sections and the values of all symbols defined in them
+    let input = binary.file.input;
+    let sections = header.section_headers(input)?;
+    for (idx, section) in sections.enumerate() {
+        if section.flags & SHF_ALLOC != 0 {
+            let sh_addr = field(
+                header.shoff,
+                idx,
+                header.shentsize,
+                if is_64 { 16 } else { 12 },
+            )?;
+            add_word(data, sh_addr, encoding, base)?;
+        }
+
+        if !matches!(section.typ, SHT_SYMTAB | SHT_DYNSYM) {
+            continue;
+        }
+
+        let symbol_err = ElfLoaderErr::ElfParser {
+            source: "Symbol out of range",
+        };
+        let (entry_size, value_offset, shndx_offset) = if is_64 { (24, 8, 6) } else { (16, 4, 14) };
+        let start = usize::try_from(section.offset).map_err(|_| symbol_err.clone())?;
+        let end = usize::try_from(section.size)
+            .ok()
+            .and_then(|size| start.checked_add(size))
+            .ok_or(symbol_err.clone())?;
+        for sym in (start..end).step_by(entry_size) {
+            let shndx = sym
+                .checked_add(shndx_offset)
+                .and_then(|offset| encoding.u16(input, offset).ok())
+                .ok_or(symbol_err.clone())?;
+            if shndx != SHN_UNDEF && shndx < SHN_LORESERVE {
+                add_word(data, sym + value_offset, encoding, base)?;
+            }
+        }
+    }
+
+    Ok(())
+}
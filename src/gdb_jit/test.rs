@@ -0,0 +1,66 @@
+use core::convert::TryInto;
+use std::fs;
+
+use crate::arch::test::init;
+use crate::gdb_jit::*;
+use crate::*;
+
+#[test]
+fn register_and_unregister() {
+    init();
+    let binary_blob = fs::read("test/test.x86_64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+
+    let image = JitImage::register(&binary, 0x1000_0000).expect("Can't register?");
+    let descriptor = unsafe { &*core::ptr::addr_of!(__jit_debug_descriptor) };
+    assert!(!descriptor.first_entry.is_null());
+    assert_eq!(
+        unsafe { (*descriptor.first_entry).symfile_addr },
+        image.symfile().as_ptr()
+    );
+
+    // The debugger sees the addresses the binary runs at
+    let rebased = ElfBinary::new(image.symfile()).expect("Got proper ELF file");
+    assert_eq!(rebased.entry_point(), 0x1000_0000 + binary.entry_point());
+    for (orig, moved) in binary.program_headers().zip(rebased.program_headers()) {
+        assert_eq!(moved.virtual_addr(), 0x1000_0000 + orig.virtual_addr());
+    }
+    let mut main = None;
+    rebased
+        .for_each_symbol(|sym| {
+            if rebased.symbol_name(sym) == "main" {
+                main = Some(sym.value());
+            }
+        })
+        .expect("Has symbols");
+    // nm test/test.x86_64 | grep main: 000000000000064a T main
+    assert_eq!(main, Some(0x1000_0000 + 0x64a));
+
+    drop(image);
+    let descriptor = unsafe { &*core::ptr::addr_of!(__jit_debug_descriptor) };
+    assert!(descriptor.first_entry.is_null());
+}
+
+#[test]
+fn rebase_big_endian() {
+    init();
+    // EM_PPC with a PT_NOTE at 0x100
+    let file = crate::arch::test::big_endian_elf32(20, &[(4, 0x100, 4)], &[0; 4]);
+    let binary = ElfBinary::new(file.as_slice()).expect("Got proper ELF file");
+
+    let mut symfile = file.clone();
+    rebase(&binary, &mut symfile, 0x1000_0000).expect("Can rebase");
+    let word = |offset: usize| u32::from_be_bytes(symfile[offset..offset + 4].try_into().unwrap());
+    assert_eq!(word(0x18), 0x1000_0000);
+    assert_eq!(word(52 + 8), 0x1000_0000);
+    assert_eq!(word(52 + 32 + 8), 0x1000_0100);
+    // Only the addresses changed
+    assert_eq!(word(52 + 32 + 4), 0x100);
+
+    // A copy that is too short for the program headers
+    let mut symfile = file.clone();
+    assert!(matches!(
+        rebase(&binary, &mut symfile[..0x40], 0x1000_0000),
+        Err(ElfLoaderErr::ElfParser { .. })
+    ));
+}
@@ -0,0 +1,258 @@
+//! A structured, readelf-like summary of a binary.
+//!
+//! Useful to print diagnostics about a binary before loading it, in
+//! environments where binutils are not available:
+//!
+//! ```no_run
+//! # fn print(bytes: &[u8]) -> Result<(), elfloader::ElfLoaderErr> {
+//! let binary = elfloader::ElfBinary::new(bytes)?;
+//! let summary = elfloader::inspect::Summary::new(&binary)?;
+//! log::info!("{}", summary);
+//! # Ok(())
+//! # }
+//! ```
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::{ElfBinary, ElfLoaderErr, Flags, Machine};
+use xmas_elf::header::{Class, Data, OsAbi, Type as ElfType};
+use xmas_elf::program::Type;
+use xmas_elf::sections::ShType;
+
+#[cfg(test)]
+mod test;
+
+/// Summary of a single program header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SegmentSummary {
+    pub typ: Type,
+    pub flags: Flags,
+    pub offset: u64,
+    pub virtual_addr: u64,
+    pub physical_addr: u64,
+    pub file_size: u64,
+    pub mem_size: u64,
+    pub align: u64,
+}
+
+/// A raw entry of the dynamic segment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DynamicEntry {
+    pub tag: u64,
+    pub value: u64,
+}
+
+/// Number of entries in a relocation section.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RelocationSummary<'s> {
+    /// Name of the section (e.g., `.rela.dyn`).
+    pub section: &'s str,
+    /// True for SHT_RELA sections, false for SHT_REL.
+    pub has_addend: bool,
+    pub count: u64,
+}
+
+/// Summary of the ELF header, program headers, dynamic entries and
+/// relocation sections of a binary.
+#[derive(Debug, Clone)]
+pub struct Summary<'s> {
+    pub class: Class,
+    pub data: Data,
+    pub os_abi: OsAbi,
+    pub elf_type: ElfType,
+    pub machine: Machine,
+    pub entry_point: u64,
+    pub segments: Vec<SegmentSummary>,
+    pub dynamic: Vec<DynamicEntry>,
+    pub relocations: Vec<RelocationSummary<'s>>,
+}
+
+impl<'s> Summary<'s> {
+    /// Collect the summary of `binary`.
+    pub fn new(binary: &ElfBinary<'s>) -> Result<Summary<'s>, ElfLoaderErr> {
+        let header = binary.file.header;
+
+        let mut segments = Vec::new();
+        for p in binary.program_headers() {
+            segments.push(SegmentSummary {
+                typ: p.get_type()?,
+                flags: p.flags(),
+                offset: p.offset(),
+                virtual_addr: p.virtual_addr(),
+                physical_addr: p.physical_addr(),
+                file_size: p.file_size(),
+                mem_size: p.mem_size(),
+                align: p.align(),
+            });
+        }
+
+        let dynamic = binary
+            .raw_dynamic_entries()
+            .map(|(tag, value)| DynamicEntry { tag, value })
+            .collect();
+
+        let is_64 = header.pt1.class() == Class::SixtyFour;
+        let mut relocations = Vec::new();
+        for section in binary.file.section_iter() {
+            let (has_addend, entry_size) = match (section.get_type()?, is_64) {
+                (ShType::Rela, true) => (true, 24),
+                (ShType::Rela, false) => (true, 12),
+                (ShType::Rel, true) => (false, 16),
+                (ShType::Rel, false) => (false, 8),
+                _ => continue,
+            };
+            let count = section.size() / entry_size;
+            relocations.push(RelocationSummary {
+                section: section.get_name(&binary.file)?,
+                has_addend,
+                count,
+            });
+        }
+
+        Ok(Summary {
+            class: header.pt1.class(),
+            data: header.pt1.data(),
+            os_abi: header.pt1.os_abi(),
+            elf_type: header.pt2.type_().as_type(),
+            machine: binary.get_arch(),
+            entry_point: binary.entry_point(),
+            segments,
+            dynamic,
+            relocations,
+        })
+    }
+}
+
+/// Name of a dynamic tag as printed by readelf (without the DT_ prefix).
+pub fn dynamic_tag_name(tag: u64) -> Option<&'static str> {
+    Some(match tag {
+        0 => "NULL",
+        1 => "NEEDED",
+        2 => "PLTRELSZ",
+        3 => "PLTGOT",
+        4 => "HASH",
+        5 => "STRTAB",
+        6 => "SYMTAB",
+        7 => "RELA",
+        8 => "RELASZ",
+        9 => "RELAENT",
+        10 => "STRSZ",
+        11 => "SYMENT",
+        12 => "INIT",
+        13 => "FINI",
+        14 => "SONAME",
+        15 => "RPATH",
+        16 => "SYMBOLIC",
+        17 => "REL",
+        18 => "RELSZ",
+        19 => "RELENT",
+        20 => "PLTREL",
+        21 => "DEBUG",
+        22 => "TEXTREL",
+        23 => "JMPREL",
+        24 => "BIND_NOW",
+        25 => "INIT_ARRAY",
+        26 => "FINI_ARRAY",
+        27 => "INIT_ARRAYSZ",
+        28 => "FINI_ARRAYSZ",
+        29 => "RUNPATH",
+        30 => "FLAGS",
+        32 => "PREINIT_ARRAY",
+        33 => "PREINIT_ARRAYSZ",
+        34 => "SYMTAB_SHNDX",
+        35 => "RELRSZ",
+        36 => "RELR",
+        37 => "RELRENT",
+        0x6ffffef5 => "GNU_HASH",
+        0x6ffffff0 => "VERSYM",
+        0x6ffffff9 => "RELACOUNT",
+        0x6ffffffa => "RELCOUNT",
+        0x6ffffffb => "FLAGS_1",
+        0x6ffffffc => "VERDEF",
+        0x6ffffffd => "VERDEFNUM",
+        0x6ffffffe => "VERNEED",
+        0x6fffffff => "VERNEEDNUM",
+        _ => return None,
+    })
+}
+
+impl<'s> fmt::Display for Summary<'s> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "ELF Header:")?;
+        writeln!(f, "  Class:       {:?}", self.class)?;
+        writeln!(f, "  Data:        {:?}", self.data)?;
+        writeln!(f, "  OS/ABI:      {:?}", self.os_abi)?;
+        writeln!(f, "  Type:        {:?}", self.elf_type)?;
+        writeln!(f, "  Machine:     {:?}", self.machine)?;
+        writeln!(f, "  Entry point: {:#x}", self.entry_point)?;
+
+        writeln!(f, "Program Headers:")?;
+        writeln!(
+            f,
+            "  {:<16} {:<10} {:<18} {:<18} {:<10} {:<10} {:<3} Align",
+            "Type", "Offset", "VirtAddr", "PhysAddr", "FileSiz", "MemSiz", "Flg"
+        )?;
+        for s in self.segments.iter() {
+            let typ = match s.typ {
+                Type::Null => "NULL",
+                Type::Load => "LOAD",
+                Type::Dynamic => "DYNAMIC",
+                Type::Interp => "INTERP",
+                Type::Note => "NOTE",
+                Type::ShLib => "SHLIB",
+                Type::Phdr => "PHDR",
+                Type::Tls => "TLS",
+                Type::GnuRelro => "GNU_RELRO",
+                Type::OsSpecific(0x6474e550) => "GNU_EH_FRAME",
+                Type::OsSpecific(0x6474e551) => "GNU_STACK",
+                Type::OsSpecific(0x6474e553) => "GNU_PROPERTY",
+                Type::OsSpecific(typ) => match crate::openbsd::SegmentKind::from(typ) {
+                    Some(kind) => kind.name(),
+                    None => "LOOS+",
+                },
+                Type::ProcessorSpecific(_) => "LOPROC+",
+            };
+            writeln!(
+                f,
+                "  {:<16} {:#010x} {:#018x} {:#018x} {:#010x} {:#010x} {}{}{} {:#x}",
+                typ,
+                s.offset,
+                s.virtual_addr,
+                s.physical_addr,
+                s.file_size,
+                s.mem_size,
+                if s.flags.is_read() { 'R' } else { ' ' },
+                if s.flags.is_write() { 'W' } else { ' ' },
+                if s.flags.is_execute() { 'E' } else { ' ' },
+                s.align
+            )?;
+        }
+
+        if !self.dynamic.is_empty() {
+            writeln!(
+                f,
+                "Dynamic section contains {} entries:",
+                self.dynamic.len()
+            )?;
+            for entry in self.dynamic.iter() {
+                match dynamic_tag_name(entry.tag) {
+                    Some(name) => writeln!(f, "  {:<18} {:#x}", name, entry.value)?,
+                    None => writeln!(f, "  {:<#18x} {:#x}", entry.tag, entry.value)?,
+                }
+            }
+        }
+
+        for reloc in self.relocations.iter() {
+            writeln!(
+                f,
+                "Relocation section '{}' contains {} {} entries",
+                reloc.section,
+                reloc.count,
+                if reloc.has_addend { "RELA" } else { "REL" }
+            )?;
+        }
+
+        Ok(())
+    }
+}
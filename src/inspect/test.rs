@@ -0,0 +1,47 @@
+use std::fs;
+use std::string::ToString;
+
+use crate::arch::test::init;
+use crate::inspect::*;
+use crate::*;
+
+#[test]
+fn summary_x86_64() {
+    init();
+    let binary_blob = fs::read("test/test.x86_64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+    let summary = Summary::new(&binary).expect("Can't summarize?");
+
+    assert_eq!(summary.machine, Machine::X86_64);
+    assert_eq!(summary.segments.len(), 9);
+    assert_eq!(
+        summary.segments[2],
+        SegmentSummary {
+            typ: xmas_elf::program::Type::Load,
+            flags: Flags(1 | 4),
+            offset: 0,
+            virtual_addr: 0,
+            physical_addr: 0,
+            file_size: 0x888,
+            mem_size: 0x888,
+            align: 0x200000,
+        }
+    );
+
+    // readelf -d: Dynamic section at offset 0xdc8 contains 27 entries (incl. NULL)
+    assert_eq!(summary.dynamic.len(), 26);
+    assert_eq!(summary.dynamic[0], DynamicEntry { tag: 1, value: 1 });
+    assert_eq!(dynamic_tag_name(summary.dynamic[12].tag), Some("DEBUG"));
+
+    // readelf -r: .rela.dyn contains 8 entries, .rela.plt contains 1 entry
+    assert_eq!(summary.relocations.len(), 2);
+    assert_eq!(summary.relocations[0].section, ".rela.dyn");
+    assert_eq!(summary.relocations[0].count, 8);
+    assert_eq!(summary.relocations[1].section, ".rela.plt");
+    assert_eq!(summary.relocations[1].count, 1);
+
+    let text = summary.to_string();
+    assert!(text.contains("  Machine:     X86_64\n"));
+    assert!(text.contains("  FLAGS_1            0x8000001\n"));
+    assert!(text.contains("Relocation section '.rela.plt' contains 1 RELA entries\n"));
+}
@@ -7,12 +7,68 @@
 extern crate std;
 #[cfg(test)]
 extern crate env_logger;
+#[cfg(all(feature = "parallel", not(test)))]
+extern crate std;
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
 
+pub mod addr32;
 mod binary;
 pub use binary::ElfBinary;
 
 pub mod arch;
-pub use arch::RelocationType;
+pub mod boot;
+pub use arch::{
+    JumpSlotFixup, LazyBindingAbi, LazyBindingSlots, PltSelector, RelocationType, TlsSegment,
+    TrampolineArg,
+};
+
+pub mod debug;
+mod debuglink;
+pub mod dwarf;
+pub mod elf;
+#[cfg(feature = "alloc")]
+pub mod export;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub use debuglink::{crc32, DebugLink};
+#[cfg(feature = "gdb-jit")]
+pub mod gdb_jit;
+#[cfg(feature = "alloc")]
+pub mod inspect;
+mod maps;
+pub use maps::Mappings;
+#[cfg(feature = "parallel")]
+mod parallel;
+#[cfg(feature = "parallel")]
+pub use parallel::RelocationSink;
+pub mod note;
+mod once;
+pub mod openbsd;
+mod permissions;
+pub use permissions::SegmentPermissions;
+#[cfg(feature = "alloc")]
+pub mod plan;
+mod reloc;
+pub use reloc::{RelocationResolver, RelocationValue};
+mod relocations;
+pub use relocations::Relocations;
+mod segments;
+pub use segments::Segment;
+mod strtab;
+pub use strtab::StringTable;
+mod symbols;
+#[cfg(feature = "alloc")]
+pub use symbols::SymbolIndex;
+pub use symbols::{FunctionRange, Symbol, SymbolTableKind, Symbols};
+pub mod runpath;
+mod slice_loader;
+pub use slice_loader::SliceLoader;
+#[cfg(feature = "alloc")]
+pub mod tls;
+#[cfg(feature = "unwind")]
+pub mod unwind;
 
 use core::fmt;
 use core::iter::Filter;
@@ -21,14 +77,66 @@ use bitflags::bitflags;
 use xmas_elf::dynamic::*;
 use xmas_elf::program::ProgramIter;
 
+pub use xmas_elf::dynamic::Tag;
 pub use xmas_elf::header::Machine;
 pub use xmas_elf::program::{Flags, ProgramHeader, ProgramHeader64};
-pub use xmas_elf::sections::{Rel, Rela};
+pub use xmas_elf::sections::{Rel, Rela, ShType};
 pub use xmas_elf::symbol_table::{Entry, Entry64};
 pub use xmas_elf::{P32, P64};
 
 /// An iterator over [`ProgramHeader`] whose type is `LOAD`.
 pub type LoadableHeaders<'a, 'b> = Filter<ProgramIter<'a, 'b>, fn(&ProgramHeader) -> bool>;
+
+/// A LOAD segment to allocate, see [`ElfLoader::allocate`].
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub struct AllocateRequest {
+    /// (Unrelocated) start address of the segment.
+    pub vaddr: VAddr,
+    /// Physical address of the segment (`p_paddr`).
+    pub paddr: PAddr,
+    /// Size of the segment in memory.
+    pub mem_size: u64,
+    /// Size of the file-backed part of the segment, the rest is zeroed.
+    pub file_size: u64,
+    /// Alignment of the segment.
+    pub align: u64,
+    /// The permissions of the segment once loaded.
+    pub flags: SegmentPermissions,
+}
+
+impl<'a> From<ProgramHeader<'a>> for AllocateRequest {
+    fn from(header: ProgramHeader<'a>) -> AllocateRequest {
+        AllocateRequest {
+            vaddr: header.virtual_addr(),
+            paddr: header.physical_addr(),
+            mem_size: header.mem_size(),
+            file_size: header.file_size(),
+            align: header.align(),
+            flags: header.flags().into(),
+        }
+    }
+}
+
+/// An iterator over the LOAD segments of a binary, passed to
+/// [`ElfLoader::allocate`].
+#[derive(Clone)]
+pub struct AllocateRequests<'a, 'b> {
+    headers: LoadableHeaders<'a, 'b>,
+}
+
+impl<'a, 'b> AllocateRequests<'a, 'b> {
+    pub(crate) fn new(headers: LoadableHeaders<'a, 'b>) -> AllocateRequests<'a, 'b> {
+        AllocateRequests { headers }
+    }
+}
+
+impl<'a, 'b> Iterator for AllocateRequests<'a, 'b> {
+    type Item = AllocateRequest;
+
+    fn next(&mut self) -> Option<AllocateRequest> {
+        self.headers.next().map(AllocateRequest::from)
+    }
+}
 pub type PAddr = u64;
 pub type VAddr = u64;
 
@@ -38,24 +146,431 @@ pub type VAddr = u64;
 #[allow(dead_code)]
 pub struct RelocationEntry {
     pub rtype: RelocationType,
+    /// The raw relocation type number (also for types decoded as `Unknown`).
+    pub type_num: u32,
+    /// The raw `r_info` field (symbol index and type).
+    pub info: u64,
     pub offset: u64,
     pub index: u32,
+    /// The addend of the relocation.
+    ///
+    /// For REL entries (which have no explicit addend) this is the implicit
+    /// addend read from the relocated location in the file, or None if that
+    /// location is not file-backed.
     pub addend: Option<u64>,
+    /// The class of the binary, which determines the width of word-sized
+    /// relocations (e.g., RELATIVE on RISC-V).
+    pub class: elf::Class,
+    /// The LOAD segment containing `offset`, None if no LOAD segment covers
+    /// it.
+    pub segment: Option<RelocationSegment>,
+}
+
+/// The LOAD segment patched by a relocation, see [`RelocationEntry::segment`].
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub struct RelocationSegment {
+    /// Index of the program header.
+    pub index: usize,
+    /// (Unrelocated) start address of the segment.
+    pub vaddr: VAddr,
+    /// Size of the segment in memory.
+    pub mem_size: u64,
+    /// The permissions of the segment once loaded.
+    pub flags: SegmentPermissions,
+    /// Whether the relocated location lies in a GNU_RELRO segment, i.e., is
+    /// made read-only after relocation.
+    pub relro: bool,
+}
+
+/// What a GOT slot is patched with, see [`GotFixup`].
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub enum GotFixupKind {
+    /// The load bias plus the addend (R_*_RELATIVE).
+    Relative,
+    /// The address of the symbol plus the addend (R_*_GLOB_DAT).
+    GlobDat,
+    /// The address of the function (R_*_JUMP_SLOT), also the slots that
+    /// are bound lazily.
+    JumpSlot,
+    /// The result of calling the resolver at the load bias plus the addend
+    /// (R_*_IRELATIVE).
+    Ifunc,
+}
+
+/// A word sized slot of the binary that is patched at load time.
+///
+/// Returned by [`ElfBinary::got_fixups`].
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub struct GotFixup {
+    /// (Unrelocated) address of the slot.
+    pub address: VAddr,
+    pub kind: GotFixupKind,
+    /// Index of the symbol in the dynamic symbol table (0 for none).
+    pub symbol: u32,
+    /// The addend of the relocation.
+    pub addend: Option<u64>,
+}
+
+/// A step of the startup code of an image whose LOAD segments are stored
+/// at their load memory address (LMA, `p_paddr`), e.g. in flash, but run
+/// at their virtual memory address (VMA, `p_vaddr`).
+///
+/// Returned by [`ElfBinary::scatter_load`]. `segment` is the index of the
+/// program header, to tell apart overlays that share a VMA.
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub enum ScatterLoad {
+    /// Copy `size` bytes from `lma` to `vma`.
+    Copy {
+        segment: usize,
+        lma: u64,
+        vma: VAddr,
+        size: u64,
+    },
+    /// Zero `size` bytes at `vma`.
+    Zero {
+        segment: usize,
+        vma: VAddr,
+        size: u64,
+    },
+}
+
+/// The initialization image of the thread-local storage (the PT_TLS
+/// segment), see [`ElfBinary::tls_template`].
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub struct TlsTemplate<'s> {
+    /// (Unrelocated) address of the image.
+    pub vaddr: VAddr,
+    /// The initialized data (`.tdata`).
+    pub tdata: &'s [u8],
+    /// Size of the zero-initialized data (`.tbss`) following `tdata`.
+    pub tbss_size: u64,
+    /// Size of the TLS block of a thread (`tdata` and `.tbss`).
+    pub mem_size: u64,
+    /// Alignment of the TLS block of a thread.
+    pub align: u64,
+}
+
+/// A LOAD segment as it is laid out in memory, see
+/// [`ElfBinary::memory_image`].
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub struct MemoryRegion<'s> {
+    /// Index of the program header.
+    pub segment: usize,
+    /// Address of the segment in memory.
+    pub vaddr: VAddr,
+    /// Size of the segment in memory (`p_memsz`).
+    pub mem_size: u64,
+    /// Permissions of the segment.
+    pub flags: SegmentPermissions,
+    /// The file contents placed at `vaddr`, without relocations applied.
+    pub data: &'s [u8],
+    /// Start of the zero-filled remainder of the segment (`.bss`), following
+    /// `data`.
+    pub zero_start: VAddr,
+    /// Size of the zero-filled remainder in bytes.
+    pub zero_size: u64,
+}
+
+/// A PT_GNU_RELRO segment: the region that becomes read-only once the
+/// relocations were applied.
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RelroSegment {
+    /// (Unrelocated) start address of the region.
+    pub vaddr: VAddr,
+    /// Size of the region in bytes.
+    pub mem_size: u64,
+    /// Alignment of the segment.
+    pub align: u64,
+    /// Page size the region is protected with, see [`LoadConfig::page_size`].
+    pub page_size: u64,
+}
+
+impl RelroSegment {
+    /// The pages to protect, [`RelroSegment::page_range`] for the
+    /// configured `page_size`.
+    pub fn pages(&self) -> Result<(VAddr, VAddr), ElfLoaderErr> {
+        self.page_range(self.page_size)
+    }
+
+    /// The pages to protect for the given `page_size`: the start is rounded
+    /// down and the end is rounded down as well, since the last page may
+    /// contain writable data following the region. `page_size` must be a
+    /// power of two.
+    ///
+    /// Fails with `AddressOverflow` if the region wraps around the end of
+    /// the address space.
+    pub fn page_range(&self, page_size: u64) -> Result<(VAddr, VAddr), ElfLoaderErr> {
+        let mask = !(page_size - 1);
+        let end = self
+            .vaddr
+            .checked_add(self.mem_size)
+            .ok_or(ElfLoaderErr::AddressOverflow {
+                source: "p_vaddr + p_memsz",
+            })?;
+        Ok((self.vaddr & mask, end & mask))
+    }
+}
+
+/// Options for [`ElfBinary::load_with_config`].
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub struct LoadConfig {
+    /// Don't apply the GNU_RELRO segments after relocation, the caller
+    /// does so with [`ElfBinary::finalize`] (e.g., after it ran its own
+    /// fixups on the relocated data).
+    pub defer_relro: bool,
+    /// Check the LOAD segments with [`ElfBinary::validate_load_segments`]
+    /// before issuing any callbacks.
+    pub validate_segments: bool,
+    /// Upper bounds on the resources requested from the loader.
+    pub limits: Limits,
+    /// Call [`ElfLoader::progress`] after every this many relocations (and
+    /// after the last one). With 0 relocation progress is only reported
+    /// once all relocations were applied.
+    pub progress_interval: u64,
+    /// Request a single region spanning all LOAD segments with
+    /// [`ElfLoader::allocate_coalesced`] instead of calling
+    /// [`ElfLoader::allocate`].
+    pub coalesce_allocation: bool,
+    /// Page size of the target (e.g., 4 KiB, 16 KiB, 64 KiB or 2 MiB) used
+    /// to check the LOAD segments and to round the RELRO regions (see
+    /// [`RelroSegment::pages`]). Must be a power of two.
+    pub page_size: u64,
+    /// Reject binaries with a higher [`ElfBinary::abi_version`] with
+    /// `UnsupportedAbiVersion`, None accepts any version.
+    pub max_abi_version: Option<u8>,
+    /// Apply the RELATIVE relocations for a binary loaded at this base
+    /// address in the crate, writing the relocated values with
+    /// [`ElfLoader::write_u64`] (ELF64) or [`ElfLoader::write_u32`] (ELF32)
+    /// instead of passing them to [`ElfLoader::relocate`]. None passes all
+    /// relocations to `relocate`.
+    pub relative_base: Option<VAddr>,
+}
+
+impl LoadConfig {
+    /// The page size assumed unless configured otherwise (4 KiB).
+    pub const DEFAULT_PAGE_SIZE: u64 = 0x1000;
+}
+
+impl Default for LoadConfig {
+    fn default() -> Self {
+        LoadConfig {
+            defer_relro: false,
+            validate_segments: false,
+            limits: Limits::default(),
+            progress_interval: 0,
+            coalesce_allocation: false,
+            page_size: LoadConfig::DEFAULT_PAGE_SIZE,
+            max_abi_version: None,
+            relative_base: None,
+        }
+    }
+}
+
+/// A single region covering all LOAD segments of a binary, see
+/// [`ElfBinary::coalesced_allocation`].
+pub struct CoalescedAllocation<'a, 'b> {
+    /// Start of the region: the lowest LOAD address, rounded down to `align`.
+    pub vaddr: VAddr,
+    /// Size of the region: up to the highest LOAD end address, rounded up
+    /// to `align`.
+    pub mem_size: u64,
+    /// The largest alignment of the LOAD segments.
+    pub align: u64,
+    /// The LOAD segments inside the region, e.g., to apply their flags.
+    pub segments: AllocateRequests<'a, 'b>,
+}
+
+/// Whether a LOAD segment is loaded, see [`ElfLoader::disposition`].
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub enum Disposition {
+    Load,
+    Skip,
+}
+
+/// Progress of a load, passed to [`ElfLoader::progress`].
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub enum LoadProgress {
+    /// `completed` of `total` LOAD segments were loaded (or skipped).
+    Segments { completed: u64, total: u64 },
+    /// `completed` of `total` relocations were applied.
+    Relocations { completed: u64, total: u64 },
+}
+
+/// Proof that a binary passed the checks of
+/// [`ElfBinary::load_with_config`], returned by [`ElfBinary::validate`].
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub struct ValidationToken {
+    config: LoadConfig,
+    fingerprint: (usize, u32),
+}
+
+impl ValidationToken {
+    /// The configuration the binary was validated for.
+    pub fn config(&self) -> &LoadConfig {
+        &self.config
+    }
+}
+
+/// What [`ElfBinary::load_with_stats`] asked the loader to do.
+#[derive(Eq, PartialEq, Debug, Clone, Copy, Default)]
+pub struct LoadStats {
+    /// Number of LOAD segments passed to [`ElfLoader::load`].
+    pub segments: usize,
+    /// Number of LOAD segments skipped by [`ElfLoader::disposition`].
+    pub segments_skipped: usize,
+    /// Bytes of file contents passed to [`ElfLoader::load`].
+    pub bytes_copied: u64,
+    /// Bytes of the LOAD segments not backed by the file (e.g., `.bss`),
+    /// which the loader has to zero.
+    pub bytes_zeroed: u64,
+    /// Relocations passed to [`ElfLoader::relocate`], by type (without
+    /// those targeting skipped segments).
+    pub relocations: RelocationCounts,
+    /// Size of the TLS block (`p_memsz` of the PT_TLS segment).
+    pub tls_size: u64,
+}
+
+/// Number of relocations per (raw) relocation type.
+///
+/// Keeps up to [`RelocationCounts::CAPACITY`] distinct types, relocations
+/// of any further types are only counted in `other`.
+#[derive(Eq, PartialEq, Debug, Clone, Copy, Default)]
+pub struct RelocationCounts {
+    counts: [(u32, u64); RelocationCounts::CAPACITY],
+    len: usize,
+    /// Relocations whose type didn't fit in the table.
+    pub other: u64,
+}
+
+impl RelocationCounts {
+    /// Maximum number of distinct relocation types tracked.
+    pub const CAPACITY: usize = 16;
+
+    /// Count a relocation of type `type_num`.
+    pub fn add(&mut self, type_num: u32) {
+        self.add_count(type_num, 1);
+    }
+
+    /// Add the counts of `other` to these.
+    pub fn merge(&mut self, other: &RelocationCounts) {
+        for (type_num, count) in other.iter() {
+            self.add_count(type_num, count);
+        }
+        self.other += other.other;
+    }
+
+    fn add_count(&mut self, type_num: u32, n: u64) {
+        let counts = &mut self.counts[..self.len];
+        if let Some((_, count)) = counts.iter_mut().find(|(typ, _)| *typ == type_num) {
+            *count += n;
+        } else if self.len < RelocationCounts::CAPACITY {
+            self.counts[self.len] = (type_num, n);
+            self.len += 1;
+        } else {
+            self.other += n;
+        }
+    }
+
+    /// Number of relocations of type `type_num`.
+    pub fn get(&self, type_num: u32) -> u64 {
+        self.iter()
+            .find(|&(typ, _)| typ == type_num)
+            .map_or(0, |(_, count)| count)
+    }
+
+    /// Iterate over the (type, count) pairs in the order the types were
+    /// first seen.
+    pub fn iter(&self) -> impl Iterator<Item = (u32, u64)> + '_ {
+        self.counts[..self.len].iter().copied()
+    }
+
+    /// Total number of relocations.
+    pub fn total(&self) -> u64 {
+        self.iter().map(|(_, count)| count).sum::<u64>() + self.other
+    }
+}
+
+/// Resource limits enforced before any loader callback is issued.
+///
+/// Loading fails with `LimitsExceeded` if the binary asks for more. A
+/// limit of None means unlimited.
+#[derive(Eq, PartialEq, Debug, Clone, Copy, Default)]
+pub struct Limits {
+    /// Maximum `p_memsz` of a single LOAD segment.
+    pub max_segment_size: Option<u64>,
+    /// Maximum sum of the `p_memsz` of all LOAD segments.
+    pub max_total_size: Option<u64>,
+    /// Maximum number of dynamic relocation entries.
+    pub max_relocations: Option<u64>,
 }
 
 #[derive(PartialEq, Clone, Debug)]
 pub enum ElfLoaderErr {
-    ElfParser { source: &'static str },
+    ElfParser {
+        source: &'static str,
+    },
     OutOfMemory,
     SymbolTableNotFound,
     UnsupportedElfFormat,
     UnsupportedElfVersion,
     UnsupportedEndianness,
     UnsupportedAbi,
+    /// The ABI version of the binary exceeds [`LoadConfig::max_abi_version`].
+    UnsupportedAbiVersion {
+        version: u8,
+    },
     UnsupportedElfType,
     UnsupportedSectionData,
     UnsupportedArchitecture,
     UnsupportedRelocationEntry,
+    /// The program header with the given index is malformed.
+    InvalidSegment {
+        index: usize,
+        reason: &'static str,
+    },
+    /// The memory ranges of the LOAD segments with the given program header
+    /// indices overlap.
+    OverlappingSegments {
+        first: usize,
+        second: usize,
+    },
+    /// An address or size computed from the given header fields wraps around.
+    AddressOverflow {
+        source: &'static str,
+    },
+    /// The file contents of the program header with the given index lie
+    /// outside of the ELF file.
+    SegmentOutOfBounds {
+        index: usize,
+    },
+    /// The binary requests more resources than allowed by [`Limits`].
+    LimitsExceeded {
+        limit: &'static str,
+    },
+    /// [`LoadConfig::page_size`] is not a power of two.
+    InvalidPageSize {
+        page_size: u64,
+    },
+    /// The binary can't be loaded as a self-contained static PIE.
+    NotStaticPie {
+        reason: &'static str,
+    },
+    /// The ELF file is malformed.
+    Parse {
+        error: elf::ParseError,
+    },
+    /// The lazy binding selector doesn't identify a PLT relocation.
+    InvalidPltSelector {
+        selector: u64,
+    },
+    /// The build ID of the binary differs from the expected one, see
+    /// [`ElfBinary::check_build_id`].
+    BuildIdMismatch,
+    /// A [`plan::LoadPlan`] is malformed or doesn't match the binary.
+    InvalidPlan {
+        reason: &'static str,
+    },
 }
 
 impl From<&'static str> for ElfLoaderErr {
@@ -64,6 +579,12 @@ impl From<&'static str> for ElfLoaderErr {
     }
 }
 
+impl From<elf::ParseError> for ElfLoaderErr {
+    fn from(error: elf::ParseError) -> Self {
+        ElfLoaderErr::Parse { error }
+    }
+}
+
 impl fmt::Display for ElfLoaderErr {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -74,12 +595,36 @@ impl fmt::Display for ElfLoaderErr {
             ElfLoaderErr::UnsupportedElfVersion => write!(f, "ELF version not supported"),
             ElfLoaderErr::UnsupportedEndianness => write!(f, "ELF endianness not supported"),
             ElfLoaderErr::UnsupportedAbi => write!(f, "ELF ABI not supported"),
+            ElfLoaderErr::UnsupportedAbiVersion { version } => {
+                write!(f, "ELF ABI version {} not supported", version)
+            }
             ElfLoaderErr::UnsupportedElfType => write!(f, "ELF type not supported"),
             ElfLoaderErr::UnsupportedSectionData => write!(f, "Can't handle this section data"),
             ElfLoaderErr::UnsupportedArchitecture => write!(f, "Unsupported Architecture"),
             ElfLoaderErr::UnsupportedRelocationEntry => {
                 write!(f, "Can't handle relocation entry")
             }
+            ElfLoaderErr::InvalidSegment { index, reason } => {
+                write!(f, "Invalid program header {}: {}", index, reason)
+            }
+            ElfLoaderErr::OverlappingSegments { first, second } => {
+                write!(f, "Program headers {} and {} overlap", first, second)
+            }
+            ElfLoaderErr::AddressOverflow { source } => write!(f, "Overflow in {}", source),
+            ElfLoaderErr::SegmentOutOfBounds { index } => {
+                write!(f, "Program header {} points outside of the file", index)
+            }
+            ElfLoaderErr::LimitsExceeded { limit } => write!(f, "Exceeded limit {}", limit),
+            ElfLoaderErr::InvalidPageSize { page_size } => {
+                write!(f, "Invalid page size {:#x}", page_size)
+            }
+            ElfLoaderErr::NotStaticPie { reason } => write!(f, "Not a static PIE: {}", reason),
+            ElfLoaderErr::Parse { error } => write!(f, "Malformed ELF file: {}", error),
+            ElfLoaderErr::InvalidPltSelector { selector } => {
+                write!(f, "No PLT relocation for selector {:#x}", selector)
+            }
+            ElfLoaderErr::BuildIdMismatch => write!(f, "Unexpected build ID"),
+            ElfLoaderErr::InvalidPlan { reason } => write!(f, "Invalid load plan: {}", reason),
         }
     }
 }
@@ -118,11 +663,130 @@ bitflags! {
     }
 }
 
+bitflags! {
+    /// Flags of the DT_FLAGS .dynamic entry.
+    #[derive(Default)]
+    pub struct DynamicFlags: u64 {
+        /// The object may reference the `$ORIGIN` substitution string.
+        const ORIGIN = 0x1;
+        /// Symbol resolution starts at the object itself.
+        const SYMBOLIC = 0x2;
+        /// Relocations may modify non-writable segments.
+        const TEXTREL = 0x4;
+        /// All relocations must be processed before control is transferred.
+        const BIND_NOW = 0x8;
+        /// The object uses the static TLS model.
+        const STATIC_TLS = 0x10;
+    }
+}
+
+bitflags! {
+    /// Flags of a section header (sh_flags).
+    #[derive(Default)]
+    pub struct SectionFlags: u64 {
+        const WRITE = 0x1;
+        const ALLOC = 0x2;
+        const EXECINSTR = 0x4;
+        const MERGE = 0x10;
+        const STRINGS = 0x20;
+        const INFO_LINK = 0x40;
+        const LINK_ORDER = 0x80;
+        const OS_NONCONFORMING = 0x100;
+        const GROUP = 0x200;
+        const TLS = 0x400;
+        const COMPRESSED = 0x800;
+    }
+}
+
+/// A section of the ELF file as returned by [`ElfBinary::sections`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Section<'s> {
+    /// Name of the section (e.g., `.text`).
+    pub name: &'s str,
+    /// Virtual address of the section (zero if it is not allocated).
+    pub address: VAddr,
+    /// Offset of the section in the file.
+    pub offset: u64,
+    /// Size of the section in bytes.
+    pub size: u64,
+    pub flags: SectionFlags,
+    pub typ: ShType,
+}
+
+/// The identification of a binary from its ELF header, see
+/// [`ElfBinary::probe`].
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub struct ElfSummary {
+    pub class: elf::Class,
+    pub endianness: elf::Endianness,
+    /// `e_machine`
+    pub machine: u16,
+    /// `e_type`
+    pub typ: u16,
+    /// `e_entry`
+    pub entry: u64,
+    /// `e_phnum`
+    pub phnum: u16,
+}
+
+/// What kind of program a binary is, see [`ElfBinary::kind`].
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub enum BinaryKind {
+    /// A position dependent executable (ET_EXEC).
+    Executable,
+    /// A position independent executable (ET_DYN).
+    Pie,
+    /// A shared library (ET_DYN).
+    SharedLibrary,
+}
+
 /// Information parse from the .dynamic section
 pub struct DynamicInfo {
+    pub flags: DynamicFlags,
     pub flags1: DynamicFlags1,
+    /// Address of the RELA relocation table (DT_RELA).
     pub rela: u64,
+    /// Size of the RELA relocation table in bytes (DT_RELASZ).
     pub rela_size: u64,
+    /// Address of the REL relocation table (DT_REL).
+    pub rel: u64,
+    /// Size of the REL relocation table in bytes (DT_RELSZ).
+    pub rel_size: u64,
+    /// Address of the PLT relocation table (DT_JMPREL).
+    pub jmprel: u64,
+    /// Size of the PLT relocation table in bytes (DT_PLTRELSZ).
+    pub pltrel_size: u64,
+    /// Type of the PLT relocation entries (DT_PLTREL), DT_REL or DT_RELA.
+    pub pltrel: u64,
+    /// Address of the PLT GOT (DT_PLTGOT).
+    pub pltgot: Option<u64>,
+    /// Address of the value of the DT_DEBUG entry.
+    pub debug: Option<u64>,
+    /// Address of the dynamic string table (DT_STRTAB).
+    pub strtab: Option<u64>,
+    /// Size of the dynamic string table in bytes (DT_STRSZ).
+    pub strtab_size: u64,
+    /// Offset of the DT_RPATH string in the dynamic string table.
+    pub rpath: Option<u64>,
+    /// Offset of the DT_RUNPATH string in the dynamic string table.
+    pub runpath: Option<u64>,
+    /// Offset of the DT_SONAME string in the dynamic string table.
+    pub soname: Option<u64>,
+    /// Number of DT_NEEDED entries (shared libraries the binary depends on).
+    pub needed: u64,
+    /// Number of local MIPS GOT entries (DT_MIPS_LOCAL_GOTNO).
+    pub mips_local_gotno: Option<u64>,
+    /// First dynamic symbol with a MIPS GOT entry (DT_MIPS_GOTSYM).
+    pub mips_gotsym: Option<u64>,
+    /// Number of dynamic symbols (DT_MIPS_SYMTABNO).
+    pub mips_symtabno: Option<u64>,
+    /// Whether DT_RISCV_VARIANT_CC is present: some PLT relocations refer to
+    /// symbols with a variant calling convention, which have to be bound
+    /// eagerly (see [`ElfBinary::riscv_variant_cc_symbols`]).
+    pub riscv_variant_cc: bool,
+    /// Whether DT_TEXTREL is present (older binaries use it instead of
+    /// DF_TEXTREL in `flags`).
+    pub textrel: bool,
 }
 
 /// Implement this trait for customized ELF loading.
@@ -132,29 +796,105 @@ pub struct DynamicInfo {
 /// then `load` will be called to fill the allocated regions, and finally
 /// `relocate` is called for every entry in the RELA table.
 pub trait ElfLoader {
-    /// Allocates a virtual region specified by `load_headers`.
-    fn allocate(&mut self, load_headers: LoadableHeaders) -> Result<(), ElfLoaderErr>;
+    /// Allocates the virtual regions of the LOAD segments in `requests`.
+    fn allocate(&mut self, requests: AllocateRequests) -> Result<(), ElfLoaderErr>;
+
+    /// Allocates one virtual region for all LOAD segments, called instead
+    /// of `allocate` if [`LoadConfig::coalesce_allocation`] is set.
+    ///
+    /// Note: The default implementation calls `allocate` with the LOAD
+    /// segments.
+    fn allocate_coalesced(&mut self, allocation: CoalescedAllocation) -> Result<(), ElfLoaderErr> {
+        self.allocate(allocation.segments)
+    }
 
-    /// Copies `region` into memory starting at `base`.
+    /// Copies `region` into memory starting at `base`, part of a segment
+    /// with the permissions `flags`.
     /// The caller makes sure that there was an `allocate` call previously
     /// to initialize the region.
-    fn load(&mut self, flags: Flags, base: VAddr, region: &[u8]) -> Result<(), ElfLoaderErr>;
+    fn load(
+        &mut self,
+        flags: SegmentPermissions,
+        base: VAddr,
+        region: &[u8],
+    ) -> Result<(), ElfLoaderErr>;
 
     /// Request for the client to relocate the given `entry`
     /// within the loaded ELF file.
     fn relocate(&mut self, entry: RelocationEntry) -> Result<(), ElfLoaderErr>;
 
-    /// Inform client about where the initial TLS data is located.
+    /// Writes `value` to the 64-bit word at `vaddr` (the link-time address,
+    /// like the `base` of `load`).
+    ///
+    /// Called instead of `relocate` for the RELATIVE relocations of ELF64
+    /// binaries if [`LoadConfig::relative_base`] is set.
+    ///
+    /// Note: The default implementation fails with
+    /// `UnsupportedRelocationEntry`.
+    fn write_u64(&mut self, _vaddr: VAddr, _value: u64) -> Result<(), ElfLoaderErr> {
+        Err(ElfLoaderErr::UnsupportedRelocationEntry)
+    }
+
+    /// Like `write_u64`, for the 32-bit words relocated in ELF32 binaries.
+    fn write_u32(&mut self, _vaddr: VAddr, _value: u32) -> Result<(), ElfLoaderErr> {
+        Err(ElfLoaderErr::UnsupportedRelocationEntry)
+    }
+
+    /// In case the binary has text relocations (DT_TEXTREL) this is called
+    /// for every non-writable LOAD segment before the relocate calls, so the
+    /// loader can temporarily make the region writable.
+    ///
+    /// Note: The default implementation is a no-op which is sufficient for
+    /// loaders that keep all regions writable during loading.
+    fn make_writable(&mut self, _base: VAddr, _size: usize) -> Result<(), ElfLoaderErr> {
+        Ok(())
+    }
+
+    /// Counterpart to `make_writable`: called after the relocate calls
+    /// are completed to restore the original `flags` of the region.
+    ///
+    /// Note: The default implementation is a no-op.
+    fn restore_permissions(
+        &mut self,
+        _base: VAddr,
+        _size: usize,
+        _flags: SegmentPermissions,
+    ) -> Result<(), ElfLoaderErr> {
+        Ok(())
+    }
+
+    /// Inform client about the initial TLS data (the PT_TLS segment).
+    ///
+    /// `tdata` is the initialization image (`.tdata`) located at
+    /// `tdata_start`, it is followed by `tbss_length` bytes that need to be
+    /// zeroed (`.tbss`). The TLS block of a thread has to be aligned to
+    /// `align`.
     fn tls(
         &mut self,
         _tdata_start: VAddr,
-        _tdata_length: u64,
-        _total_size: u64,
+        _tdata: &[u8],
+        _tbss_length: u64,
         _align: u64,
     ) -> Result<(), ElfLoaderErr> {
         Ok(())
     }
 
+    /// Called for every OpenBSD specific segment (PT_OPENBSD_*) once all
+    /// LOAD segments were loaded, with the (unrelocated) range of the segment.
+    ///
+    /// A loader for OpenBSD binaries has to fill a `Randomize` region with
+    /// random bytes, for example.
+    ///
+    /// Note: The default implementation is a no-op.
+    fn openbsd_segment(
+        &mut self,
+        _kind: openbsd::SegmentKind,
+        _base: VAddr,
+        _size: usize,
+    ) -> Result<(), ElfLoaderErr> {
+        Ok(())
+    }
+
     /// In case there is a `.data.rel.ro` section we instruct the loader
     /// to change the passed offset to read-only (this is called after
     /// the relocate calls are completed).
@@ -164,6 +904,40 @@ pub trait ElfLoader {
     fn make_readonly(&mut self, _base: VAddr, _size: usize) -> Result<(), ElfLoaderErr> {
         Ok(())
     }
+
+    /// Called for every GNU_RELRO segment once the relocations were applied
+    /// (or from [`ElfBinary::finalize`] if [`LoadConfig::defer_relro`] is set).
+    ///
+    /// Loaders that protect whole pages can use [`RelroSegment::pages`].
+    ///
+    /// Note: The default implementation calls `make_readonly` with the
+    /// unrounded range of the segment.
+    fn relro(&mut self, segment: RelroSegment) -> Result<(), ElfLoaderErr> {
+        self.make_readonly(segment.vaddr, segment.mem_size as usize)
+    }
+
+    /// Called for every LOAD segment after `allocate`, to decide whether the
+    /// segment is loaded.
+    ///
+    /// For a segment that is skipped there is no `load` call, relocations
+    /// targeting it are not passed to `relocate` and it isn't made writable
+    /// (for text relocations) or read-only (RELRO). This is called once per
+    /// segment; only the first 64 LOAD segments can be skipped, skipping a
+    /// later one fails with `InvalidSegment`.
+    ///
+    /// Note: The default implementation loads all segments.
+    fn disposition(&mut self, _header: ProgramHeader) -> Result<Disposition, ElfLoaderErr> {
+        Ok(Disposition::Load)
+    }
+
+    /// Called after every loaded LOAD segment and periodically while
+    /// relocating (see [`LoadConfig::progress_interval`]), e.g., to update a
+    /// progress bar or feed a watchdog. Returning an error aborts loading.
+    ///
+    /// Note: The default implementation is a no-op.
+    fn progress(&mut self, _progress: LoadProgress) -> Result<(), ElfLoaderErr> {
+        Ok(())
+    }
 }
 
 #[cfg(doctest)]
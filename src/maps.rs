@@ -0,0 +1,67 @@
+//! A `/proc/self/maps` style report of the planned mappings of a binary.
+
+use core::fmt;
+
+use crate::{ElfBinary, SectionFlags, SegmentPermissions, VAddr};
+use xmas_elf::program::Type;
+
+/// The LOAD, TLS and GNU_RELRO segments of a binary relocated to `base`.
+///
+/// Created by [`ElfBinary::mappings`]. The `Display` implementation prints
+/// one line per segment in the format
+///
+/// ```text
+/// start-end perms offset type sections
+/// ```
+///
+/// where `sections` lists the names of the allocated sections that are
+/// placed in the segment.
+pub struct Mappings<'a, 's> {
+    pub(crate) binary: &'a ElfBinary<'s>,
+    pub(crate) base: VAddr,
+}
+
+impl<'a, 's> fmt::Display for Mappings<'a, 's> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let width = if self.binary.file.header.pt1.class() == xmas_elf::header::Class::ThirtyTwo {
+            8
+        } else {
+            16
+        };
+
+        for header in self.binary.program_headers() {
+            let typ = match header.get_type() {
+                Ok(Type::Load) => "LOAD",
+                Ok(Type::Tls) => "TLS",
+                Ok(Type::GnuRelro) => "RELRO",
+                _ => continue,
+            };
+
+            // Addresses wrap around like they would in the loaded image
+            let start = header.virtual_addr();
+            let size = header.mem_size();
+            let end = start.wrapping_add(size);
+            let flags = header.flags();
+            write!(
+                f,
+                "{:0width$x}-{:0width$x} {}p {:08x} {:<5}",
+                self.base.wrapping_add(start),
+                self.base.wrapping_add(end),
+                SegmentPermissions::from(flags),
+                header.offset(),
+                typ,
+                width = width
+            )?;
+
+            for section in self.binary.sections() {
+                let addr = section.address;
+                if section.flags.contains(SectionFlags::ALLOC) && addr.wrapping_sub(start) < size {
+                    write!(f, " {}", section.name)?;
+                }
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
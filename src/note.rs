@@ -0,0 +1,187 @@
+//! ELF notes (PT_NOTE segments).
+//!
+//! A note consists of an owner name, a type that is interpreted relative
+//! to the owner and a descriptor. GNU toolchains use notes to record e.g.
+//! the minimum kernel version a binary needs (`NT_GNU_ABI_TAG`) or a
+//! unique ID of the build (`NT_GNU_BUILD_ID`).
+
+use crate::elf::{Class, Encoding};
+
+#[cfg(test)]
+mod test;
+
+/// Owner name of the notes written by GNU toolchains.
+pub const GNU_OWNER: &str = "GNU";
+/// GNU note holding the OS and minimum kernel version (`.note.ABI-tag`).
+pub const NT_GNU_ABI_TAG: u32 = 1;
+/// GNU note holding the unique build ID (`.note.gnu.build-id`).
+pub const NT_GNU_BUILD_ID: u32 = 3;
+/// GNU note holding program properties (`.note.gnu.property`).
+pub const NT_GNU_PROPERTY_TYPE_0: u32 = 5;
+
+/// A single note.
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub struct Note<'s> {
+    /// Owner of the note (without the NUL terminator).
+    pub name: &'s [u8],
+    /// Type of the note, its meaning depends on `name`.
+    pub typ: u32,
+    /// The descriptor (payload) of the note.
+    pub desc: &'s [u8],
+}
+
+/// An iterator over the notes in the contents of a PT_NOTE segment.
+///
+/// Stops at the first malformed note.
+pub struct Notes<'s> {
+    data: &'s [u8],
+    align: usize,
+    encoding: Encoding,
+}
+
+impl<'s> Notes<'s> {
+    /// Parse the notes in `data`, whose fields are padded to `align`
+    /// bytes (the `p_align` of the segment, 4 or 8) and encoded with the
+    /// byte order of `encoding`.
+    pub fn new(data: &'s [u8], align: u64, encoding: Encoding) -> Notes<'s> {
+        Notes {
+            data,
+            align: if align == 8 { 8 } else { 4 },
+            encoding,
+        }
+    }
+}
+
+impl<'s> Iterator for Notes<'s> {
+    type Item = Note<'s>;
+
+    fn next(&mut self) -> Option<Note<'s>> {
+        let pad = |len: usize| {
+            len.checked_add(self.align - 1)
+                .map(|l| l & !(self.align - 1))
+        };
+        let name_size = self.encoding.u32(self.data, 0).ok()? as usize;
+        let desc_size = self.encoding.u32(self.data, 4).ok()? as usize;
+        let typ = self.encoding.u32(self.data, 8).ok()?;
+
+        let desc_start = pad(12usize.checked_add(name_size)?)?;
+        let desc_end = desc_start.checked_add(desc_size)?;
+        let name = self.data.get(12..12 + name_size)?;
+        let desc = self.data.get(desc_start..desc_end)?;
+
+        let next = pad(desc_end)?.min(self.data.len());
+        self.data = &self.data[next..];
+        Some(Note {
+            name: name.split(|&b| b == 0).next().unwrap_or(&[]),
+            typ,
+            desc,
+        })
+    }
+}
+
+/// The operating system of an `NT_GNU_ABI_TAG` note.
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub enum AbiOs {
+    Linux,
+    Hurd,
+    Solaris,
+    FreeBsd,
+    Other(u32),
+}
+
+impl AbiOs {
+    fn from(os: u32) -> AbiOs {
+        match os {
+            0 => AbiOs::Linux,
+            1 => AbiOs::Hurd,
+            2 => AbiOs::Solaris,
+            3 => AbiOs::FreeBsd,
+            os => AbiOs::Other(os),
+        }
+    }
+}
+
+/// The OS and minimum kernel version a binary was built for, from its
+/// `NT_GNU_ABI_TAG` note (`readelf -n` prints e.g. "OS: Linux, ABI: 3.2.0").
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub struct GnuAbiTag {
+    pub os: AbiOs,
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl GnuAbiTag {
+    /// Parse the descriptor of an `NT_GNU_ABI_TAG` note (four 32-bit words
+    /// in the byte order of `encoding`).
+    pub fn parse(note: &Note, encoding: Encoding) -> Option<GnuAbiTag> {
+        if note.name != GNU_OWNER.as_bytes() || note.typ != NT_GNU_ABI_TAG {
+            return None;
+        }
+        let word = |i: usize| encoding.u32(note.desc, 4 * i).ok();
+        Some(GnuAbiTag {
+            os: AbiOs::from(word(0)?),
+            major: word(1)?,
+            minor: word(2)?,
+            patch: word(3)?,
+        })
+    }
+
+    /// The minimum kernel version as a (major, minor, patch) tuple.
+    pub fn version(&self) -> (u32, u32, u32) {
+        (self.major, self.minor, self.patch)
+    }
+}
+
+/// A program property of a `NT_GNU_PROPERTY_TYPE_0` note.
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub struct GnuProperty<'s> {
+    /// Type of the property (`pr_type`), processor specific types start at
+    /// 0xc0000000.
+    pub typ: u32,
+    /// The data of the property (`pr_data`).
+    pub data: &'s [u8],
+}
+
+/// An iterator over the properties of a `NT_GNU_PROPERTY_TYPE_0` note.
+///
+/// Stops at the first malformed property.
+pub struct GnuProperties<'s> {
+    data: &'s [u8],
+    align: usize,
+    encoding: Encoding,
+}
+
+impl<'s> GnuProperties<'s> {
+    /// Parse the properties in the descriptor of `note` of a file with
+    /// `encoding`. They are padded to 8 bytes in ELF64 files and to 4 bytes
+    /// otherwise.
+    ///
+    /// Returns None if `note` is not a GNU property note.
+    pub fn new(note: &Note<'s>, encoding: Encoding) -> Option<GnuProperties<'s>> {
+        if note.name != GNU_OWNER.as_bytes() || note.typ != NT_GNU_PROPERTY_TYPE_0 {
+            return None;
+        }
+        Some(GnuProperties {
+            data: note.desc,
+            align: if encoding.class == Class::Elf64 { 8 } else { 4 },
+            encoding,
+        })
+    }
+}
+
+impl<'s> Iterator for GnuProperties<'s> {
+    type Item = GnuProperty<'s>;
+
+    fn next(&mut self) -> Option<GnuProperty<'s>> {
+        let typ = self.encoding.u32(self.data, 0).ok()?;
+        let size = self.encoding.u32(self.data, 4).ok()? as usize;
+        let data = self.data.get(8..8usize.checked_add(size)?)?;
+        let next = (8 + size)
+            .checked_add(self.align - 1)
+            .map(|l| l & !(self.align - 1))?
+            .min(self.data.len());
+        self.data = &self.data[next..];
+        Some(GnuProperty { typ, data })
+    }
+}
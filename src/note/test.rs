@@ -0,0 +1,191 @@
+use super::*;
+use crate::arch::test::big_endian_elf32;
+use crate::elf::Endianness;
+use crate::*;
+use std::fs;
+use std::vec::Vec;
+
+const ELF64: Encoding = Encoding {
+    class: Class::Elf64,
+    endianness: Endianness::Little,
+};
+const ELF32: Encoding = Encoding {
+    class: Class::Elf32,
+    endianness: Endianness::Little,
+};
+
+#[test]
+fn check_notes() {
+    let binary_blob = fs::read("test/test.x86_64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+
+    // readelf -n: .note.ABI-tag and .note.gnu.build-id
+    let notes: Vec<Note> = binary.notes().collect();
+    assert_eq!(notes.len(), 2);
+    assert_eq!(notes[0].name, b"GNU");
+    assert_eq!(notes[0].typ, NT_GNU_ABI_TAG);
+    assert_eq!(notes[0].desc.len(), 0x10);
+    assert_eq!(notes[1].name, b"GNU");
+    assert_eq!(notes[1].typ, NT_GNU_BUILD_ID);
+    assert_eq!(notes[1].desc[..4], [0x05, 0xe7, 0xd0, 0xb3]);
+}
+
+#[test]
+fn check_build_id() {
+    let build_id = |path| {
+        let binary_blob = fs::read(path).expect("Can't read binary");
+        let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+        binary.build_id().map(|id| id.to_vec())
+    };
+
+    // readelf -n: Build ID: 05e7d0b384114e3a416b2c759cc6926b502ec75f
+    let id = build_id("test/test.x86_64").expect("Has build ID");
+    assert_eq!(id.len(), 20);
+    assert_eq!(id[..4], [0x05, 0xe7, 0xd0, 0xb3]);
+    assert_eq!(id[16..], [0x50, 0x2e, 0xc7, 0x5f]);
+    assert_eq!(build_id("test/static_pie.x86_64"), None);
+}
+
+#[test]
+fn check_expected_build_id() {
+    let binary_blob = fs::read("test/test.x86_64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+    let id = binary.build_id().expect("Has build ID").to_vec();
+    assert_eq!(binary.check_build_id(&id), Ok(()));
+    assert_eq!(
+        binary.check_build_id(&id[..19]),
+        Err(ElfLoaderErr::BuildIdMismatch)
+    );
+    let mut other = id.clone();
+    other[0] ^= 1;
+    assert_eq!(
+        binary.check_build_id(&other),
+        Err(ElfLoaderErr::BuildIdMismatch)
+    );
+
+    let binary_blob = fs::read("test/static_pie.x86_64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+    assert_eq!(
+        binary.check_build_id(&id),
+        Err(ElfLoaderErr::BuildIdMismatch)
+    );
+}
+
+#[test]
+fn check_gnu_abi_tag() {
+    let abi_tag = |path| {
+        let binary_blob = fs::read(path).expect("Can't read binary");
+        let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+        binary.gnu_abi_tag()
+    };
+
+    let tag = abi_tag("test/test.x86_64").expect("Has ABI tag");
+    assert_eq!(tag.os, AbiOs::Linux);
+    assert_eq!(tag.version(), (3, 2, 0));
+    assert_eq!(
+        abi_tag("test/test.x86").map(|tag| tag.version()),
+        Some((3, 2, 0))
+    );
+    assert_eq!(
+        abi_tag("test/test.aarch64").map(|tag| tag.version()),
+        Some((3, 7, 0))
+    );
+    assert_eq!(abi_tag("test/static_pie.x86_64"), None);
+}
+
+#[test]
+fn check_gnu_properties() {
+    // A FEATURE_1_AND and a PAUTH property (padded to 8 bytes)
+    let mut desc = Vec::new();
+    desc.extend_from_slice(&0xc000_0000u32.to_le_bytes());
+    desc.extend_from_slice(&4u32.to_le_bytes());
+    desc.extend_from_slice(&[1, 0, 0, 0, 0, 0, 0, 0]);
+    desc.extend_from_slice(&0xc000_0001u32.to_le_bytes());
+    desc.extend_from_slice(&16u32.to_le_bytes());
+    desc.extend_from_slice(&0x1000_0002u64.to_le_bytes());
+    desc.extend_from_slice(&5u64.to_le_bytes());
+    let note = Note {
+        name: b"GNU",
+        typ: NT_GNU_PROPERTY_TYPE_0,
+        desc: &desc,
+    };
+
+    let properties: Vec<GnuProperty> = GnuProperties::new(&note, ELF64)
+        .expect("Is a property note")
+        .collect();
+    assert_eq!(properties.len(), 2);
+    assert_eq!(properties[0].typ, 0xc000_0000);
+    assert_eq!(properties[0].data, [1, 0, 0, 0]);
+    assert_eq!(properties[1].data.len(), 16);
+
+    let parsed = arch::aarch64::Properties::parse(properties.into_iter(), ELF64);
+    assert_eq!(parsed.feature_1, arch::aarch64::Feature1::BTI);
+    assert_eq!(
+        parsed.pauth,
+        Some(arch::aarch64::PauthAbi {
+            platform: 0x1000_0002,
+            version: 5
+        })
+    );
+
+    // With the 4 byte padding of ELF32 files the second property starts at
+    // the padding of the first and is malformed
+    assert_eq!(
+        GnuProperties::new(&note, ELF32)
+            .expect("Is a property note")
+            .count(),
+        1
+    );
+    let other = Note {
+        typ: NT_GNU_ABI_TAG,
+        ..note
+    };
+    assert!(GnuProperties::new(&other, ELF64).is_none());
+}
+
+#[test]
+fn check_malformed_notes() {
+    // A note whose descriptor extends past the end of the segment
+    let mut data = Vec::new();
+    data.extend_from_slice(&4u32.to_le_bytes());
+    data.extend_from_slice(&0x100u32.to_le_bytes());
+    data.extend_from_slice(&NT_GNU_ABI_TAG.to_le_bytes());
+    data.extend_from_slice(b"GNU\0");
+    data.extend_from_slice(&[0; 16]);
+    assert_eq!(Notes::new(&data, 4, ELF64).count(), 0);
+
+    data[4..8].copy_from_slice(&16u32.to_le_bytes());
+    let notes: Vec<Note> = Notes::new(&data, 4, ELF64).collect();
+    assert_eq!(notes.len(), 1);
+    let tag = GnuAbiTag::parse(&notes[0], ELF64).expect("Valid ABI tag");
+    assert_eq!(tag.os, AbiOs::Linux);
+    assert_eq!(tag.version(), (0, 0, 0));
+}
+
+#[test]
+fn check_big_endian_notes() {
+    // An NT_GNU_ABI_TAG (Linux 3.2.0) and an NT_GNU_BUILD_ID note
+    let mut data = Vec::new();
+    for (typ, desc) in [
+        (
+            NT_GNU_ABI_TAG,
+            &[0, 0, 0, 0, 0, 0, 0, 3, 0, 0, 0, 2, 0, 0, 0, 0][..],
+        ),
+        (NT_GNU_BUILD_ID, &[0xde, 0xad, 0xbe, 0xef][..]),
+    ] {
+        data.extend_from_slice(&4u32.to_be_bytes());
+        data.extend_from_slice(&(desc.len() as u32).to_be_bytes());
+        data.extend_from_slice(&typ.to_be_bytes());
+        data.extend_from_slice(b"GNU\0");
+        data.extend_from_slice(desc);
+    }
+    // PT_NOTE of a PowerPC (EM_PPC = 20) binary
+    let blob = big_endian_elf32(20, &[(4, 0x100, data.len() as u32)], &data);
+    let binary = ElfBinary::new(blob.as_slice()).expect("Got proper ELF file");
+
+    assert_eq!(binary.notes().count(), 2);
+    let tag = binary.gnu_abi_tag().expect("Has ABI tag");
+    assert_eq!(tag.os, AbiOs::Linux);
+    assert_eq!(tag.version(), (3, 2, 0));
+    assert_eq!(binary.build_id(), Some(&[0xde, 0xad, 0xbe, 0xef][..]));
+}
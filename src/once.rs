@@ -0,0 +1,71 @@
+//! A value computed on first use, usable without `std` or `alloc`.
+
+use core::cell::UnsafeCell;
+use core::mem::MaybeUninit;
+use core::sync::atomic::{AtomicU8, Ordering};
+
+const EMPTY: u8 = 0;
+const RUNNING: u8 = 1;
+const DONE: u8 = 2;
+
+/// A cell that is written once, by the first caller of [`Once::get_or_init`].
+///
+/// Concurrent callers wait (spin) until the value is available, so the
+/// initializer should be short and must not call back into the same cell.
+pub(crate) struct Once<T> {
+    state: AtomicU8,
+    value: UnsafeCell<MaybeUninit<T>>,
+}
+
+// The value is only written while `state` is RUNNING (by a single thread)
+// and only read after it became DONE.
+unsafe impl<T: Send + Sync> Sync for Once<T> {}
+unsafe impl<T: Send> Send for Once<T> {}
+
+/// Resets the state if the initializer panics, so other callers retry
+/// instead of waiting forever.
+struct Reset<'a>(&'a AtomicU8);
+
+impl Drop for Reset<'_> {
+    fn drop(&mut self) {
+        self.0.store(EMPTY, Ordering::Release);
+    }
+}
+
+impl<T> Once<T> {
+    pub(crate) const fn new() -> Once<T> {
+        Once {
+            state: AtomicU8::new(EMPTY),
+            value: UnsafeCell::new(MaybeUninit::uninit()),
+        }
+    }
+
+    /// Returns the value, computing it with `init` if this is the first call.
+    pub(crate) fn get_or_init(&self, init: impl FnOnce() -> T) -> &T {
+        let mut init = Some(init);
+        loop {
+            match self
+                .state
+                .compare_exchange(EMPTY, RUNNING, Ordering::Acquire, Ordering::Acquire)
+            {
+                Ok(_) => {
+                    let reset = Reset(&self.state);
+                    let value = (init.take().expect("Initializer runs once"))();
+                    core::mem::forget(reset);
+                    unsafe { (*self.value.get()).write(value) };
+                    self.state.store(DONE, Ordering::Release);
+                }
+                Err(DONE) => return unsafe { (*self.value.get()).assume_init_ref() },
+                Err(_) => core::hint::spin_loop(),
+            }
+        }
+    }
+}
+
+impl<T> Drop for Once<T> {
+    fn drop(&mut self) {
+        if *self.state.get_mut() == DONE {
+            unsafe { self.value.get_mut().assume_init_drop() };
+        }
+    }
+}
@@ -0,0 +1,60 @@
+//! OpenBSD specific program headers.
+//!
+//! OpenBSD marks special regions of a binary with its own segment types
+//! (see `exec_elf.h`). They are passed to [`crate::ElfLoader::openbsd_segment`]
+//! after all LOAD segments were loaded.
+
+#[cfg(test)]
+mod test;
+
+/// Section `.openbsd.mutable`: stays writable after `mimmutable`.
+pub const PT_OPENBSD_MUTABLE: u32 = 0x65a3dbe5;
+/// Section `.openbsd.randomdata`: has to be filled with random bytes.
+pub const PT_OPENBSD_RANDOMIZE: u32 = 0x65a3dbe6;
+/// The program needs W^X violating mappings.
+pub const PT_OPENBSD_WXNEEDED: u32 = 0x65a3dbe7;
+/// The program is not compiled with branch target CFI.
+pub const PT_OPENBSD_NOBTCFI: u32 = 0x65a3dbe8;
+/// Section `.openbsd.syscalls`: table of permitted system call sites.
+pub const PT_OPENBSD_SYSCALLS: u32 = 0x65a3dbe9;
+/// Section `.openbsd.bootdata`: has to be filled with boot loader data.
+pub const PT_OPENBSD_BOOTDATA: u32 = 0x65a41be6;
+
+/// The kind of an OpenBSD specific segment.
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SegmentKind {
+    Mutable,
+    Randomize,
+    WxNeeded,
+    NoBtCfi,
+    Syscalls,
+    BootData,
+}
+
+impl SegmentKind {
+    /// Match the `p_type` of a program header to an OpenBSD segment kind.
+    pub fn from(typ: u32) -> Option<SegmentKind> {
+        Some(match typ {
+            PT_OPENBSD_MUTABLE => SegmentKind::Mutable,
+            PT_OPENBSD_RANDOMIZE => SegmentKind::Randomize,
+            PT_OPENBSD_WXNEEDED => SegmentKind::WxNeeded,
+            PT_OPENBSD_NOBTCFI => SegmentKind::NoBtCfi,
+            PT_OPENBSD_SYSCALLS => SegmentKind::Syscalls,
+            PT_OPENBSD_BOOTDATA => SegmentKind::BootData,
+            _ => return None,
+        })
+    }
+
+    /// Name of the segment type as printed by readelf.
+    pub fn name(&self) -> &'static str {
+        match self {
+            SegmentKind::Mutable => "OPENBSD_MUTABLE",
+            SegmentKind::Randomize => "OPENBSD_RANDOMIZE",
+            SegmentKind::WxNeeded => "OPENBSD_WXNEEDED",
+            SegmentKind::NoBtCfi => "OPENBSD_NOBTCFI",
+            SegmentKind::Syscalls => "OPENBSD_SYSCALLS",
+            SegmentKind::BootData => "OPENBSD_BOOTDATA",
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use super::*;
+use crate::*;
+use std::fs;
+use std::vec::Vec;
+
+struct SegmentLoader(Vec<(SegmentKind, VAddr, usize)>);
+
+impl ElfLoader for SegmentLoader {
+    fn allocate(&mut self, _requests: AllocateRequests) -> Result<(), ElfLoaderErr> {
+        Ok(())
+    }
+
+    fn load(
+        &mut self,
+        _flags: SegmentPermissions,
+        _base: VAddr,
+        _region: &[u8],
+    ) -> Result<(), ElfLoaderErr> {
+        Ok(())
+    }
+
+    fn relocate(&mut self, _entry: RelocationEntry) -> Result<(), ElfLoaderErr> {
+        Ok(())
+    }
+
+    fn openbsd_segment(
+        &mut self,
+        kind: SegmentKind,
+        base: VAddr,
+        size: usize,
+    ) -> Result<(), ElfLoaderErr> {
+        self.0.push((kind, base, size));
+        Ok(())
+    }
+}
+
+#[test]
+fn segment_kinds() {
+    assert_eq!(SegmentKind::from(0x65a3dbe6), Some(SegmentKind::Randomize));
+    assert_eq!(SegmentKind::from(0x65a41be6), Some(SegmentKind::BootData));
+    assert_eq!(SegmentKind::from(0x6474e550), None);
+    assert_eq!(SegmentKind::WxNeeded.name(), "OPENBSD_WXNEEDED");
+}
+
+#[test]
+fn randomize_segment() {
+    let mut binary_blob = fs::read("test/test.x86_64").expect("Can't read binary");
+
+    // Turn the PT_NOTE header (the 6th program header) into PT_OPENBSD_RANDOMIZE
+    let phdr = 64 + 5 * 56;
+    assert_eq!(binary_blob[phdr..phdr + 4], 4u32.to_le_bytes());
+    binary_blob[phdr..phdr + 4].copy_from_slice(&PT_OPENBSD_RANDOMIZE.to_le_bytes());
+
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+    let mut loader = SegmentLoader(Vec::new());
+    binary.load(&mut loader).expect("Can't load?");
+    assert_eq!(loader.0, [(SegmentKind::Randomize, 0x254, 0x44)]);
+}
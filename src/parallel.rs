@@ -0,0 +1,77 @@
+//! Applying relocations from several threads (`parallel` feature).
+//!
+//! Binaries with hundreds of thousands of (mostly RELATIVE) relocations
+//! spend most of their load time in `relocate`. Since relocations write to
+//! distinct locations, they can be applied in any order.
+
+use std::vec::Vec;
+
+use crate::{ElfBinary, ElfLoaderErr, RelocationCounts, RelocationEntry, Relocations};
+
+/// Applies relocations, possibly from several threads at the same time.
+///
+/// Used instead of [`crate::ElfLoader::relocate`] by
+/// [`ElfBinary::load_parallel`].
+pub trait RelocationSink: Sync {
+    /// Apply the relocation `entry`, like [`crate::ElfLoader::relocate`].
+    fn relocate(&self, entry: RelocationEntry) -> Result<(), ElfLoaderErr>;
+}
+
+/// Passes the relocations of `binary` for which `skip` is false to `sink`,
+/// splitting the relocation table in `threads` contiguous parts.
+///
+/// Returns the first error any of the threads encountered.
+pub(crate) fn relocate<S, F>(
+    binary: &ElfBinary,
+    sink: &S,
+    threads: usize,
+    skip: F,
+) -> Result<RelocationCounts, ElfLoaderErr>
+where
+    S: RelocationSink,
+    F: Fn(u64) -> bool + Sync,
+{
+    let total = binary.relocation_count() as usize;
+    let threads = threads.clamp(1, total.max(1));
+    let chunk = total.div_ceil(threads);
+    let skip = &skip;
+
+    std::thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads)
+            .map(|thread| {
+                scope.spawn(move || {
+                    let mut counts = RelocationCounts::default();
+                    let entries = Relocations::new(binary, false)
+                        .skip(thread * chunk)
+                        .take(chunk);
+                    for entry in entries {
+                        let entry = entry?;
+                        if !skip(entry.offset) {
+                            counts.add(entry.type_num);
+                            sink.relocate(entry)?;
+                        }
+                    }
+                    Ok(counts)
+                })
+            })
+            .collect();
+
+        let mut result = Ok(RelocationCounts::default());
+        for worker in workers {
+            match worker.join() {
+                Ok(Ok(counts)) => {
+                    if let Ok(total) = result.as_mut() {
+                        total.merge(&counts);
+                    }
+                }
+                Ok(Err(e)) => {
+                    if result.is_ok() {
+                        result = Err(e);
+                    }
+                }
+                Err(panic) => std::panic::resume_unwind(panic),
+            }
+        }
+        result
+    })
+}
@@ -0,0 +1,93 @@
+use core::fmt;
+
+use bitflags::bitflags;
+
+use crate::Flags;
+
+bitflags! {
+    /// Access permissions of a segment (the PF_R, PF_W and PF_X bits of
+    /// `p_flags`).
+    #[derive(Default)]
+    pub struct SegmentPermissions: u32 {
+        const EXECUTE = 0x1;
+        const WRITE = 0x2;
+        const READ = 0x4;
+    }
+}
+
+impl SegmentPermissions {
+    /// `PROT_READ`, `PROT_WRITE` and `PROT_EXEC` of `mmap` and `mprotect`.
+    const PROT_READ: u64 = 0x1;
+    const PROT_WRITE: u64 = 0x2;
+    const PROT_EXEC: u64 = 0x4;
+
+    pub fn is_read(&self) -> bool {
+        self.contains(SegmentPermissions::READ)
+    }
+
+    pub fn is_write(&self) -> bool {
+        self.contains(SegmentPermissions::WRITE)
+    }
+
+    pub fn is_execute(&self) -> bool {
+        self.contains(SegmentPermissions::EXECUTE)
+    }
+
+    /// Combines `read`, `write` and `execute` for the permissions that are
+    /// set, e.g., to build the permission bits of a page table entry.
+    pub fn to_bits(&self, read: u64, write: u64, execute: u64) -> u64 {
+        let mut bits = 0;
+        if self.is_read() {
+            bits |= read;
+        }
+        if self.is_write() {
+            bits |= write;
+        }
+        if self.is_execute() {
+            bits |= execute;
+        }
+        bits
+    }
+
+    /// The `prot` argument of `mmap`/`mprotect` for these permissions (the
+    /// values are the same on all Unix-like systems).
+    pub fn to_prot(&self) -> u64 {
+        self.to_bits(Self::PROT_READ, Self::PROT_WRITE, Self::PROT_EXEC)
+    }
+
+    /// The permissions of a `prot` argument of `mmap`/`mprotect`.
+    pub fn from_prot(prot: u64) -> SegmentPermissions {
+        let mut permissions = SegmentPermissions::empty();
+        permissions.set(SegmentPermissions::READ, prot & Self::PROT_READ != 0);
+        permissions.set(SegmentPermissions::WRITE, prot & Self::PROT_WRITE != 0);
+        permissions.set(SegmentPermissions::EXECUTE, prot & Self::PROT_EXEC != 0);
+        permissions
+    }
+}
+
+/// Drops the OS and processor specific bits of `p_flags`.
+impl From<Flags> for SegmentPermissions {
+    fn from(flags: Flags) -> SegmentPermissions {
+        SegmentPermissions::from_bits_truncate(flags.0)
+    }
+}
+
+impl From<SegmentPermissions> for Flags {
+    fn from(permissions: SegmentPermissions) -> Flags {
+        Flags(permissions.bits())
+    }
+}
+
+/// Formats the permissions like `ls` and `/proc/self/maps`, e.g., `r-x`.
+impl fmt::Display for SegmentPermissions {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let flag = |set, c| if set { c } else { '-' };
+        write!(
+            f,
+            "{}{}{}",
+            flag(self.is_read(), 'r'),
+            flag(self.is_write(), 'w'),
+            flag(self.is_execute(), 'x')
+        )
+    }
+}
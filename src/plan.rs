@@ -0,0 +1,707 @@
+//! Precomputed load plans.
+//!
+//! A [`LoadPlan`] records the ordered callbacks [`ElfBinary::load_with_config`]
+//! issues for a binary, so they can be replayed against any [`ElfLoader`]
+//! later without parsing the dynamic section or the relocation tables again.
+//! Like the callbacks, the plan uses unrelocated addresses, it holds for
+//! every base address the loader picks.
+//!
+//! For hot-swapping a loaded binary, [`LoadPlan::update`] computes only the
+//! callbacks that bring a loaded binary up to date with a new version of it.
+//!
+//! With the `serde` feature, [`LoadPlan`] and [`LoadOp`] implement
+//! `Serialize` and `Deserialize`, e.g., to store a plan at build time. The
+//! line based text form (see the `Display` implementation and
+//! [`LoadPlan::parse`]) is meant for comparing plans with golden files.
+//!
+//! ```no_run
+//! # fn plan(bytes: &[u8], loader: &mut dyn elfloader::ElfLoader) -> Result<(), elfloader::ElfLoaderErr> {
+//! use elfloader::plan::LoadPlan;
+//!
+//! let binary = elfloader::ElfBinary::new(bytes)?;
+//! let text = LoadPlan::new(&binary, &Default::default())?.to_string();
+//! // ...
+//! LoadPlan::parse(&text)?.replay(&binary, loader)?;
+//! # Ok(())
+//! # }
+//! ```
+
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::fmt;
+
+use crate::openbsd::SegmentKind;
+use crate::{
+    AllocateRequests, CoalescedAllocation, ElfBinary, ElfLoader, ElfLoaderErr, LoadConfig,
+    RelocationEntry, RelocationType, RelroSegment, SegmentPermissions, VAddr,
+};
+
+#[cfg(test)]
+mod test;
+
+/// The fields of a [`LoadOp::Relocate`].
+type RelocationKey = (u32, u64, u64, u32, Option<u64>);
+
+/// All OpenBSD segment kinds, to parse their names.
+const OPENBSD_KINDS: [SegmentKind; 6] = [
+    SegmentKind::Mutable,
+    SegmentKind::Randomize,
+    SegmentKind::WxNeeded,
+    SegmentKind::NoBtCfi,
+    SegmentKind::Syscalls,
+    SegmentKind::BootData,
+];
+
+/// A single loader callback of a [`LoadPlan`].
+///
+/// File contents are referenced by their offset in the ELF file.
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LoadOp {
+    /// [`ElfLoader::allocate`] with the LOAD segments of the binary.
+    Allocate,
+    /// [`ElfLoader::allocate_coalesced`].
+    AllocateCoalesced {
+        vaddr: VAddr,
+        mem_size: u64,
+        align: u64,
+    },
+    /// [`ElfLoader::load`] with `size` bytes at `offset` in the file.
+    Load {
+        flags: u32,
+        vaddr: VAddr,
+        offset: u64,
+        size: u64,
+    },
+    /// [`ElfLoader::relocate`], the relocation type is decoded again from
+    /// `type_num` on replay.
+    Relocate {
+        type_num: u32,
+        info: u64,
+        offset: u64,
+        index: u32,
+        addend: Option<u64>,
+    },
+    /// [`ElfLoader::make_writable`].
+    MakeWritable { base: VAddr, size: u64 },
+    /// [`ElfLoader::restore_permissions`].
+    RestorePermissions { base: VAddr, size: u64, flags: u32 },
+    /// [`ElfLoader::tls`] with the `size` bytes at `offset` in the file as
+    /// initialization image.
+    Tls {
+        tdata_start: VAddr,
+        offset: u64,
+        size: u64,
+        tbss_length: u64,
+        align: u64,
+    },
+    /// [`ElfLoader::openbsd_segment`].
+    OpenbsdSegment {
+        kind: SegmentKind,
+        base: VAddr,
+        size: u64,
+    },
+    /// [`ElfLoader::relro`].
+    Relro(RelroSegment),
+}
+
+/// How to bring a loaded binary up to date, see [`LoadPlan::update`].
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub enum Reload {
+    /// The LOAD segments are laid out as before: the plan loads the changed
+    /// segments and applies their relocations again (it is empty if nothing
+    /// changed).
+    Partial(LoadPlan),
+    /// The layout of the LOAD segments changed: the binary has to be
+    /// unloaded and loaded again with the (full) plan.
+    Full(LoadPlan),
+}
+
+/// The ordered loader callbacks of a binary, see the [module
+/// documentation](self).
+#[derive(Eq, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LoadPlan {
+    ops: Vec<LoadOp>,
+    /// Size and CRC32 of the recorded binary, if known.
+    fingerprint: Option<(usize, u32)>,
+}
+
+impl LoadPlan {
+    /// Records the callbacks for loading `binary` with `config`.
+    ///
+    /// All LOAD segments are loaded ([`ElfLoader::disposition`]) and
+    /// [`ElfLoader::progress`] calls are not recorded. All relocations are
+    /// recorded as [`LoadOp::Relocate`], [`LoadConfig::relative_base`] is
+    /// ignored.
+    pub fn new(binary: &ElfBinary, config: &LoadConfig) -> Result<LoadPlan, ElfLoaderErr> {
+        let mut recorder = Recorder {
+            input: binary.file.input,
+            ops: Vec::new(),
+        };
+        let config = LoadConfig {
+            relative_base: None,
+            ..*config
+        };
+        binary.load_with_config(&mut recorder, &config)?;
+        Ok(LoadPlan {
+            ops: recorder.ops,
+            fingerprint: Some(binary.fingerprint()),
+        })
+    }
+
+    /// The callbacks to update `old`, loaded with `config`, to `new`.
+    ///
+    /// If the LOAD segments of both binaries have the same addresses, sizes,
+    /// flags and alignment, the plan only holds the callbacks for segments
+    /// whose contents, relocations or referenced symbols (`st_value` and
+    /// `st_shndx` in `.dynsym`) changed: `load`, `relocate`,
+    /// `make_writable`/`restore_permissions` and `relro` for them, and `tls`
+    /// if the TLS template changed. Binaries with the same build ID are
+    /// considered identical. Note that the loader has to be able to write to
+    /// segments which were made read-only by the previous `relro` calls.
+    pub fn update(
+        old: &ElfBinary,
+        new: &ElfBinary,
+        config: &LoadConfig,
+    ) -> Result<Reload, ElfLoaderErr> {
+        let plan = LoadPlan::new(new, config)?;
+        let layout = |binary: &ElfBinary| {
+            let segments: Vec<_> = binary
+                .load_segments()
+                .map(|s| (s.vaddr, s.mem_size, s.file_size, s.flags, s.align))
+                .collect();
+            segments
+        };
+        if layout(old) != layout(new) {
+            return Ok(Reload::Full(plan));
+        }
+        if old.build_id().is_some() && old.build_id() == new.build_id() {
+            return Ok(Reload::Partial(LoadPlan {
+                ops: Vec::new(),
+                fingerprint: plan.fingerprint,
+            }));
+        }
+
+        let previous = LoadPlan::new(old, config)?;
+        let segments: Vec<_> = new.load_segments().collect();
+        let mut changed: Vec<bool> = segments
+            .iter()
+            .zip(old.load_segments())
+            .map(|(s, o)| {
+                file_data(new, s.offset, s.file_size) != file_data(old, o.offset, o.file_size)
+            })
+            .collect();
+        let segment_of = |addr: u64| {
+            segments
+                .iter()
+                .position(|s| addr >= s.vaddr && addr - s.vaddr < s.mem_size)
+        };
+
+        // A relocation which was added or removed (or whose implicit addend
+        // changed) requires reloading the segment it patches.
+        let relocations = |plan: &LoadPlan| -> BTreeSet<RelocationKey> {
+            plan.ops
+                .iter()
+                .filter_map(|op| match *op {
+                    LoadOp::Relocate {
+                        type_num,
+                        info,
+                        offset,
+                        index,
+                        addend,
+                    } => Some((type_num, info, offset, index, addend)),
+                    _ => None,
+                })
+                .collect()
+        };
+        let (before, after) = (relocations(&previous), relocations(&plan));
+        for &(_, _, offset, _, _) in before.symmetric_difference(&after) {
+            if let Some(index) = segment_of(offset) {
+                changed[index] = true;
+            }
+        }
+
+        // So does a relocation whose symbol moved (e.g., a function in a
+        // changed .text), even though the relocation itself is the same.
+        let symbols = |binary: &ElfBinary| -> Vec<(u64, u16)> {
+            binary
+                .dynamic_symbols()
+                .map(|symbols| symbols.map(|s| (s.value, s.shndx)).collect())
+                .unwrap_or_default()
+        };
+        let (old_symbols, new_symbols) = (symbols(old), symbols(new));
+        for &(_, _, offset, symbol, _) in &after {
+            let symbol = symbol as usize;
+            if symbol != 0 && old_symbols.get(symbol) != new_symbols.get(symbol) {
+                if let Some(index) = segment_of(offset) {
+                    changed[index] = true;
+                }
+            }
+        }
+
+        let is_changed = |addr: u64| segment_of(addr).is_none_or(|index| changed[index]);
+        let tls_changed = previous.tls_template(old) != plan.tls_template(new);
+        let ops = plan
+            .ops
+            .iter()
+            .copied()
+            .filter(|op| match *op {
+                LoadOp::Allocate | LoadOp::AllocateCoalesced { .. } => false,
+                LoadOp::Load { vaddr, .. } => is_changed(vaddr),
+                LoadOp::Relocate { offset, .. } => is_changed(offset),
+                LoadOp::MakeWritable { base, .. }
+                | LoadOp::RestorePermissions { base, .. }
+                | LoadOp::OpenbsdSegment { base, .. } => is_changed(base),
+                LoadOp::Tls { .. } => tls_changed,
+                LoadOp::Relro(segment) => is_changed(segment.vaddr),
+            })
+            .collect();
+        Ok(Reload::Partial(LoadPlan {
+            ops,
+            fingerprint: plan.fingerprint,
+        }))
+    }
+
+    /// The TLS callback of the plan, with the initialization image.
+    fn tls_template<'s>(
+        &self,
+        binary: &ElfBinary<'s>,
+    ) -> Option<(VAddr, Option<&'s [u8]>, u64, u64)> {
+        self.ops.iter().find_map(|op| match *op {
+            LoadOp::Tls {
+                tdata_start,
+                offset,
+                size,
+                tbss_length,
+                align,
+            } => Some((
+                tdata_start,
+                file_data(binary, offset, size),
+                tbss_length,
+                align,
+            )),
+            _ => None,
+        })
+    }
+
+    /// A plan with the given callbacks, which is not tied to a binary.
+    pub fn from_ops(ops: Vec<LoadOp>) -> LoadPlan {
+        LoadPlan {
+            ops,
+            fingerprint: None,
+        }
+    }
+
+    /// The recorded callbacks, in order.
+    pub fn ops(&self) -> &[LoadOp] {
+        &self.ops
+    }
+
+    /// Issues the recorded callbacks to `loader`, with the file contents
+    /// taken from `binary`.
+    ///
+    /// Returns `InvalidPlan` if the plan was recorded for another binary
+    /// (detected by the size and the CRC32 of the file) or references data
+    /// outside of the file.
+    pub fn replay(
+        &self,
+        binary: &ElfBinary,
+        loader: &mut dyn ElfLoader,
+    ) -> Result<(), ElfLoaderErr> {
+        if self.fingerprint.is_some_and(|f| f != binary.fingerprint()) {
+            return Err(ElfLoaderErr::InvalidPlan {
+                reason: "recorded for another binary",
+            });
+        }
+        let data = |offset, size| file_data(binary, offset, size);
+        let out_of_bounds = ElfLoaderErr::InvalidPlan {
+            reason: "data outside of the file",
+        };
+        let size = |size: u64| {
+            usize::try_from(size).map_err(|_| ElfLoaderErr::AddressOverflow {
+                source: "load plan size",
+            })
+        };
+
+        for op in self.ops.iter() {
+            match *op {
+                LoadOp::Allocate => loader.allocate(binary.iter_loadable_headers())?,
+                LoadOp::AllocateCoalesced {
+                    vaddr,
+                    mem_size,
+                    align,
+                } => loader.allocate_coalesced(CoalescedAllocation {
+                    vaddr,
+                    mem_size,
+                    align,
+                    segments: binary.iter_loadable_headers(),
+                })?,
+                LoadOp::Load {
+                    flags,
+                    vaddr,
+                    offset,
+                    size,
+                } => {
+                    let region = data(offset, size).ok_or_else(|| out_of_bounds.clone())?;
+                    loader.load(SegmentPermissions::from_bits_truncate(flags), vaddr, region)?
+                }
+                LoadOp::Relocate {
+                    type_num,
+                    info,
+                    offset,
+                    index,
+                    addend,
+                } => loader.relocate(RelocationEntry {
+                    rtype: RelocationType::from(binary.get_arch(), type_num)?,
+                    type_num,
+                    info,
+                    offset,
+                    index,
+                    addend,
+                    class: binary.header.encoding.class,
+                    segment: binary.relocation_segment(offset),
+                })?,
+                LoadOp::MakeWritable { base, size: s } => loader.make_writable(base, size(s)?)?,
+                LoadOp::RestorePermissions {
+                    base,
+                    size: s,
+                    flags,
+                } => loader.restore_permissions(
+                    base,
+                    size(s)?,
+                    SegmentPermissions::from_bits_truncate(flags),
+                )?,
+                LoadOp::Tls {
+                    tdata_start,
+                    offset,
+                    size,
+                    tbss_length,
+                    align,
+                } => {
+                    let tdata = data(offset, size).ok_or_else(|| out_of_bounds.clone())?;
+                    loader.tls(tdata_start, tdata, tbss_length, align)?
+                }
+                LoadOp::OpenbsdSegment {
+                    kind,
+                    base,
+                    size: s,
+                } => loader.openbsd_segment(kind, base, size(s)?)?,
+                LoadOp::Relro(segment) => loader.relro(segment)?,
+            }
+        }
+        Ok(())
+    }
+
+    /// Parses the text form of a plan (as written by its `Display`
+    /// implementation).
+    pub fn parse(text: &str) -> Result<LoadPlan, ElfLoaderErr> {
+        let mut plan = LoadPlan::from_ops(Vec::new());
+        for line in text.lines().map(str::trim).filter(|l| !l.is_empty()) {
+            let mut words = line.split_whitespace();
+            let op = words.next().unwrap_or("");
+            let mut next = || -> Result<u64, ElfLoaderErr> {
+                words
+                    .next()
+                    .and_then(parse_number)
+                    .ok_or(ElfLoaderErr::InvalidPlan {
+                        reason: "malformed number",
+                    })
+            };
+            let truncated = || ElfLoaderErr::InvalidPlan {
+                reason: "number out of range",
+            };
+            match op {
+                "fingerprint" => {
+                    let size = usize::try_from(next()?).map_err(|_| truncated())?;
+                    let crc = u32::try_from(next()?).map_err(|_| truncated())?;
+                    plan.fingerprint = Some((size, crc));
+                }
+                "allocate" => plan.ops.push(LoadOp::Allocate),
+                "allocate-coalesced" => plan.ops.push(LoadOp::AllocateCoalesced {
+                    vaddr: next()?,
+                    mem_size: next()?,
+                    align: next()?,
+                }),
+                "load" => plan.ops.push(LoadOp::Load {
+                    flags: u32::try_from(next()?).map_err(|_| truncated())?,
+                    vaddr: next()?,
+                    offset: next()?,
+                    size: next()?,
+                }),
+                "relocate" | "relocate-rel" => plan.ops.push(LoadOp::Relocate {
+                    type_num: u32::try_from(next()?).map_err(|_| truncated())?,
+                    info: next()?,
+                    offset: next()?,
+                    index: u32::try_from(next()?).map_err(|_| truncated())?,
+                    addend: if op == "relocate" {
+                        Some(next()?)
+                    } else {
+                        None
+                    },
+                }),
+                "make-writable" => plan.ops.push(LoadOp::MakeWritable {
+                    base: next()?,
+                    size: next()?,
+                }),
+                "restore-permissions" => plan.ops.push(LoadOp::RestorePermissions {
+                    base: next()?,
+                    size: next()?,
+                    flags: u32::try_from(next()?).map_err(|_| truncated())?,
+                }),
+                "tls" => plan.ops.push(LoadOp::Tls {
+                    tdata_start: next()?,
+                    offset: next()?,
+                    size: next()?,
+                    tbss_length: next()?,
+                    align: next()?,
+                }),
+                "relro" => plan.ops.push(LoadOp::Relro(RelroSegment {
+                    vaddr: next()?,
+                    mem_size: next()?,
+                    align: next()?,
+                    page_size: next()?,
+                })),
+                name => {
+                    let kind = OPENBSD_KINDS
+                        .iter()
+                        .find(|kind| kind.name() == name)
+                        .copied()
+                        .ok_or(ElfLoaderErr::InvalidPlan {
+                            reason: "unknown operation",
+                        })?;
+                    plan.ops.push(LoadOp::OpenbsdSegment {
+                        kind,
+                        base: next()?,
+                        size: next()?,
+                    })
+                }
+            }
+        }
+        Ok(plan)
+    }
+}
+
+/// The `size` bytes at `offset` in the file of `binary`.
+fn file_data<'s>(binary: &ElfBinary<'s>, offset: u64, size: u64) -> Option<&'s [u8]> {
+    let start = usize::try_from(offset).ok()?;
+    let end = start.checked_add(usize::try_from(size).ok()?)?;
+    binary.file.input.get(start..end)
+}
+
+/// Parses a decimal or `0x` prefixed hexadecimal number.
+fn parse_number(word: &str) -> Option<u64> {
+    match word.strip_prefix("0x") {
+        Some(hex) => u64::from_str_radix(hex, 16).ok(),
+        None => word.parse().ok(),
+    }
+}
+
+/// One operation per line, with all numbers in hexadecimal.
+impl fmt::Display for LoadPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some((size, crc)) = self.fingerprint {
+            writeln!(f, "fingerprint {:#x} {:#x}", size, crc)?;
+        }
+        for op in self.ops.iter() {
+            match *op {
+                LoadOp::Allocate => writeln!(f, "allocate")?,
+                LoadOp::AllocateCoalesced {
+                    vaddr,
+                    mem_size,
+                    align,
+                } => writeln!(
+                    f,
+                    "allocate-coalesced {:#x} {:#x} {:#x}",
+                    vaddr, mem_size, align
+                )?,
+                LoadOp::Load {
+                    flags,
+                    vaddr,
+                    offset,
+                    size,
+                } => writeln!(
+                    f,
+                    "load {:#x} {:#x} {:#x} {:#x}",
+                    flags, vaddr, offset, size
+                )?,
+                LoadOp::Relocate {
+                    type_num,
+                    info,
+                    offset,
+                    index,
+                    addend,
+                } => {
+                    let op = if addend.is_some() {
+                        "relocate"
+                    } else {
+                        "relocate-rel"
+                    };
+                    write!(
+                        f,
+                        "{} {:#x} {:#x} {:#x} {:#x}",
+                        op, type_num, info, offset, index
+                    )?;
+                    match addend {
+                        Some(addend) => writeln!(f, " {:#x}", addend)?,
+                        None => writeln!(f)?,
+                    }
+                }
+                LoadOp::MakeWritable { base, size } => {
+                    writeln!(f, "make-writable {:#x} {:#x}", base, size)?
+                }
+                LoadOp::RestorePermissions { base, size, flags } => writeln!(
+                    f,
+                    "restore-permissions {:#x} {:#x} {:#x}",
+                    base, size, flags
+                )?,
+                LoadOp::Tls {
+                    tdata_start,
+                    offset,
+                    size,
+                    tbss_length,
+                    align,
+                } => writeln!(
+                    f,
+                    "tls {:#x} {:#x} {:#x} {:#x} {:#x}",
+                    tdata_start, offset, size, tbss_length, align
+                )?,
+                LoadOp::OpenbsdSegment { kind, base, size } => {
+                    writeln!(f, "{} {:#x} {:#x}", kind.name(), base, size)?
+                }
+                LoadOp::Relro(segment) => writeln!(
+                    f,
+                    "relro {:#x} {:#x} {:#x} {:#x}",
+                    segment.vaddr, segment.mem_size, segment.align, segment.page_size
+                )?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The loader recording a [`LoadPlan`].
+struct Recorder<'s> {
+    input: &'s [u8],
+    ops: Vec<LoadOp>,
+}
+
+impl Recorder<'_> {
+    /// Offset of `data` in the file.
+    fn offset(&self, data: &[u8]) -> Result<u64, ElfLoaderErr> {
+        if data.is_empty() {
+            return Ok(0);
+        }
+        let start = self.input.as_ptr() as usize;
+        (data.as_ptr() as usize)
+            .checked_sub(start)
+            .filter(|offset| offset + data.len() <= self.input.len())
+            .map(|offset| offset as u64)
+            .ok_or(ElfLoaderErr::InvalidPlan {
+                reason: "data outside of the file",
+            })
+    }
+}
+
+impl ElfLoader for Recorder<'_> {
+    fn allocate(&mut self, _requests: AllocateRequests) -> Result<(), ElfLoaderErr> {
+        self.ops.push(LoadOp::Allocate);
+        Ok(())
+    }
+
+    fn allocate_coalesced(&mut self, allocation: CoalescedAllocation) -> Result<(), ElfLoaderErr> {
+        self.ops.push(LoadOp::AllocateCoalesced {
+            vaddr: allocation.vaddr,
+            mem_size: allocation.mem_size,
+            align: allocation.align,
+        });
+        Ok(())
+    }
+
+    fn load(
+        &mut self,
+        flags: SegmentPermissions,
+        base: VAddr,
+        region: &[u8],
+    ) -> Result<(), ElfLoaderErr> {
+        let offset = self.offset(region)?;
+        self.ops.push(LoadOp::Load {
+            flags: flags.bits(),
+            vaddr: base,
+            offset,
+            size: region.len() as u64,
+        });
+        Ok(())
+    }
+
+    fn relocate(&mut self, entry: RelocationEntry) -> Result<(), ElfLoaderErr> {
+        self.ops.push(LoadOp::Relocate {
+            type_num: entry.type_num,
+            info: entry.info,
+            offset: entry.offset,
+            index: entry.index,
+            addend: entry.addend,
+        });
+        Ok(())
+    }
+
+    fn make_writable(&mut self, base: VAddr, size: usize) -> Result<(), ElfLoaderErr> {
+        self.ops.push(LoadOp::MakeWritable {
+            base,
+            size: size as u64,
+        });
+        Ok(())
+    }
+
+    fn restore_permissions(
+        &mut self,
+        base: VAddr,
+        size: usize,
+        flags: SegmentPermissions,
+    ) -> Result<(), ElfLoaderErr> {
+        self.ops.push(LoadOp::RestorePermissions {
+            base,
+            size: size as u64,
+            flags: flags.bits(),
+        });
+        Ok(())
+    }
+
+    fn tls(
+        &mut self,
+        tdata_start: VAddr,
+        tdata: &[u8],
+        tbss_length: u64,
+        align: u64,
+    ) -> Result<(), ElfLoaderErr> {
+        let offset = self.offset(tdata)?;
+        self.ops.push(LoadOp::Tls {
+            tdata_start,
+            offset,
+            size: tdata.len() as u64,
+            tbss_length,
+            align,
+        });
+        Ok(())
+    }
+
+    fn openbsd_segment(
+        &mut self,
+        kind: SegmentKind,
+        base: VAddr,
+        size: usize,
+    ) -> Result<(), ElfLoaderErr> {
+        self.ops.push(LoadOp::OpenbsdSegment {
+            kind,
+            base,
+            size: size as u64,
+        });
+        Ok(())
+    }
+
+    fn relro(&mut self, segment: RelroSegment) -> Result<(), ElfLoaderErr> {
+        self.ops.push(LoadOp::Relro(segment));
+        Ok(())
+    }
+}
@@ -0,0 +1,208 @@
+use super::*;
+use crate::arch::test::*;
+use std::fs;
+use std::string::ToString;
+use std::vec;
+use std::vec::Vec;
+
+/// The callbacks of a direct load and of a replayed plan of `file`.
+fn load_and_replay(file: &str, config: &LoadConfig) -> (TestLoader, TestLoader, LoadPlan) {
+    init();
+    let binary_blob = fs::read(file).expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+
+    let mut direct = TestLoader::new(0x1000_0000);
+    binary
+        .load_with_config(&mut direct, config)
+        .expect("Can't load?");
+
+    let plan = LoadPlan::new(&binary, config).expect("Can't record plan");
+    let mut replayed = TestLoader::new(0x1000_0000);
+    plan.replay(&binary, &mut replayed).expect("Can't replay");
+    (direct, replayed, plan)
+}
+
+#[test]
+fn replay_matches_load() {
+    let (direct, replayed, plan) = load_and_replay("test/test.x86_64", &Default::default());
+    assert_eq!(direct.actions, replayed.actions);
+    assert!(plan.ops().contains(&LoadOp::Allocate));
+    assert!(plan
+        .ops()
+        .iter()
+        .any(|op| matches!(op, LoadOp::Relocate { .. })));
+
+    let config = LoadConfig {
+        coalesce_allocation: true,
+        ..Default::default()
+    };
+    let (direct, replayed, _) = load_and_replay("test/test.x86_64", &config);
+    assert_eq!(direct.actions, replayed.actions);
+
+    let (direct, replayed, _) = load_and_replay("test/tls.x86_64", &Default::default());
+    assert_eq!(direct.actions, replayed.actions);
+    assert_eq!(direct.tdata, replayed.tdata);
+    assert!(!replayed.tdata.is_empty());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_derives() {
+    fn serializable<T: serde::Serialize + serde::de::DeserializeOwned>() {}
+    serializable::<LoadPlan>();
+    serializable::<LoadOp>();
+}
+
+#[test]
+fn text_round_trip() {
+    let (_, _, plan) = load_and_replay("test/test.x86_64", &Default::default());
+    let text = plan.to_string();
+    assert!(text.starts_with("fingerprint "));
+    assert!(text.lines().any(|line| line == "allocate"));
+    assert_eq!(LoadPlan::parse(&text), Ok(plan));
+
+    let (_, _, plan) = load_and_replay("test/test.x86", &Default::default());
+    let text = plan.to_string();
+    assert!(text.lines().any(|line| line.starts_with("relocate ")));
+    assert_eq!(LoadPlan::parse(&text), Ok(plan));
+
+    let plan = LoadPlan::from_ops(vec![
+        LoadOp::OpenbsdSegment {
+            kind: SegmentKind::Randomize,
+            base: 0x2000,
+            size: 0x10,
+        },
+        LoadOp::Relocate {
+            type_num: 8,
+            info: 8,
+            offset: 0x3000,
+            index: 0,
+            addend: None,
+        },
+    ]);
+    assert_eq!(
+        plan.to_string(),
+        "OPENBSD_RANDOMIZE 0x2000 0x10\nrelocate-rel 0x8 0x8 0x3000 0x0\n"
+    );
+    assert_eq!(LoadPlan::parse(&plan.to_string()), Ok(plan));
+
+    assert_eq!(
+        LoadPlan::parse("load 0x5 zz"),
+        Err(ElfLoaderErr::InvalidPlan {
+            reason: "malformed number"
+        })
+    );
+    assert_eq!(
+        LoadPlan::parse("jump 0x0"),
+        Err(ElfLoaderErr::InvalidPlan {
+            reason: "unknown operation"
+        })
+    );
+}
+
+#[test]
+fn replay_checks_binary() {
+    let (_, _, plan) = load_and_replay("test/test.x86_64", &Default::default());
+    let other = fs::read("test/test.x86").expect("Can't read binary");
+    let other = ElfBinary::new(other.as_slice()).expect("Got proper ELF file");
+    assert_eq!(
+        plan.replay(&other, &mut TestLoader::new(0)),
+        Err(ElfLoaderErr::InvalidPlan {
+            reason: "recorded for another binary"
+        })
+    );
+
+    let plan = LoadPlan::from_ops(vec![LoadOp::Load {
+        flags: 0,
+        vaddr: 0,
+        offset: u64::MAX,
+        size: 1,
+    }]);
+    assert_eq!(
+        plan.replay(&other, &mut TestLoader::new(0)),
+        Err(ElfLoaderErr::InvalidPlan {
+            reason: "data outside of the file"
+        })
+    );
+}
+
+/// The reload of `old` to `new`, which are both variants of `file`.
+fn update(file: &str, old: &[(usize, u8)], new: &[(usize, u8)]) -> Reload {
+    let patched = |patch: &[(usize, u8)]| {
+        let mut blob = fs::read(file).expect("Can't read binary");
+        for &(offset, value) in patch {
+            blob[offset] = value;
+        }
+        blob
+    };
+    let (old, new) = (patched(old), patched(new));
+    let old = ElfBinary::new(old.as_slice()).expect("Got proper ELF file");
+    let new = ElfBinary::new(new.as_slice()).expect("Got proper ELF file");
+    LoadPlan::update(&old, &new, &Default::default()).expect("Can't compute update")
+}
+
+#[test]
+fn update_changed_segments() {
+    let partial = |reload| match reload {
+        Reload::Partial(plan) => plan,
+        Reload::Full(_) => panic!("Expected a partial reload"),
+    };
+    let loads = |plan: &LoadPlan| -> Vec<VAddr> {
+        plan.ops()
+            .iter()
+            .filter_map(|op| match op {
+                LoadOp::Load { vaddr, .. } => Some(*vaddr),
+                _ => None,
+            })
+            .collect()
+    };
+    let count =
+        |plan: &LoadPlan, f: fn(&LoadOp) -> bool| plan.ops().iter().filter(|op| f(op)).count();
+
+    // Unchanged
+    let plan = partial(update("test/test.riscv64", &[], &[]));
+    assert!(plan.ops().is_empty());
+
+    // A byte in .text: only the first LOAD segment is reloaded, all
+    // relocations patch the second one
+    let plan = partial(update("test/test.riscv64", &[], &[(0x600, 0)]));
+    assert_eq!(loads(&plan), vec![0x0]);
+    assert_eq!(count(&plan, |op| matches!(op, LoadOp::Relocate { .. })), 0);
+    assert_eq!(count(&plan, |op| matches!(op, LoadOp::Relro(_))), 0);
+
+    // The addend of the first R_RISCV_RELATIVE (.rela.dyn in the first
+    // segment) changes the relocated data of the second one
+    let plan = partial(update("test/test.riscv64", &[], &[(0x430, 0xad)]));
+    assert_eq!(loads(&plan), vec![0x0, 0x1e20]);
+    assert_eq!(count(&plan, |op| matches!(op, LoadOp::Relocate { .. })), 11);
+    assert_eq!(count(&plan, |op| matches!(op, LoadOp::Relro(_))), 1);
+    assert_eq!(count(&plan, |op| matches!(op, LoadOp::Allocate)), 0);
+
+    // st_value of _init (.dynsym entry 4 in the first segment) moves, the
+    // R_RISCV_64 referencing it patches the second one
+    let plan = partial(update("test/test.riscv64", &[], &[(0x2c0, 0x10)]));
+    assert_eq!(loads(&plan), vec![0x0, 0x1e20]);
+    assert_eq!(count(&plan, |op| matches!(op, LoadOp::Relocate { .. })), 11);
+
+    // Binaries with the same build ID are considered identical
+    let plan = partial(update("test/test.x86_64", &[], &[(0x1000, 0xff)]));
+    assert!(plan.ops().is_empty());
+    let plan = partial(update(
+        "test/test.x86_64",
+        &[],
+        &[(0x1000, 0xff), (0x284, 0)],
+    ));
+    assert_eq!(loads(&plan), vec![0x0, 0x200db8]);
+}
+
+#[test]
+fn update_changed_layout() {
+    // p_memsz of the second LOAD segment
+    match update("test/test.riscv64", &[], &[(0x110, 0x90)]) {
+        Reload::Full(plan) => {
+            assert_eq!(plan.ops()[0], LoadOp::Allocate);
+            assert_eq!(plan.ops().len(), 1 + 2 + 11 + 1);
+        }
+        Reload::Partial(_) => panic!("Expected a full reload"),
+    }
+}
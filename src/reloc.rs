@@ -0,0 +1,88 @@
+//! Computing the values of dynamic relocations.
+//!
+//! The formulas for the relocation types are given by the psABI of each
+//! architecture (see the `arch` modules). [`RelocationEntry::evaluate`]
+//! applies them, using a [`RelocationResolver`] for the inputs only the
+//! embedder knows (symbol addresses, TLS layout). Writing the result into
+//! the loaded image is left to the caller.
+
+use crate::arch::{aarch64, arm, riscv, x86, x86_64};
+use crate::{ElfLoaderErr, RelocationEntry, RelocationType, VAddr};
+
+/// Provides the values a relocation formula depends on.
+pub trait RelocationResolver {
+    /// The value S of the symbol with index `index` in the dynamic symbol
+    /// table, i.e., its (relocated) address. For TLS symbols this is the
+    /// offset of the symbol in the TLS block of its module.
+    fn symbol_value(&mut self, index: u32) -> Result<u64, ElfLoaderErr>;
+
+    /// The load bias B, i.e., the difference between the addresses the
+    /// binary is loaded at and its link-time addresses.
+    fn load_bias(&self) -> u64;
+
+    /// The id of the module whose TLS block contains the symbol with index
+    /// `index` (or of the binary itself, for index 0).
+    fn tls_module(&mut self, _index: u32) -> Result<usize, ElfLoaderErr> {
+        Err(ElfLoaderErr::UnsupportedRelocationEntry)
+    }
+
+    /// Thread pointer relative offset of the TLS block of `module`.
+    fn tls_offset(&mut self, _module: usize) -> Result<i64, ElfLoaderErr> {
+        Err(ElfLoaderErr::UnsupportedRelocationEntry)
+    }
+}
+
+/// The result of evaluating a relocation: the low `width` bytes of `value`
+/// are to be stored (little-endian) at `target`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RelocationValue {
+    pub target: VAddr,
+    pub value: u64,
+    pub width: usize,
+}
+
+impl RelocationEntry {
+    /// Computes the value of this relocation.
+    ///
+    /// Returns None for relocations that don't modify memory (`R_*_NONE`)
+    /// and `UnsupportedRelocationEntry` for types that can't be expressed
+    /// as a single store (e.g., COPY or IRELATIVE) or that are not known to
+    /// the evaluator.
+    pub fn evaluate<R: RelocationResolver>(
+        &self,
+        resolver: &mut R,
+    ) -> Result<Option<RelocationValue>, ElfLoaderErr> {
+        let result = match self.rtype {
+            RelocationType::x86(typ) => x86::evaluate(typ, self, resolver)?,
+            RelocationType::x86_64(typ) => x86_64::evaluate(typ, self, resolver)?,
+            RelocationType::Arm(typ) => arm::evaluate(typ, self, resolver)?,
+            RelocationType::AArch64(typ) => aarch64::evaluate(typ, self, resolver)?,
+            RelocationType::RiscV(typ) => riscv::evaluate(typ, self, resolver)?,
+            _ => return Err(ElfLoaderErr::UnsupportedRelocationEntry),
+        };
+
+        Ok(result.map(|(value, width)| RelocationValue {
+            target: resolver.load_bias().wrapping_add(self.offset),
+            value,
+            width,
+        }))
+    }
+
+    /// The addend A, which must be present.
+    pub(crate) fn required_addend(&self) -> Result<u64, ElfLoaderErr> {
+        self.addend.ok_or(ElfLoaderErr::UnsupportedRelocationEntry)
+    }
+}
+
+/// Thread pointer relative offset of the symbol with index `index` plus `addend`.
+pub(crate) fn tp_relative<R: RelocationResolver>(
+    resolver: &mut R,
+    index: u32,
+    addend: u64,
+) -> Result<u64, ElfLoaderErr> {
+    let module = resolver.tls_module(index)?;
+    let offset = resolver.tls_offset(module)?;
+    Ok((offset as u64)
+        .wrapping_add(resolver.symbol_value(index)?)
+        .wrapping_add(addend))
+}
@@ -0,0 +1,203 @@
+//! Iterating over the dynamic relocation entries of a binary.
+
+use core::convert::TryFrom;
+
+use crate::{elf, ElfBinary, ElfLoaderErr, RelocationEntry, RelocationType, ShType};
+use xmas_elf::sections::SectionHeader;
+
+/// DT_PLTREL values.
+const DT_RELA: u64 = 7;
+const DT_REL: u64 = 17;
+
+/// The raw entries of a REL or RELA table.
+#[derive(Clone, Copy)]
+struct Table<'s> {
+    entries: &'s [u8],
+    has_addend: bool,
+}
+
+/// An iterator over the dynamic relocation entries of a binary.
+///
+/// Created by [`ElfBinary::relocations`]. The entries are taken from the
+/// `.rela.dyn`/`.rel.dyn` and `.rela.plt`/`.rel.plt` sections, or if the
+/// binary has no such sections (e.g., because the section headers were
+/// stripped) from the DT_RELA, DT_REL and DT_JMPREL tables of the dynamic
+/// segment.
+pub struct Relocations<'a, 's> {
+    binary: &'a ElfBinary<'s>,
+    tables: [Option<Result<Table<'s>, ElfLoaderErr>>; 3],
+    table: usize,
+    position: usize,
+}
+
+impl<'a, 's> Relocations<'a, 's> {
+    /// The relocations applied by [`ElfBinary::load`], and the PLT
+    /// relocations if `plt` is set.
+    pub(crate) fn new(binary: &'a ElfBinary<'s>, plt: bool) -> Relocations<'a, 's> {
+        let section_table = |section: SectionHeader<'s>| {
+            let entries = usize::try_from(section.offset())
+                .ok()
+                .zip(usize::try_from(section.size()).ok())
+                .and_then(|(start, size)| binary.file.input.get(start..start.checked_add(size)?))
+                .ok_or(ElfLoaderErr::ElfParser {
+                    source: "Relocation section is outside of the file",
+                })?;
+            Ok(Table {
+                entries,
+                has_addend: section.get_type() == Ok(ShType::Rela),
+            })
+        };
+        let dynamic_table = |addr: u64, size: u64, has_addend: bool| {
+            if size == 0 {
+                return None;
+            }
+            Some(
+                binary
+                    .virtual_data(addr, size)
+                    .map(|entries| Table {
+                        entries,
+                        has_addend,
+                    })
+                    .ok_or(ElfLoaderErr::ElfParser {
+                        source: "Dynamic relocation table is not file-backed",
+                    }),
+            )
+        };
+
+        let mut tables = [None, None, None];
+        let dynamic = match binary.try_dynamic() {
+            Ok(dynamic) => dynamic,
+            Err(e) => {
+                tables[0] = Some(Err(e));
+                None
+            }
+        };
+        match (binary.relocation_section(), dynamic) {
+            (Some(section), _) => tables[0] = Some(section_table(section)),
+            (None, Some(dynamic)) => {
+                tables[0] = dynamic_table(dynamic.rel, dynamic.rel_size, false);
+                tables[1] = dynamic_table(dynamic.rela, dynamic.rela_size, true);
+            }
+            (None, None) => {}
+        }
+        if plt {
+            let plt_section = binary
+                .file
+                .find_section_by_name(".rela.plt")
+                .or_else(|| binary.file.find_section_by_name(".rel.plt"));
+            match (plt_section, dynamic) {
+                (Some(section), _) => tables[2] = Some(section_table(section)),
+                (None, Some(dynamic)) if dynamic.pltrel == DT_RELA => {
+                    tables[2] = dynamic_table(dynamic.jmprel, dynamic.pltrel_size, true)
+                }
+                (None, Some(dynamic)) if dynamic.pltrel == DT_REL => {
+                    tables[2] = dynamic_table(dynamic.jmprel, dynamic.pltrel_size, false)
+                }
+                _ => {}
+            }
+        }
+
+        Relocations {
+            binary,
+            tables,
+            table: 0,
+            position: 0,
+        }
+    }
+
+    /// The PLT relocations only, and the size of their entries (0 if the
+    /// binary has none).
+    pub(crate) fn plt(
+        binary: &'a ElfBinary<'s>,
+    ) -> Result<(Relocations<'a, 's>, u64), ElfLoaderErr> {
+        binary.try_dynamic()?;
+        let mut relocations = Relocations::new(binary, true);
+        let plt = relocations.tables[2].take().transpose()?;
+        let entry_size = plt.map_or(0, |table| relocations.entry_size(table) as u64);
+        relocations.tables = [None, None, plt.map(Ok)];
+        Ok((relocations, entry_size))
+    }
+
+    fn entry_size(&self, table: Table) -> usize {
+        elf::Relocation::size(self.binary.header.encoding.class, table.has_addend)
+    }
+
+    /// Decodes a single REL or RELA entry.
+    fn entry(&self, bytes: &[u8], has_addend: bool) -> Result<RelocationEntry, ElfLoaderErr> {
+        let encoding = self.binary.header.encoding;
+        let entry = elf::Relocation::parse(bytes, encoding, has_addend)?;
+        let type_num = entry.typ(encoding.class);
+        Ok(RelocationEntry {
+            rtype: RelocationType::from(self.binary.get_arch(), type_num)?,
+            type_num,
+            info: entry.info,
+            offset: entry.offset,
+            index: entry.symbol(encoding.class),
+            addend: entry
+                .addend
+                .or_else(|| self.binary.implicit_addend(entry.offset)),
+            class: encoding.class,
+            segment: self.binary.relocation_segment(entry.offset),
+        })
+    }
+}
+
+impl<'a, 's> Iterator for Relocations<'a, 's> {
+    type Item = Result<RelocationEntry, ElfLoaderErr>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let table = match self.tables.get(self.table)? {
+                Some(Ok(table)) => *table,
+                Some(Err(e)) => {
+                    // Stop after reporting a malformed table
+                    let e = e.clone();
+                    self.table = self.tables.len();
+                    return Some(Err(e));
+                }
+                None => {
+                    self.table += 1;
+                    continue;
+                }
+            };
+
+            let entry_size = self.entry_size(table);
+            match table.entries.get(self.position..self.position + entry_size) {
+                Some(bytes) => {
+                    self.position += entry_size;
+                    return Some(self.entry(bytes, table.has_addend));
+                }
+                None => {
+                    self.table += 1;
+                    self.position = 0;
+                }
+            }
+        }
+    }
+
+    fn nth(&mut self, mut n: usize) -> Option<Self::Item> {
+        // Skip over entries without decoding them
+        loop {
+            match self.tables.get(self.table)? {
+                Some(Ok(table)) => {
+                    let table = *table;
+                    let entry_size = self.entry_size(table);
+                    let remaining = table.entries.len().saturating_sub(self.position) / entry_size;
+                    if n < remaining {
+                        self.position += n * entry_size;
+                        return self.next();
+                    }
+                    n -= remaining;
+                    self.table += 1;
+                    self.position = 0;
+                }
+                Some(Err(_)) if n == 0 => return self.next(),
+                Some(Err(_)) => {
+                    n -= 1;
+                    self.table = self.tables.len();
+                }
+                None => self.table += 1,
+            }
+        }
+    }
+}
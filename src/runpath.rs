@@ -0,0 +1,71 @@
+//! Helpers for DT_RPATH/DT_RUNPATH library search paths.
+//!
+//! The strings returned by [`crate::ElfBinary::rpath`] and
+//! [`crate::ElfBinary::runpath`] are colon separated lists of directories
+//! that may contain the dynamic string tokens `$ORIGIN` and `$LIB`
+//! (also written as `${ORIGIN}` and `${LIB}`). A dependency resolver is
+//! expected to split the list and substitute the tokens before searching.
+
+use core::fmt;
+
+#[cfg(test)]
+mod test;
+
+/// Iterate over the non-empty directories of a colon separated search path.
+pub fn entries(path: &str) -> impl Iterator<Item = &str> {
+    path.split(':').filter(|entry| !entry.is_empty())
+}
+
+/// Returns the value of `$ORIGIN` for a binary located at `binary_path`.
+///
+/// This is the directory containing the binary (without trailing slash),
+/// or "." in case the path has no directory component.
+pub fn origin(binary_path: &str) -> &str {
+    match binary_path.rfind('/') {
+        Some(0) => "/",
+        Some(idx) => &binary_path[..idx],
+        None => ".",
+    }
+}
+
+/// Writes `entry` into `out` with `$ORIGIN` and `$LIB` substituted.
+///
+/// Unknown tokens (e.g. `$PLATFORM`) are copied verbatim so the caller can
+/// decide how to treat them.
+pub fn expand<W: fmt::Write>(entry: &str, origin: &str, lib: &str, out: &mut W) -> fmt::Result {
+    let mut rest = entry;
+    while let Some(idx) = rest.find('$') {
+        out.write_str(&rest[..idx])?;
+        rest = &rest[idx..];
+
+        if let Some((value, len)) =
+            token(rest, "ORIGIN", origin).or_else(|| token(rest, "LIB", lib))
+        {
+            out.write_str(value)?;
+            rest = &rest[len..];
+        } else {
+            out.write_char('$')?;
+            rest = &rest[1..];
+        }
+    }
+    out.write_str(rest)
+}
+
+/// Matches `$name` or `${name}` at the start of `s`.
+///
+/// Returns `value` and the number of bytes consumed in case of a match.
+fn token<'a>(s: &str, name: &str, value: &'a str) -> Option<(&'a str, usize)> {
+    let s = s.strip_prefix('$')?;
+    if let Some(braced) = s.strip_prefix('{') {
+        let tail = braced.strip_prefix(name)?;
+        tail.strip_prefix('}')?;
+        Some((value, name.len() + 3))
+    } else {
+        let tail = s.strip_prefix(name)?;
+        // `$ORIGINAL` is not the `$ORIGIN` token
+        match tail.chars().next() {
+            Some(c) if c.is_ascii_alphanumeric() || c == '_' => None,
+            _ => Some((value, name.len() + 1)),
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use std::string::String;
+use std::vec::Vec;
+
+use crate::runpath::*;
+
+fn expand_to_string(entry: &str, origin: &str, lib: &str) -> String {
+    let mut out = String::new();
+    expand(entry, origin, lib, &mut out).expect("Can't expand?");
+    out
+}
+
+#[test]
+fn split_entries() {
+    let dirs: Vec<&str> = entries("$ORIGIN/../lib::/opt/lib:").collect();
+    assert_eq!(dirs, ["$ORIGIN/../lib", "/opt/lib"]);
+    assert_eq!(entries("").count(), 0);
+}
+
+#[test]
+fn origin_of_binary() {
+    assert_eq!(origin("/usr/bin/ls"), "/usr/bin");
+    assert_eq!(origin("/init"), "/");
+    assert_eq!(origin("a.out"), ".");
+}
+
+#[test]
+fn expand_tokens() {
+    assert_eq!(
+        expand_to_string("$ORIGIN/../lib", "/usr/bin", "lib64"),
+        "/usr/bin/../lib"
+    );
+    assert_eq!(
+        expand_to_string("/opt/${LIB}/${ORIGIN}", "/bin", "lib64"),
+        "/opt/lib64//bin"
+    );
+    assert_eq!(
+        expand_to_string("/opt/$LIB", "/usr/bin", "lib32"),
+        "/opt/lib32"
+    );
+}
+
+#[test]
+fn expand_keeps_unknown_tokens() {
+    assert_eq!(
+        expand_to_string("/opt/$PLATFORM/$ORIGINAL$", "/bin", "lib"),
+        "/opt/$PLATFORM/$ORIGINAL$"
+    );
+    assert_eq!(expand_to_string("/opt/${LIB", "/bin", "lib"), "/opt/${LIB");
+}
@@ -0,0 +1,192 @@
+//! Decoded program headers, cached so loading and the segment queries
+//! don't have to decode the program header table over and over again, and
+//! the [`Segment`] wrapper handed out by [`crate::ElfBinary::segments`].
+
+use core::convert::TryFrom;
+
+use xmas_elf::program::{self, Flags, Type};
+
+use crate::elf::{ProgramHeader, ProgramHeaders, PT_DYNAMIC, PT_INTERP, PT_LOAD};
+use crate::{ElfLoaderErr, PAddr, SegmentPermissions, VAddr};
+
+/// A program header of a binary, with the same accessors for ELF32 and
+/// ELF64 files.
+#[derive(Clone, Copy, Debug)]
+pub struct Segment<'s> {
+    index: usize,
+    header: program::ProgramHeader<'s>,
+    input: &'s [u8],
+}
+
+impl<'s> Segment<'s> {
+    pub(crate) fn new(
+        index: usize,
+        header: program::ProgramHeader<'s>,
+        input: &'s [u8],
+    ) -> Segment<'s> {
+        Segment {
+            index,
+            header,
+            input,
+        }
+    }
+
+    /// Index of the program header.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// The underlying program header.
+    pub fn header(&self) -> program::ProgramHeader<'s> {
+        self.header
+    }
+
+    /// Type of the segment (`p_type`).
+    pub fn typ(&self) -> Result<Type, ElfLoaderErr> {
+        Ok(self.header.get_type()?)
+    }
+
+    /// Permissions of the segment (`p_flags`).
+    pub fn flags(&self) -> SegmentPermissions {
+        self.header.flags().into()
+    }
+
+    /// (Unrelocated) start address of the segment (`p_vaddr`).
+    pub fn vaddr(&self) -> VAddr {
+        self.header.virtual_addr()
+    }
+
+    /// Physical address of the segment (`p_paddr`).
+    pub fn paddr(&self) -> PAddr {
+        self.header.physical_addr()
+    }
+
+    /// File offset of the segment (`p_offset`).
+    pub fn offset(&self) -> u64 {
+        self.header.offset()
+    }
+
+    /// Size of the segment in the file (`p_filesz`).
+    pub fn file_size(&self) -> u64 {
+        self.header.file_size()
+    }
+
+    /// Size of the segment in memory (`p_memsz`).
+    pub fn mem_size(&self) -> u64 {
+        self.header.mem_size()
+    }
+
+    /// Alignment of the segment (`p_align`).
+    pub fn align(&self) -> u64 {
+        self.header.align()
+    }
+
+    /// The file contents of the segment (`p_filesz` bytes at `p_offset`).
+    pub fn data(&self) -> Result<&'s [u8], ElfLoaderErr> {
+        let end =
+            self.offset()
+                .checked_add(self.file_size())
+                .ok_or(ElfLoaderErr::AddressOverflow {
+                    source: "p_offset + p_filesz",
+                })?;
+        let range = (
+            usize::try_from(self.offset()).ok(),
+            usize::try_from(end).ok(),
+        );
+        match range {
+            (Some(start), Some(end)) => self.input.get(start..end),
+            _ => None,
+        }
+        .ok_or(ElfLoaderErr::SegmentOutOfBounds { index: self.index })
+    }
+}
+
+/// A decoded LOAD program header.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub(crate) struct LoadSegment {
+    /// Index of the program header.
+    pub(crate) index: usize,
+    pub(crate) flags: Flags,
+    pub(crate) offset: u64,
+    pub(crate) vaddr: u64,
+    pub(crate) paddr: u64,
+    pub(crate) file_size: u64,
+    pub(crate) mem_size: u64,
+    pub(crate) align: u64,
+}
+
+impl LoadSegment {
+    pub(crate) fn new(index: usize, header: &ProgramHeader) -> LoadSegment {
+        LoadSegment {
+            index,
+            flags: Flags(header.flags),
+            offset: header.offset,
+            vaddr: header.vaddr,
+            paddr: header.paddr,
+            file_size: header.file_size,
+            mem_size: header.mem_size,
+            align: header.align,
+        }
+    }
+
+    /// Does `vaddr` lie in the memory of the segment?
+    pub(crate) fn contains(&self, vaddr: u64) -> bool {
+        vaddr >= self.vaddr && vaddr - self.vaddr < self.mem_size
+    }
+}
+
+/// The program headers that are looked up most often, decoded once when
+/// the binary is parsed.
+///
+/// Holds up to [`SegmentCache::CAPACITY`] LOAD segments (binaries usually
+/// have two to four); for binaries with more, `load` is None and the
+/// program headers are decoded on every query instead.
+#[derive(Clone, Debug)]
+pub(crate) struct SegmentCache {
+    load: Option<([LoadSegment; SegmentCache::CAPACITY], usize)>,
+    /// Index of the PT_DYNAMIC program header.
+    pub(crate) dynamic: Option<usize>,
+    /// Index of the PT_INTERP program header.
+    pub(crate) interp: Option<usize>,
+}
+
+impl SegmentCache {
+    pub(crate) const CAPACITY: usize = 8;
+
+    pub(crate) fn new(program_headers: ProgramHeaders) -> SegmentCache {
+        let mut load = [LoadSegment::default(); SegmentCache::CAPACITY];
+        let mut len = 0;
+        let mut complete = true;
+        let mut dynamic = None;
+        let mut interp = None;
+        for (index, header) in program_headers.enumerate() {
+            match header.typ {
+                PT_LOAD if len < SegmentCache::CAPACITY => {
+                    load[len] = LoadSegment::new(index, &header);
+                    len += 1;
+                }
+                PT_LOAD => complete = false,
+                PT_DYNAMIC if dynamic.is_none() => dynamic = Some(index),
+                PT_INTERP if interp.is_none() => interp = Some(index),
+                _ => {}
+            }
+        }
+
+        SegmentCache {
+            load: if complete { Some((load, len)) } else { None },
+            dynamic,
+            interp,
+        }
+    }
+
+    /// The cached LOAD segments, or None if there were too many to cache.
+    pub(crate) fn load_segments(&self) -> Option<&[LoadSegment]> {
+        self.load.as_ref().map(|(load, len)| &load[..*len])
+    }
+
+    /// Forget the LOAD segments, so they are decoded on every query.
+    #[cfg(test)]
+    pub(crate) fn clear_load_segments(&mut self) {
+        self.load = None;
+    }
+}
@@ -0,0 +1,138 @@
+//! A loader that places a binary into a caller-provided memory region.
+
+use crate::elf::Endianness;
+use crate::{
+    AllocateRequests, ElfBinary, ElfLoader, ElfLoaderErr, RelocationEntry, RelocationResolver,
+    SegmentPermissions, VAddr,
+};
+
+#[cfg(test)]
+mod test;
+
+/// Loads a binary into `memory`, which the binary will run at address `base`.
+///
+/// Every access is bounds-checked against the slice: segments that don't
+/// fit fail with `OutOfMemory`. LOAD segments are zeroed before their file
+/// contents are copied (this takes care of `.bss`) and relocations are
+/// applied with [`RelocationEntry::evaluate`] and written in the byte order
+/// of the binary.
+///
+/// Relocations that need the value of a symbol are rejected with
+/// `UnsupportedRelocationEntry`, only self-contained binaries (e.g.,
+/// kernels or static PIEs) can be loaded.
+///
+/// ```no_run
+/// # fn load(bytes: &[u8], ram: &mut [u8]) -> Result<(), elfloader::ElfLoaderErr> {
+/// let binary = elfloader::ElfBinary::new(bytes)?;
+/// let base = ram.as_ptr() as u64;
+/// let mut loader = elfloader::SliceLoader::new(&binary, ram, base);
+/// binary.load(&mut loader)?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct SliceLoader<'a> {
+    memory: &'a mut [u8],
+    base: VAddr,
+    bias: u64,
+    endianness: Endianness,
+}
+
+impl<'a> SliceLoader<'a> {
+    /// Create a loader for `binary` that writes to `memory`, where `memory[0]`
+    /// is at address `base` when the binary runs.
+    ///
+    /// Position independent binaries are loaded at `base`, executables at
+    /// their link-time addresses (which have to lie within the region).
+    pub fn new(binary: &ElfBinary, memory: &'a mut [u8], base: VAddr) -> SliceLoader<'a> {
+        SliceLoader::with_bias(binary, memory, base, binary.load_bias(base))
+    }
+
+    /// Create a loader for `binary` with load bias `bias`, where `memory[0]`
+    /// is at address `base`.
+    pub(crate) fn with_bias(
+        binary: &ElfBinary,
+        memory: &'a mut [u8],
+        base: VAddr,
+        bias: u64,
+    ) -> SliceLoader<'a> {
+        SliceLoader {
+            memory,
+            base,
+            bias,
+            endianness: binary.header.encoding.endianness,
+        }
+    }
+
+    /// The part of the memory backing the (unrelocated) address range
+    /// `vaddr..vaddr + size`.
+    fn region(&mut self, vaddr: VAddr, size: u64) -> Result<&mut [u8], ElfLoaderErr> {
+        let start = self
+            .bias
+            .checked_add(vaddr)
+            .and_then(|addr| addr.checked_sub(self.base))
+            .ok_or(ElfLoaderErr::OutOfMemory)?;
+        let end = start.checked_add(size).ok_or(ElfLoaderErr::OutOfMemory)?;
+        if end > self.memory.len() as u64 {
+            return Err(ElfLoaderErr::OutOfMemory);
+        }
+        Ok(&mut self.memory[start as usize..end as usize])
+    }
+
+    /// Write the low `width` bytes of `value` to `vaddr`.
+    fn write(&mut self, vaddr: VAddr, value: u64, width: usize) -> Result<(), ElfLoaderErr> {
+        let endianness = self.endianness;
+        let region = self.region(vaddr, width as u64)?;
+        match endianness {
+            Endianness::Little => region.copy_from_slice(&value.to_le_bytes()[..width]),
+            Endianness::Big => region.copy_from_slice(&value.to_be_bytes()[8 - width..]),
+        }
+        Ok(())
+    }
+}
+
+impl<'a> RelocationResolver for SliceLoader<'a> {
+    fn symbol_value(&mut self, _index: u32) -> Result<u64, ElfLoaderErr> {
+        Err(ElfLoaderErr::UnsupportedRelocationEntry)
+    }
+
+    fn load_bias(&self) -> u64 {
+        self.bias
+    }
+}
+
+impl<'a> ElfLoader for SliceLoader<'a> {
+    fn allocate(&mut self, requests: AllocateRequests) -> Result<(), ElfLoaderErr> {
+        for request in requests {
+            self.region(request.vaddr, request.mem_size)?.fill(0);
+        }
+        Ok(())
+    }
+
+    fn load(
+        &mut self,
+        _flags: SegmentPermissions,
+        base: VAddr,
+        region: &[u8],
+    ) -> Result<(), ElfLoaderErr> {
+        self.region(base, region.len() as u64)?
+            .copy_from_slice(region);
+        Ok(())
+    }
+
+    fn relocate(&mut self, entry: RelocationEntry) -> Result<(), ElfLoaderErr> {
+        if let Some(value) = entry.evaluate(self)? {
+            // `target` is already relocated, go back to the link-time address
+            let vaddr = value.target.wrapping_sub(self.bias);
+            self.write(vaddr, value.value, value.width)?;
+        }
+        Ok(())
+    }
+
+    fn write_u64(&mut self, vaddr: VAddr, value: u64) -> Result<(), ElfLoaderErr> {
+        self.write(vaddr, value, 8)
+    }
+
+    fn write_u32(&mut self, vaddr: VAddr, value: u32) -> Result<(), ElfLoaderErr> {
+        self.write(vaddr, value.into(), 4)
+    }
+}
@@ -0,0 +1,128 @@
+use super::*;
+use crate::LoadConfig;
+use std::fs;
+use std::vec;
+
+#[test]
+fn load_static_executable() {
+    let binary_blob = fs::read("test/test_nopie.riscv64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+
+    // LOAD segments span 0x10000..0x18800
+    let mut memory = vec![0xffu8; 0x8800];
+    let mut loader = SliceLoader::new(&binary, &mut memory, 0x10000);
+    binary.load(&mut loader).expect("Can't load?");
+
+    assert_eq!(memory[..0x6aec], binary_blob[..0x6aec]);
+    assert_eq!(
+        memory[0x7fe0..0x7fe0 + 0x1a8],
+        binary_blob[0x6fe0..0x6fe0 + 0x1a8]
+    );
+    assert!(memory[0x7fe0 + 0x1a8..].iter().all(|&b| b == 0));
+}
+
+#[test]
+fn out_of_bounds() {
+    let binary_blob = fs::read("test/test_nopie.riscv64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+
+    let mut memory = vec![0u8; 0x87ff];
+    let mut loader = SliceLoader::new(&binary, &mut memory, 0x10000);
+    assert_eq!(binary.load(&mut loader), Err(ElfLoaderErr::OutOfMemory));
+
+    let mut memory = vec![0u8; 0x8800];
+    let mut loader = SliceLoader::new(&binary, &mut memory, 0x10001);
+    assert_eq!(binary.load(&mut loader), Err(ElfLoaderErr::OutOfMemory));
+}
+
+#[test]
+fn relative_relocations() {
+    let binary_blob = fs::read("test/test.x86_64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+
+    let mut memory = vec![0u8; 0x201018];
+    let mut loader = SliceLoader::new(&binary, &mut memory, 0x1000_0000);
+
+    // The R_X86_64_RELATIVE entries are applied, but the binary needs
+    // symbols from libc
+    assert_eq!(
+        binary.load(&mut loader),
+        Err(ElfLoaderErr::UnsupportedRelocationEntry)
+    );
+    assert_eq!(memory[0x200db8..0x200dc0], 0x1000_0640u64.to_le_bytes());
+    assert_eq!(memory[0x200dc0..0x200dc8], 0x1000_0600u64.to_le_bytes());
+    assert_eq!(memory[0x201008..0x201010], 0x1020_1008u64.to_le_bytes());
+}
+
+#[test]
+fn relative_base() {
+    let binary_blob = fs::read("test/static_pie.x86_64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+
+    let mut expected = vec![0u8; 0x5000];
+    let mut loader = SliceLoader::new(&binary, &mut expected, 0x1000_0000);
+    binary.load(&mut loader).expect("Can't load?");
+
+    let config = LoadConfig {
+        relative_base: Some(0x1000_0000),
+        ..Default::default()
+    };
+    let mut memory = vec![0u8; 0x5000];
+    let mut loader = SliceLoader::new(&binary, &mut memory, 0x1000_0000);
+    binary
+        .load_with_config(&mut loader, &config)
+        .expect("Can't load?");
+    assert_eq!(memory[0x4008..0x4010], 0x1000_2000u64.to_le_bytes());
+    assert_eq!(memory[0x4010..0x4018], 0x1000_4000u64.to_le_bytes());
+    assert_eq!(memory, expected);
+}
+
+#[test]
+fn flat_image() {
+    let binary_blob = fs::read("test/test_nopie.riscv64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+
+    // Executables ignore the base
+    assert_eq!(binary.flat_image_span(0x1234), Ok(Some((0x10000, 0x8800))));
+    let mut image = vec![0xffu8; 0x9000];
+    assert_eq!(binary.write_flat_image(0, &mut image), Ok(0x8800));
+    assert_eq!(image[..0x6aec], binary_blob[..0x6aec]);
+    assert!(image[0x6aec..0x7fe0].iter().all(|&b| b == 0));
+    assert!(image[0x7fe0 + 0x1a8..0x8800].iter().all(|&b| b == 0));
+    assert!(image[0x8800..].iter().all(|&b| b == 0xff));
+    assert_eq!(
+        binary.write_flat_image(0, &mut image[..0x87ff]),
+        Err(ElfLoaderErr::OutOfMemory)
+    );
+
+    // RELATIVE relocations are applied for the base
+    let binary_blob = fs::read("test/static_pie.x86_64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+    assert_eq!(
+        binary.flat_image_span(0x20_0000),
+        Ok(Some((0x20_0000, 0x4018)))
+    );
+    let mut image = vec![0u8; 0x4018];
+    assert_eq!(binary.write_flat_image(0x20_0000, &mut image), Ok(0x4018));
+    assert_eq!(image[0x4008..0x4010], 0x20_2000u64.to_le_bytes());
+    assert_eq!(image[0x4010..0x4018], 0x20_4000u64.to_le_bytes());
+
+    #[cfg(feature = "alloc")]
+    assert_eq!(binary.flat_image(0x20_0000), Ok(image));
+}
+
+#[test]
+fn big_endian_writes() {
+    // EM_PPC, the LOAD segment covers 0x0..0x104
+    let file = crate::arch::test::big_endian_elf32(20, &[], &[0; 4]);
+    let binary = ElfBinary::new(file.as_slice()).expect("Got proper ELF file");
+
+    let mut memory = vec![0u8; 0x104];
+    let mut loader = SliceLoader::new(&binary, &mut memory, 0);
+    loader.write_u32(0xf8, 0x1122_3344).unwrap();
+    loader.write_u64(0xfc, 0x5566_7788_99aa_bbcc).unwrap();
+    assert_eq!(
+        memory[0xf8..],
+        [0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xaa, 0xbb, 0xcc]
+    );
+}
@@ -0,0 +1,41 @@
+use core::convert::TryFrom;
+use core::str;
+
+/// A string table section (`.strtab`, `.dynstr` or `.shstrtab`).
+///
+/// Strings are referenced by their byte offset into the table and are NUL
+/// terminated. All lookups are bounds checked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StringTable<'s> {
+    data: &'s [u8],
+}
+
+impl<'s> StringTable<'s> {
+    /// Wrap the raw contents of a string table section.
+    pub fn new(data: &'s [u8]) -> StringTable<'s> {
+        StringTable { data }
+    }
+
+    /// The raw contents of the table.
+    pub fn data(&self) -> &'s [u8] {
+        self.data
+    }
+
+    /// Returns the bytes of the string at `offset` without the NUL terminator.
+    ///
+    /// Returns None if `offset` is out of bounds or the string is not
+    /// terminated within the table.
+    pub fn get_bytes(&self, offset: u64) -> Option<&'s [u8]> {
+        let tail = self.data.get(usize::try_from(offset).ok()?..)?;
+        let len = tail.iter().position(|&b| b == 0)?;
+        Some(&tail[..len])
+    }
+
+    /// Returns the string at `offset`.
+    ///
+    /// Returns None if `offset` is out of bounds, the string is not
+    /// terminated within the table or is not valid UTF-8.
+    pub fn get(&self, offset: u64) -> Option<&'s str> {
+        str::from_utf8(self.get_bytes(offset)?).ok()
+    }
+}
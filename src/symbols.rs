@@ -0,0 +1,288 @@
+//! Symbol table entries with the same layout for ELF32 and ELF64 files,
+//! see [`crate::ElfBinary::symbols`].
+
+use core::slice::ChunksExact;
+
+use xmas_elf::sections;
+use xmas_elf::symbol_table::{Binding, Type, Visibility};
+
+use crate::elf;
+use crate::{ElfLoaderErr, StringTable, VAddr};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+#[cfg(test)]
+mod test;
+
+/// The symbol table entries were read from.
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub enum SymbolTableKind {
+    /// The full symbol table (`.symtab`), removed by `strip`.
+    Symtab,
+    /// The dynamic symbol table (`.dynsym`), only holds the symbols needed
+    /// for dynamic linking.
+    Dynsym,
+}
+
+/// A symbol table entry, with its name looked up in the string table
+/// linked to the symbol table.
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub struct Symbol<'s> {
+    /// Index of the symbol in its table (e.g., as referenced by
+    /// relocations).
+    pub index: u32,
+    /// Name of the symbol, empty for unnamed symbols and names outside of
+    /// the string table.
+    pub name: &'s str,
+    /// `st_value`, the (unrelocated) address of defined symbols.
+    pub value: u64,
+    /// `st_size`
+    pub size: u64,
+    /// `st_info` (binding and type).
+    pub info: u8,
+    /// `st_other` (visibility).
+    pub other: u8,
+    /// `st_shndx`, index of the section the symbol is defined in.
+    pub shndx: u16,
+}
+
+impl<'s> Symbol<'s> {
+    /// The symbol binding (STB_*).
+    pub fn binding(&self) -> Result<Binding, ElfLoaderErr> {
+        match self.info >> 4 {
+            0 => Ok(Binding::Local),
+            1 => Ok(Binding::Global),
+            2 => Ok(Binding::Weak),
+            b @ 10..=12 => Ok(Binding::OsSpecific(b)),
+            b @ 13..=15 => Ok(Binding::ProcessorSpecific(b)),
+            _ => Err(ElfLoaderErr::ElfParser {
+                source: "Invalid value for binding",
+            }),
+        }
+    }
+
+    /// The symbol type (STT_*).
+    pub fn typ(&self) -> Result<Type, ElfLoaderErr> {
+        match self.info & 0xf {
+            0 => Ok(Type::NoType),
+            1 => Ok(Type::Object),
+            2 => Ok(Type::Func),
+            3 => Ok(Type::Section),
+            4 => Ok(Type::File),
+            5 => Ok(Type::Common),
+            6 => Ok(Type::Tls),
+            t @ 10..=12 => Ok(Type::OsSpecific(t)),
+            t @ 13..=15 => Ok(Type::ProcessorSpecific(t)),
+            _ => Err(ElfLoaderErr::ElfParser {
+                source: "Invalid value for type",
+            }),
+        }
+    }
+
+    /// The symbol visibility (STV_*).
+    pub fn visibility(&self) -> Visibility {
+        match self.other & 0x3 {
+            0 => Visibility::Default,
+            1 => Visibility::Internal,
+            2 => Visibility::Hidden,
+            _ => Visibility::Protected,
+        }
+    }
+
+    /// Whether the symbol is defined in the binary (not SHN_UNDEF).
+    pub fn is_defined(&self) -> bool {
+        self.shndx != sections::SHN_UNDEF
+    }
+
+    /// Whether the symbol has global or weak binding.
+    pub fn is_global(&self) -> bool {
+        matches!(self.binding(), Ok(Binding::Global | Binding::Weak))
+    }
+
+    /// Whether the symbol is a defined global symbol other objects can
+    /// link against (default or protected visibility).
+    pub fn is_exported(&self) -> bool {
+        self.is_defined()
+            && self.is_global()
+            && matches!(
+                self.visibility(),
+                Visibility::Default | Visibility::Protected
+            )
+    }
+
+    /// Whether the symbol is a function (`STT_FUNC`).
+    pub fn is_function(&self) -> bool {
+        matches!(self.typ(), Ok(Type::Func))
+    }
+
+    /// Whether the symbol is a data object (`STT_OBJECT`).
+    pub fn is_object(&self) -> bool {
+        matches!(self.typ(), Ok(Type::Object))
+    }
+
+    /// Whether the symbol is a defined function or data object whose
+    /// `[value, value + size)` range contains `vaddr`.
+    pub fn contains(&self, vaddr: VAddr) -> bool {
+        self.is_defined()
+            && (self.is_function() || self.is_object())
+            && self.value <= vaddr
+            && vaddr - self.value < self.size
+    }
+}
+
+/// The address range of a function, see
+/// [`crate::ElfBinary::function_ranges`].
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub struct FunctionRange<'s> {
+    /// Name of the function.
+    pub name: &'s str,
+    /// Address of the first instruction.
+    pub start: VAddr,
+    /// Size of the function in bytes.
+    pub size: u64,
+}
+
+impl<'s> FunctionRange<'s> {
+    /// The address after the last instruction.
+    pub fn end(&self) -> VAddr {
+        self.start.saturating_add(self.size)
+    }
+
+    /// Whether `vaddr` is an address of the function.
+    pub fn contains(&self, vaddr: VAddr) -> bool {
+        self.start <= vaddr && vaddr < self.end()
+    }
+
+    /// The function containing `vaddr` in `table`, which is sorted by
+    /// `start` and has no overlapping ranges (as built by
+    /// [`crate::ElfBinary::function_range_table`]).
+    pub fn lookup<'a>(
+        table: &'a [FunctionRange<'s>],
+        vaddr: VAddr,
+    ) -> Option<&'a FunctionRange<'s>> {
+        let next = table.partition_point(|function| function.start <= vaddr);
+        table[..next]
+            .last()
+            .filter(|function| function.contains(vaddr))
+    }
+}
+
+/// An iterator over the entries of a symbol table, see
+/// [`crate::ElfBinary::symbols`].
+#[derive(Clone)]
+pub struct Symbols<'s> {
+    entries: ChunksExact<'s, u8>,
+    strtab: Option<StringTable<'s>>,
+    encoding: elf::Encoding,
+    kind: SymbolTableKind,
+    index: u32,
+}
+
+impl<'s> Symbols<'s> {
+    pub(crate) fn new(
+        table: &'s [u8],
+        strtab: Option<StringTable<'s>>,
+        encoding: elf::Encoding,
+        kind: SymbolTableKind,
+    ) -> Symbols<'s> {
+        Symbols {
+            entries: table.chunks_exact(elf::Symbol::size(encoding.class)),
+            strtab,
+            encoding,
+            kind,
+            index: 0,
+        }
+    }
+
+    /// The symbol table the entries are read from.
+    pub fn kind(&self) -> SymbolTableKind {
+        self.kind
+    }
+}
+
+impl<'s> Iterator for Symbols<'s> {
+    type Item = Symbol<'s>;
+
+    fn next(&mut self) -> Option<Symbol<'s>> {
+        let entry = self.entries.next()?;
+        // The chunks have the size of an entry, so parsing can't fail
+        let symbol = elf::Symbol::parse(entry, self.encoding).ok()?;
+        let index = self.index;
+        self.index += 1;
+        Some(Symbol {
+            index,
+            name: self
+                .strtab
+                .and_then(|strtab| strtab.get(symbol.name.into()))
+                .unwrap_or(""),
+            value: symbol.value,
+            size: symbol.size,
+            info: symbol.info,
+            other: symbol.other,
+            shndx: symbol.shndx,
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.entries.size_hint()
+    }
+}
+
+impl<'s> ExactSizeIterator for Symbols<'s> {}
+
+/// The function and data object symbols sorted by address, to look up
+/// many addresses (e.g., when symbolizing backtraces), see
+/// [`crate::ElfBinary::symbol_index`].
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+pub struct SymbolIndex<'s> {
+    /// Sorted by `value`.
+    symbols: Vec<Symbol<'s>>,
+    /// `ends[i]` is the highest end address of `symbols[..=i]`, so the
+    /// backwards search in `lookup` can stop early.
+    ends: Vec<VAddr>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'s> SymbolIndex<'s> {
+    pub(crate) fn new(symbols: Symbols<'s>) -> SymbolIndex<'s> {
+        let mut symbols: Vec<Symbol<'s>> = symbols
+            .filter(|symbol| {
+                symbol.is_defined()
+                    && (symbol.is_function() || symbol.is_object())
+                    && symbol.size > 0
+            })
+            .collect();
+        symbols.sort_by_key(|symbol| symbol.value);
+        let mut end = 0;
+        let ends = symbols
+            .iter()
+            .map(|symbol| {
+                end = end.max(symbol.value.saturating_add(symbol.size));
+                end
+            })
+            .collect();
+        SymbolIndex { symbols, ends }
+    }
+
+    /// The symbol containing `vaddr`, the one with the highest address if
+    /// symbols overlap.
+    pub fn lookup(&self, vaddr: VAddr) -> Option<Symbol<'s>> {
+        let candidates = self.symbols.partition_point(|symbol| symbol.value <= vaddr);
+        (0..candidates)
+            .rev()
+            .take_while(|&i| self.ends[i] > vaddr)
+            .map(|i| self.symbols[i])
+            .find(|symbol| symbol.contains(vaddr))
+    }
+
+    /// Number of symbols in the index.
+    pub fn len(&self) -> usize {
+        self.symbols.len()
+    }
+
+    /// Whether the index holds no symbols.
+    pub fn is_empty(&self) -> bool {
+        self.symbols.is_empty()
+    }
+}
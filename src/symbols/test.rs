@@ -0,0 +1,178 @@
+use std::fs;
+use std::vec::Vec;
+
+use xmas_elf::symbol_table::{Binding, Type, Visibility};
+
+use crate::{ElfBinary, ElfLoaderErr, FunctionRange, Symbol, SymbolTableKind};
+
+#[test]
+fn symbols() {
+    for (file, index, value, size) in [
+        ("test/test.x86_64", 58, 0x64a, 88),
+        ("test/test.x86", 64, 0x1199, 107),
+    ] {
+        let binary_blob = fs::read(file).expect("Can't read binary");
+        let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+
+        let symbols: Vec<Symbol> = binary.symbols().expect("Has .symtab").collect();
+        let main = symbols
+            .iter()
+            .find(|symbol| symbol.name == "main")
+            .expect("Has main");
+        assert_eq!(main.index, index);
+        assert_eq!(main.value, value);
+        assert_eq!(main.size, size);
+        assert_eq!(main.shndx, 14);
+        assert!(main.is_defined());
+        assert_eq!(main.binding(), Ok(Binding::Global));
+        assert_eq!(main.typ(), Ok(Type::Func));
+        assert!(matches!(main.visibility(), Visibility::Default));
+
+        // Same entries as for_each_symbol
+        let mut expected = Vec::new();
+        binary
+            .for_each_symbol(|entry| expected.push((binary.symbol_name(entry), entry.value())))
+            .expect("Has .symtab");
+        let names: Vec<(&str, u64)> = symbols
+            .iter()
+            .map(|symbol| (symbol.name, symbol.value))
+            .collect();
+        assert_eq!(names.len(), expected.len());
+        assert!(names[1..] == expected[1..]);
+    }
+}
+
+#[test]
+fn dynamic_symbols() {
+    let binary_blob = fs::read("test/test.x86_64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+
+    let symbols: Vec<Symbol> = binary.dynamic_symbols().expect("Has .dynsym").collect();
+    assert_eq!(symbols.len(), 7);
+    assert_eq!(symbols[2].name, "printf");
+    assert!(!symbols[2].is_defined());
+    assert_eq!(symbols[2].typ(), Ok(Type::Func));
+
+    let mut names = Vec::new();
+    assert_eq!(
+        binary.for_each_symbol_any(|symbol| names.push(symbol.name)),
+        Ok(SymbolTableKind::Symtab)
+    );
+    assert_eq!(names.len(), 63);
+
+    // Stripped binaries have no .symtab
+    let mut binary_blob = binary_blob.clone();
+    let symtab = binary_blob
+        .windows(8)
+        .position(|name| name == b".symtab\0")
+        .expect("Has .symtab");
+    binary_blob[symtab + 1] = b'x';
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+    assert_eq!(
+        binary.symbols().err(),
+        Some(ElfLoaderErr::SymbolTableNotFound)
+    );
+    assert_eq!(
+        binary.any_symbols().map(|symbols| symbols.kind()),
+        Ok(SymbolTableKind::Dynsym)
+    );
+    let mut names = Vec::new();
+    assert_eq!(
+        binary.for_each_symbol_any(|symbol| names.push(symbol.name)),
+        Ok(SymbolTableKind::Dynsym)
+    );
+    assert_eq!(names.len(), 7);
+    assert_eq!(names[2], "printf");
+}
+
+#[test]
+fn filtered_symbols() {
+    let binary_blob = fs::read("test/test.x86_64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+
+    let mut functions: Vec<&str> = binary
+        .exported_functions()
+        .expect("Has .symtab")
+        .map(|symbol| symbol.name)
+        .collect();
+    functions.sort_unstable();
+    assert_eq!(
+        functions,
+        [
+            "__libc_csu_fini",
+            "__libc_csu_init",
+            "_fini",
+            "_init",
+            "_start",
+            "main"
+        ]
+    );
+
+    let data: Vec<&str> = binary
+        .global_data_symbols()
+        .expect("Has .symtab")
+        .map(|symbol| symbol.name)
+        .collect();
+    assert_eq!(data, ["__dso_handle", "_IO_stdin_used", "__TMC_END__"]);
+}
+
+#[test]
+fn symbol_for_address() {
+    let binary_blob = fs::read("test/test.x86_64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+
+    let name = |vaddr| {
+        binary
+            .symbol_for_address(vaddr)
+            .expect("Has .symtab")
+            .map(|symbol| symbol.name)
+    };
+    assert_eq!(name(0x64a), Some("main"));
+    assert_eq!(name(0x64a + 87), Some("main"));
+    assert_eq!(name(0x64a + 88), None);
+    assert_eq!(name(0x730), Some("_IO_stdin_used"));
+    assert_eq!(name(0), None);
+
+    #[cfg(feature = "alloc")]
+    {
+        let index = binary.symbol_index().expect("Has .symtab");
+        assert!(!index.is_empty());
+        for vaddr in [0, 0x540, 0x56a, 0x649, 0x64a, 0x6a1, 0x6a2, 0x730, 0x734] {
+            assert_eq!(
+                index.lookup(vaddr),
+                binary.symbol_for_address(vaddr).unwrap()
+            );
+        }
+    }
+}
+
+#[test]
+fn function_ranges() {
+    let binary_blob = fs::read("test/test.x86_64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+
+    let functions: Vec<FunctionRange> = binary.function_ranges().expect("Has .symtab").collect();
+    assert!(functions.contains(&FunctionRange {
+        name: "main",
+        start: 0x64a,
+        size: 88
+    }));
+    assert!(functions.iter().all(|function| function.size > 0));
+
+    #[cfg(feature = "alloc")]
+    {
+        // test.x86_64 is position independent
+        let table = binary
+            .function_range_table(0x1000_0000)
+            .expect("Has .symtab");
+        assert_eq!(table.len(), functions.len());
+        assert!(table.windows(2).all(|pair| pair[0].end() <= pair[1].start));
+
+        let lookup = |vaddr| FunctionRange::lookup(&table, vaddr).map(|function| function.name);
+        assert_eq!(lookup(0x1000_064a), Some("main"));
+        assert_eq!(lookup(0x1000_06a1), Some("main"));
+        assert_eq!(lookup(0x1000_06a2), None);
+        assert_eq!(lookup(0x64a), None);
+        assert_eq!(lookup(0), None);
+    }
+}
@@ -0,0 +1,257 @@
+//! Thread-local storage layout for a set of loaded objects.
+//!
+//! Every object with a PT_TLS segment gets a module id (starting at 1) that
+//! indexes the dynamic thread vector (DTV). Objects that are present at
+//! program start are additionally placed in the static TLS block, where
+//! their data lives at a fixed offset from the thread pointer. How that
+//! block is arranged around the thread pointer depends on the architecture:
+//!
+//! * Variant I (AArch64, ARM, RISC-V, ...): the thread pointer points to the
+//!   thread control block (TCB), the TLS blocks follow at positive offsets.
+//! * Variant II (x86, x86-64, ...): the TLS blocks are placed below the
+//!   thread pointer, which points to the TCB at the end.
+
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use xmas_elf::program::Type;
+
+use crate::{ElfBinary, ElfLoaderErr, Machine};
+
+#[cfg(test)]
+mod test;
+
+/// Identifies a module in the DTV, the first module has id 1.
+pub type ModuleId = usize;
+
+/// Arrangement of the static TLS block around the thread pointer.
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub enum TlsVariant {
+    /// TCB at the thread pointer, TLS blocks above it.
+    I,
+    /// TLS blocks below the thread pointer, TCB above it.
+    II,
+}
+
+impl TlsVariant {
+    /// The TLS variant used by the ABI of `machine`.
+    pub fn for_machine(machine: Machine) -> Result<TlsVariant, ElfLoaderErr> {
+        match machine {
+            Machine::X86 | Machine::X86_64 => Ok(TlsVariant::II),
+            Machine::Arm | Machine::AArch64 | Machine::RISC_V => Ok(TlsVariant::I),
+            _ => Err(ElfLoaderErr::UnsupportedArchitecture),
+        }
+    }
+}
+
+/// TLS requirements of a single module.
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub struct TlsModule {
+    /// Index of the module in the DTV.
+    pub id: ModuleId,
+    /// Size of the initialization image (.tdata).
+    pub image_size: usize,
+    /// Size of the TLS block (.tdata and .tbss).
+    pub mem_size: usize,
+    /// Alignment of the TLS block.
+    pub align: usize,
+    /// Offset of the TLS block from the thread pointer, `None` for modules
+    /// that are not part of the static TLS block.
+    pub tp_offset: Option<isize>,
+}
+
+/// Assigns module ids and computes the static TLS block layout.
+#[derive(Debug, Clone)]
+pub struct TlsRegistry {
+    variant: TlsVariant,
+    tcb_size: usize,
+    modules: Vec<TlsModule>,
+    /// Bytes used by TLS blocks in the static area (excluding the TCB).
+    static_used: usize,
+    static_align: usize,
+    /// Size of the static TLS area including the TCB.
+    static_size: usize,
+    /// Offset of the thread pointer from the start of the static TLS area.
+    thread_pointer_offset: usize,
+}
+
+impl TlsRegistry {
+    /// Create an empty registry.
+    ///
+    /// `tcb_size` is the size of the thread control block, for variant I
+    /// this is the space reserved between the thread pointer and the first
+    /// TLS block (e.g. 16 on AArch64, 0 on RISC-V).
+    pub fn new(variant: TlsVariant, tcb_size: usize) -> TlsRegistry {
+        TlsRegistry {
+            variant,
+            tcb_size,
+            modules: Vec::new(),
+            static_used: 0,
+            static_align: 1,
+            static_size: tcb_size,
+            thread_pointer_offset: 0,
+        }
+    }
+
+    /// Register a module and place its TLS block in the static TLS area.
+    ///
+    /// Fails with `AddressOverflow` if the static TLS area would no longer
+    /// fit in the address space.
+    pub fn register_static(
+        &mut self,
+        image_size: usize,
+        mem_size: usize,
+        align: usize,
+    ) -> Result<ModuleId, ElfLoaderErr> {
+        let align = Self::check(image_size, mem_size, align)?;
+        let static_align = self.static_align.max(align);
+        let (tp_offset, static_used, static_size, thread_pointer_offset) = match self.variant {
+            TlsVariant::I => {
+                let start = self
+                    .tcb_size
+                    .checked_add(self.static_used)
+                    .and_then(|used| align_up(used, align));
+                let end = start.and_then(|start| start.checked_add(mem_size));
+                match (start.and_then(|start| isize::try_from(start).ok()), end) {
+                    (Some(tp_offset), Some(end)) => (tp_offset, end - self.tcb_size, end, 0),
+                    _ => return Err(Self::overflow()),
+                }
+            }
+            TlsVariant::II => {
+                let end = self
+                    .static_used
+                    .checked_add(mem_size)
+                    .and_then(|used| align_up(used, align));
+                let thread_pointer = end.and_then(|end| align_up(end, static_align));
+                let size = thread_pointer.and_then(|tp| tp.checked_add(self.tcb_size));
+                match (
+                    end.and_then(|end| isize::try_from(end).ok()),
+                    thread_pointer,
+                    size,
+                ) {
+                    (Some(tp_offset), Some(thread_pointer), Some(size)) => {
+                        (-tp_offset, tp_offset as usize, size, thread_pointer)
+                    }
+                    _ => return Err(Self::overflow()),
+                }
+            }
+        };
+        self.static_used = static_used;
+        self.static_align = static_align;
+        self.static_size = static_size;
+        self.thread_pointer_offset = thread_pointer_offset;
+        Ok(self.push(image_size, mem_size, align, Some(tp_offset)))
+    }
+
+    /// Register a module that is only reachable through the DTV (i.e.
+    /// loaded after the threads were started).
+    pub fn register_dynamic(
+        &mut self,
+        image_size: usize,
+        mem_size: usize,
+        align: usize,
+    ) -> Result<ModuleId, ElfLoaderErr> {
+        let align = Self::check(image_size, mem_size, align)?;
+        Ok(self.push(image_size, mem_size, align, None))
+    }
+
+    /// Register the PT_TLS segment of `binary` in the static TLS area.
+    ///
+    /// Returns `None` if the binary has no TLS segment.
+    pub fn register_binary(
+        &mut self,
+        binary: &ElfBinary,
+    ) -> Result<Option<ModuleId>, ElfLoaderErr> {
+        for header in binary.program_headers() {
+            if header.get_type()? == Type::Tls {
+                return self
+                    .register_static(
+                        header.file_size() as usize,
+                        header.mem_size() as usize,
+                        header.align() as usize,
+                    )
+                    .map(Some);
+            }
+        }
+        Ok(None)
+    }
+
+    /// The module with id `id`.
+    pub fn module(&self, id: ModuleId) -> Option<&TlsModule> {
+        id.checked_sub(1).and_then(|idx| self.modules.get(idx))
+    }
+
+    /// All registered modules, ordered by id.
+    pub fn modules(&self) -> impl Iterator<Item = &TlsModule> {
+        self.modules.iter()
+    }
+
+    /// Number of module entries a DTV needs (excluding the generation count).
+    pub fn dtv_len(&self) -> usize {
+        self.modules.len()
+    }
+
+    /// The TLS variant this registry lays out.
+    pub fn variant(&self) -> TlsVariant {
+        self.variant
+    }
+
+    /// Size of the static TLS area including the TCB.
+    pub fn static_size(&self) -> usize {
+        self.static_size
+    }
+
+    /// Required alignment of the static TLS area.
+    pub fn static_align(&self) -> usize {
+        self.static_align
+    }
+
+    /// Offset of the thread pointer from the start of the static TLS area.
+    pub fn thread_pointer_offset(&self) -> usize {
+        self.thread_pointer_offset
+    }
+
+    fn check(image_size: usize, mem_size: usize, align: usize) -> Result<usize, ElfLoaderErr> {
+        // p_align of 0 and 1 both mean no alignment constraint
+        let align = align.max(1);
+        if !align.is_power_of_two() {
+            return Err(ElfLoaderErr::ElfParser {
+                source: "TLS alignment is not a power of two",
+            });
+        }
+        if image_size > mem_size {
+            return Err(ElfLoaderErr::ElfParser {
+                source: "TLS image is larger than the TLS block",
+            });
+        }
+        Ok(align)
+    }
+
+    fn overflow() -> ElfLoaderErr {
+        ElfLoaderErr::AddressOverflow {
+            source: "static TLS area",
+        }
+    }
+
+    fn push(
+        &mut self,
+        image_size: usize,
+        mem_size: usize,
+        align: usize,
+        tp_offset: Option<isize>,
+    ) -> ModuleId {
+        let id = self.modules.len() + 1;
+        self.modules.push(TlsModule {
+            id,
+            image_size,
+            mem_size,
+            align,
+            tp_offset,
+        });
+        id
+    }
+}
+
+/// Rounds `value` up to `align`, `None` if the result does not fit.
+fn align_up(value: usize, align: usize) -> Option<usize> {
+    Some(value.checked_add(align - 1)? & !(align - 1))
+}
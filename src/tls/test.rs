@@ -0,0 +1,90 @@
+use super::*;
+use std::fs;
+
+#[test]
+fn variant_1_layout() {
+    let mut registry = TlsRegistry::new(TlsVariant::I, 16);
+    assert_eq!(registry.register_static(4, 8, 4), Ok(1));
+    assert_eq!(registry.register_static(0, 0x20, 32), Ok(2));
+    assert_eq!(registry.register_dynamic(1, 1, 0), Ok(3));
+
+    assert_eq!(registry.module(1).unwrap().tp_offset, Some(16));
+    assert_eq!(registry.module(2).unwrap().tp_offset, Some(32));
+    assert_eq!(registry.module(3).unwrap().tp_offset, None);
+    assert_eq!(registry.module(0), None);
+    assert_eq!(registry.module(4), None);
+
+    assert_eq!(registry.dtv_len(), 3);
+    assert_eq!(registry.static_size(), 64);
+    assert_eq!(registry.static_align(), 32);
+    assert_eq!(registry.thread_pointer_offset(), 0);
+}
+
+#[test]
+fn variant_2_layout() {
+    let mut registry = TlsRegistry::new(TlsVariant::II, 0x40);
+    assert_eq!(registry.register_static(4, 8, 4), Ok(1));
+    assert_eq!(registry.register_static(3, 0x21, 16), Ok(2));
+
+    assert_eq!(registry.module(1).unwrap().tp_offset, Some(-8));
+    assert_eq!(registry.module(2).unwrap().tp_offset, Some(-48));
+
+    assert_eq!(registry.static_size(), 48 + 0x40);
+    assert_eq!(registry.static_align(), 16);
+    assert_eq!(registry.thread_pointer_offset(), 48);
+}
+
+#[test]
+fn invalid_modules() {
+    let mut registry = TlsRegistry::new(TlsVariant::II, 0);
+    assert!(registry.register_static(4, 8, 3).is_err());
+    assert!(registry.register_static(8, 4, 4).is_err());
+    assert_eq!(registry.dtv_len(), 0);
+}
+
+#[test]
+fn static_area_overflow() {
+    let overflow = Err(ElfLoaderErr::AddressOverflow {
+        source: "static TLS area",
+    });
+    let mut registry = TlsRegistry::new(TlsVariant::I, 16);
+    assert_eq!(registry.register_static(0, usize::MAX - 8, 1), overflow);
+    assert_eq!(registry.register_static(0, 8, 1), Ok(1));
+    assert_eq!(
+        registry.register_static(0, 8, 1 << (usize::BITS - 1)),
+        overflow
+    );
+    assert_eq!(registry.static_size(), 24);
+
+    let mut registry = TlsRegistry::new(TlsVariant::II, 16);
+    assert_eq!(registry.register_static(0, usize::MAX, 1), overflow);
+    assert_eq!(registry.register_static(0, usize::MAX - 8, 1), overflow);
+    assert_eq!(registry.register_static(0, 8, 8), Ok(1));
+    assert_eq!(registry.static_size(), 24);
+    assert_eq!(registry.thread_pointer_offset(), 8);
+    assert_eq!(registry.dtv_len(), 1);
+}
+
+#[test]
+fn register_binaries() {
+    let tls = fs::read("test/tls.x86_64").expect("Can't read binary");
+    let tls = ElfBinary::new(tls.as_slice()).expect("Got proper ELF file");
+    let no_tls = fs::read("test/test.x86_64").expect("Can't read binary");
+    let no_tls = ElfBinary::new(no_tls.as_slice()).expect("Got proper ELF file");
+
+    let variant = TlsVariant::for_machine(tls.get_arch()).unwrap();
+    let mut registry = TlsRegistry::new(variant, 0);
+    assert_eq!(registry.register_binary(&no_tls), Ok(None));
+    // readelf -l: TLS FileSiz 0x4 MemSiz 0x8 Align 0x4
+    assert_eq!(registry.register_binary(&tls), Ok(Some(1)));
+    assert_eq!(
+        registry.module(1),
+        Some(&TlsModule {
+            id: 1,
+            image_size: 4,
+            mem_size: 8,
+            align: 4,
+            tp_offset: Some(-8),
+        })
+    );
+}
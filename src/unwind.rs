@@ -0,0 +1,139 @@
+//! Unwinding through images loaded by this crate.
+//!
+//! Unwinders such as `unwinding` or `gimli` find the unwind tables of the
+//! code they walk through via `dl_iterate_phdr` or linker symbols, so
+//! they don't know about images loaded into anonymous memory.
+//! [`UnwindImage`] records where the text and the unwind tables of a loaded
+//! image ended up, and [`UnwindImage::find`] picks the image of a PC.
+//! Plugging a list of them into `unwinding`'s custom finder looks like this:
+//!
+//! ```ignore
+//! use unwinding::custom_eh_frame_finder::{EhFrameFinder, FrameInfo, FrameInfoKind};
+//!
+//! struct Images;
+//!
+//! unsafe impl EhFrameFinder for Images {
+//!     fn find(&self, pc: usize) -> Option<FrameInfo> {
+//!         let images = IMAGES.lock();
+//!         let image = UnwindImage::find(&images, pc as u64)?;
+//!         let kind = match image.table()? {
+//!             UnwindTable::EhFrameHdr(address) => FrameInfoKind::EhFrameHdr(address as usize),
+//!             UnwindTable::EhFrame(address) => FrameInfoKind::EhFrame(address as usize),
+//!         };
+//!         Some(FrameInfo {
+//!             text_base: Some(image.text_start as usize),
+//!             kind,
+//!         })
+//!     }
+//! }
+//!
+//! unwinding::custom_eh_frame_finder::set_custom_eh_frame_finder(&Images)?;
+//! ```
+//!
+//! With `gimli`, the fields are the `BaseAddresses` of the image
+//! (`set_text`, `set_eh_frame` and `set_eh_frame_hdr`).
+
+use xmas_elf::program::Type;
+
+use crate::dwarf::EhFrameHdr;
+use crate::elf;
+use crate::{ElfBinary, ElfLoaderErr, SegmentPermissions, VAddr};
+
+#[cfg(test)]
+mod test;
+
+/// The unwind table to search for the FDE of a PC.
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub enum UnwindTable {
+    /// Load address of the `.eh_frame_hdr` binary search table.
+    EhFrameHdr(VAddr),
+    /// Load address of `.eh_frame`, to be scanned linearly.
+    EhFrame(VAddr),
+}
+
+/// Where the code and the unwind tables of a loaded image are.
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub struct UnwindImage {
+    /// Load address of the first executable LOAD segment.
+    pub text_start: VAddr,
+    /// End of the last executable LOAD segment.
+    pub text_end: VAddr,
+    /// Load address of `.eh_frame`, if known.
+    pub eh_frame: Option<VAddr>,
+    /// Load address of `.eh_frame_hdr` (PT_GNU_EH_FRAME), if present.
+    pub eh_frame_hdr: Option<VAddr>,
+    /// Difference between load addresses and the addresses in the file.
+    pub bias: u64,
+}
+
+impl UnwindImage {
+    /// Collects the unwind information of `binary` loaded at `base`.
+    ///
+    /// The `.eh_frame_hdr` table is found through the PT_GNU_EH_FRAME
+    /// program header, `.eh_frame` through the pointer in that table or
+    /// the section headers. Fails if the binary has no executable LOAD
+    /// segment or its `.eh_frame_hdr` is malformed.
+    pub fn new(binary: &ElfBinary, base: VAddr) -> Result<UnwindImage, ElfLoaderErr> {
+        let bias = binary.load_bias(base);
+
+        let mut text: Option<(VAddr, VAddr)> = None;
+        for segment in binary.load_segments() {
+            if !SegmentPermissions::from(segment.flags).is_execute() {
+                continue;
+            }
+            let end = segment.vaddr.checked_add(segment.mem_size).ok_or(
+                ElfLoaderErr::AddressOverflow {
+                    source: "p_vaddr + p_memsz",
+                },
+            )?;
+            text = Some(match text {
+                Some((start, text_end)) => (start.min(segment.vaddr), text_end.max(end)),
+                None => (segment.vaddr, end),
+            });
+        }
+        let (text_start, text_end) = text.ok_or(ElfLoaderErr::ElfParser {
+            source: "No executable segment",
+        })?;
+
+        let mut eh_frame_hdr = None;
+        let mut eh_frame = None;
+        let header = binary
+            .segments()
+            .find(|segment| segment.typ() == Ok(Type::OsSpecific(elf::PT_GNU_EH_FRAME)));
+        if let Some(header) = header {
+            let hdr =
+                EhFrameHdr::parse(header.data()?, header.vaddr(), bias, binary.header.encoding)?;
+            eh_frame_hdr = Some(header.vaddr().wrapping_add(bias));
+            eh_frame = Some(hdr.eh_frame);
+        }
+        if eh_frame.is_none() {
+            eh_frame = binary.debug_sections(base).section_address(".eh_frame");
+        }
+
+        Ok(UnwindImage {
+            text_start: text_start.wrapping_add(bias),
+            text_end: text_end.wrapping_add(bias),
+            eh_frame,
+            eh_frame_hdr,
+            bias,
+        })
+    }
+
+    /// Whether `pc` is in the text of the image.
+    pub fn contains(&self, pc: VAddr) -> bool {
+        self.text_start <= pc && pc < self.text_end
+    }
+
+    /// The table to search for the FDE of a PC of this image, the binary
+    /// search table if there is one.
+    pub fn table(&self) -> Option<UnwindTable> {
+        self.eh_frame_hdr
+            .map(UnwindTable::EhFrameHdr)
+            .or(self.eh_frame.map(UnwindTable::EhFrame))
+    }
+
+    /// The image in `images` whose text contains `pc`.
+    pub fn find(images: &[UnwindImage], pc: VAddr) -> Option<&UnwindImage> {
+        images.iter().find(|image| image.contains(pc))
+    }
+}
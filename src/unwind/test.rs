@@ -0,0 +1,42 @@
+use std::fs;
+
+use super::*;
+
+#[test]
+fn unwind_image() {
+    let binary_blob = fs::read("test/test.x86_64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+
+    // readelf -l: R-X LOAD at 0x0 (0x888 bytes), GNU_EH_FRAME at 0x740
+    let image = UnwindImage::new(&binary, 0x1000_0000).expect("Has text");
+    assert_eq!(
+        image,
+        UnwindImage {
+            text_start: 0x1000_0000,
+            text_end: 0x1000_0888,
+            eh_frame: Some(0x1000_0780),
+            eh_frame_hdr: Some(0x1000_0740),
+            bias: 0x1000_0000,
+        }
+    );
+    assert_eq!(image.table(), Some(UnwindTable::EhFrameHdr(0x1000_0740)));
+    assert!(image.contains(0x1000_064a));
+    assert!(!image.contains(0x1000_0888));
+
+    let binary_blob = fs::read("test/test_nopie.x86_64").expect("Can't read binary");
+    let binary = ElfBinary::new(binary_blob.as_slice()).expect("Got proper ELF file");
+    let nopie = UnwindImage::new(&binary, 0x1000_0000).expect("Has text");
+    assert_eq!(nopie.bias, 0);
+    assert_eq!(nopie.text_start, 0x40_0000);
+
+    let images = [image, nopie];
+    assert_eq!(UnwindImage::find(&images, 0x1000_064a), Some(&image));
+    assert_eq!(UnwindImage::find(&images, 0x40_0400), Some(&nopie));
+    assert_eq!(UnwindImage::find(&images, 0), None);
+
+    let without_hdr = UnwindImage {
+        eh_frame_hdr: None,
+        ..image
+    };
+    assert_eq!(without_hdr.table(), Some(UnwindTable::EhFrame(0x1000_0780)));
+}
@@ -0,0 +1,10 @@
+// gcc -Os -fPIE -static-pie -nostdlib -o static_pie.x86_64 static_pie.c -Wl,--build-id=none
+static int value = 42;
+int *pointer = &value;
+const char *message = "static-pie";
+
+void _start(void)
+{
+    for (;;)
+        ;
+}
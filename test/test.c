@@ -1,5 +1,7 @@
 // gcc -o test.x86_64 test.c -fPIC -fPIE
 // gcc -o test_nopie.x86_64 test.c -no-pie
+// gcc -o runpath.x86_64 test.c -fPIC -fPIE -Wl,-rpath,'$ORIGIN/../lib:/opt/${LIB}' -Wl,--enable-new-dtags
+// objcopy --add-gnu-debuglink=test.x86_64 test.x86_64 debuglink.x86_64
 // aarch64-linux-gnu-gcc -o test.aarch64 test.c -fPIC -fPIE
 // aarch64-linux-gnu-gcc -o test_nopie.aarch64 test.c -no-pie
 #include <stdio.h>